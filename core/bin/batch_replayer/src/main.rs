@@ -0,0 +1,94 @@
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_config::{configs::chain::NetworkConfig, PostgresConfig};
+use zksync_core::batch_replayer::BatchReplayer;
+use zksync_dal::ConnectionPool;
+use zksync_env_config::FromEnv;
+use zksync_types::L1BatchNumber;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Replays sealed L1 batches and compares outputs with the persisted data",
+    long_about = None
+)]
+struct Cli {
+    /// Number of the first L1 batch to replay.
+    #[arg(long)]
+    from_l1_batch: u32,
+    /// Number of the last L1 batch to replay (inclusive). If not specified, only the first L1 batch is replayed.
+    #[arg(long)]
+    to_l1_batch: Option<u32>,
+    /// Outputs replay reports as JSON objects (one per line), so that they are machine-readable.
+    #[arg(long)]
+    json: bool,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
+    let log_format = vlog::log_format_from_env();
+    #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
+    let sentry_url = vlog::sentry_url_from_env();
+    #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
+    let environment = vlog::environment_from_env();
+
+    let mut builder = vlog::ObservabilityBuilder::new().with_log_format(log_format);
+    if let Some(sentry_url) = sentry_url {
+        builder = builder
+            .with_sentry_url(&sentry_url)
+            .context("Invalid Sentry URL")?
+            .with_sentry_environment(environment);
+    }
+    let _guard = builder.build();
+
+    let cli = Cli::parse();
+    let to_l1_batch = cli.to_l1_batch.unwrap_or(cli.from_l1_batch);
+    anyhow::ensure!(
+        cli.from_l1_batch <= to_l1_batch,
+        "Invalid L1 batch range: {}..={to_l1_batch}",
+        cli.from_l1_batch
+    );
+
+    let network_config = NetworkConfig::from_env().context("NetworkConfig::from_env()")?;
+    let postgres_config = PostgresConfig::from_env().context("PostgresConfig::from_env()")?;
+    // The replayer only reads from Postgres; one connection is used to load data,
+    // and another one is held by the batch executor.
+    let connection_pool = ConnectionPool::builder(postgres_config.replica_url()?, 2)
+        .build()
+        .await
+        .context("failed to build a connection pool")?;
+    let replayer = BatchReplayer::new(connection_pool, network_config.zksync_network_id);
+
+    let mut diverged_batch_count = 0;
+    for number in cli.from_l1_batch..=to_l1_batch {
+        let report = replayer
+            .replay(L1BatchNumber(number))
+            .await
+            .with_context(|| format!("failed replaying L1 batch #{number}"))?;
+        if cli.json {
+            println!("{}", serde_json::to_string(&report)?);
+        } else if report.is_match() {
+            println!(
+                "L1 batch #{number} ({} miniblocks, {} txs) matches persisted data",
+                report.miniblock_count, report.tx_count
+            );
+        } else {
+            println!("L1 batch #{number} diverges from persisted data:");
+            for mismatch in &report.mismatches {
+                println!("  {:?}: {}", mismatch.output, mismatch.details);
+            }
+        }
+
+        if !report.is_match() {
+            diverged_batch_count += 1;
+        }
+    }
+
+    if diverged_batch_count > 0 {
+        anyhow::bail!("{diverged_batch_count} L1 batch(es) diverged from persisted data");
+    }
+    tracing::info!("All replayed L1 batches match persisted data");
+    Ok(())
+}
@@ -59,6 +59,9 @@ enum Command {
         /// Flag that allows to revert already executed blocks, it's ultra dangerous and required only for fixing external nodes
         #[arg(long)]
         allow_executed_block_reversion: bool,
+        /// Outputs what would be reverted as a JSON object without modifying any data.
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Clears failed L1 transactions.
@@ -138,7 +141,35 @@ async fn main() -> anyhow::Result<()> {
             rollback_tree,
             rollback_sk_cache,
             allow_executed_block_reversion,
+            dry_run,
         } => {
+            let l1_batch_number = L1BatchNumber(l1_batch_number);
+            let mut flags = BlockReverterFlags::empty();
+            if rollback_postgres {
+                flags |= BlockReverterFlags::POSTGRES;
+            }
+            if rollback_tree {
+                flags |= BlockReverterFlags::TREE;
+            }
+            if rollback_sk_cache {
+                flags |= BlockReverterFlags::SK_CACHE;
+            }
+
+            if dry_run {
+                if allow_executed_block_reversion {
+                    // No confirmation is required since no data is modified.
+                    block_reverter.change_rollback_executed_l1_batches_allowance(
+                        L1ExecutedBatchesRevert::Allowed,
+                    );
+                }
+                let plan = block_reverter
+                    .plan_rollback(l1_batch_number, flags)
+                    .await
+                    .context("failed computing rollback plan")?;
+                println!("{}", serde_json::to_string_pretty(&plan)?);
+                return Ok(());
+            }
+
             if !rollback_tree && rollback_postgres {
                 println!("You want to rollback Postgres DB without rolling back tree.");
                 println!(
@@ -169,19 +200,12 @@ async fn main() -> anyhow::Result<()> {
                 );
             }
 
-            let mut flags = BlockReverterFlags::empty();
-            if rollback_postgres {
-                flags |= BlockReverterFlags::POSTGRES;
-            }
-            if rollback_tree {
-                flags |= BlockReverterFlags::TREE;
-            }
-            if rollback_sk_cache {
-                flags |= BlockReverterFlags::SK_CACHE;
-            }
+            block_reverter.rollback_db(l1_batch_number, flags).await;
             block_reverter
-                .rollback_db(L1BatchNumber(l1_batch_number), flags)
+                .check_rollback(l1_batch_number, flags)
                 .await
+                .context("state is inconsistent after rollback")?;
+            println!("Rollback to L1 batch #{l1_batch_number} completed and verified");
         }
         Command::ClearFailedL1Transactions => block_reverter.clear_failed_l1_transactions().await,
     }
@@ -7,20 +7,15 @@ use std::{
 
 use anyhow::Context as _;
 use chrono::Utc;
-use ethabi::{Contract, Token};
-use lazy_static::lazy_static;
 use regex::Regex;
 use tokio::time;
 use zksync_config::ContractVerifierConfig;
 use zksync_dal::{ConnectionPool, StorageProcessor};
 use zksync_env_config::FromEnv;
 use zksync_queued_job_processor::{async_trait, JobProcessor};
-use zksync_types::{
-    contract_verification_api::{
-        CompilationArtifacts, CompilerType, DeployContractCalldata, SourceCodeData,
-        VerificationInfo, VerificationRequest,
-    },
-    Address,
+use zksync_types::contract_verification_api::{
+    CompilationArtifacts, CompilerType, ConstructorArgs, SourceCodeData, VerificationInfo,
+    VerificationMatch, VerificationRequest,
 };
 
 use crate::{
@@ -29,15 +24,10 @@ use crate::{
     zkvyper_utils::{ZkVyper, ZkVyperInput},
 };
 
-lazy_static! {
-    static ref DEPLOYER_CONTRACT: Contract = zksync_contracts::deployer_contract();
-}
-
-#[derive(Debug)]
-enum ConstructorArgs {
-    Check(Vec<u8>),
-    Ignore,
-}
+/// Size of the VM word. Bytecode produced by `zksolc` and `zkvyper` consists of an odd number of words.
+const WORD_SIZE: usize = 32;
+/// Size of the metadata hash appended to the bytecode by `zksolc` and `zkvyper`.
+const METADATA_HASH_SIZE: usize = WORD_SIZE;
 
 #[derive(Debug)]
 pub struct ContractVerifier {
@@ -58,6 +48,7 @@ impl ContractVerifier {
         mut request: VerificationRequest,
         config: ContractVerifierConfig,
     ) -> Result<VerificationInfo, ContractVerifierError> {
+        let has_metadata_hash = Self::has_metadata_hash(&request);
         let artifacts = Self::compile(request.clone(), config).await?;
 
         // Bytecode should be present because it is checked when accepting request.
@@ -69,14 +60,20 @@ impl ContractVerifier {
                 tracing::warn!("Contract is missing in DB for already accepted verification request. Contract address: {:#?}", request.req.contract_address);
                 ContractVerifierError::InternalError
             })?;
-        let constructor_args = Self::decode_constructor_arguments_from_calldata(
-            creation_tx_calldata,
-            request.req.contract_address,
-        );
+        let constructor_args = creation_tx_calldata
+            .constructor_args(request.req.contract_address)
+            .map_err(|err| {
+                tracing::warn!(
+                    "Failed decoding constructor arguments for contract {:?}: {:#}",
+                    request.req.contract_address,
+                    err
+                );
+                ContractVerifierError::InternalError
+            })?;
 
-        if artifacts.bytecode != deployed_bytecode {
-            return Err(ContractVerifierError::BytecodeMismatch);
-        }
+        let match_type =
+            Self::match_bytecode(&artifacts.bytecode, &deployed_bytecode, has_metadata_hash)
+                .ok_or(ContractVerifierError::BytecodeMismatch)?;
 
         match constructor_args {
             ConstructorArgs::Check(args) => {
@@ -84,7 +81,7 @@ impl ContractVerifier {
                     return Err(ContractVerifierError::IncorrectConstructorArguments);
                 }
             }
-            ConstructorArgs::Ignore => {
+            ConstructorArgs::Ignore | ConstructorArgs::Unknown => {
                 request.req.constructor_arguments = Vec::new().into();
             }
         }
@@ -93,9 +90,65 @@ impl ContractVerifier {
             request,
             artifacts,
             verified_at: Utc::now(),
+            match_type,
+            verified_via_address: None,
+            constructor_arguments_unknown: false,
         })
     }
 
+    /// Checks whether the compiled bytecode has a metadata hash appended, which is the case unless
+    /// it is explicitly disabled in the standard JSON input.
+    fn has_metadata_hash(request: &VerificationRequest) -> bool {
+        match &request.req.source_code_data {
+            SourceCodeData::StandardJsonInput(input) => {
+                input
+                    .get("settings")
+                    .and_then(|settings| settings.get("metadata"))
+                    .and_then(|metadata| metadata.get("bytecodeHash"))
+                    .and_then(serde_json::Value::as_str)
+                    != Some("none")
+            }
+            _ => true,
+        }
+    }
+
+    /// Compares the compiled bytecode with the deployed one. Unlike EVM compilers, `zksolc` and `zkvyper`
+    /// don't append CBOR-encoded metadata; instead, the keccak256 hash of the metadata occupies the last word
+    /// of the bytecode. Since the bytecode must consist of an odd number of words, a zero word may be inserted
+    /// before the hash (or appended to the code if the hash is disabled). If bytecodes differ only in metadata
+    /// and padding, the executable code is identical, and the match is considered partial.
+    fn match_bytecode(
+        compiled: &[u8],
+        deployed: &[u8],
+        has_metadata_hash: bool,
+    ) -> Option<VerificationMatch> {
+        if compiled == deployed {
+            return Some(VerificationMatch::Full);
+        }
+        let compiled_code = Self::executable_code(compiled, has_metadata_hash)?;
+        // Contracts are deployed with the metadata hash unless it's explicitly disabled; in the latter case,
+        // bytecodes either match fully or don't match at all.
+        let deployed_code = Self::executable_code(deployed, true)?;
+        (compiled_code == deployed_code).then_some(VerificationMatch::Partial)
+    }
+
+    /// Strips the metadata hash and padding words from the bytecode. Returns `None` if the bytecode
+    /// is not well-formed.
+    fn executable_code(bytecode: &[u8], has_metadata_hash: bool) -> Option<&[u8]> {
+        if bytecode.len() % WORD_SIZE != 0 || (bytecode.len() / WORD_SIZE) % 2 == 0 {
+            return None;
+        }
+        let mut code = if has_metadata_hash {
+            &bytecode[..bytecode.len() - METADATA_HASH_SIZE]
+        } else {
+            bytecode
+        };
+        while let Some(unpadded_code) = code.strip_suffix(&[0_u8; WORD_SIZE]) {
+            code = unpadded_code;
+        }
+        (!code.is_empty()).then_some(code)
+    }
+
     async fn compile_zksolc(
         request: VerificationRequest,
         config: ContractVerifierConfig,
@@ -301,6 +354,7 @@ impl ContractVerifier {
                     libraries: None,
                     output_selection: Some(default_output_selection),
                     optimizer,
+                    evm_version: None,
                     is_system: request.req.is_system,
                     metadata: None,
                 };
@@ -339,82 +393,6 @@ impl ContractVerifier {
         })
     }
 
-    fn decode_constructor_arguments_from_calldata(
-        calldata: DeployContractCalldata,
-        contract_address_to_verify: Address,
-    ) -> ConstructorArgs {
-        match calldata {
-            DeployContractCalldata::Deploy(calldata) => {
-                let create = DEPLOYER_CONTRACT.function("create").unwrap();
-                let create2 = DEPLOYER_CONTRACT.function("create2").unwrap();
-
-                let create_acc = DEPLOYER_CONTRACT.function("createAccount").unwrap();
-                let create2_acc = DEPLOYER_CONTRACT.function("create2Account").unwrap();
-
-                let force_deploy = DEPLOYER_CONTRACT
-                    .function("forceDeployOnAddresses")
-                    .unwrap();
-                // It's assumed that `create` and `create2` methods have the same parameters
-                // and the same for `createAccount` and `create2Account`.
-                match &calldata[0..4] {
-                    selector
-                        if selector == create.short_signature()
-                            || selector == create2.short_signature() =>
-                    {
-                        let tokens = create
-                            .decode_input(&calldata[4..])
-                            .expect("Failed to decode input");
-                        // Constructor arguments are in the third parameter.
-                        ConstructorArgs::Check(tokens[2].clone().into_bytes().expect(
-                            "The third parameter of `create/create2` should be of type `bytes`",
-                        ))
-                    }
-                    selector
-                        if selector == create_acc.short_signature()
-                            || selector == create2_acc.short_signature() =>
-                    {
-                        let tokens = create
-                            .decode_input(&calldata[4..])
-                            .expect("Failed to decode input");
-                        // Constructor arguments are in the third parameter.
-                        ConstructorArgs::Check(
-                            tokens[2].clone().into_bytes().expect(
-                                "The third parameter of `createAccount/create2Account` should be of type `bytes`",
-                            ),
-                        )
-                    }
-                    selector if selector == force_deploy.short_signature() => {
-                        let tokens = force_deploy
-                            .decode_input(&calldata[4..])
-                            .expect("Failed to decode input");
-                        let deployments = tokens[0].clone().into_array().unwrap();
-                        for deployment in deployments {
-                            match deployment {
-                                Token::Tuple(tokens) => {
-                                    let address = tokens[1].clone().into_address().unwrap();
-                                    if address == contract_address_to_verify {
-                                        let call_constructor =
-                                            tokens[2].clone().into_bool().unwrap();
-                                        return if call_constructor {
-                                            let input = tokens[4].clone().into_bytes().unwrap();
-                                            ConstructorArgs::Check(input)
-                                        } else {
-                                            ConstructorArgs::Ignore
-                                        };
-                                    }
-                                }
-                                _ => panic!("Expected `deployment` to be a tuple"),
-                            }
-                        }
-                        panic!("Couldn't find force deployment for given address");
-                    }
-                    _ => ConstructorArgs::Ignore,
-                }
-            }
-            DeployContractCalldata::Ignore => ConstructorArgs::Ignore,
-        }
-    }
-
     async fn process_result(
         storage: &mut StorageProcessor<'_>,
         request_id: usize,
@@ -534,3 +512,85 @@ impl JobProcessor for ContractVerifier {
         Ok(1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bytecodes produced by `zksolc` with the metadata hash enabled. The former has a padding word
+    /// before the hash, and the latter doesn't.
+    const PADDED_BYTECODE: &[u8] = include_bytes!(
+        "../../../../etc/multivm_bootloaders/vm_boojum_integration/fee_estimate.yul/fee_estimate.yul.zbin"
+    );
+    const UNPADDED_BYTECODE: &[u8] = include_bytes!(
+        "../../../../etc/multivm_bootloaders/vm_boojum_integration/gas_test.yul/gas_test.yul.zbin"
+    );
+
+    fn with_metadata_hash(bytecode: &[u8], hash: [u8; 32]) -> Vec<u8> {
+        let mut bytecode = bytecode.to_vec();
+        let hash_start = bytecode.len() - METADATA_HASH_SIZE;
+        bytecode[hash_start..].copy_from_slice(&hash);
+        bytecode
+    }
+
+    fn without_metadata_hash(bytecode: &[u8]) -> Vec<u8> {
+        let mut code = ContractVerifier::executable_code(bytecode, true)
+            .unwrap()
+            .to_vec();
+        if (code.len() / WORD_SIZE) % 2 == 0 {
+            code.extend_from_slice(&[0; WORD_SIZE]);
+        }
+        code
+    }
+
+    #[test]
+    fn parsing_zksolc_bytecode() {
+        let code = ContractVerifier::executable_code(PADDED_BYTECODE, true).unwrap();
+        // The padding word before the hash is stripped.
+        assert_eq!(code.len(), PADDED_BYTECODE.len() - 2 * WORD_SIZE);
+        let code = ContractVerifier::executable_code(UNPADDED_BYTECODE, true).unwrap();
+        assert_eq!(code.len(), UNPADDED_BYTECODE.len() - WORD_SIZE);
+
+        let truncated = &PADDED_BYTECODE[..PADDED_BYTECODE.len() - WORD_SIZE];
+        assert_eq!(ContractVerifier::executable_code(truncated, true), None);
+        assert_eq!(
+            ContractVerifier::executable_code(&PADDED_BYTECODE[1..], true),
+            None
+        );
+        assert_eq!(ContractVerifier::executable_code(&[0; 96], true), None);
+    }
+
+    #[test]
+    fn matching_zksolc_bytecode() {
+        for deployed in [PADDED_BYTECODE, UNPADDED_BYTECODE] {
+            assert_eq!(
+                ContractVerifier::match_bytecode(deployed, deployed, true),
+                Some(VerificationMatch::Full)
+            );
+
+            let compiled = with_metadata_hash(deployed, [1; 32]);
+            assert_eq!(
+                ContractVerifier::match_bytecode(&compiled, deployed, true),
+                Some(VerificationMatch::Partial)
+            );
+
+            let compiled = without_metadata_hash(deployed);
+            assert_eq!(compiled.len() % (2 * WORD_SIZE), WORD_SIZE);
+            assert_eq!(
+                ContractVerifier::match_bytecode(&compiled, deployed, false),
+                Some(VerificationMatch::Partial)
+            );
+
+            let mut compiled = with_metadata_hash(deployed, [1; 32]);
+            compiled[0] ^= 1;
+            assert_eq!(
+                ContractVerifier::match_bytecode(&compiled, deployed, true),
+                None
+            );
+        }
+        assert_eq!(
+            ContractVerifier::match_bytecode(PADDED_BYTECODE, UNPADDED_BYTECODE, true),
+            None
+        );
+    }
+}
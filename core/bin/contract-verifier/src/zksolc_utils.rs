@@ -60,6 +60,9 @@ pub struct Settings {
     /// The optimizer settings.
     #[serde(default)]
     pub optimizer: Optimizer,
+    /// The EVM version passed to solc.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evm_version: Option<String>,
     /// The metadata settings.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Metadata>,
@@ -4,6 +4,7 @@ use anyhow::Context;
 use serde::Deserialize;
 use url::Url;
 use zksync_basic_types::{Address, L1ChainId, L2ChainId, MiniblockNumber};
+use zksync_config::ObjectStoreConfig;
 use zksync_core::api_server::{
     tx_sender::TxSenderConfig,
     web3::{state::InternalApiConfig, Namespace},
@@ -119,6 +120,9 @@ pub struct OptionalENConfig {
     /// Maximum response body size in MiBs. Default is 10 MiB.
     #[serde(default = "OptionalENConfig::default_max_response_body_size_mb")]
     pub max_response_body_size_mb: usize,
+    /// Whether to return structured validation diagnostics as the error data of `eth_sendRawTransaction`.
+    #[serde(default)]
+    pub validation_diagnostics_in_errors: bool,
 
     // Other API config settings
     /// Interval between polling DB for pubsub (in ms).
@@ -195,6 +199,27 @@ pub struct OptionalENConfig {
     /// 0 means that sealing is synchronous; this is mostly useful for performance comparison, testing etc.
     #[serde(default = "OptionalENConfig::default_miniblock_seal_queue_capacity")]
     pub miniblock_seal_queue_capacity: usize,
+    /// Whether to bootstrap the node from the newest snapshot available on the main node if Postgres is empty.
+    /// Snapshot data is loaded from the object store configured via `EN_SNAPSHOTS_OBJECT_STORE_*` env variables.
+    #[serde(default)]
+    pub snapshots_recovery_enabled: bool,
+    /// Path to the KZG trusted setup. Must be set if the main node posts pubdata in EIP-4844 blobs, so that
+    /// L1 batch commitments computed by the node match ones computed by the main node.
+    pub kzg_trusted_setup_path: Option<String>,
+    /// Path to the RocksDB directory of the Merkle tree used to reconstruct state from L1 pubdata.
+    /// If set, the node additionally verifies L1 batches against commit transactions on L1, without trusting
+    /// the main node. The reconstructed state is only used for verification; the node still syncs from the main node.
+    pub l1_sync_merkle_tree_path: Option<String>,
+    /// Number of confirmations for L1 blocks processed when verifying state against L1. If not set,
+    /// only finalized L1 blocks are processed.
+    pub l1_sync_confirmations: Option<u64>,
+    /// Additional main node URLs to fail over to if the main node URL is unavailable, in the order of preference.
+    /// The fetcher and the transaction proxy switch to a fallback only if it agrees on recent miniblock hashes.
+    /// Intentionally private: use [`ExternalNodeConfig::main_node_urls()`] as it manages the missing port.
+    main_node_fallback_urls: Option<Vec<String>>,
+    /// Interval between health checks of main node endpoints in milliseconds.
+    #[serde(default = "OptionalENConfig::default_main_node_health_check_interval_ms")]
+    main_node_health_check_interval_ms: u64,
 }
 
 impl OptionalENConfig {
@@ -297,6 +322,10 @@ impl OptionalENConfig {
         10
     }
 
+    const fn default_main_node_health_check_interval_ms() -> u64 {
+        5_000
+    }
+
     pub fn polling_interval(&self) -> Duration {
         Duration::from_millis(self.polling_interval)
     }
@@ -335,6 +364,10 @@ impl OptionalENConfig {
         Duration::from_secs(self.merkle_tree_stalled_writes_timeout_sec)
     }
 
+    pub fn main_node_health_check_interval(&self) -> Duration {
+        Duration::from_millis(self.main_node_health_check_interval_ms)
+    }
+
     pub fn api_namespaces(&self) -> Vec<Namespace> {
         self.api_namespaces
             .clone()
@@ -407,6 +440,13 @@ impl PostgresConfig {
     }
 }
 
+/// Reads the object store config used for snapshot recovery from `EN_SNAPSHOTS_OBJECT_STORE_*` env variables.
+pub(crate) fn read_snapshots_object_store_config() -> anyhow::Result<ObjectStoreConfig> {
+    envy::prefixed("EN_SNAPSHOTS_OBJECT_STORE_")
+        .from_env::<ObjectStoreConfig>()
+        .context("could not load snapshots object store config")
+}
+
 /// External Node Config contains all the configuration required for the EN operation.
 /// It is split into three parts: required, optional and remote for easier navigation.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -418,6 +458,17 @@ pub struct ExternalNodeConfig {
 }
 
 impl ExternalNodeConfig {
+    /// Returns the main node URL followed by fallback URLs in the order of preference.
+    pub fn main_node_urls(&self) -> anyhow::Result<Vec<String>> {
+        let mut urls = vec![self.required.main_node_url()?];
+        for url in self.optional.main_node_fallback_urls.iter().flatten() {
+            let url =
+                RequiredENConfig::get_url(url).context("Could not parse fallback main node URL")?;
+            urls.push(url);
+        }
+        Ok(urls)
+    }
+
     /// Loads config from the environment variables and
     /// fetches contracts addresses from the main node.
     pub async fn collect() -> anyhow::Result<Self> {
@@ -515,6 +566,7 @@ impl From<ExternalNodeConfig> for InternalApiConfig {
             l2_testnet_paymaster_addr: config.remote.l2_testnet_paymaster_addr,
             req_entities_limit: config.optional.req_entities_limit,
             fee_history_limit: config.optional.fee_history_limit,
+            validation_diagnostics_in_errors: config.optional.validation_diagnostics_in_errors,
         }
     }
 }
@@ -536,6 +588,8 @@ impl From<ExternalNodeConfig> for TxSenderConfig {
             max_allowed_l2_tx_gas_limit: u32::MAX,
             validation_computational_gas_limit: u32::MAX,
             chain_id: config.remote.l2_chain_id,
+            // Transactions are proxied to the main node, which enforces mempool limits.
+            mempool_limits: Default::default(),
         }
     }
 }
@@ -25,6 +25,12 @@ fn parsing_optional_config_from_empty_env() {
         128 * BYTES_IN_MEGABYTE
     );
     assert_eq!(config.max_response_body_size(), 10 * BYTES_IN_MEGABYTE);
+    assert!(!config.snapshots_recovery_enabled);
+    assert_eq!(config.main_node_fallback_urls, None);
+    assert_eq!(
+        config.main_node_health_check_interval(),
+        Duration::from_secs(5)
+    );
 }
 
 #[test]
@@ -44,6 +50,11 @@ fn parsing_optional_config_from_env() {
         ("EN_MERKLE_TREE_MULTI_GET_CHUNK_SIZE", "1000"),
         ("EN_MERKLE_TREE_BLOCK_CACHE_SIZE_MB", "32"),
         ("EN_MAX_RESPONSE_BODY_SIZE_MB", "1"),
+        (
+            "EN_MAIN_NODE_FALLBACK_URLS",
+            "http://fallback-0:3050,http://fallback-1:3050",
+        ),
+        ("EN_MAIN_NODE_HEALTH_CHECK_INTERVAL_MS", "1000"),
     ];
     let env_vars = env_vars
         .into_iter()
@@ -70,4 +81,12 @@ fn parsing_optional_config_from_env() {
         32 * BYTES_IN_MEGABYTE
     );
     assert_eq!(config.max_response_body_size(), BYTES_IN_MEGABYTE);
+    assert_eq!(
+        config.main_node_fallback_urls.unwrap(),
+        ["http://fallback-0:3050", "http://fallback-1:3050"]
+    );
+    assert_eq!(
+        config.main_node_health_check_interval(),
+        Duration::from_secs(1)
+    );
 }
@@ -1,4 +1,4 @@
-use std::{sync::Arc, time::Duration};
+use std::{path::Path, sync::Arc, time::Duration};
 
 use anyhow::Context;
 use clap::Parser;
@@ -28,11 +28,15 @@ use zksync_core::{
     },
     sync_layer::{
         batch_status_updater::BatchStatusUpdater, external_io::ExternalIO, fetcher::FetcherCursor,
-        genesis::perform_genesis_if_needed, ActionQueue, MainNodeClient, SyncState,
+        genesis::perform_genesis_if_needed, l1_sync::L1BatchSyncer,
+        snapshot_recovery::SnapshotApplier, ActionQueue, FailoverMainNodeClient, SyncState,
     },
 };
 use zksync_dal::{healthcheck::ConnectionPoolHealthCheck, ConnectionPool};
+use zksync_eth_client::clients::http::QueryClient;
 use zksync_health_check::CheckHealth;
+use zksync_merkle_tree::RocksDBWrapper;
+use zksync_object_store::ObjectStoreFactory;
 use zksync_state::PostgresStorageCaches;
 use zksync_storage::RocksDB;
 use zksync_utils::wait_for_tasks::wait_for_tasks;
@@ -43,7 +47,7 @@ mod metrics;
 const RELEASE_MANIFEST: &str =
     std::include_str!("../../../../.github/release-please/manifest.json");
 
-use crate::config::ExternalNodeConfig;
+use crate::config::{read_snapshots_object_store_config, ExternalNodeConfig};
 
 /// Creates the state keeper configured to work in the external node mode.
 #[allow(clippy::too_many_arguments)]
@@ -53,6 +57,7 @@ async fn build_state_keeper(
     config: &ExternalNodeConfig,
     connection_pool: ConnectionPool,
     sync_state: SyncState,
+    main_node_client: FailoverMainNodeClient,
     l2_erc20_bridge_addr: Address,
     miniblock_sealer_handle: MiniblockSealerHandle,
     stop_receiver: watch::Receiver<bool>,
@@ -77,9 +82,6 @@ async fn build_state_keeper(
             config.optional.enum_index_migration_chunk_size,
         ));
 
-    let main_node_url = config.required.main_node_url().unwrap();
-    let main_node_client = <dyn MainNodeClient>::json_rpc(&main_node_url)
-        .expect("Failed creating JSON-RPC client for main node");
     let io = ExternalIO::new(
         miniblock_sealer_handle,
         connection_pool,
@@ -98,6 +100,7 @@ async fn build_state_keeper(
 async fn init_tasks(
     config: ExternalNodeConfig,
     connection_pool: ConnectionPool,
+    main_node_client: FailoverMainNodeClient,
 ) -> anyhow::Result<(
     Vec<task::JoinHandle<anyhow::Result<()>>>,
     watch::Sender<bool>,
@@ -112,14 +115,10 @@ async fn init_tasks(
 
     let version = semver::Version::parse(release_manifest_version)
         .expect("version in manifest is a correct semver format; qed");
-    let main_node_url = config
-        .required
-        .main_node_url()
-        .expect("Main node URL is incorrect");
     let (stop_sender, stop_receiver) = watch::channel(false);
     let mut healthchecks: Vec<Box<dyn CheckHealth>> = Vec::new();
     // Create components.
-    let gas_adjuster = Arc::new(MainNodeGasPriceFetcher::new(&main_node_url));
+    let gas_adjuster = Arc::new(MainNodeGasPriceFetcher::new(main_node_client.clone()));
 
     let sync_state = SyncState::new();
     let (action_queue_sender, action_queue) = ActionQueue::new();
@@ -154,6 +153,7 @@ async fn init_tasks(
         &config,
         connection_pool.clone(),
         sync_state.clone(),
+        main_node_client.clone(),
         config.remote.l2_erc20_bridge_addr,
         miniblock_sealer_handle,
         stop_receiver.clone(),
@@ -161,8 +161,6 @@ async fn init_tasks(
     )
     .await;
 
-    let main_node_client = <dyn MainNodeClient>::json_rpc(&main_node_url)
-        .context("Failed creating JSON-RPC client for main node")?;
     let singleton_pool_builder = ConnectionPool::singleton(&config.postgres.database_url);
     let fetcher_cursor = {
         let pool = singleton_pool_builder
@@ -175,7 +173,7 @@ async fn init_tasks(
             .context("failed to load `MainNodeFetcher` cursor from Postgres")?
     };
     let fetcher = fetcher_cursor.into_fetcher(
-        Box::new(main_node_client),
+        Box::new(main_node_client.clone()),
         action_queue_sender,
         sync_state.clone(),
         stop_receiver.clone(),
@@ -192,8 +190,10 @@ async fn init_tasks(
         block_cache_capacity: config.optional.merkle_tree_block_cache_size(),
         memtable_capacity: config.optional.merkle_tree_memtable_capacity(),
         stalled_writes_timeout: config.optional.merkle_tree_stalled_writes_timeout(),
+        kzg_trusted_setup_path: config.optional.kzg_trusted_setup_path.as_deref(),
     })
-    .await;
+    .await
+    .context("failed initializing metadata calculator")?;
     healthchecks.push(Box::new(metadata_calculator.tree_health_check()));
 
     let consistency_checker = ConsistencyChecker::new(
@@ -209,7 +209,7 @@ async fn init_tasks(
     );
 
     let batch_status_updater = BatchStatusUpdater::new(
-        &main_node_url,
+        main_node_client.clone(),
         singleton_pool_builder
             .build()
             .await
@@ -227,16 +227,42 @@ async fn init_tasks(
 
     let consistency_checker_handle = tokio::spawn(consistency_checker.run(stop_receiver.clone()));
 
+    if let Some(l1_sync_tree_path) = &config.optional.l1_sync_merkle_tree_path {
+        let eth_client_url = config
+            .required
+            .eth_client_url()
+            .context("L1 client URL is incorrect")?;
+        let eth_client = QueryClient::new(&eth_client_url).context("failed creating L1 client")?;
+        let l1_sync_pool = singleton_pool_builder
+            .build()
+            .await
+            .context("failed to build a connection pool for L1BatchSyncer")?;
+        let tree_db = RocksDBWrapper::new(Path::new(l1_sync_tree_path));
+        let l1_batch_syncer = L1BatchSyncer::new(
+            eth_client,
+            l1_sync_pool,
+            config.remote.diamond_proxy_addr,
+            tree_db,
+        )
+        .with_confirmations(config.optional.l1_sync_confirmations);
+        healthchecks.push(Box::new(l1_batch_syncer.health_check()));
+        task_handles.push(tokio::spawn(l1_batch_syncer.run(stop_receiver.clone())));
+    }
+
     let updater_handle = task::spawn(batch_status_updater.run(stop_receiver.clone()));
     let sk_handle = task::spawn(state_keeper.run());
     let fetcher_handle = tokio::spawn(fetcher.run());
     let gas_adjuster_handle = tokio::spawn(gas_adjuster.clone().run(stop_receiver.clone()));
+    let main_node_health_checks_handle = tokio::spawn(main_node_client.clone().run_health_checks(
+        config.optional.main_node_health_check_interval(),
+        stop_receiver.clone(),
+    ));
 
     let (tx_sender, vm_barrier, cache_update_handle) = {
         let mut tx_sender_builder =
             TxSenderBuilder::new(config.clone().into(), connection_pool.clone())
                 .with_main_connection_pool(connection_pool.clone())
-                .with_tx_proxy(&main_node_url);
+                .with_tx_proxy(main_node_client);
 
         // Add rate limiter if enabled.
         if let Some(tps_limit) = config.optional.transactions_per_sec_limit {
@@ -320,6 +346,7 @@ async fn init_tasks(
         updater_handle,
         tree_handle,
         gas_adjuster_handle,
+        main_node_health_checks_handle,
     ]);
     task_handles.push(consistency_checker_handle);
 
@@ -425,9 +452,29 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("Started the external node");
     tracing::info!("Main node URL is: {}", main_node_url);
 
-    // Make sure that genesis is performed.
-    let main_node_client = <dyn MainNodeClient>::json_rpc(&main_node_url)
+    let main_node_client = FailoverMainNodeClient::new(&config.main_node_urls()?)
         .context("Failed creating JSON-RPC client for main node")?;
+    main_node_client
+        .seed_checkpoints(&mut connection_pool.access_storage().await?)
+        .await?;
+    if config.optional.snapshots_recovery_enabled {
+        let blob_store_config = read_snapshots_object_store_config()
+            .context("Failed loading object store config for snapshots")?;
+        let blob_store = ObjectStoreFactory::new(blob_store_config)
+            .create_store()
+            .await?;
+        let status = SnapshotApplier::new(&connection_pool, &main_node_client, &*blob_store)
+            .apply()
+            .await
+            .context("Applying snapshot failed")?;
+        if let Some(status) = status {
+            tracing::info!(
+                "Node is recovered from snapshot at L1 batch #{}",
+                status.l1_batch_number
+            );
+        }
+    }
+    // Make sure that genesis is performed.
     perform_genesis_if_needed(
         &mut connection_pool.access_storage().await.unwrap(),
         config.remote.l2_chain_id,
@@ -436,12 +483,16 @@ async fn main() -> anyhow::Result<()> {
     .await
     .context("Performing genesis failed")?;
 
-    let (task_handles, stop_sender, health_check_handle, stop_receiver) =
-        init_tasks(config.clone(), connection_pool.clone())
-            .await
-            .context("init_tasks")?;
+    let (task_handles, stop_sender, health_check_handle, stop_receiver) = init_tasks(
+        config.clone(),
+        connection_pool.clone(),
+        main_node_client.clone(),
+    )
+    .await
+    .context("init_tasks")?;
 
-    let reorg_detector = ReorgDetector::new(&main_node_url, connection_pool.clone(), stop_receiver);
+    let reorg_detector =
+        ReorgDetector::new(main_node_client, connection_pool.clone(), stop_receiver);
     let mut reorg_detector_handle = tokio::spawn(reorg_detector.run()).fuse();
 
     let particular_crypto_alerts = None;
@@ -271,7 +271,7 @@ async fn main() -> anyhow::Result<()> {
         SnapshotsObjectStoreConfig::from_env().context("SnapshotsObjectStoreConfig::from_env()")?;
     let blob_store = ObjectStoreFactory::new(object_store_config.0)
         .create_store()
-        .await;
+        .await?;
 
     let postgres_config = PostgresConfig::from_env().context("PostgresConfig")?;
     let creator_config =
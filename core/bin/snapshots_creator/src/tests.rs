@@ -153,7 +153,7 @@ async fn persisting_snapshot_metadata() {
     let pool = ConnectionPool::test_pool().await;
     let mut rng = thread_rng();
     let object_store_factory = ObjectStoreFactory::mock();
-    let object_store = object_store_factory.create_store().await;
+    let object_store = object_store_factory.create_store().await.unwrap();
 
     // Insert some data to Postgres.
     let mut conn = pool.access_storage().await.unwrap();
@@ -193,7 +193,7 @@ async fn persisting_snapshot_factory_deps() {
     let pool = ConnectionPool::test_pool().await;
     let mut rng = thread_rng();
     let object_store_factory = ObjectStoreFactory::mock();
-    let object_store = object_store_factory.create_store().await;
+    let object_store = object_store_factory.create_store().await.unwrap();
 
     // Insert some data to Postgres.
     let mut conn = pool.access_storage().await.unwrap();
@@ -204,7 +204,7 @@ async fn persisting_snapshot_factory_deps() {
         .unwrap();
     let snapshot_l1_batch_number = L1BatchNumber(8);
 
-    let object_store = object_store_factory.create_store().await;
+    let object_store = object_store_factory.create_store().await.unwrap();
     let SnapshotFactoryDependencies { factory_deps } =
         object_store.get(snapshot_l1_batch_number).await.unwrap();
     let actual_deps: HashSet<_> = factory_deps.into_iter().collect();
@@ -216,7 +216,7 @@ async fn persisting_snapshot_logs() {
     let pool = ConnectionPool::test_pool().await;
     let mut rng = thread_rng();
     let object_store_factory = ObjectStoreFactory::mock();
-    let object_store = object_store_factory.create_store().await;
+    let object_store = object_store_factory.create_store().await.unwrap();
 
     // Insert some data to Postgres.
     let mut conn = pool.access_storage().await.unwrap();
@@ -227,7 +227,7 @@ async fn persisting_snapshot_logs() {
         .unwrap();
     let snapshot_l1_batch_number = L1BatchNumber(8);
 
-    let object_store = object_store_factory.create_store().await;
+    let object_store = object_store_factory.create_store().await.unwrap();
     let mut actual_logs = HashSet::new();
     for chunk_id in 0..MIN_CHUNK_COUNT {
         let key = SnapshotStorageLogsStorageKey {
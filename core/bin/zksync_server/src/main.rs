@@ -21,7 +21,10 @@ use zksync_core::{
     genesis_init, initialize_components, is_genesis_needed, setup_sigint_handler,
     temp_config_store::TempConfigStore, Component, Components,
 };
-use zksync_env_config::FromEnv;
+use zksync_env_config::{
+    object_store::{ProverObjectStoreConfig, SnapshotsObjectStoreConfig},
+    FromEnv,
+};
 use zksync_storage::RocksDB;
 use zksync_utils::wait_for_tasks::wait_for_tasks;
 
@@ -122,6 +125,12 @@ async fn main() -> anyhow::Result<()> {
         gas_adjuster_config: GasAdjusterConfig::from_env().ok(),
         prover_configs: ProverConfigs::from_env().ok(),
         object_store_config: ObjectStoreConfig::from_env().ok(),
+        prover_object_store_config: ProverObjectStoreConfig::from_env()
+            .ok()
+            .map(|config| config.0),
+        snapshots_object_store_config: SnapshotsObjectStoreConfig::from_env()
+            .ok()
+            .map(|config| config.0),
     };
 
     let postgres_config = configs.postgres_config.clone().context("PostgresConfig")?;
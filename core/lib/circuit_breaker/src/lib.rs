@@ -6,7 +6,10 @@ use thiserror::Error;
 use tokio::sync::watch;
 use zksync_config::configs::chain::CircuitBreakerConfig;
 
+use crate::operator_nonce::OperatorNonceIssue;
+
 pub mod l1_txs;
+pub mod operator_nonce;
 pub mod replication_lag;
 pub mod utils;
 
@@ -16,6 +19,8 @@ pub enum CircuitBreakerError {
     FailedL1Transaction,
     #[error("Replication lag ({0:?}) is above the threshold ({1:?})")]
     ReplicationLag(u32, u32),
+    #[error("Operator nonce issue {0:?} is not resolved for more than {1:?}")]
+    OperatorNonceIssue(OperatorNonceIssue, Duration),
 }
 
 /// Checks circuit breakers
@@ -0,0 +1,54 @@
+use std::time::{Duration, Instant};
+
+use tokio::sync::watch;
+
+use crate::{CircuitBreaker, CircuitBreakerError};
+
+/// Anomaly in the operator nonce on L1 compared to the nonces of `eth_txs` stored in Postgres.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperatorNonceIssue {
+    /// No `eth_txs` use nonces in the `l1_nonce..first_pending_nonce` range, so the pending `eth_txs` cannot be mined.
+    Gap {
+        l1_nonce: u64,
+        first_pending_nonce: u64,
+    },
+    /// The nonce of an `eth_tx` was consumed on L1 by a transaction not sent by the server.
+    ExternallyConsumed { nonce: u64 },
+}
+
+/// Operator nonce issue together with the moment it was first detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetectedNonceIssue {
+    pub issue: OperatorNonceIssue,
+    pub detected_at: Instant,
+}
+
+impl DetectedNonceIssue {
+    pub fn new(issue: OperatorNonceIssue) -> Self {
+        Self {
+            issue,
+            detected_at: Instant::now(),
+        }
+    }
+}
+
+/// Checks operator nonce issues reported by the Ethereum transaction manager. Issues are normally resolved
+/// by the manager automatically; the circuit breaker is triggered only if an issue persists for longer than the limit.
+#[derive(Debug)]
+pub struct OperatorNonceChecker {
+    pub issues: watch::Receiver<Option<DetectedNonceIssue>>,
+    pub limit: Duration,
+}
+
+#[async_trait::async_trait]
+impl CircuitBreaker for OperatorNonceChecker {
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        let detected_issue = *self.issues.borrow();
+        match detected_issue {
+            Some(detected) if detected.detected_at.elapsed() > self.limit => Err(
+                CircuitBreakerError::OperatorNonceIssue(detected.issue, self.limit),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
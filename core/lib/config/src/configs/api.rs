@@ -89,6 +89,10 @@ pub struct Web3JsonRpcConfig {
     pub websocket_requests_per_minute_limit: Option<u32>,
     /// Tree API url, currently used to proxy `getProof` calls to the tree
     pub tree_api_url: Option<String>,
+    /// Whether to return structured validation diagnostics as the error data of `eth_sendRawTransaction`
+    /// when a transaction fails account validation. If disabled (the default), the error data is left empty
+    /// and the diagnostics are only available via `zks_debugValidation`.
+    pub validation_diagnostics_in_errors: Option<bool>,
 }
 
 impl Web3JsonRpcConfig {
@@ -126,6 +130,7 @@ impl Web3JsonRpcConfig {
             max_response_body_size_mb: Default::default(),
             websocket_requests_per_minute_limit: Default::default(),
             tree_api_url: None,
+            validation_diagnostics_in_errors: None,
         }
     }
 
@@ -212,6 +217,10 @@ impl Web3JsonRpcConfig {
     pub fn tree_api_url(&self) -> Option<String> {
         self.tree_api_url.clone()
     }
+
+    pub fn validation_diagnostics_in_errors(&self) -> bool {
+        self.validation_diagnostics_in_errors.unwrap_or(false)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -1,6 +1,6 @@
 use std::{str::FromStr, time::Duration};
 
-use serde::Deserialize;
+use serde::{de, Deserialize, Deserializer};
 use zksync_basic_types::{network::Network, Address, L2ChainId};
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -93,6 +93,63 @@ pub struct StateKeeperConfig {
 
     /// Number of keys that is processed by enum_index migration in State Keeper each L1 batch.
     pub enum_index_migration_chunk_size: Option<usize>,
+
+    /// Optional seal criteria applied in the specified order after the built-in ones. Built-in criteria
+    /// (slots, gas, pubdata, geometry etc.) are always applied since they ensure that L1 batches can be proven.
+    #[serde(default)]
+    pub seal_criteria: Vec<OptionalSealCriterion>,
+    /// Max total size (in bytes) of long L2-to-L1 messages in an L1 batch; used by the `l2_to_l1_messages` criterion.
+    pub max_l2_to_l1_messages_in_batch: Option<usize>,
+    /// Max wall-clock time (in ms) spent by the state keeper executing an L1 batch, after which the batch is sealed.
+    /// Since this limit isn't deterministic, it's enforced by the timeout sealer rather than as a seal criterion.
+    pub max_batch_execution_time_ms: Option<u64>,
+    /// Caps on the L2 gas used in an L1 batch by transactions calling specific contracts, in the `<address>:<gas>`
+    /// format; used by the `contract_gas_caps` criterion.
+    #[serde(default)]
+    pub contract_gas_caps: Vec<ContractGasCap>,
+}
+
+/// Seal criteria that can be enabled in [`StateKeeperConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OptionalSealCriterion {
+    /// Limits the number of L2-to-L1 messages in an L1 batch.
+    L2ToL1Messages,
+    /// Limits the L2 gas used in an L1 batch by transactions calling specific contracts.
+    ContractGasCaps,
+}
+
+/// Cap on the L2 gas used in an L1 batch by transactions calling a specific contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContractGasCap {
+    pub address: Address,
+    pub max_gas: u64,
+}
+
+impl FromStr for ContractGasCap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, max_gas) = s
+            .split_once(':')
+            .ok_or_else(|| format!("expected `<address>:<gas>`, got `{s}`"))?;
+        let address = address
+            .trim()
+            .parse()
+            .map_err(|err| format!("invalid contract address `{address}`: {err}"))?;
+        let max_gas = max_gas
+            .trim()
+            .parse()
+            .map_err(|err| format!("invalid gas cap `{max_gas}`: {err}"))?;
+        Ok(Self { address, max_gas })
+    }
+}
+
+impl<'de> Deserialize<'de> for ContractGasCap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
 }
 
 impl StateKeeperConfig {
@@ -121,12 +178,20 @@ impl StateKeeperConfig {
             virtual_blocks_per_miniblock: 1,
             upload_witness_inputs_to_gcs: false,
             enum_index_migration_chunk_size: None,
+            seal_criteria: vec![],
+            max_l2_to_l1_messages_in_batch: None,
+            max_batch_execution_time_ms: None,
+            contract_gas_caps: vec![],
         }
     }
 
     pub fn enum_index_migration_chunk_size(&self) -> usize {
         self.enum_index_migration_chunk_size.unwrap_or(1_000)
     }
+
+    pub fn max_batch_execution_time(&self) -> Option<Duration> {
+        self.max_batch_execution_time_ms.map(Duration::from_millis)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -147,6 +212,10 @@ pub struct CircuitBreakerConfig {
     pub http_req_max_retry_number: usize,
     pub http_req_retry_interval_sec: u8,
     pub replication_lag_limit_sec: Option<u32>,
+    /// Maximum duration an operator nonce issue (a nonce gap or a nonce consumed by an external transaction)
+    /// may remain unresolved by the Ethereum transaction manager before the circuit breaker is triggered.
+    /// If not set, such issues never trigger the circuit breaker.
+    pub operator_nonce_issue_limit_sec: Option<u32>,
 }
 
 impl CircuitBreakerConfig {
@@ -154,11 +223,28 @@ impl CircuitBreakerConfig {
         Duration::from_millis(self.sync_interval_ms)
     }
 
+    pub fn operator_nonce_issue_limit(&self) -> Option<Duration> {
+        self.operator_nonce_issue_limit_sec
+            .map(|limit| Duration::from_secs(limit.into()))
+    }
+
     pub fn http_req_retry_interval(&self) -> Duration {
         Duration::from_secs(self.http_req_retry_interval_sec as u64)
     }
 }
 
+/// Policy for ordering L2 transactions of different accounts in the mempool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MempoolTxOrdering {
+    /// Transactions are ordered by the time they were received at.
+    #[default]
+    Fifo,
+    /// Transactions are ordered by their effective tip, i.e. `max_priority_fee_per_gas` capped by
+    /// `max_fee_per_gas` minus the fair L2 gas price, and then by the time they were received at.
+    PriorityFee,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct MempoolConfig {
     pub sync_interval_ms: u64,
@@ -167,6 +253,16 @@ pub struct MempoolConfig {
     pub stuck_tx_timeout: u64,
     pub remove_stuck_txs: bool,
     pub delay_interval: u64,
+    /// Minimum fee bump (in percent) required to replace a pending L2 transaction with the same nonce.
+    /// If not specified, a replacement transaction must not have lower fees than the replaced one.
+    #[serde(default)]
+    pub min_replacement_fee_bump_percent: Option<u64>,
+    /// Maximum number of pending L2 transactions per account kept in the mempool. Not limited if not specified.
+    #[serde(default)]
+    pub max_pending_txs_per_account: Option<usize>,
+    /// Ordering policy for L2 transactions of different accounts. If not specified, FIFO ordering is used.
+    #[serde(default)]
+    pub tx_ordering: MempoolTxOrdering,
 }
 
 impl MempoolConfig {
@@ -1,7 +1,7 @@
 use std::time::Duration;
 
 use serde::Deserialize;
-use zksync_basic_types::H256;
+use zksync_basic_types::{Address, H256};
 
 /// Configuration for the Ethereum sender crate.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -35,6 +35,15 @@ impl ETHSenderConfig {
                 l1_batch_min_age_before_execute_seconds: None,
                 max_acceptable_priority_fee_in_gwei: 100000000000,
                 proof_loading_mode: ProofLoadingMode::OldProofFromDb,
+                pubdata_sending_mode: PubdataSendingMode::Calldata,
+                kzg_trusted_setup_path: None,
+                blob_tx_resend_interval_blocks: None,
+                signer_mode: OperatorSignerMode::PrivateKey,
+                remote_signer_url: None,
+                remote_signer_operator_address: None,
+                remote_signer_tls_cert_path: None,
+                remote_signer_tls_key_path: None,
+                remote_signer_ca_cert_path: None,
             },
             gas_adjuster: GasAdjusterConfig {
                 default_priority_fee_per_gas: 1000000000,
@@ -63,6 +72,27 @@ pub enum ProofLoadingMode {
     FriProofFromGcs,
 }
 
+/// How L1 batch pubdata is posted to L1.
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq)]
+pub enum PubdataSendingMode {
+    /// Pubdata is posted as a part of the commit transaction calldata.
+    #[default]
+    Calldata,
+    /// Pubdata is posted in EIP-4844 blobs. Requires L1 contracts supporting blob pubdata.
+    Blobs,
+}
+
+/// How transactions sent from the operator account are signed.
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq)]
+pub enum OperatorSignerMode {
+    /// Transactions are signed locally with the private key loaded from `ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY`.
+    #[default]
+    PrivateKey,
+    /// Transactions are signed by a remote signer speaking the Web3Signer-compatible `eth_signTransaction`
+    /// JSON-RPC protocol; the operator private key is not available to the server.
+    Remote,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct SenderConfig {
     pub aggregated_proof_sizes: Vec<usize>,
@@ -96,9 +126,39 @@ pub struct SenderConfig {
 
     /// The mode in which proofs are loaded, either from DB/GCS for FRI/Old proof.
     pub proof_loading_mode: ProofLoadingMode,
+
+    /// The mode in which L1 batch pubdata is posted to L1.
+    #[serde(default)]
+    pub pubdata_sending_mode: PubdataSendingMode,
+    /// Path to the KZG trusted setup file (in the format of `trusted_setup.txt` from the consensus specs).
+    /// Required if pubdata is posted in blobs.
+    pub kzg_trusted_setup_path: Option<String>,
+    /// Minimum number of L1 blocks between sending attempts of a blob transaction. A blob transaction can only
+    /// be replaced in the L1 mempool with all its fees doubled, so it is replaced only if stuck for this number
+    /// of blocks. If not specified, [`Self::DEFAULT_BLOB_TX_RESEND_INTERVAL_BLOCKS`] is used.
+    #[serde(default)]
+    pub blob_tx_resend_interval_blocks: Option<u32>,
+
+    /// How transactions sent from the operator account are signed.
+    #[serde(default)]
+    pub signer_mode: OperatorSignerMode,
+    /// URL of the remote signer. Required if the remote signer is used.
+    pub remote_signer_url: Option<String>,
+    /// Address of the operator account managed by the remote signer. Required if the remote signer is used.
+    pub remote_signer_operator_address: Option<Address>,
+    /// Path to the PEM-encoded client certificate (chain) used to authenticate with the remote signer.
+    /// If specified, `remote_signer_tls_key_path` must be specified as well.
+    pub remote_signer_tls_cert_path: Option<String>,
+    /// Path to the PEM-encoded PKCS #8 private key for the client certificate.
+    pub remote_signer_tls_key_path: Option<String>,
+    /// Path to the PEM-encoded CA certificate used to verify the remote signer. If not specified,
+    /// the system root certificates are used.
+    pub remote_signer_ca_cert_path: Option<String>,
 }
 
 impl SenderConfig {
+    pub const DEFAULT_BLOB_TX_RESEND_INTERVAL_BLOCKS: u32 = 10;
+
     /// Converts `self.tx_poll_period` into `Duration`.
     pub fn tx_poll_period(&self) -> Duration {
         Duration::from_secs(self.tx_poll_period)
@@ -109,6 +169,11 @@ impl SenderConfig {
         Duration::from_secs(self.aggregate_tx_poll_period)
     }
 
+    pub fn blob_tx_resend_interval_blocks(&self) -> u32 {
+        self.blob_tx_resend_interval_blocks
+            .unwrap_or(Self::DEFAULT_BLOB_TX_RESEND_INTERVAL_BLOCKS)
+    }
+
     // Don't load private key, if it's not required.
     pub fn private_key(&self) -> Option<H256> {
         std::env::var("ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY")
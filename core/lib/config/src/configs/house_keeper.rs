@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use serde::Deserialize;
 
 /// Configuration for the house keeper.
@@ -16,4 +18,18 @@ pub struct HouseKeeperConfig {
     pub fri_prover_stats_reporting_interval_ms: u64,
     pub fri_proof_compressor_job_retrying_interval_ms: u64,
     pub fri_proof_compressor_stats_reporting_interval_ms: u64,
+    /// Interval between object store garbage collection runs. Garbage collection removes prover artifacts
+    /// for L1 batches proven on L1 and expired storage snapshots. If not set, garbage collection is disabled.
+    #[serde(default)]
+    pub object_store_gc_interval_ms: Option<u64>,
+    /// Period after which storage snapshots are removed by garbage collection. The latest snapshot
+    /// is never removed. If not set, snapshots are retained indefinitely.
+    #[serde(default)]
+    pub snapshots_retention_period_sec: Option<u64>,
+}
+
+impl HouseKeeperConfig {
+    pub fn snapshots_retention_period(&self) -> Option<Duration> {
+        self.snapshots_retention_period_sec.map(Duration::from_secs)
+    }
 }
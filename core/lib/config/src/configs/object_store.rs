@@ -5,6 +5,8 @@ pub enum ObjectStoreMode {
     GCS,
     GCSWithCredentialFile,
     FileBacked,
+    /// S3-compatible storage (AWS S3, MinIO etc.). Uses `s3_*` config params.
+    S3,
 }
 
 /// Configuration for the object store
@@ -15,4 +17,17 @@ pub struct ObjectStoreConfig {
     pub file_backed_base_path: String,
     pub gcs_credential_file_path: String,
     pub max_retries: u16,
+    /// Endpoint of the S3-compatible storage, e.g. `http://localhost:9000` for a local MinIO instance.
+    /// If not specified, the AWS S3 endpoint for `s3_region` is used.
+    #[serde(default)]
+    pub s3_endpoint: Option<String>,
+    /// Region of the S3 bucket. If not specified, `us-east-1` is used.
+    #[serde(default)]
+    pub s3_region: Option<String>,
+    /// Access key ID used to authenticate S3 requests.
+    #[serde(default)]
+    pub s3_access_key_id: Option<String>,
+    /// Secret access key used to authenticate S3 requests.
+    #[serde(default)]
+    pub s3_secret_access_key: Option<String>,
 }
@@ -0,0 +1,2 @@
+ALTER TABLE eth_txs DROP COLUMN IF EXISTS blob_sidecar;
+ALTER TABLE eth_txs_history DROP COLUMN IF EXISTS blob_base_fee_per_gas;
@@ -0,0 +1,35 @@
+-- Hash of the deployed bytecode; allows propagating verification info to contracts with identical bytecode.
+-- Backfilled below for contracts verified before the column was added.
+ALTER TABLE contracts_verification_info ADD COLUMN IF NOT EXISTS bytecode_hash BYTEA;
+CREATE INDEX IF NOT EXISTS contracts_verification_info_bytecode_hash_idx ON contracts_verification_info (bytecode_hash);
+
+-- Backfill bytecode hashes of already verified contracts from the latest writes to their account code storage slots.
+UPDATE contracts_verification_info
+SET
+    bytecode_hash = latest_code.value
+FROM
+    (
+        SELECT DISTINCT
+            ON (storage_logs.key) storage_logs.key,
+            storage_logs.value
+        FROM
+            storage_logs
+        WHERE
+            storage_logs.address = '\x0000000000000000000000000000000000008002'::bytea
+            AND storage_logs.key IN (
+                SELECT
+                    '\x000000000000000000000000'::bytea || address
+                FROM
+                    contracts_verification_info
+                WHERE
+                    bytecode_hash IS NULL
+            )
+        ORDER BY
+            storage_logs.key,
+            storage_logs.miniblock_number DESC,
+            storage_logs.operation_number DESC
+    ) AS latest_code
+WHERE
+    latest_code.key = '\x000000000000000000000000'::bytea || contracts_verification_info.address
+    AND latest_code.value != '\x0000000000000000000000000000000000000000000000000000000000000000'::bytea
+    AND contracts_verification_info.bytecode_hash IS NULL;
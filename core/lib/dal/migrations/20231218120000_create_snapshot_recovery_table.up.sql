@@ -0,0 +1,11 @@
+CREATE TABLE snapshot_recovery
+(
+    l1_batch_number                BIGINT    NOT NULL PRIMARY KEY,
+    l1_batch_root_hash             BYTEA     NOT NULL,
+    miniblock_number               BIGINT    NOT NULL,
+    miniblock_hash                 BYTEA     NOT NULL,
+    storage_logs_chunks_processed  BOOLEAN[] NOT NULL,
+
+    created_at                     TIMESTAMP NOT NULL,
+    updated_at                     TIMESTAMP NOT NULL
+);
@@ -0,0 +1,3 @@
+-- Initial writes restored from a snapshot reference L1 batches preceding the snapshot, which are not present
+-- in the database. Initial writes are deleted explicitly when reverting L1 batches.
+ALTER TABLE initial_writes DROP CONSTRAINT IF EXISTS initial_writes_l1_batch_number_fkey;
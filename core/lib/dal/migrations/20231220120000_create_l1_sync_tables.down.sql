@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS l1_sync_factory_deps;
+DROP TABLE IF EXISTS l1_sync_storage;
+DROP TABLE IF EXISTS l1_sync_batches;
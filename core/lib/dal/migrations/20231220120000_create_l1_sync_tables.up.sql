@@ -0,0 +1,42 @@
+CREATE TABLE l1_sync_batches
+(
+    l1_batch_number        BIGINT    NOT NULL PRIMARY KEY,
+    timestamp              BIGINT    NOT NULL,
+    root_hash              BYTEA     NOT NULL,
+    rollup_last_leaf_index BIGINT    NOT NULL,
+    l2_to_l1_logs          BYTEA[]   NOT NULL,
+    l2_to_l1_messages      BYTEA[]   NOT NULL,
+    -- `NULL` for the L1 batch the sync was started from.
+    commit_tx_hash         BYTEA,
+    commit_l1_block_number BIGINT    NOT NULL,
+
+    created_at             TIMESTAMP NOT NULL,
+    updated_at             TIMESTAMP NOT NULL
+);
+
+-- Slot values are stored for each L1 batch in which the slot was updated, so that L1 batch reverts can be followed.
+CREATE TABLE l1_sync_storage
+(
+    hashed_key        BYTEA     NOT NULL,
+    l1_batch_number   BIGINT    NOT NULL,
+    enumeration_index BIGINT    NOT NULL,
+    value             BYTEA     NOT NULL,
+
+    created_at        TIMESTAMP NOT NULL,
+    updated_at        TIMESTAMP NOT NULL,
+    PRIMARY KEY (hashed_key, l1_batch_number)
+);
+
+CREATE INDEX l1_sync_storage_enumeration_index_idx ON l1_sync_storage (enumeration_index, l1_batch_number);
+CREATE INDEX l1_sync_storage_l1_batch_number_idx ON l1_sync_storage (l1_batch_number);
+
+CREATE TABLE l1_sync_factory_deps
+(
+    bytecode_hash   BYTEA     NOT NULL PRIMARY KEY,
+    bytecode        BYTEA     NOT NULL,
+    -- L1 batch in which the bytecode was first published.
+    l1_batch_number BIGINT    NOT NULL,
+
+    created_at      TIMESTAMP NOT NULL,
+    updated_at      TIMESTAMP NOT NULL
+);
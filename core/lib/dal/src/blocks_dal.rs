@@ -6,12 +6,14 @@ use std::{
 
 use anyhow::Context as _;
 use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
+use serde::Serialize;
 use sqlx::Row;
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
     block::{BlockGasCount, L1BatchHeader, MiniblockHeader},
     commitment::{L1BatchMetadata, L1BatchWithMetadata},
-    Address, L1BatchNumber, LogQuery, MiniblockNumber, ProtocolVersionId, H256,
+    Address, L1BatchNumber, LogQuery, MiniblockNumber, ProtocolVersionId,
+    ACCOUNT_CODE_STORAGE_ADDRESS, FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256,
     MAX_GAS_PER_PUBDATA_BYTE, U256,
 };
 
@@ -22,6 +24,32 @@ use crate::{
     StorageProcessor,
 };
 
+/// Numbers of rows in Postgres tables affected by reverting the node state to a certain L1 batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RevertRowCounts {
+    pub l1_batches: u64,
+    pub miniblocks: u64,
+    /// Transactions are not removed, but rather reset to the pending state.
+    pub transactions: u64,
+    pub call_traces: u64,
+    pub events: u64,
+    pub l2_to_l1_logs: u64,
+    pub tokens: u64,
+    pub factory_deps: u64,
+    pub storage_logs: u64,
+    /// Number of distinct storage slots reverted to previous values or removed.
+    pub storage: u64,
+    pub initial_writes: u64,
+    pub protective_reads: u64,
+}
+
+impl RevertRowCounts {
+    /// Checks whether a revert would not affect any rows.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 #[derive(Debug)]
 pub struct BlocksDal<'a, 'c> {
     pub(crate) storage: &'a mut StorageProcessor<'c>,
@@ -1881,6 +1909,18 @@ impl BlocksDal<'_, '_> {
         last_batch_to_keep: Option<L1BatchNumber>,
     ) -> sqlx::Result<()> {
         let block_number = last_batch_to_keep.map_or(-1, |number| number.0 as i64);
+        // Initial writes are not linked to L1 batches with a foreign key since a node recovered from a snapshot
+        // has initial writes for L1 batches not present in the database; thus, they need to be deleted explicitly.
+        sqlx::query!(
+            r#"
+            DELETE FROM initial_writes
+            WHERE
+                l1_batch_number > $1
+            "#,
+            block_number
+        )
+        .execute(self.storage.conn())
+        .await?;
         sqlx::query!(
             r#"
             DELETE FROM l1_batches
@@ -1921,6 +1961,167 @@ impl BlocksDal<'_, '_> {
         Ok(())
     }
 
+    /// Counts rows that would be removed or reset by reverting Postgres data so that the specified L1 batch
+    /// and miniblock are the last ones left. The conditions mirror the ones used by the corresponding
+    /// rollback / deletion methods.
+    pub async fn get_revert_row_counts(
+        &mut self,
+        last_l1_batch_to_keep: L1BatchNumber,
+        last_miniblock_to_keep: MiniblockNumber,
+    ) -> sqlx::Result<RevertRowCounts> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        l1_batches
+                    WHERE
+                        number > $1
+                ) AS "l1_batches!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        miniblocks
+                    WHERE
+                        number > $2
+                ) AS "miniblocks!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        transactions
+                    WHERE
+                        miniblock_number > $2
+                ) AS "transactions!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        call_traces
+                    WHERE
+                        tx_hash IN (
+                            SELECT
+                                hash
+                            FROM
+                                transactions
+                            WHERE
+                                miniblock_number > $2
+                        )
+                ) AS "call_traces!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        events
+                    WHERE
+                        miniblock_number > $2
+                ) AS "events!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        l2_to_l1_logs
+                    WHERE
+                        miniblock_number > $2
+                ) AS "l2_to_l1_logs!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        tokens
+                    WHERE
+                        l2_address IN (
+                            SELECT
+                                SUBSTRING(key, 12, 20)
+                            FROM
+                                storage_logs
+                            WHERE
+                                storage_logs.address = $3
+                                AND miniblock_number > $2
+                                AND NOT EXISTS (
+                                    SELECT
+                                        1
+                                    FROM
+                                        storage_logs AS s
+                                    WHERE
+                                        s.hashed_key = storage_logs.hashed_key
+                                        AND (s.miniblock_number, s.operation_number) >= (storage_logs.miniblock_number, storage_logs.operation_number)
+                                        AND s.value = $4
+                                )
+                        )
+                ) AS "tokens!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        factory_deps
+                    WHERE
+                        miniblock_number > $2
+                ) AS "factory_deps!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        storage_logs
+                    WHERE
+                        miniblock_number > $2
+                ) AS "storage_logs!",
+                (
+                    SELECT
+                        COUNT(DISTINCT hashed_key)
+                    FROM
+                        storage_logs
+                    WHERE
+                        miniblock_number > $2
+                ) AS "storage!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        initial_writes
+                    WHERE
+                        l1_batch_number > $1
+                ) AS "initial_writes!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        protective_reads
+                    WHERE
+                        l1_batch_number > $1
+                ) AS "protective_reads!"
+            "#,
+            last_l1_batch_to_keep.0 as i64,
+            last_miniblock_to_keep.0 as i64,
+            ACCOUNT_CODE_STORAGE_ADDRESS.as_bytes(),
+            FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH.as_bytes()
+        )
+        .instrument("get_revert_row_counts")
+        .with_arg("last_l1_batch_to_keep", &last_l1_batch_to_keep)
+        .with_arg("last_miniblock_to_keep", &last_miniblock_to_keep)
+        .report_latency()
+        .fetch_one(self.storage.conn())
+        .await?;
+
+        Ok(RevertRowCounts {
+            l1_batches: row.l1_batches as u64,
+            miniblocks: row.miniblocks as u64,
+            transactions: row.transactions as u64,
+            call_traces: row.call_traces as u64,
+            events: row.events as u64,
+            l2_to_l1_logs: row.l2_to_l1_logs as u64,
+            tokens: row.tokens as u64,
+            factory_deps: row.factory_deps as u64,
+            storage_logs: row.storage_logs as u64,
+            storage: row.storage as u64,
+            initial_writes: row.initial_writes as u64,
+            protective_reads: row.protective_reads as u64,
+        })
+    }
+
     /// Returns sum of predicted gas costs on the given L1 batch range.
     /// Panics if the sum doesn't fit into `u32`.
     pub async fn get_l1_batches_predicted_gas(
@@ -2377,4 +2578,43 @@ mod tests {
             assert_eq!(gas, 3 * expected_gas);
         }
     }
+
+    #[tokio::test]
+    async fn getting_revert_row_counts() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        conn.blocks_dal()
+            .delete_l1_batches(L1BatchNumber(0))
+            .await
+            .unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        let header = L1BatchHeader::new(
+            L1BatchNumber(1),
+            100,
+            Address::default(),
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::default(),
+        );
+        conn.blocks_dal()
+            .insert_l1_batch(&header, &[], BlockGasCount::default(), &[], &[])
+            .await
+            .unwrap();
+
+        let counts = conn
+            .blocks_dal()
+            .get_revert_row_counts(L1BatchNumber(1), MiniblockNumber(0))
+            .await
+            .unwrap();
+        assert!(counts.is_empty(), "{counts:?}");
+
+        let counts = conn
+            .blocks_dal()
+            .get_revert_row_counts(L1BatchNumber(0), MiniblockNumber(0))
+            .await
+            .unwrap();
+        assert_eq!(counts.l1_batches, 1);
+        assert!(!counts.is_empty());
+    }
 }
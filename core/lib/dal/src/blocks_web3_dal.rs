@@ -1,3 +1,5 @@
+use std::{ops, str::FromStr};
+
 use bigdecimal::BigDecimal;
 use sqlx::Row;
 use zksync_system_constants::EMPTY_UNCLES_HASH;
@@ -623,6 +625,66 @@ impl BlocksWeb3Dal<'_, '_> {
             Ok(l1_batch_details.map(api::L1BatchDetails::from))
         }
     }
+
+    /// Returns updates for L1 batches in the specified range that have reached the specified `status`.
+    /// For statuses other than `sealed`, only batches with a confirmed L1 transaction are returned.
+    pub async fn get_l1_batch_status_updates(
+        &mut self,
+        status: api::L1BatchStatus,
+        numbers: ops::RangeInclusive<L1BatchNumber>,
+    ) -> sqlx::Result<Vec<api::L1BatchStatusUpdate>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batches.number,
+                commit_tx.tx_hash AS "commit_tx_hash?",
+                prove_tx.tx_hash AS "prove_tx_hash?",
+                execute_tx.tx_hash AS "execute_tx_hash?"
+            FROM
+                l1_batches
+                LEFT JOIN eth_txs_history AS commit_tx ON (
+                    l1_batches.eth_commit_tx_id = commit_tx.eth_tx_id
+                    AND commit_tx.confirmed_at IS NOT NULL
+                )
+                LEFT JOIN eth_txs_history AS prove_tx ON (
+                    l1_batches.eth_prove_tx_id = prove_tx.eth_tx_id
+                    AND prove_tx.confirmed_at IS NOT NULL
+                )
+                LEFT JOIN eth_txs_history AS execute_tx ON (
+                    l1_batches.eth_execute_tx_id = execute_tx.eth_tx_id
+                    AND execute_tx.confirmed_at IS NOT NULL
+                )
+            WHERE
+                l1_batches.number BETWEEN $1 AND $2
+            ORDER BY
+                l1_batches.number
+            "#,
+            numbers.start().0 as i64,
+            numbers.end().0 as i64
+        )
+        .instrument("get_l1_batch_status_updates")
+        .with_arg("status", &status)
+        .with_arg("numbers", &numbers)
+        .report_latency()
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        let updates = rows.into_iter().filter_map(|row| {
+            let l1_tx_hash = match status {
+                api::L1BatchStatus::Sealed => None,
+                api::L1BatchStatus::Committed => Some(row.commit_tx_hash?),
+                api::L1BatchStatus::Proven => Some(row.prove_tx_hash?),
+                api::L1BatchStatus::Executed => Some(row.execute_tx_hash?),
+            };
+            Some(api::L1BatchStatusUpdate {
+                l1_batch_number: L1BatchNumber(row.number as u32),
+                status,
+                l1_tx_hash: l1_tx_hash
+                    .map(|hash| H256::from_str(&hash).expect("Incorrect L1 tx hash")),
+            })
+        });
+        Ok(updates.collect())
+    }
 }
 
 #[cfg(test)]
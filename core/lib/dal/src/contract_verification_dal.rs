@@ -7,11 +7,14 @@ use anyhow::Context as _;
 use sqlx::postgres::types::PgInterval;
 use zksync_types::{
     contract_verification_api::{
-        DeployContractCalldata, VerificationIncomingRequest, VerificationInfo, VerificationRequest,
-        VerificationRequestStatus,
+        ConstructorArgs, DeployContractCalldata, VerificationIncomingRequest, VerificationInfo,
+        VerificationRequest, VerificationRequestStatus,
     },
+    event::DEPLOY_EVENT_SIGNATURE,
     get_code_key, Address, CONTRACT_DEPLOYER_ADDRESS, FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH,
+    H256,
 };
+use zksync_utils::{address_to_h256, h256_to_account_address};
 
 use crate::{models::storage_verification_request::StorageVerificationRequest, StorageProcessor};
 
@@ -185,22 +188,41 @@ impl ContractVerificationDal<'_, '_> {
         .await?;
 
         let address = verification_info.request.req.contract_address;
+        let code_hashed_key = get_code_key(&address).hashed_key();
         // Serialization should always succeed.
         let verification_info_json = serde_json::to_value(verification_info)
             .expect("Failed to serialize verification info into serde_json");
         sqlx::query!(
             r#"
             INSERT INTO
-                contracts_verification_info (address, verification_info)
+                contracts_verification_info (address, verification_info, bytecode_hash)
             VALUES
-                ($1, $2)
+                (
+                    $1,
+                    $2,
+                    (
+                        SELECT
+                            value
+                        FROM
+                            storage_logs
+                        WHERE
+                            hashed_key = $3
+                        ORDER BY
+                            miniblock_number DESC,
+                            operation_number DESC
+                        LIMIT
+                            1
+                    )
+                )
             ON CONFLICT (address) DO
             UPDATE
             SET
-                verification_info = $2
+                verification_info = $2,
+                bytecode_hash = EXCLUDED.bytecode_hash
             "#,
             address.as_bytes(),
-            &verification_info_json
+            &verification_info_json,
+            code_hashed_key.as_bytes()
         )
         .execute(transaction.conn())
         .await?;
@@ -289,7 +311,24 @@ impl ContractVerificationDal<'_, '_> {
             SELECT
                 factory_deps.bytecode,
                 transactions.data AS "data?",
-                transactions.contract_address AS "contract_address?"
+                transactions.contract_address AS "contract_address?",
+                transactions.initiator_address AS "initiator_address?",
+                (
+                    SELECT
+                        topic2
+                    FROM
+                        events
+                    WHERE
+                        events.tx_hash = storage_logs.tx_hash
+                        AND events.address = $3
+                        AND events.topic1 = $4
+                        AND events.topic4 = $5
+                    ORDER BY
+                        events.miniblock_number,
+                        events.event_index_in_block
+                    LIMIT
+                        1
+                ) AS "deployer?"
             FROM
                 (
                     SELECT
@@ -310,26 +349,40 @@ impl ContractVerificationDal<'_, '_> {
                 storage_logs.value != $2
             "#,
             hashed_key.as_bytes(),
-            FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH.as_bytes()
+            FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH.as_bytes(),
+            CONTRACT_DEPLOYER_ADDRESS.as_bytes(),
+            DEPLOY_EVENT_SIGNATURE.as_bytes(),
+            address_to_h256(&address).as_bytes()
         )
         .fetch_optional(self.storage.conn())
         .await?
         else {
             return Ok(None);
         };
-        let calldata = match row.contract_address {
-            Some(contract_address) if contract_address == CONTRACT_DEPLOYER_ADDRESS.0.to_vec() => {
-                // `row.contract_address` and `row.data` are either both `None` or both `Some(_)`.
-                // In this arm it's checked that `row.contract_address` is `Some(_)`, so it's safe to unwrap `row.data`.
-                let data: serde_json::Value = row.data.context("data missing")?;
-                let calldata_str: String = serde_json::from_value(
-                    data.get("calldata").context("calldata missing")?.clone(),
-                )
-                .context("failed parsing calldata")?;
-                let calldata = hex::decode(&calldata_str[2..]).context("invalid calldata")?;
-                DeployContractCalldata::Deploy(calldata)
+
+        // The contract is deployed directly if the transaction calls the contract deployer, and the deployer
+        // of the contract (as per the `ContractDeployed` event) is the transaction initiator. Otherwise, the contract
+        // is deployed by a factory or another contract, and the transaction calldata is unrelated to it.
+        let deployed_by_initiator = match (&row.deployer, &row.initiator_address) {
+            (Some(deployer), Some(initiator)) => {
+                h256_to_account_address(&H256::from_slice(deployer)).as_bytes()
+                    == initiator.as_slice()
             }
-            _ => DeployContractCalldata::Ignore,
+            _ => false,
+        };
+        let is_direct_deployment = deployed_by_initiator
+            && row.contract_address.as_deref() == Some(CONTRACT_DEPLOYER_ADDRESS.as_bytes());
+        let calldata = if is_direct_deployment {
+            // `row.contract_address` and `row.data` are either both `None` or both `Some(_)`.
+            // Here, it's checked that `row.contract_address` is `Some(_)`, so it's safe to unwrap `row.data`.
+            let data: serde_json::Value = row.data.context("data missing")?;
+            let calldata_str: String =
+                serde_json::from_value(data.get("calldata").context("calldata missing")?.clone())
+                    .context("failed parsing calldata")?;
+            let calldata = hex::decode(&calldata_str[2..]).context("invalid calldata")?;
+            DeployContractCalldata::Deploy(calldata)
+        } else {
+            DeployContractCalldata::Ignore
         };
         Ok(Some((row.bytecode, calldata)))
     }
@@ -486,9 +539,21 @@ impl ContractVerificationDal<'_, '_> {
         Ok(result)
     }
 
+    /// Returns verification info for the contract at the specified address. If the contract is not verified,
+    /// falls back to the verification info of a contract with the same bytecode.
     pub async fn get_contract_verification_info(
         &mut self,
         address: Address,
+    ) -> anyhow::Result<Option<VerificationInfo>> {
+        if let Some(info) = self.get_direct_verification_info(address).await? {
+            return Ok(Some(info));
+        }
+        self.get_verification_info_by_bytecode(address).await
+    }
+
+    async fn get_direct_verification_info(
+        &mut self,
+        address: Address,
     ) -> anyhow::Result<Option<VerificationInfo>> {
         let Some(row) = sqlx::query!(
             r#"
@@ -511,4 +576,314 @@ impl ContractVerificationDal<'_, '_> {
         };
         Ok(Some(serde_json::from_value(info).context("invalid info")?))
     }
+
+    /// Returns verification info of a contract with the same bytecode as the contract at the specified address.
+    /// Constructor arguments are decoded from the deployment calldata of the contract at `address`; if this is impossible
+    /// (e.g., the contract is deployed by a factory), they are marked as unknown.
+    async fn get_verification_info_by_bytecode(
+        &mut self,
+        address: Address,
+    ) -> anyhow::Result<Option<VerificationInfo>> {
+        let hashed_key = get_code_key(&address).hashed_key();
+        let Some(row) = sqlx::query!(
+            r#"
+            SELECT
+                address,
+                verification_info
+            FROM
+                contracts_verification_info
+            WHERE
+                bytecode_hash = (
+                    SELECT
+                        value
+                    FROM
+                        storage_logs
+                    WHERE
+                        hashed_key = $1
+                    ORDER BY
+                        miniblock_number DESC,
+                        operation_number DESC
+                    LIMIT
+                        1
+                )
+            ORDER BY
+                address
+            LIMIT
+                1
+            "#,
+            hashed_key.as_bytes()
+        )
+        .fetch_optional(self.storage.conn())
+        .await?
+        else {
+            return Ok(None);
+        };
+        let Some(info) = row.verification_info else {
+            return Ok(None);
+        };
+        let mut info: VerificationInfo = serde_json::from_value(info).context("invalid info")?;
+        let Some((_, calldata)) = self.get_contract_info_for_verification(address).await? else {
+            return Ok(None);
+        };
+
+        let constructor_args = calldata.constructor_args(address).unwrap_or_else(|err| {
+            tracing::warn!(
+                "Failed decoding constructor arguments for contract {address:?}: {err:#}"
+            );
+            ConstructorArgs::Unknown
+        });
+        let (constructor_args, constructor_args_unknown) = match constructor_args {
+            ConstructorArgs::Check(args) => (args, false),
+            ConstructorArgs::Ignore => (vec![], false),
+            ConstructorArgs::Unknown => (vec![], true),
+        };
+        info.request.req.contract_address = address;
+        info.request.req.constructor_arguments = constructor_args.into();
+        info.constructor_arguments_unknown = constructor_args_unknown;
+        info.verified_via_address = Some(Address::from_slice(&row.address));
+        Ok(Some(info))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use assert_matches::assert_matches;
+    use sqlx::types::chrono::Utc;
+    use zksync_types::{
+        contract_verification_api::{
+            CompilationArtifacts, CompilerVersions, SourceCodeData, VerificationMatch,
+        },
+        ethabi::Token,
+        l2::L2Tx,
+        tx::IncludedTxLocation,
+        L1BatchNumber, MiniblockNumber, ProtocolVersion, StorageLog, VmEvent, U256,
+    };
+
+    use super::*;
+    use crate::{
+        tests::{create_miniblock_header, mock_execution_result, mock_l2_transaction},
+        ConnectionPool,
+    };
+
+    /// Contract deployments performed by a single transaction, as `(deployer, contract_address)` tuples.
+    type Deployments = Vec<(Address, Address)>;
+
+    fn mock_deployment_tx(contract_address: Address, function: &str, args: Vec<u8>) -> L2Tx {
+        let calldata = zksync_contracts::deployer_contract()
+            .function(function)
+            .unwrap()
+            .encode_input(&[
+                Token::FixedBytes(vec![0; 32]),
+                Token::FixedBytes(vec![0; 32]),
+                Token::Bytes(args),
+            ])
+            .unwrap();
+        let mut tx = mock_l2_transaction();
+        tx.execute.contract_address = contract_address;
+        tx.execute.calldata = calldata;
+        tx
+    }
+
+    fn mock_verification_info(contract_address: Address, args: Vec<u8>) -> VerificationInfo {
+        VerificationInfo {
+            request: VerificationRequest {
+                id: 1,
+                req: VerificationIncomingRequest {
+                    contract_address,
+                    source_code_data: SourceCodeData::SolSingleFile("contract Test {}".to_owned()),
+                    contract_name: "Test".to_owned(),
+                    compiler_versions: CompilerVersions::Solc {
+                        compiler_zksolc_version: "v1.3.18".to_owned(),
+                        compiler_solc_version: "0.8.23".to_owned(),
+                    },
+                    optimization_used: true,
+                    optimizer_mode: None,
+                    constructor_arguments: args.into(),
+                    is_system: false,
+                },
+            },
+            artifacts: CompilationArtifacts {
+                bytecode: vec![1; 32],
+                abi: serde_json::json!([]),
+            },
+            verified_at: Utc::now(),
+            match_type: VerificationMatch::Full,
+            verified_via_address: None,
+            constructor_arguments_unknown: false,
+        }
+    }
+
+    /// Executes the provided transactions in a single miniblock. All deployed contracts get the same bytecode.
+    async fn execute_deployments(
+        conn: &mut StorageProcessor<'_>,
+        bytecode_hash: H256,
+        transactions: Vec<(L2Tx, Deployments)>,
+    ) {
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        conn.blocks_dal()
+            .insert_miniblock(&create_miniblock_header(0))
+            .await
+            .unwrap();
+        let mut miniblock_header = create_miniblock_header(1);
+        miniblock_header.l2_tx_count = transactions.len() as u16;
+        conn.blocks_dal()
+            .insert_miniblock(&miniblock_header)
+            .await
+            .unwrap();
+        conn.storage_dal()
+            .insert_factory_deps(
+                MiniblockNumber(1),
+                &HashMap::from([(bytecode_hash, vec![1; 32])]),
+            )
+            .await;
+
+        let mut tx_results = vec![];
+        let mut storage_logs = vec![];
+        let mut events = vec![];
+        for (i, (tx, deployments)) in transactions.into_iter().enumerate() {
+            conn.transactions_dal()
+                .insert_transaction_l2(tx.clone(), Default::default())
+                .await;
+            let location = IncludedTxLocation {
+                tx_hash: tx.hash(),
+                tx_index_in_miniblock: i as u32,
+                tx_initiator_address: tx.initiator_account(),
+            };
+            let tx_events: Vec<_> = deployments
+                .iter()
+                .map(|(deployer, address)| VmEvent {
+                    location: (L1BatchNumber(1), i as u32),
+                    address: CONTRACT_DEPLOYER_ADDRESS,
+                    indexed_topics: vec![
+                        *DEPLOY_EVENT_SIGNATURE,
+                        address_to_h256(deployer),
+                        bytecode_hash,
+                        address_to_h256(address),
+                    ],
+                    value: vec![],
+                })
+                .collect();
+            let tx_logs = deployments
+                .iter()
+                .map(|(_, address)| StorageLog::new_write_log(get_code_key(address), bytecode_hash))
+                .collect();
+            storage_logs.push((tx.hash(), tx_logs));
+            events.push((location, tx_events));
+            tx_results.push(mock_execution_result(tx));
+        }
+
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_miniblock(MiniblockNumber(1), &tx_results, U256::one())
+            .await;
+        conn.storage_logs_dal()
+            .insert_storage_logs(MiniblockNumber(1), &storage_logs)
+            .await;
+        let events: Vec<_> = events
+            .iter()
+            .map(|(location, events)| (*location, events.iter().collect()))
+            .collect();
+        conn.events_dal()
+            .save_events(MiniblockNumber(1), &events)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn propagating_verification_info_to_contracts_with_same_bytecode() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+
+        let verified_address = Address::repeat_byte(1);
+        let direct_address = Address::repeat_byte(2);
+        let factory_address = Address::repeat_byte(3);
+        let factory_deployed_address = Address::repeat_byte(4);
+        let parent_address = Address::repeat_byte(5);
+        let clone_address = Address::repeat_byte(6);
+
+        let verified_tx = mock_deployment_tx(CONTRACT_DEPLOYER_ADDRESS, "create", vec![1; 32]);
+        let direct_tx = mock_deployment_tx(CONTRACT_DEPLOYER_ADDRESS, "create2", vec![2; 32]);
+        let factory_tx = mock_deployment_tx(factory_address, "create", vec![3; 32]);
+        // The deployed contract clones itself in the constructor.
+        let clone_tx = mock_deployment_tx(CONTRACT_DEPLOYER_ADDRESS, "create", vec![4; 32]);
+        let transactions = vec![
+            (
+                verified_tx.clone(),
+                vec![(verified_tx.initiator_account(), verified_address)],
+            ),
+            (
+                direct_tx.clone(),
+                vec![(direct_tx.initiator_account(), direct_address)],
+            ),
+            (
+                factory_tx,
+                vec![(factory_address, factory_deployed_address)],
+            ),
+            (
+                clone_tx.clone(),
+                vec![
+                    (clone_tx.initiator_account(), parent_address),
+                    (parent_address, clone_address),
+                ],
+            ),
+        ];
+        execute_deployments(&mut conn, H256::repeat_byte(0xff), transactions).await;
+
+        let (_, calldata) = conn
+            .contract_verification_dal()
+            .get_contract_info_for_verification(clone_address)
+            .await
+            .unwrap()
+            .expect("no clone info");
+        assert_matches!(calldata, DeployContractCalldata::Ignore);
+
+        conn.contract_verification_dal()
+            .save_verification_info(mock_verification_info(verified_address, vec![1; 32]))
+            .await
+            .unwrap();
+
+        let info = conn
+            .contract_verification_dal()
+            .get_contract_verification_info(verified_address)
+            .await
+            .unwrap()
+            .expect("no verification info");
+        assert_eq!(info.request.req.constructor_arguments.0, [1; 32]);
+        assert_eq!(info.verified_via_address, None);
+        assert!(!info.constructor_arguments_unknown);
+
+        let info = conn
+            .contract_verification_dal()
+            .get_contract_verification_info(direct_address)
+            .await
+            .unwrap()
+            .expect("no verification info for direct deployment");
+        assert_eq!(info.request.req.contract_address, direct_address);
+        assert_eq!(info.request.req.constructor_arguments.0, [2; 32]);
+        assert_eq!(info.verified_via_address, Some(verified_address));
+        assert!(!info.constructor_arguments_unknown);
+
+        for address in [factory_deployed_address, clone_address] {
+            let info = conn
+                .contract_verification_dal()
+                .get_contract_verification_info(address)
+                .await
+                .unwrap()
+                .expect("no verification info for indirect deployment");
+            assert_eq!(info.request.req.contract_address, address);
+            assert!(info.request.req.constructor_arguments.0.is_empty());
+            assert_eq!(info.verified_via_address, Some(verified_address));
+            assert!(info.constructor_arguments_unknown);
+        }
+
+        let unknown_address = Address::repeat_byte(0xaa);
+        let info = conn
+            .contract_verification_dal()
+            .get_contract_verification_info(unknown_address)
+            .await
+            .unwrap();
+        assert!(info.is_none());
+    }
 }
@@ -1,4 +1,4 @@
-use std::{convert::TryFrom, str::FromStr};
+use std::{convert::TryFrom, ops, str::FromStr};
 
 use anyhow::Context as _;
 use sqlx::{
@@ -7,8 +7,8 @@ use sqlx::{
 };
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
-    eth_sender::{EthTx, TxHistory, TxHistoryToSend},
-    Address, L1BatchNumber, H256, U256,
+    eth_sender::{EthTx, EthTxBlobSidecar, TxHistory, TxHistoryToSend},
+    Address, L1BatchNumber, Nonce, H256, U256,
 };
 
 use crate::{
@@ -18,6 +18,17 @@ use crate::{
     StorageProcessor,
 };
 
+/// Brief information about an Ethereum transaction sent for L1 batches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EthTxSummary {
+    pub id: u32,
+    pub nonce: Nonce,
+    pub tx_type: AggregatedActionType,
+    pub has_failed: bool,
+    /// Hash of the confirmed transaction attempt, if any.
+    pub confirmed_tx_hash: Option<H256>,
+}
+
 #[derive(Debug)]
 pub struct EthSenderDal<'a, 'c> {
     pub(crate) storage: &'a mut StorageProcessor<'c>,
@@ -174,8 +185,11 @@ impl EthSenderDal<'_, '_> {
         tx_type: AggregatedActionType,
         contract_address: Address,
         predicted_gas_cost: u32,
+        blob_sidecar: Option<EthTxBlobSidecar>,
     ) -> sqlx::Result<EthTx> {
         let address = format!("{:#x}", contract_address);
+        let blob_sidecar = blob_sidecar
+            .map(|sidecar| bincode::serialize(&sidecar).expect("can't serialize blob sidecar"));
         let eth_tx = sqlx::query_as!(
             StorageEthTx,
             r#"
@@ -186,11 +200,12 @@ impl EthSenderDal<'_, '_> {
                     tx_type,
                     contract_address,
                     predicted_gas_cost,
+                    blob_sidecar,
                     created_at,
                     updated_at
                 )
             VALUES
-                ($1, $2, $3, $4, $5, NOW(), NOW())
+                ($1, $2, $3, $4, $5, $6, NOW(), NOW())
             RETURNING
                 *
             "#,
@@ -198,7 +213,8 @@ impl EthSenderDal<'_, '_> {
             nonce as i64,
             tx_type.to_string(),
             address,
-            predicted_gas_cost as i64
+            predicted_gas_cost as i64,
+            blob_sidecar
         )
         .fetch_one(self.storage.conn())
         .await?;
@@ -210,6 +226,7 @@ impl EthSenderDal<'_, '_> {
         eth_tx_id: u32,
         base_fee_per_gas: u64,
         priority_fee_per_gas: u64,
+        blob_base_fee_per_gas: Option<u64>,
         tx_hash: H256,
         raw_signed_tx: Vec<u8>,
     ) -> anyhow::Result<Option<u32>> {
@@ -217,6 +234,10 @@ impl EthSenderDal<'_, '_> {
             i64::try_from(priority_fee_per_gas).context("Can't convert u64 to i64")?;
         let base_fee_per_gas =
             i64::try_from(base_fee_per_gas).context("Can't convert u64 to i64")?;
+        let blob_base_fee_per_gas = blob_base_fee_per_gas
+            .map(i64::try_from)
+            .transpose()
+            .context("Can't convert u64 to i64")?;
         let tx_hash = format!("{:#x}", tx_hash);
 
         Ok(sqlx::query!(
@@ -228,11 +249,12 @@ impl EthSenderDal<'_, '_> {
                     priority_fee_per_gas,
                     tx_hash,
                     signed_raw_tx,
+                    blob_base_fee_per_gas,
                     created_at,
                     updated_at
                 )
             VALUES
-                ($1, $2, $3, $4, $5, NOW(), NOW())
+                ($1, $2, $3, $4, $5, $6, NOW(), NOW())
             ON CONFLICT (tx_hash) DO NOTHING
             RETURNING
                 id
@@ -241,7 +263,8 @@ impl EthSenderDal<'_, '_> {
             base_fee_per_gas,
             priority_fee_per_gas,
             tx_hash,
-            raw_signed_tx
+            raw_signed_tx,
+            blob_base_fee_per_gas
         )
         .fetch_optional(self.storage.conn())
         .await?
@@ -358,6 +381,72 @@ impl EthSenderDal<'_, '_> {
         Ok(Some(H256::from_str(tx_hash).context("invalid tx_hash")?))
     }
 
+    /// Returns Ethereum transactions committing, proving or executing L1 batches after the specified one.
+    pub async fn get_eth_txs_for_l1_batches_after(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Vec<EthTxSummary>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                eth_txs.id,
+                eth_txs.nonce,
+                eth_txs.tx_type,
+                eth_txs.has_failed,
+                eth_txs_history.tx_hash AS "confirmed_tx_hash?"
+            FROM
+                eth_txs
+                LEFT JOIN eth_txs_history ON eth_txs.confirmed_eth_tx_history_id = eth_txs_history.id
+            WHERE
+                eth_txs.id IN (
+                    SELECT
+                        eth_commit_tx_id
+                    FROM
+                        l1_batches
+                    WHERE
+                        number > $1
+                    UNION
+                    SELECT
+                        eth_prove_tx_id
+                    FROM
+                        l1_batches
+                    WHERE
+                        number > $1
+                    UNION
+                    SELECT
+                        eth_execute_tx_id
+                    FROM
+                        l1_batches
+                    WHERE
+                        number > $1
+                )
+            ORDER BY
+                eth_txs.id
+            "#,
+            l1_batch_number.0 as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let confirmed_tx_hash = row
+                    .confirmed_tx_hash
+                    .map(|hash| H256::from_str(hash.trim_start_matches("0x")))
+                    .transpose()
+                    .context("invalid tx_hash")?;
+                Ok(EthTxSummary {
+                    id: row.id as u32,
+                    nonce: Nonce(row.nonce as u32),
+                    tx_type: AggregatedActionType::from_str(&row.tx_type)
+                        .map_err(|err| anyhow::anyhow!("invalid tx_type: {err}"))?,
+                    has_failed: row.has_failed,
+                    confirmed_tx_hash,
+                })
+            })
+            .collect()
+    }
+
     /// This method inserts a fake transaction into the database that would make the corresponding L1 batch
     /// to be considered committed/proven/executed.
     ///
@@ -528,6 +617,118 @@ impl EthSenderDal<'_, '_> {
         Ok(row.map(|row| row.nonce as u64 + 1))
     }
 
+    /// Locks the latest `eth_tx` row until the end of the current transaction, so that assigning nonces
+    /// to new `eth_txs` and [reassigning nonces](Self::reassign_nonces()) of existing ones are serialized.
+    /// Other `eth_txs` are not locked. Must be called inside a transaction.
+    pub async fn lock_nonces(&mut self) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            SELECT
+                id
+            FROM
+                eth_txs
+            ORDER BY
+                id DESC
+            LIMIT
+                1
+            FOR UPDATE
+            "#
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the number of `eth_txs` (including confirmed and failed ones) with nonces in the specified range.
+    pub async fn get_number_of_eth_txs_with_nonces(
+        &mut self,
+        nonces: ops::Range<Nonce>,
+    ) -> sqlx::Result<usize> {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                eth_txs
+            WHERE
+                nonce >= $1
+                AND nonce < $2
+            "#,
+            i64::from(nonces.start.0),
+            i64::from(nonces.end.0)
+        )
+        .fetch_one(self.storage.conn())
+        .await?
+        .count;
+        Ok(count as usize)
+    }
+
+    /// Reassigns nonces of `eth_txs` not confirmed on L1, so that they are sequential starting from `start_nonce`.
+    /// Affected `eth_txs` are the ones with the specified IDs and the ones with the nonce greater than or equal
+    /// to `start_nonce`, ordered by ID. Sending attempts for these `eth_txs` that were not sent yet are removed,
+    /// since they are signed with outdated nonces.
+    ///
+    /// Returns IDs of `eth_txs` with reassigned nonces.
+    pub async fn reassign_nonces(
+        &mut self,
+        eth_tx_ids: &[u32],
+        start_nonce: Nonce,
+    ) -> sqlx::Result<Vec<u32>> {
+        let eth_tx_ids: Vec<_> = eth_tx_ids.iter().map(|&id| id as i32).collect();
+        let mut transaction = self.storage.start_transaction().await?;
+        transaction.eth_sender_dal().lock_nonces().await?;
+        let reassigned_ids = sqlx::query!(
+            r#"
+            UPDATE eth_txs
+            SET
+                nonce = new_nonces.nonce,
+                updated_at = NOW()
+            FROM
+                (
+                    SELECT
+                        id,
+                        $2 + ROW_NUMBER() OVER (
+                            ORDER BY
+                                id
+                        ) - 1 AS nonce
+                    FROM
+                        eth_txs
+                    WHERE
+                        confirmed_eth_tx_history_id IS NULL
+                        AND NOT has_failed
+                        AND (
+                            id = ANY ($1)
+                            OR nonce >= $2
+                        )
+                ) AS new_nonces
+            WHERE
+                eth_txs.id = new_nonces.id
+            RETURNING
+                eth_txs.id
+            "#,
+            &eth_tx_ids,
+            i64::from(start_nonce.0)
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let reassigned_ids: Vec<_> = reassigned_ids.into_iter().map(|row| row.id).collect();
+
+        sqlx::query!(
+            r#"
+            DELETE FROM eth_txs_history
+            WHERE
+                eth_tx_id = ANY ($1)
+                AND sent_at_block IS NULL
+            "#,
+            &reassigned_ids
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        Ok(reassigned_ids.into_iter().map(|id| id as u32).collect())
+    }
+
     pub async fn mark_failed_transaction(&mut self, eth_tx_id: u32) -> sqlx::Result<()> {
         sqlx::query!(
             r#"
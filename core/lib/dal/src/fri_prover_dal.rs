@@ -538,6 +538,32 @@ impl FriProverDal<'_, '_> {
         .unwrap();
     }
 
+    /// Returns L1 batch numbers for the specified prover job IDs. Unknown IDs are omitted from the returned map.
+    pub async fn get_l1_batch_numbers_for_jobs(
+        &mut self,
+        ids: &[u32],
+    ) -> sqlx::Result<HashMap<u32, L1BatchNumber>> {
+        let ids: Vec<_> = ids.iter().map(|&id| i64::from(id)).collect();
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                id,
+                l1_batch_number
+            FROM
+                prover_jobs_fri
+            WHERE
+                id = ANY ($1)
+            "#,
+            &ids
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.id as u32, L1BatchNumber(row.l1_batch_number as u32)))
+            .collect())
+    }
+
     pub async fn get_scheduler_proof_job_id(
         &mut self,
         l1_batch_number: L1BatchNumber,
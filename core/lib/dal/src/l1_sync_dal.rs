@@ -0,0 +1,622 @@
+use std::collections::HashMap;
+
+use zksync_types::{
+    l2_to_l1_log::{L2ToL1Log, UserL2ToL1Log},
+    L1BatchNumber, MiniblockNumber, ProtocolVersionId, H256,
+};
+
+use crate::{instrument::InstrumentExt, StorageProcessor};
+
+/// L1 batch reconstructed from the data published on L1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1SyncBatch {
+    pub number: L1BatchNumber,
+    pub timestamp: u64,
+    pub root_hash: H256,
+    pub rollup_last_leaf_index: u64,
+    pub l2_to_l1_logs: Vec<UserL2ToL1Log>,
+    pub l2_to_l1_messages: Vec<Vec<u8>>,
+    /// Hash of the L1 transaction committing the batch. `None` for the batch the sync was started from.
+    pub commit_tx_hash: Option<H256>,
+    pub commit_l1_block_number: u64,
+}
+
+/// Storage slot reconstructed from the data published on L1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct L1SyncStorageEntry {
+    pub hashed_key: H256,
+    pub enumeration_index: u64,
+    pub value: H256,
+}
+
+/// DAL for the state reconstructed by the external node purely from L1 data. The state is stored separately
+/// from the main node-provided state since pubdata does not contain preimages of the hashed storage keys.
+#[derive(Debug)]
+pub struct L1SyncDal<'a, 'c> {
+    pub(crate) storage: &'a mut StorageProcessor<'c>,
+}
+
+impl L1SyncDal<'_, '_> {
+    /// Seeds the L1 sync state with the local storage state as of the end of the specified L1 batch.
+    /// This is used to initialize sync, since the initial state cannot be reconstructed from L1.
+    /// Returns the number of seeded storage slots.
+    pub async fn seed_from_local_state(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        last_miniblock_number: MiniblockNumber,
+    ) -> sqlx::Result<u64> {
+        let seeded_slots = sqlx::query!(
+            r#"
+            INSERT INTO
+                l1_sync_storage (
+                    hashed_key,
+                    enumeration_index,
+                    value,
+                    l1_batch_number,
+                    created_at,
+                    updated_at
+                )
+            SELECT DISTINCT
+                ON (storage_logs.hashed_key) storage_logs.hashed_key,
+                initial_writes.index,
+                storage_logs.value,
+                $1,
+                NOW(),
+                NOW()
+            FROM
+                storage_logs
+                INNER JOIN initial_writes ON storage_logs.hashed_key = initial_writes.hashed_key
+            WHERE
+                storage_logs.miniblock_number <= $2
+                AND initial_writes.l1_batch_number <= $1
+            ORDER BY
+                storage_logs.hashed_key,
+                storage_logs.miniblock_number DESC,
+                storage_logs.operation_number DESC
+            "#,
+            l1_batch_number.0 as i64,
+            last_miniblock_number.0 as i64
+        )
+        .instrument("seed_from_local_state#storage")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .report_latency()
+        .execute(self.storage.conn())
+        .await?
+        .rows_affected();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                l1_sync_factory_deps (bytecode_hash, bytecode, l1_batch_number, created_at, updated_at)
+            SELECT
+                bytecode_hash,
+                bytecode,
+                $1,
+                NOW(),
+                NOW()
+            FROM
+                factory_deps
+            WHERE
+                miniblock_number <= $2
+            "#,
+            l1_batch_number.0 as i64,
+            last_miniblock_number.0 as i64
+        )
+        .instrument("seed_from_local_state#factory_deps")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .report_latency()
+        .execute(self.storage.conn())
+        .await?;
+        Ok(seeded_slots)
+    }
+
+    pub async fn insert_batch(&mut self, batch: &L1SyncBatch) -> sqlx::Result<()> {
+        let l2_to_l1_logs: Vec<_> = batch
+            .l2_to_l1_logs
+            .iter()
+            .map(|log| log.0.to_bytes().to_vec())
+            .collect();
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                l1_sync_batches (
+                    l1_batch_number,
+                    timestamp,
+                    root_hash,
+                    rollup_last_leaf_index,
+                    l2_to_l1_logs,
+                    l2_to_l1_messages,
+                    commit_tx_hash,
+                    commit_l1_block_number,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, NOW(), NOW())
+            "#,
+            batch.number.0 as i64,
+            batch.timestamp as i64,
+            batch.root_hash.as_bytes(),
+            batch.rollup_last_leaf_index as i64,
+            &l2_to_l1_logs,
+            &batch.l2_to_l1_messages,
+            batch.commit_tx_hash.as_ref().map(H256::as_bytes),
+            batch.commit_l1_block_number as i64
+        )
+        .instrument("insert_batch")
+        .with_arg("l1_batch_number", &batch.number)
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the latest L1 batch reconstructed from L1, or `None` if the sync was not started yet.
+    pub async fn get_last_batch(&mut self) -> sqlx::Result<Option<L1SyncBatch>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                timestamp,
+                root_hash,
+                rollup_last_leaf_index,
+                l2_to_l1_logs,
+                l2_to_l1_messages,
+                commit_tx_hash,
+                commit_l1_block_number
+            FROM
+                l1_sync_batches
+            ORDER BY
+                l1_batch_number DESC
+            LIMIT
+                1
+            "#
+        )
+        .instrument("get_last_batch")
+        .report_latency()
+        .fetch_optional(self.storage.conn())
+        .await?;
+
+        Ok(row.map(|row| L1SyncBatch {
+            number: L1BatchNumber(row.l1_batch_number as u32),
+            timestamp: row.timestamp as u64,
+            root_hash: H256::from_slice(&row.root_hash),
+            rollup_last_leaf_index: row.rollup_last_leaf_index as u64,
+            l2_to_l1_logs: row
+                .l2_to_l1_logs
+                .iter()
+                .map(|bytes| UserL2ToL1Log(L2ToL1Log::from_slice(bytes)))
+                .collect(),
+            l2_to_l1_messages: row.l2_to_l1_messages,
+            commit_tx_hash: row.commit_tx_hash.as_deref().map(H256::from_slice),
+            commit_l1_block_number: row.commit_l1_block_number as u64,
+        }))
+    }
+
+    /// Returns root hashes of up to `limit` L1 batches reconstructed from L1 after the specified batch,
+    /// ordered by the L1 batch number.
+    pub async fn get_root_hashes(
+        &mut self,
+        after: L1BatchNumber,
+        limit: usize,
+    ) -> sqlx::Result<Vec<(L1BatchNumber, H256)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                root_hash
+            FROM
+                l1_sync_batches
+            WHERE
+                l1_batch_number > $1
+            ORDER BY
+                l1_batch_number
+            LIMIT
+                $2
+            "#,
+            after.0 as i64,
+            limit as i64
+        )
+        .instrument("get_root_hashes")
+        .with_arg("after", &after)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let number = L1BatchNumber(row.l1_batch_number as u32);
+                (number, H256::from_slice(&row.root_hash))
+            })
+            .collect())
+    }
+
+    /// Returns the latest values of storage slots with the specified enumeration indices. Missing indices
+    /// are not present in the returned map.
+    pub async fn get_storage_by_indices(
+        &mut self,
+        enumeration_indices: &[u64],
+    ) -> sqlx::Result<HashMap<u64, L1SyncStorageEntry>> {
+        let indices: Vec<_> = enumeration_indices
+            .iter()
+            .map(|&index| index as i64)
+            .collect();
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT
+                ON (enumeration_index) hashed_key,
+                enumeration_index,
+                value
+            FROM
+                l1_sync_storage
+            WHERE
+                enumeration_index = ANY ($1)
+            ORDER BY
+                enumeration_index,
+                l1_batch_number DESC
+            "#,
+            &indices
+        )
+        .instrument("get_storage_by_indices")
+        .with_arg("indices.len", &indices.len())
+        .report_latency()
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let entry = L1SyncStorageEntry {
+                    hashed_key: H256::from_slice(&row.hashed_key),
+                    enumeration_index: row.enumeration_index as u64,
+                    value: H256::from_slice(&row.value),
+                };
+                (entry.enumeration_index, entry)
+            })
+            .collect())
+    }
+
+    /// Returns the latest values of up to `limit` storage slots with enumeration indices greater
+    /// than `after_index`, ordered by the index.
+    pub async fn get_storage_chunk(
+        &mut self,
+        after_index: u64,
+        limit: usize,
+    ) -> sqlx::Result<Vec<L1SyncStorageEntry>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT
+                ON (enumeration_index) hashed_key,
+                enumeration_index,
+                value
+            FROM
+                l1_sync_storage
+            WHERE
+                enumeration_index > $1
+            ORDER BY
+                enumeration_index,
+                l1_batch_number DESC
+            LIMIT
+                $2
+            "#,
+            after_index as i64,
+            limit as i64
+        )
+        .instrument("get_storage_chunk")
+        .with_arg("after_index", &after_index)
+        .report_latency()
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1SyncStorageEntry {
+                hashed_key: H256::from_slice(&row.hashed_key),
+                enumeration_index: row.enumeration_index as u64,
+                value: H256::from_slice(&row.value),
+            })
+            .collect())
+    }
+
+    /// Inserts storage slot values updated in the specified L1 batch.
+    pub async fn insert_storage(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        entries: &[L1SyncStorageEntry],
+    ) -> sqlx::Result<()> {
+        let hashed_keys: Vec<_> = entries
+            .iter()
+            .map(|entry| entry.hashed_key.as_bytes())
+            .collect();
+        let indices: Vec<_> = entries
+            .iter()
+            .map(|entry| entry.enumeration_index as i64)
+            .collect();
+        let values: Vec<_> = entries.iter().map(|entry| entry.value.as_bytes()).collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                l1_sync_storage (
+                    hashed_key,
+                    enumeration_index,
+                    value,
+                    l1_batch_number,
+                    created_at,
+                    updated_at
+                )
+            SELECT
+                u.hashed_key,
+                u.enumeration_index,
+                u.value,
+                $4,
+                NOW(),
+                NOW()
+            FROM
+                UNNEST($1::bytea[], $2::BIGINT[], $3::bytea[]) AS u (hashed_key, enumeration_index, value)
+            "#,
+            &hashed_keys as &[&[u8]],
+            &indices,
+            &values as &[&[u8]],
+            l1_batch_number.0 as i64
+        )
+        .instrument("insert_storage")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("entries.len", &entries.len())
+        .report_latency()
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    pub async fn insert_factory_deps(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        factory_deps: &HashMap<H256, Vec<u8>>,
+    ) -> sqlx::Result<()> {
+        let (bytecode_hashes, bytecodes): (Vec<_>, Vec<_>) = factory_deps
+            .iter()
+            .map(|(hash, bytecode)| (hash.as_bytes(), bytecode.as_slice()))
+            .unzip();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                l1_sync_factory_deps (bytecode_hash, bytecode, l1_batch_number, created_at, updated_at)
+            SELECT
+                u.bytecode_hash,
+                u.bytecode,
+                $3,
+                NOW(),
+                NOW()
+            FROM
+                UNNEST($1::bytea[], $2::bytea[]) AS u (bytecode_hash, bytecode)
+            ON CONFLICT (bytecode_hash) DO NOTHING
+            "#,
+            &bytecode_hashes as &[&[u8]],
+            &bytecodes as &[&[u8]],
+            l1_batch_number.0 as i64
+        )
+        .instrument("insert_factory_deps")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_factory_dep(&mut self, bytecode_hash: H256) -> sqlx::Result<Option<Vec<u8>>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                bytecode
+            FROM
+                l1_sync_factory_deps
+            WHERE
+                bytecode_hash = $1
+            "#,
+            bytecode_hash.as_bytes()
+        )
+        .instrument("get_factory_dep")
+        .with_arg("bytecode_hash", &bytecode_hash)
+        .fetch_optional(self.storage.conn())
+        .await?;
+        Ok(row.map(|row| row.bytecode))
+    }
+
+    /// Removes all L1 sync data after the specified L1 batch. Used to follow L1 batch reverts on L1.
+    pub async fn rollback_to_batch(
+        &mut self,
+        last_batch_to_keep: L1BatchNumber,
+    ) -> sqlx::Result<()> {
+        let mut transaction = self.storage.start_transaction().await?;
+        sqlx::query!(
+            r#"
+            DELETE FROM l1_sync_storage
+            WHERE
+                l1_batch_number > $1
+            "#,
+            last_batch_to_keep.0 as i64
+        )
+        .instrument("rollback_to_batch#storage")
+        .with_arg("last_batch_to_keep", &last_batch_to_keep)
+        .report_latency()
+        .execute(transaction.conn())
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM l1_sync_factory_deps
+            WHERE
+                l1_batch_number > $1
+            "#,
+            last_batch_to_keep.0 as i64
+        )
+        .instrument("rollback_to_batch#factory_deps")
+        .with_arg("last_batch_to_keep", &last_batch_to_keep)
+        .execute(transaction.conn())
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM l1_sync_batches
+            WHERE
+                l1_batch_number > $1
+            "#,
+            last_batch_to_keep.0 as i64
+        )
+        .instrument("rollback_to_batch#batches")
+        .with_arg("last_batch_to_keep", &last_batch_to_keep)
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await
+    }
+
+    /// Returns the first L1 batch in the local storage with the specified or newer protocol version.
+    pub async fn get_first_local_batch_with_protocol_version(
+        &mut self,
+        protocol_version: ProtocolVersionId,
+    ) -> sqlx::Result<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MIN(number) AS "number"
+            FROM
+                l1_batches
+            WHERE
+                protocol_version >= $1
+            "#,
+            protocol_version as i32
+        )
+        .instrument("get_first_local_batch_with_protocol_version")
+        .with_arg("protocol_version", &protocol_version)
+        .fetch_one(self.storage.conn())
+        .await?;
+        Ok(row.number.map(|number| L1BatchNumber(number as u32)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConnectionPool;
+
+    #[tokio::test]
+    async fn manipulating_l1_sync_state() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        let mut dal = conn.l1_sync_dal();
+        assert_eq!(dal.get_last_batch().await.unwrap(), None);
+
+        let batch = L1SyncBatch {
+            number: L1BatchNumber(1),
+            timestamp: 100,
+            root_hash: H256::repeat_byte(1),
+            rollup_last_leaf_index: 3,
+            l2_to_l1_logs: vec![UserL2ToL1Log(L2ToL1Log {
+                shard_id: 0,
+                is_service: false,
+                tx_number_in_block: 1,
+                sender: Default::default(),
+                key: H256::repeat_byte(2),
+                value: H256::repeat_byte(3),
+            })],
+            l2_to_l1_messages: vec![vec![1, 2, 3]],
+            commit_tx_hash: Some(H256::repeat_byte(4)),
+            commit_l1_block_number: 10,
+        };
+        dal.insert_batch(&batch).await.unwrap();
+        assert_eq!(dal.get_last_batch().await.unwrap(), Some(batch.clone()));
+        assert_eq!(
+            dal.get_root_hashes(L1BatchNumber(0), 10).await.unwrap(),
+            [(batch.number, batch.root_hash)]
+        );
+        assert!(dal
+            .get_root_hashes(L1BatchNumber(1), 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let entries = [
+            L1SyncStorageEntry {
+                hashed_key: H256::repeat_byte(1),
+                enumeration_index: 1,
+                value: H256::repeat_byte(0xff),
+            },
+            L1SyncStorageEntry {
+                hashed_key: H256::repeat_byte(2),
+                enumeration_index: 2,
+                value: H256::repeat_byte(0xfe),
+            },
+        ];
+        dal.insert_storage(L1BatchNumber(1), &entries)
+            .await
+            .unwrap();
+        let updated_entry = L1SyncStorageEntry {
+            value: H256::zero(),
+            ..entries[1]
+        };
+        dal.insert_storage(L1BatchNumber(2), &[updated_entry])
+            .await
+            .unwrap();
+
+        let loaded = dal.get_storage_by_indices(&[1, 2, 3]).await.unwrap();
+        assert_eq!(loaded, HashMap::from([(1, entries[0]), (2, updated_entry)]));
+        let chunk = dal.get_storage_chunk(1, 10).await.unwrap();
+        assert_eq!(chunk, [updated_entry]);
+
+        let bytecode_hash = H256::repeat_byte(0xaa);
+        let factory_deps = HashMap::from([(bytecode_hash, vec![0; 32])]);
+        dal.insert_factory_deps(L1BatchNumber(1), &factory_deps)
+            .await
+            .unwrap();
+        assert_eq!(
+            dal.get_factory_dep(bytecode_hash).await.unwrap(),
+            Some(vec![0; 32])
+        );
+
+        dal.rollback_to_batch(L1BatchNumber(0)).await.unwrap();
+        assert_eq!(dal.get_last_batch().await.unwrap(), None);
+        assert!(dal
+            .get_storage_by_indices(&[1, 2])
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(dal.get_factory_dep(bytecode_hash).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn rolling_back_l1_sync_storage() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        let mut dal = conn.l1_sync_dal();
+
+        let entry = L1SyncStorageEntry {
+            hashed_key: H256::repeat_byte(1),
+            enumeration_index: 1,
+            value: H256::repeat_byte(0xff),
+        };
+        let updated_entry = L1SyncStorageEntry {
+            value: H256::zero(),
+            ..entry
+        };
+        let new_entry = L1SyncStorageEntry {
+            hashed_key: H256::repeat_byte(2),
+            enumeration_index: 2,
+            value: H256::repeat_byte(0xfe),
+        };
+        dal.insert_storage(L1BatchNumber(1), &[entry])
+            .await
+            .unwrap();
+        dal.insert_storage(L1BatchNumber(2), &[updated_entry, new_entry])
+            .await
+            .unwrap();
+        let chunk = dal.get_storage_chunk(0, 10).await.unwrap();
+        assert_eq!(chunk, [updated_entry, new_entry]);
+
+        dal.rollback_to_batch(L1BatchNumber(1)).await.unwrap();
+        let chunk = dal.get_storage_chunk(0, 10).await.unwrap();
+        assert_eq!(chunk, [entry]);
+        let loaded = dal.get_storage_by_indices(&[1, 2]).await.unwrap();
+        assert_eq!(loaded, HashMap::from([(1, entry)]));
+    }
+}
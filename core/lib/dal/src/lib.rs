@@ -16,8 +16,8 @@ use crate::{
     fri_witness_generator_dal::FriWitnessGeneratorDal, gpu_prover_queue_dal::GpuProverQueueDal,
     proof_generation_dal::ProofGenerationDal, protocol_versions_dal::ProtocolVersionsDal,
     protocol_versions_web3_dal::ProtocolVersionsWeb3Dal, prover_dal::ProverDal,
-    snapshots_creator_dal::SnapshotsCreatorDal, snapshots_dal::SnapshotsDal,
-    storage_dal::StorageDal, storage_logs_dal::StorageLogsDal,
+    snapshot_recovery_dal::SnapshotRecoveryDal, snapshots_creator_dal::SnapshotsCreatorDal,
+    snapshots_dal::SnapshotsDal, storage_dal::StorageDal, storage_logs_dal::StorageLogsDal,
     storage_logs_dedup_dal::StorageLogsDedupDal, storage_web3_dal::StorageWeb3Dal,
     sync_dal::SyncDal, system_dal::SystemDal, tokens_dal::TokensDal,
     tokens_web3_dal::TokensWeb3Dal, transactions_dal::TransactionsDal,
@@ -45,12 +45,14 @@ pub mod fri_witness_generator_dal;
 pub mod gpu_prover_queue_dal;
 pub mod healthcheck;
 mod instrument;
+pub mod l1_sync_dal;
 mod metrics;
 mod models;
 pub mod proof_generation_dal;
 pub mod protocol_versions_dal;
 pub mod protocol_versions_web3_dal;
 pub mod prover_dal;
+pub mod snapshot_recovery_dal;
 pub mod snapshots_creator_dal;
 pub mod snapshots_dal;
 pub mod storage_dal;
@@ -251,4 +253,12 @@ impl<'a> StorageProcessor<'a> {
     pub fn snapshots_creator_dal(&mut self) -> SnapshotsCreatorDal<'_, 'a> {
         SnapshotsCreatorDal { storage: self }
     }
+
+    pub fn snapshot_recovery_dal(&mut self) -> SnapshotRecoveryDal<'_, 'a> {
+        SnapshotRecoveryDal { storage: self }
+    }
+
+    pub fn l1_sync_dal(&mut self) -> L1SyncDal<'_, 'a> {
+        L1SyncDal { storage: self }
+    }
 }
@@ -22,6 +22,7 @@ pub struct StorageEthTx {
     pub updated_at: NaiveDateTime,
     // TODO (SMA-1614): remove the field
     pub sent_at_block: Option<i32>,
+    pub blob_sidecar: Option<Vec<u8>>,
 }
 
 #[derive(Debug, Default)]
@@ -54,6 +55,7 @@ pub struct StorageTxHistory {
     pub updated_at: NaiveDateTime,
     pub signed_raw_tx: Option<Vec<u8>>,
     pub sent_at_block: Option<i32>,
+    pub blob_base_fee_per_gas: Option<i64>,
 }
 
 impl From<StorageEthTx> for EthTx {
@@ -67,6 +69,9 @@ impl From<StorageEthTx> for EthTx {
             tx_type: AggregatedActionType::from_str(&tx.tx_type).expect("Wrong agg type"),
             created_at_timestamp: tx.created_at.timestamp() as u64,
             predicted_gas_cost: tx.predicted_gas_cost as u64,
+            blob_sidecar: tx.blob_sidecar.map(|sidecar| {
+                bincode::deserialize(&sidecar).expect("Incorrect blob sidecar in db")
+            }),
         }
     }
 }
@@ -84,6 +89,7 @@ impl From<StorageTxHistory> for TxHistory {
                 .expect("Should rely only on the new txs"),
 
             sent_at_block: history.sent_at_block.map(|block| block as u32),
+            blob_base_fee_per_gas: history.blob_base_fee_per_gas.map(|fee| fee as u64),
         }
     }
 }
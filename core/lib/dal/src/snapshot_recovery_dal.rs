@@ -0,0 +1,148 @@
+use zksync_types::{snapshots::SnapshotRecoveryStatus, L1BatchNumber, MiniblockNumber, H256};
+
+use crate::{instrument::InstrumentExt, StorageProcessor};
+
+#[derive(Debug)]
+pub struct SnapshotRecoveryDal<'a, 'c> {
+    pub(crate) storage: &'a mut StorageProcessor<'c>,
+}
+
+impl SnapshotRecoveryDal<'_, '_> {
+    /// Saves the snapshot recovery status, overwriting the existing one if it's present.
+    pub async fn set_applied_snapshot_status(
+        &mut self,
+        status: &SnapshotRecoveryStatus,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                snapshot_recovery (
+                    l1_batch_number,
+                    l1_batch_root_hash,
+                    miniblock_number,
+                    miniblock_hash,
+                    storage_logs_chunks_processed,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, $3, $4, $5, NOW(), NOW())
+            ON CONFLICT (l1_batch_number) DO
+            UPDATE
+            SET
+                l1_batch_root_hash = excluded.l1_batch_root_hash,
+                miniblock_number = excluded.miniblock_number,
+                miniblock_hash = excluded.miniblock_hash,
+                storage_logs_chunks_processed = excluded.storage_logs_chunks_processed,
+                updated_at = excluded.updated_at
+            "#,
+            status.l1_batch_number.0 as i64,
+            status.l1_batch_root_hash.as_bytes(),
+            status.miniblock_number.0 as i64,
+            status.miniblock_hash.as_bytes(),
+            &status.storage_logs_chunks_processed,
+        )
+        .instrument("set_applied_snapshot_status")
+        .with_arg("l1_batch_number", &status.l1_batch_number)
+        .report_latency()
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Marks a single storage logs chunk as processed.
+    pub async fn mark_storage_logs_chunk_as_processed(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        chunk_id: u64,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE snapshot_recovery
+            SET
+                storage_logs_chunks_processed[$2] = TRUE,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+            "#,
+            l1_batch_number.0 as i64,
+            chunk_id as i32 + 1 // Postgres arrays are 1-based
+        )
+        .instrument("mark_storage_logs_chunk_as_processed")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("chunk_id", &chunk_id)
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the snapshot recovery status, or `None` if the node wasn't recovered from a snapshot.
+    pub async fn get_applied_snapshot_status(
+        &mut self,
+    ) -> sqlx::Result<Option<SnapshotRecoveryStatus>> {
+        let record = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                l1_batch_root_hash,
+                miniblock_number,
+                miniblock_hash,
+                storage_logs_chunks_processed
+            FROM
+                snapshot_recovery
+            "#
+        )
+        .instrument("get_applied_snapshot_status")
+        .report_latency()
+        .fetch_optional(self.storage.conn())
+        .await?;
+
+        Ok(record.map(|row| SnapshotRecoveryStatus {
+            l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            l1_batch_root_hash: H256::from_slice(&row.l1_batch_root_hash),
+            miniblock_number: MiniblockNumber(row.miniblock_number as u32),
+            miniblock_hash: H256::from_slice(&row.miniblock_hash),
+            storage_logs_chunks_processed: row.storage_logs_chunks_processed,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConnectionPool;
+
+    #[tokio::test]
+    async fn manipulating_snapshot_recovery_status() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        let mut dal = conn.snapshot_recovery_dal();
+        assert_eq!(dal.get_applied_snapshot_status().await.unwrap(), None);
+
+        let mut status = SnapshotRecoveryStatus {
+            l1_batch_number: L1BatchNumber(123),
+            l1_batch_root_hash: H256::repeat_byte(1),
+            miniblock_number: MiniblockNumber(234),
+            miniblock_hash: H256::repeat_byte(2),
+            storage_logs_chunks_processed: vec![false; 3],
+        };
+        dal.set_applied_snapshot_status(&status).await.unwrap();
+        let loaded_status = dal.get_applied_snapshot_status().await.unwrap();
+        assert_eq!(loaded_status.as_ref(), Some(&status));
+        assert_eq!(status.storage_logs_chunks_left_to_process(), [0, 1, 2]);
+
+        dal.mark_storage_logs_chunk_as_processed(L1BatchNumber(123), 1)
+            .await
+            .unwrap();
+        status.storage_logs_chunks_processed[1] = true;
+        let loaded_status = dal.get_applied_snapshot_status().await.unwrap().unwrap();
+        assert_eq!(loaded_status, status);
+        assert_eq!(loaded_status.storage_logs_chunks_left_to_process(), [0, 2]);
+        assert!(!loaded_status.is_finished());
+
+        status.storage_logs_chunks_processed = vec![true; 3];
+        dal.set_applied_snapshot_status(&status).await.unwrap();
+        let loaded_status = dal.get_applied_snapshot_status().await.unwrap().unwrap();
+        assert!(loaded_status.is_finished());
+    }
+}
@@ -1,9 +1,11 @@
+use std::time::Duration;
+
 use zksync_types::{
     snapshots::{AllSnapshots, SnapshotMetadata},
     L1BatchNumber,
 };
 
-use crate::{instrument::InstrumentExt, StorageProcessor};
+use crate::{instrument::InstrumentExt, time_utils::pg_interval_from_duration, StorageProcessor};
 
 #[derive(Debug)]
 pub struct SnapshotsDal<'a, 'c> {
@@ -92,10 +94,64 @@ impl SnapshotsDal<'_, '_> {
         });
         Ok(record)
     }
+
+    /// Returns L1 batch numbers for snapshots created more than `retention_period` ago, in the ascending order.
+    /// The latest snapshot is never returned, so that there's always a snapshot to recover from.
+    pub async fn get_expired_snapshots(
+        &mut self,
+        retention_period: Duration,
+    ) -> Result<Vec<L1BatchNumber>, sqlx::Error> {
+        let retention_period = pg_interval_from_duration(retention_period);
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number
+            FROM
+                snapshots
+            WHERE
+                created_at < NOW() - $1::INTERVAL
+                AND l1_batch_number < (
+                    SELECT
+                        MAX(l1_batch_number)
+                    FROM
+                        snapshots
+                )
+            ORDER BY
+                l1_batch_number
+            "#,
+            retention_period
+        )
+        .instrument("get_expired_snapshots")
+        .report_latency()
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| L1BatchNumber(row.l1_batch_number as u32))
+            .collect())
+    }
+
+    pub async fn delete_snapshot(&mut self, l1_batch_number: L1BatchNumber) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM snapshots
+            WHERE
+                l1_batch_number = $1
+            "#,
+            l1_batch_number.0 as i32
+        )
+        .instrument("delete_snapshot")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use zksync_types::L1BatchNumber;
 
     use crate::ConnectionPool;
@@ -157,4 +213,41 @@ mod tests {
         assert!(files.contains(&"gs:///bucket/test_file1.bin".to_string()));
         assert!(files.contains(&"gs:///bucket/test_file2.bin".to_string()));
     }
+
+    #[tokio::test]
+    async fn expired_snapshots() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        let mut dal = conn.snapshots_dal();
+        for number in [10, 20, 30] {
+            dal.add_snapshot(L1BatchNumber(number), &[], "factory_deps.bin")
+                .await
+                .unwrap();
+        }
+
+        sqlx::query("UPDATE snapshots SET created_at = created_at - INTERVAL '2 hours'")
+            .execute(dal.storage.conn())
+            .await
+            .unwrap();
+
+        let expired = dal
+            .get_expired_snapshots(Duration::from_secs(3_600))
+            .await
+            .unwrap();
+        assert_eq!(expired, [L1BatchNumber(10), L1BatchNumber(20)]);
+        let expired = dal
+            .get_expired_snapshots(Duration::from_secs(86_400))
+            .await
+            .unwrap();
+        assert!(expired.is_empty());
+
+        dal.delete_snapshot(L1BatchNumber(10)).await.unwrap();
+        let snapshots = dal.get_all_snapshots().await.unwrap();
+        assert_eq!(snapshots.snapshots_l1_batch_numbers.len(), 2);
+        assert!(dal
+            .get_snapshot_metadata(L1BatchNumber(10))
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
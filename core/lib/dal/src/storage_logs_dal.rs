@@ -2,8 +2,8 @@ use std::{collections::HashMap, ops, time::Instant};
 
 use sqlx::{types::chrono::Utc, Row};
 use zksync_types::{
-    get_code_key, AccountTreeId, Address, L1BatchNumber, MiniblockNumber, StorageKey, StorageLog,
-    FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256, U256,
+    get_code_key, snapshots::SnapshotStorageLog, AccountTreeId, Address, L1BatchNumber,
+    MiniblockNumber, StorageKey, StorageLog, FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256, U256,
 };
 
 use crate::{instrument::InstrumentExt, models::storage_log::StorageTreeEntry, StorageProcessor};
@@ -67,6 +67,23 @@ impl StorageLogsDal<'_, '_> {
         copy.finish().await.unwrap();
     }
 
+    /// Inserts storage logs from a snapshot storage logs chunk. All logs are attributed to the snapshot miniblock
+    /// and have zero transaction hash, since the snapshot doesn't contain information on the originating transactions.
+    pub async fn insert_storage_logs_from_snapshot(
+        &mut self,
+        miniblock_number: MiniblockNumber,
+        snapshot_storage_logs: &[SnapshotStorageLog],
+    ) {
+        let storage_logs: Vec<_> = snapshot_storage_logs
+            .iter()
+            .map(|log| StorageLog::new_write_log(log.key, log.value))
+            .collect();
+        // Operation numbers only need to be unique for the same hashed key; since each key is present in a snapshot
+        // exactly once, it's safe to restart numbering for each chunk.
+        self.insert_storage_logs_inner(miniblock_number, &[(H256::zero(), storage_logs)], 0)
+            .await;
+    }
+
     pub async fn append_storage_logs(
         &mut self,
         block_number: MiniblockNumber,
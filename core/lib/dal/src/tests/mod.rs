@@ -10,7 +10,8 @@ use zksync_types::{
     proofs::AggregationRound,
     tx::{tx_execution_info::TxExecutionStatus, ExecutionMetrics, TransactionExecutionResult},
     Address, Execute, L1BatchNumber, L1BlockNumber, L1TxCommonData, L2ChainId, MiniblockNumber,
-    PriorityOpId, ProtocolVersion, ProtocolVersionId, H160, H256, MAX_GAS_PER_PUBDATA_BYTE, U256,
+    Nonce, PriorityOpId, ProtocolVersion, ProtocolVersionId, H160, H256, MAX_GAS_PER_PUBDATA_BYTE,
+    U256,
 };
 
 use crate::{
@@ -18,7 +19,7 @@ use crate::{
     connection::ConnectionPool,
     protocol_versions_dal::ProtocolVersionsDal,
     prover_dal::{GetProverJobsParams, ProverDal},
-    transactions_dal::{L2TxSubmissionResult, TransactionsDal},
+    transactions_dal::{L2TxInsertionLimits, L2TxSubmissionResult, TransactionsDal},
     transactions_web3_dal::TransactionsWeb3Dal,
 };
 
@@ -165,6 +166,111 @@ async fn workflow_with_submit_tx_diff_hashes() {
     assert_eq!(result, L2TxSubmissionResult::Replaced);
 }
 
+#[tokio::test]
+async fn rejecting_underpriced_replacement() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let mut storage = connection_pool.access_storage().await.unwrap();
+    let limits = L2TxInsertionLimits {
+        min_replacement_fee_bump_percent: 10,
+        max_pending_txs_per_account: None,
+    };
+
+    let tx = mock_l2_transaction();
+    let result = storage
+        .transactions_dal()
+        .insert_transaction_l2_with_limits(tx.clone(), mock_tx_execution_metrics(), &limits)
+        .await;
+    assert_eq!(result, L2TxSubmissionResult::Added);
+
+    // The fee is bumped by 8%, which is insufficient.
+    let mut replacement = tx.clone();
+    replacement.common_data.fee.max_fee_per_gas = U256::from(270_000_000u32);
+    replacement.set_input(H256::random().0.to_vec(), H256::random());
+    let result = storage
+        .transactions_dal()
+        .insert_transaction_l2_with_limits(
+            replacement.clone(),
+            mock_tx_execution_metrics(),
+            &limits,
+        )
+        .await;
+    assert_eq!(result, L2TxSubmissionResult::Underpriced);
+
+    let mut web3_dal = storage.transactions_web3_dal();
+    let stored_tx = web3_dal.get_transaction_details(tx.hash()).await.unwrap();
+    assert!(stored_tx.is_some());
+    let rejected_tx = web3_dal
+        .get_transaction_details(replacement.hash())
+        .await
+        .unwrap();
+    assert!(rejected_tx.is_none());
+
+    // Replacements are not checked if limits are not provided.
+    let result = storage
+        .transactions_dal()
+        .insert_transaction_l2(replacement.clone(), mock_tx_execution_metrics())
+        .await;
+    assert_eq!(result, L2TxSubmissionResult::Replaced);
+
+    replacement.common_data.fee.max_fee_per_gas = U256::from(297_000_000u32);
+    replacement.set_input(H256::random().0.to_vec(), H256::random());
+    let result = storage
+        .transactions_dal()
+        .insert_transaction_l2_with_limits(replacement, mock_tx_execution_metrics(), &limits)
+        .await;
+    assert_eq!(result, L2TxSubmissionResult::Replaced);
+}
+
+#[tokio::test]
+async fn rejecting_transactions_over_account_limit() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let storage = &mut connection_pool.access_storage().await.unwrap();
+    let mut transactions_dal = TransactionsDal { storage };
+    let limits = L2TxInsertionLimits {
+        min_replacement_fee_bump_percent: 0,
+        max_pending_txs_per_account: Some(2),
+    };
+
+    let initiator_address = Address::random();
+    let mock_tx_with_nonce = |nonce| {
+        let mut tx = mock_l2_transaction();
+        tx.common_data.initiator_address = initiator_address;
+        tx.common_data.nonce = Nonce(nonce);
+        tx
+    };
+    for nonce in [1, 2] {
+        let result = transactions_dal
+            .insert_transaction_l2_with_limits(
+                mock_tx_with_nonce(nonce),
+                mock_tx_execution_metrics(),
+                &limits,
+            )
+            .await;
+        assert_eq!(result, L2TxSubmissionResult::Added);
+    }
+
+    let result = transactions_dal
+        .insert_transaction_l2_with_limits(
+            mock_tx_with_nonce(3),
+            mock_tx_execution_metrics(),
+            &limits,
+        )
+        .await;
+    assert_eq!(result, L2TxSubmissionResult::AccountLimitReached);
+
+    // Replacing pending transactions and filling nonce gaps is allowed.
+    for nonce in [2, 0] {
+        let result = transactions_dal
+            .insert_transaction_l2_with_limits(
+                mock_tx_with_nonce(nonce),
+                mock_tx_execution_metrics(),
+                &limits,
+            )
+            .await;
+        assert_ne!(result, L2TxSubmissionResult::AccountLimitReached);
+    }
+}
+
 #[tokio::test]
 async fn remove_stuck_txs() {
     let connection_pool = ConnectionPool::test_pool().await;
@@ -32,6 +32,10 @@ pub enum L2TxSubmissionResult {
     AlreadyExecuted,
     Duplicate,
     Proxied,
+    /// Transaction replaces a pending transaction with the same nonce, but doesn't bump its fees enough.
+    Underpriced,
+    /// Transaction sender has reached the limit of pending transactions.
+    AccountLimitReached,
 }
 
 impl fmt::Display for L2TxSubmissionResult {
@@ -42,10 +46,23 @@ impl fmt::Display for L2TxSubmissionResult {
             Self::AlreadyExecuted => "already_executed",
             Self::Duplicate => "duplicate",
             Self::Proxied => "proxied",
+            Self::Underpriced => "underpriced",
+            Self::AccountLimitReached => "account_limit_reached",
         })
     }
 }
 
+/// Limits enforced on insertion of L2 transactions submitted via the API. These limits should be consistent
+/// with ones enforced by the mempool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct L2TxInsertionLimits {
+    /// Minimum fee bump (in percent) required to replace a pending transaction with the same nonce.
+    /// Both `max_fee_per_gas` and `max_priority_fee_per_gas` must be bumped.
+    pub min_replacement_fee_bump_percent: u64,
+    /// Maximum number of pending transactions per account. `None` means no limit.
+    pub max_pending_txs_per_account: Option<usize>,
+}
+
 #[derive(Debug)]
 pub struct TransactionsDal<'c, 'a> {
     pub(crate) storage: &'c mut StorageProcessor<'a>,
@@ -254,6 +271,64 @@ impl TransactionsDal<'_, '_> {
         &mut self,
         tx: L2Tx,
         exec_info: TransactionExecutionMetrics,
+    ) -> L2TxSubmissionResult {
+        self.insert_transaction_l2_inner(tx, exec_info, None).await
+    }
+
+    /// Same as [`Self::insert_transaction_l2()`], but additionally enforces the provided limits.
+    /// A transaction replacing a pending transaction without a sufficient fee bump is rejected
+    /// with [`L2TxSubmissionResult::Underpriced`]. A transaction from an account having the maximum allowed
+    /// number of pending transactions with lower nonces is rejected with [`L2TxSubmissionResult::AccountLimitReached`];
+    /// transactions filling nonce gaps are accepted (the mempool evicts pending transactions with the greatest nonces
+    /// in this case).
+    pub async fn insert_transaction_l2_with_limits(
+        &mut self,
+        tx: L2Tx,
+        exec_info: TransactionExecutionMetrics,
+        limits: &L2TxInsertionLimits,
+    ) -> L2TxSubmissionResult {
+        if let Some(max_pending_txs) = limits.max_pending_txs_per_account {
+            let preceding_pending_txs = self
+                .count_preceding_pending_l2_txs(tx.initiator_account(), tx.common_data.nonce)
+                .await;
+            if preceding_pending_txs >= max_pending_txs {
+                return L2TxSubmissionResult::AccountLimitReached;
+            }
+        }
+        self.insert_transaction_l2_inner(tx, exec_info, Some(limits))
+            .await
+    }
+
+    /// Counts pending L2 transactions of the specified account with nonces lower than `nonce`.
+    async fn count_preceding_pending_l2_txs(&mut self, initiator: Address, nonce: Nonce) -> usize {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                transactions
+            WHERE
+                initiator_address = $1
+                AND nonce < $2
+                AND is_priority = FALSE
+                AND miniblock_number IS NULL
+                AND error IS NULL
+            "#,
+            initiator.as_bytes(),
+            i64::from(nonce.0)
+        )
+        .fetch_one(self.storage.conn())
+        .await
+        .unwrap()
+        .count;
+        count as usize
+    }
+
+    async fn insert_transaction_l2_inner(
+        &mut self,
+        tx: L2Tx,
+        exec_info: TransactionExecutionMetrics,
+        limits: Option<&L2TxInsertionLimits>,
     ) -> L2TxSubmissionResult {
         {
             let tx_hash = tx.hash();
@@ -287,6 +362,10 @@ impl TransactionsDal<'_, '_> {
             // Otherwise, if the subquery won't return NULL it means that there is already tx with such nonce and initiator_address in DB
             // and we can replace it WHERE clause conditions are met.
             // It is worth mentioning that if WHERE clause conditions are not met, None will be returned.
+            // If limits are provided, the WHERE clause additionally requires the replacement fees to be bumped
+            // by at least the specified percentage.
+            let min_fee_bump_percent =
+                limits.map(|limits| limits.min_replacement_fee_bump_percent as i64);
             let query_result = sqlx::query!(
                 r#"
                 INSERT INTO
@@ -360,6 +439,13 @@ impl TransactionsDal<'_, '_> {
                 WHERE
                     transactions.is_priority = FALSE
                     AND transactions.miniblock_number IS NULL
+                    AND (
+                        $20::BIGINT IS NULL
+                        OR (
+                            $6 >= DIV(transactions.max_fee_per_gas * (100 + $20), 100)
+                            AND $7 >= DIV(transactions.max_priority_fee_per_gas * (100 + $20), 100)
+                        )
+                    )
                 RETURNING
                     (
                         SELECT
@@ -389,7 +475,8 @@ impl TransactionsDal<'_, '_> {
                 exec_info.gas_used as i64,
                 (exec_info.initial_storage_writes + exec_info.repeated_storage_writes) as i32,
                 exec_info.contracts_used as i32,
-                received_at
+                received_at,
+                min_fee_bump_percent
             )
                 .fetch_optional(self.storage.conn())
                 .await
@@ -399,6 +486,11 @@ impl TransactionsDal<'_, '_> {
                 Ok(option_query_result) => match option_query_result {
                     Some(true) => L2TxSubmissionResult::Replaced,
                     Some(false) => L2TxSubmissionResult::Added,
+                    None if limits.is_some()
+                        && self.has_pending_l2_tx(initiator_address, nonce).await =>
+                    {
+                        L2TxSubmissionResult::Underpriced
+                    }
                     None => L2TxSubmissionResult::AlreadyExecuted,
                 },
                 Err(err) => {
@@ -432,6 +524,32 @@ impl TransactionsDal<'_, '_> {
         }
     }
 
+    /// Checks whether there is a pending (i.e., not yet executed) L2 transaction with the specified initiator and nonce.
+    async fn has_pending_l2_tx(&mut self, initiator: Address, nonce: i64) -> bool {
+        sqlx::query!(
+            r#"
+            SELECT
+                EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        transactions
+                    WHERE
+                        initiator_address = $1
+                        AND nonce = $2
+                        AND is_priority = FALSE
+                        AND miniblock_number IS NULL
+                ) AS "exists!"
+            "#,
+            initiator.as_bytes(),
+            nonce
+        )
+        .fetch_one(self.storage.conn())
+        .await
+        .unwrap()
+        .exists
+    }
+
     pub async fn mark_txs_as_executed_in_l1_batch(
         &mut self,
         block_number: L1BatchNumber,
@@ -1036,6 +1154,37 @@ impl TransactionsDal<'_, '_> {
         }
     }
 
+    /// Removes L2 transactions evicted from or rejected by the mempool. Transactions are identified
+    /// by the initiator address and nonce; only transactions currently loaded into the mempool are removed.
+    pub async fn remove_mempool_txs(&mut self, txs: &[(Address, Nonce)]) {
+        {
+            let (initiators, nonces): (Vec<_>, Vec<_>) = txs
+                .iter()
+                .map(|(address, nonce)| (address.as_bytes().to_vec(), i64::from(nonce.0)))
+                .unzip();
+            sqlx::query!(
+                r#"
+                DELETE FROM transactions
+                WHERE
+                    in_mempool = TRUE
+                    AND miniblock_number IS NULL
+                    AND (initiator_address, nonce) IN (
+                        SELECT
+                            u.initiator_address,
+                            u.nonce
+                        FROM
+                            UNNEST($1::bytea[], $2::BIGINT[]) AS u (initiator_address, nonce)
+                    )
+                "#,
+                &initiators,
+                &nonces,
+            )
+            .execute(self.storage.conn())
+            .await
+            .unwrap();
+        }
+    }
+
     pub async fn get_last_processed_l1_block(&mut self) -> Option<L1BlockNumber> {
         {
             sqlx::query!(
@@ -52,6 +52,7 @@ impl FromEnv for MempoolConfig {
 #[cfg(test)]
 mod tests {
     use zksync_basic_types::L2ChainId;
+    use zksync_config::configs::chain::{ContractGasCap, MempoolTxOrdering, OptionalSealCriterion};
 
     use super::*;
     use crate::test_utils::{addr, EnvMutex};
@@ -86,6 +87,16 @@ mod tests {
                 virtual_blocks_per_miniblock: 1,
                 upload_witness_inputs_to_gcs: false,
                 enum_index_migration_chunk_size: Some(2_000),
+                seal_criteria: vec![
+                    OptionalSealCriterion::L2ToL1Messages,
+                    OptionalSealCriterion::ContractGasCaps,
+                ],
+                max_l2_to_l1_messages_in_batch: Some(1_000),
+                max_batch_execution_time_ms: None,
+                contract_gas_caps: vec![ContractGasCap {
+                    address: addr("0000000000000000000000000000000000008006"),
+                    max_gas: 50_000_000,
+                }],
             },
             operations_manager: OperationsManagerConfig {
                 delay_interval: 100,
@@ -97,12 +108,16 @@ mod tests {
                 stuck_tx_timeout: 10,
                 remove_stuck_txs: true,
                 delay_interval: 100,
+                min_replacement_fee_bump_percent: Some(10),
+                max_pending_txs_per_account: Some(64),
+                tx_ordering: MempoolTxOrdering::PriorityFee,
             },
             circuit_breaker: CircuitBreakerConfig {
                 sync_interval_ms: 1000,
                 http_req_max_retry_number: 5,
                 http_req_retry_interval_sec: 2,
                 replication_lag_limit_sec: Some(10),
+                operator_nonce_issue_limit_sec: Some(600),
             },
         }
     }
@@ -132,6 +147,9 @@ mod tests {
             CHAIN_STATE_KEEPER_SAVE_CALL_TRACES="false"
             CHAIN_STATE_KEEPER_UPLOAD_WITNESS_INPUTS_TO_GCS="false"
             CHAIN_STATE_KEEPER_ENUM_INDEX_MIGRATION_CHUNK_SIZE="2000"
+            CHAIN_STATE_KEEPER_SEAL_CRITERIA="l2_to_l1_messages,contract_gas_caps"
+            CHAIN_STATE_KEEPER_MAX_L2_TO_L1_MESSAGES_IN_BATCH="1000"
+            CHAIN_STATE_KEEPER_CONTRACT_GAS_CAPS="0x0000000000000000000000000000000000008006:50000000"
             CHAIN_OPERATIONS_MANAGER_DELAY_INTERVAL="100"
             CHAIN_MEMPOOL_SYNC_INTERVAL_MS="10"
             CHAIN_MEMPOOL_SYNC_BATCH_SIZE="1000"
@@ -139,10 +157,14 @@ mod tests {
             CHAIN_MEMPOOL_REMOVE_STUCK_TXS="true"
             CHAIN_MEMPOOL_DELAY_INTERVAL="100"
             CHAIN_MEMPOOL_CAPACITY="1000000"
+            CHAIN_MEMPOOL_MIN_REPLACEMENT_FEE_BUMP_PERCENT="10"
+            CHAIN_MEMPOOL_MAX_PENDING_TXS_PER_ACCOUNT="64"
+            CHAIN_MEMPOOL_TX_ORDERING="priority_fee"
             CHAIN_CIRCUIT_BREAKER_SYNC_INTERVAL_MS="1000"
             CHAIN_CIRCUIT_BREAKER_HTTP_REQ_MAX_RETRY_NUMBER="5"
             CHAIN_CIRCUIT_BREAKER_HTTP_REQ_RETRY_INTERVAL_SEC="2"
             CHAIN_CIRCUIT_BREAKER_REPLICATION_LAG_LIMIT_SEC="10"
+            CHAIN_CIRCUIT_BREAKER_OPERATOR_NONCE_ISSUE_LIMIT_SEC="600"
         "#;
         lock.set_env(config);
 
@@ -26,10 +26,12 @@ impl FromEnv for GasAdjusterConfig {
 
 #[cfg(test)]
 mod tests {
-    use zksync_config::configs::eth_sender::{ProofLoadingMode, ProofSendingMode};
+    use zksync_config::configs::eth_sender::{
+        OperatorSignerMode, ProofLoadingMode, ProofSendingMode, PubdataSendingMode,
+    };
 
     use super::*;
-    use crate::test_utils::{hash, EnvMutex};
+    use crate::test_utils::{addr, hash, EnvMutex};
 
     static MUTEX: EnvMutex = EnvMutex::new();
 
@@ -54,6 +56,17 @@ mod tests {
                 l1_batch_min_age_before_execute_seconds: Some(1000),
                 max_acceptable_priority_fee_in_gwei: 100_000_000_000,
                 proof_loading_mode: ProofLoadingMode::OldProofFromDb,
+                pubdata_sending_mode: PubdataSendingMode::Blobs,
+                kzg_trusted_setup_path: Some("etc/kzg/trusted_setup.txt".to_owned()),
+                blob_tx_resend_interval_blocks: Some(5),
+                signer_mode: OperatorSignerMode::Remote,
+                remote_signer_url: Some("https://signer.example.com:9000".to_owned()),
+                remote_signer_operator_address: Some(addr(
+                    "de03a0B5963f75f1C8485B355fF6D30f3093BDE7",
+                )),
+                remote_signer_tls_cert_path: Some("etc/signer/client.pem".to_owned()),
+                remote_signer_tls_key_path: Some("etc/signer/client.key".to_owned()),
+                remote_signer_ca_cert_path: None,
             },
             gas_adjuster: GasAdjusterConfig {
                 default_priority_fee_per_gas: 20000000000,
@@ -98,6 +111,14 @@ mod tests {
             ETH_SENDER_SENDER_L1_BATCH_MIN_AGE_BEFORE_EXECUTE_SECONDS="1000"
             ETH_SENDER_SENDER_MAX_ACCEPTABLE_PRIORITY_FEE_IN_GWEI="100000000000"
             ETH_SENDER_SENDER_PROOF_LOADING_MODE="OldProofFromDb"
+            ETH_SENDER_SENDER_PUBDATA_SENDING_MODE="Blobs"
+            ETH_SENDER_SENDER_KZG_TRUSTED_SETUP_PATH="etc/kzg/trusted_setup.txt"
+            ETH_SENDER_SENDER_BLOB_TX_RESEND_INTERVAL_BLOCKS="5"
+            ETH_SENDER_SENDER_SIGNER_MODE="Remote"
+            ETH_SENDER_SENDER_REMOTE_SIGNER_URL="https://signer.example.com:9000"
+            ETH_SENDER_SENDER_REMOTE_SIGNER_OPERATOR_ADDRESS="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
+            ETH_SENDER_SENDER_REMOTE_SIGNER_TLS_CERT_PATH="etc/signer/client.pem"
+            ETH_SENDER_SENDER_REMOTE_SIGNER_TLS_KEY_PATH="etc/signer/client.key"
         "#;
         lock.set_env(config);
 
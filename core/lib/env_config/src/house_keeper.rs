@@ -30,6 +30,8 @@ mod tests {
             fri_prover_stats_reporting_interval_ms: 30_000,
             fri_proof_compressor_job_retrying_interval_ms: 30_000,
             fri_proof_compressor_stats_reporting_interval_ms: 30_000,
+            object_store_gc_interval_ms: Some(3_600_000),
+            snapshots_retention_period_sec: Some(604_800),
         }
     }
 
@@ -50,6 +52,8 @@ mod tests {
             HOUSE_KEEPER_FRI_PROVER_STATS_REPORTING_INTERVAL_MS="30000"
             HOUSE_KEEPER_FRI_PROOF_COMPRESSOR_STATS_REPORTING_INTERVAL_MS="30000"
             HOUSE_KEEPER_FRI_PROOF_COMPRESSOR_JOB_RETRYING_INTERVAL_MS="30000"
+            HOUSE_KEEPER_OBJECT_STORE_GC_INTERVAL_MS="3600000"
+            HOUSE_KEEPER_SNAPSHOTS_RETENTION_PERIOD_SEC="604800"
         "#;
         lock.set_env(config);
 
@@ -56,6 +56,10 @@ mod tests {
             file_backed_base_path: "artifacts".to_string(),
             gcs_credential_file_path: "/path/to/credentials.json".to_string(),
             max_retries: 5,
+            s3_endpoint: None,
+            s3_region: None,
+            s3_access_key_id: None,
+            s3_secret_access_key: None,
         }
     }
 
@@ -118,4 +122,34 @@ mod tests {
         let actual = SnapshotsObjectStoreConfig::from_env().unwrap().0;
         assert_eq!(actual, expected_config("/snapshots_base_url"));
     }
+
+    #[test]
+    fn s3_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            OBJECT_STORE_BUCKET_BASE_URL="zksync-artifacts"
+            OBJECT_STORE_MODE="S3"
+            OBJECT_STORE_FILE_BACKED_BASE_PATH="artifacts"
+            OBJECT_STORE_GCS_CREDENTIAL_FILE_PATH="/path/to/credentials.json"
+            OBJECT_STORE_MAX_RETRIES="5"
+            OBJECT_STORE_S3_ENDPOINT="http://localhost:9000"
+            OBJECT_STORE_S3_REGION="eu-central-1"
+            OBJECT_STORE_S3_ACCESS_KEY_ID="access"
+            OBJECT_STORE_S3_SECRET_ACCESS_KEY="secret"
+        "#;
+        lock.set_env(config);
+        let actual = ObjectStoreConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            ObjectStoreConfig {
+                bucket_base_url: "zksync-artifacts".to_string(),
+                mode: ObjectStoreMode::S3,
+                s3_endpoint: Some("http://localhost:9000".to_string()),
+                s3_region: Some("eu-central-1".to_string()),
+                s3_access_key_id: Some("access".to_string()),
+                s3_secret_access_key: Some("secret".to_string()),
+                ..expected_config("zksync-artifacts")
+            }
+        );
+    }
 }
@@ -6,7 +6,7 @@ use vise::{
 
 pub use self::{
     query::QueryClient,
-    signing::{PKSigningClient, SigningClient},
+    signing::{operator_signer, PKSigningClient, SigningClient},
 };
 
 mod query;
@@ -20,6 +20,7 @@ enum Method {
     GetGasPrice,
     SendRawTx,
     BaseFeeHistory,
+    BlobBaseFeeHistory,
     #[metrics(name = "get_pending_block_base_fee_per_gas")]
     PendingBlockBaseFee,
     GetTxStatus,
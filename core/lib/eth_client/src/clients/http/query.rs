@@ -1,19 +1,24 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use zksync_types::web3::{
-    self,
-    contract::{
-        tokens::{Detokenize, Tokenize},
-        Contract, Options,
+use serde::Deserialize;
+use zksync_eth_signer::raw_ethereum_tx::encode_blob_tx_with_sidecar;
+use zksync_types::{
+    eth_sender::EthTxBlobSidecar,
+    web3::{
+        self,
+        contract::{
+            tokens::{Detokenize, Tokenize},
+            Contract, Options,
+        },
+        ethabi,
+        transports::Http,
+        types::{
+            Address, Block, BlockId, BlockNumber, Bytes, Filter, Log, Transaction, TransactionId,
+            TransactionReceipt, H256, U256, U64,
+        },
+        Transport, Web3,
     },
-    ethabi,
-    transports::Http,
-    types::{
-        Address, Block, BlockId, BlockNumber, Bytes, Filter, Log, Transaction, TransactionId,
-        TransactionReceipt, H256, U256, U64,
-    },
-    Web3,
 };
 
 use crate::{
@@ -22,6 +27,14 @@ use crate::{
     EthInterface,
 };
 
+/// Subset of the `eth_feeHistory` response related to EIP-4844. Not supported by the `web3` crate yet.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlobFeeHistory {
+    #[serde(default)]
+    base_fee_per_blob_gas: Vec<U256>,
+}
+
 /// An "anonymous" Ethereum client that can invoke read-only methods that aren't
 /// tied to a particular account.
 #[derive(Debug, Clone)]
@@ -87,6 +100,15 @@ impl EthInterface for QueryClient {
         Ok(tx)
     }
 
+    async fn send_raw_blob_tx(
+        &self,
+        tx: Vec<u8>,
+        blob_sidecar: &EthTxBlobSidecar,
+    ) -> Result<H256, Error> {
+        self.send_raw_tx(encode_blob_tx_with_sidecar(&tx, blob_sidecar))
+            .await
+    }
+
     async fn base_fee_history(
         &self,
         upto_block: usize,
@@ -120,6 +142,54 @@ impl EthInterface for QueryClient {
         Ok(history.into_iter().map(|fee| fee.as_u64()).collect())
     }
 
+    async fn blob_base_fee_history(
+        &self,
+        upto_block: usize,
+        block_count: usize,
+        component: &'static str,
+    ) -> Result<Vec<u64>, Error> {
+        const MAX_REQUEST_CHUNK: usize = 1024;
+
+        COUNTERS.call[&(Method::BlobBaseFeeHistory, component)].inc();
+        let latency = LATENCIES.direct[&Method::BlobBaseFeeHistory].start();
+        let mut history = Vec::with_capacity(block_count);
+        let from_block = upto_block.saturating_sub(block_count);
+
+        // Same chunking as in `base_fee_history()`.
+        for chunk_start in (from_block..=upto_block).step_by(MAX_REQUEST_CHUNK) {
+            let chunk_end = (chunk_start + MAX_REQUEST_CHUNK).min(upto_block);
+            let chunk_size = chunk_end - chunk_start;
+            let params = vec![
+                web3::helpers::serialize(&U256::from(chunk_size)),
+                web3::helpers::serialize(&BlockNumber::from(chunk_end)),
+                web3::helpers::serialize(&Vec::<f64>::new()),
+            ];
+            let chunk: BlobFeeHistory = web3::helpers::CallFuture::new(
+                self.web3.transport().execute("eth_feeHistory", params),
+            )
+            .await?;
+
+            if chunk.base_fee_per_blob_gas.is_empty() {
+                // The L1 node doesn't support EIP-4844, or blob fees are not available for the older blocks
+                // in the requested range. In the latter case, fees for the chunks already fetched are
+                // inconsistent with the rest of the range.
+                if !history.is_empty() {
+                    return Err(Error::EthereumGateway(web3::Error::InvalidResponse(
+                        format!(
+                            "blob base fees are missing for blocks {chunk_start}..={chunk_end}"
+                        ),
+                    )));
+                }
+                latency.observe();
+                return Ok(vec![]);
+            }
+            history.extend(chunk.base_fee_per_blob_gas);
+        }
+
+        latency.observe();
+        Ok(history.into_iter().map(|fee| fee.as_u64()).collect())
+    }
+
     async fn get_pending_block_base_fee_per_gas(
         &self,
         component: &'static str,
@@ -1,10 +1,17 @@
-use std::{fmt, sync::Arc};
+use std::{fmt, fs, sync::Arc};
 
 use async_trait::async_trait;
-use zksync_config::{ContractsConfig, ETHClientConfig, ETHSenderConfig};
+use zksync_config::{
+    configs::eth_sender::{OperatorSignerMode, SenderConfig},
+    ContractsConfig, ETHClientConfig, ETHSenderConfig,
+};
 use zksync_contracts::zksync_contract;
-use zksync_eth_signer::{raw_ethereum_tx::TransactionParameters, EthereumSigner, PrivateKeySigner};
+use zksync_eth_signer::{
+    raw_ethereum_tx::TransactionParameters, EthereumSigner, OperatorSigner, PrivateKeySigner,
+    RemoteSigner, RemoteSignerTls,
+};
 use zksync_types::{
+    eth_sender::EthTxBlobSidecar,
     web3::{
         self,
         contract::{
@@ -18,7 +25,7 @@ use zksync_types::{
             H160, H256, U256, U64,
         },
     },
-    L1ChainId, PackedEthSignature, EIP_1559_TX_TYPE,
+    L1ChainId, PackedEthSignature, EIP_1559_TX_TYPE, EIP_4844_TX_TYPE,
 };
 
 use super::{query::QueryClient, Method, LATENCIES};
@@ -27,8 +34,8 @@ use crate::{
     BoundEthInterface, EthInterface,
 };
 
-/// HTTP-based Ethereum client, backed by a private key to sign transactions.
-pub type PKSigningClient = SigningClient<PrivateKeySigner>;
+/// HTTP-based Ethereum client, backed by the operator signer (a private key or a remote signer) to sign transactions.
+pub type PKSigningClient = SigningClient<OperatorSigner>;
 
 impl PKSigningClient {
     pub fn from_config(
@@ -39,18 +46,13 @@ impl PKSigningClient {
         // Gather required data from the config.
         // It's done explicitly to simplify getting rid of this function later.
         let main_node_url = &eth_client.web3_url;
-        let operator_private_key = eth_sender
-            .sender
-            .private_key()
-            .expect("Operator private key is required for signing client");
         let diamond_proxy_addr = contracts_config.diamond_proxy_addr;
         let default_priority_fee_per_gas = eth_sender.gas_adjuster.default_priority_fee_per_gas;
         let l1_chain_id = eth_client.chain_id;
 
         let transport =
             web3::transports::Http::new(main_node_url).expect("Failed to create transport");
-        let operator_address = PackedEthSignature::address_from_private_key(&operator_private_key)
-            .expect("Failed to get address from private key");
+        let (operator_address, signer) = operator_signer(&eth_sender.sender);
 
         tracing::info!("Operator address: {:?}", operator_address);
 
@@ -58,7 +60,7 @@ impl PKSigningClient {
             transport,
             zksync_contract(),
             operator_address,
-            PrivateKeySigner::new(operator_private_key),
+            signer,
             diamond_proxy_addr,
             default_priority_fee_per_gas.into(),
             L1ChainId(l1_chain_id),
@@ -66,6 +68,55 @@ impl PKSigningClient {
     }
 }
 
+/// Creates the operator signer based on the config and returns it together with the operator address.
+///
+/// # Panics
+///
+/// Panics if the config is invalid for the configured signer mode.
+pub fn operator_signer(config: &SenderConfig) -> (Address, OperatorSigner) {
+    match config.signer_mode {
+        OperatorSignerMode::PrivateKey => {
+            let operator_private_key = config
+                .private_key()
+                .expect("Operator private key is required for signing client");
+            let operator_address =
+                PackedEthSignature::address_from_private_key(&operator_private_key)
+                    .expect("Failed to get address from private key");
+            let signer = PrivateKeySigner::new(operator_private_key);
+            (operator_address, signer.into())
+        }
+        OperatorSignerMode::Remote => {
+            let url = config
+                .remote_signer_url
+                .as_deref()
+                .expect("Remote signer URL is required for remote signer");
+            let operator_address = config
+                .remote_signer_operator_address
+                .expect("Operator address is required for remote signer");
+            let read_file = |path: &str| {
+                fs::read(path).unwrap_or_else(|err| panic!("Cannot read `{path}`: {err}"))
+            };
+            let tls = match (
+                &config.remote_signer_tls_cert_path,
+                &config.remote_signer_tls_key_path,
+            ) {
+                (Some(cert_path), Some(key_path)) => Some(RemoteSignerTls {
+                    client_cert_pem: read_file(cert_path),
+                    client_key_pem: read_file(key_path),
+                    ca_cert_pem: config.remote_signer_ca_cert_path.as_deref().map(read_file),
+                }),
+                (None, None) => None,
+                _ => panic!(
+                    "Both TLS certificate and key paths must be specified for remote signer client auth"
+                ),
+            };
+            let signer = RemoteSigner::new(url, operator_address, tls.as_ref())
+                .expect("Failed to create remote signer");
+            (operator_address, signer.into())
+        }
+    }
+}
+
 /// Gas limit value to be used in transaction if for some reason
 /// gas limit was not set for it.
 ///
@@ -126,6 +177,14 @@ impl<S: EthereumSigner> EthInterface for SigningClient<S> {
         self.query_client.send_raw_tx(tx).await
     }
 
+    async fn send_raw_blob_tx(
+        &self,
+        tx: Vec<u8>,
+        blob_sidecar: &EthTxBlobSidecar,
+    ) -> Result<H256, Error> {
+        self.query_client.send_raw_blob_tx(tx, blob_sidecar).await
+    }
+
     async fn base_fee_history(
         &self,
         upto_block: usize,
@@ -137,6 +196,17 @@ impl<S: EthereumSigner> EthInterface for SigningClient<S> {
             .await
     }
 
+    async fn blob_base_fee_history(
+        &self,
+        upto_block: usize,
+        block_count: usize,
+        component: &'static str,
+    ) -> Result<Vec<u64>, Error> {
+        self.query_client
+            .blob_base_fee_history(upto_block, block_count, component)
+            .await
+    }
+
     async fn get_pending_block_base_fee_per_gas(
         &self,
         component: &'static str,
@@ -245,6 +315,78 @@ impl<S: EthereumSigner> BoundEthInterface for SigningClient<S> {
         contract_addr: H160,
         options: Options,
         component: &'static str,
+    ) -> Result<SignedCallResult, Error> {
+        self.sign_tx(data, contract_addr, options, None, component)
+            .await
+    }
+
+    async fn sign_prepared_blob_tx_for_addr(
+        &self,
+        data: Vec<u8>,
+        contract_addr: H160,
+        options: Options,
+        max_fee_per_blob_gas: U256,
+        blob_sidecar: &EthTxBlobSidecar,
+        component: &'static str,
+    ) -> Result<SignedCallResult, Error> {
+        let blob_params = Some((max_fee_per_blob_gas, blob_sidecar));
+        self.sign_tx(data, contract_addr, options, blob_params, component)
+            .await
+    }
+
+    async fn allowance_on_account(
+        &self,
+        token_address: Address,
+        address: Address,
+        erc20_abi: ethabi::Contract,
+    ) -> Result<U256, Error> {
+        let latency = LATENCIES.direct[&Method::Allowance].start();
+        let res = self
+            .call_contract_function(
+                "allowance",
+                (self.inner.sender_account, address),
+                None,
+                Options::default(),
+                None,
+                token_address,
+                erc20_abi,
+            )
+            .await?;
+        latency.observe();
+        Ok(res)
+    }
+}
+
+impl<S: EthereumSigner> SigningClient<S> {
+    pub fn new(
+        transport: Http,
+        contract: ethabi::Contract,
+        operator_eth_addr: H160,
+        eth_signer: S,
+        contract_eth_addr: H160,
+        default_priority_fee_per_gas: U256,
+        chain_id: L1ChainId,
+    ) -> Self {
+        Self {
+            inner: Arc::new(ETHDirectClientInner {
+                sender_account: operator_eth_addr,
+                eth_signer,
+                contract_addr: contract_eth_addr,
+                chain_id,
+                contract,
+                default_priority_fee_per_gas,
+            }),
+            query_client: transport.into(),
+        }
+    }
+
+    async fn sign_tx(
+        &self,
+        data: Vec<u8>,
+        contract_addr: H160,
+        options: Options,
+        blob_params: Option<(U256, &EthTxBlobSidecar)>,
+        component: &'static str,
     ) -> Result<SignedCallResult, Error> {
         let latency = LATENCIES.direct[&Method::SignPreparedTx].start();
         // Fetch current max priority fee per gas
@@ -285,6 +427,11 @@ impl<S: EthereumSigner> BoundEthInterface for SigningClient<S> {
             U256::from(FALLBACK_GAS_LIMIT)
         });
 
+        let transaction_type = if blob_params.is_some() {
+            EIP_4844_TX_TYPE
+        } else {
+            EIP_1559_TX_TYPE
+        };
         let tx = TransactionParameters {
             nonce,
             to: Some(contract_addr),
@@ -294,66 +441,22 @@ impl<S: EthereumSigner> BoundEthInterface for SigningClient<S> {
             chain_id: self.inner.chain_id.0,
             max_priority_fee_per_gas,
             gas_price: None,
-            transaction_type: Some(EIP_1559_TX_TYPE.into()),
+            transaction_type: Some(transaction_type.into()),
             access_list: None,
             max_fee_per_gas,
+            max_fee_per_blob_gas: blob_params.map(|(max_fee, _)| max_fee),
+            blob_versioned_hashes: blob_params.map(|(_, sidecar)| sidecar.versioned_hashes.clone()),
         };
 
-        let signed_tx = self.inner.eth_signer.sign_transaction(tx).await?;
-        let hash = web3::signing::keccak256(&signed_tx).into();
+        let raw_tx = self.inner.eth_signer.sign_transaction(tx).await?;
+        let hash = web3::signing::keccak256(&raw_tx).into();
         latency.observe();
         Ok(SignedCallResult {
-            raw_tx: signed_tx,
+            raw_tx,
             max_priority_fee_per_gas,
             max_fee_per_gas,
             nonce,
             hash,
         })
     }
-
-    async fn allowance_on_account(
-        &self,
-        token_address: Address,
-        address: Address,
-        erc20_abi: ethabi::Contract,
-    ) -> Result<U256, Error> {
-        let latency = LATENCIES.direct[&Method::Allowance].start();
-        let res = self
-            .call_contract_function(
-                "allowance",
-                (self.inner.sender_account, address),
-                None,
-                Options::default(),
-                None,
-                token_address,
-                erc20_abi,
-            )
-            .await?;
-        latency.observe();
-        Ok(res)
-    }
-}
-
-impl<S: EthereumSigner> SigningClient<S> {
-    pub fn new(
-        transport: Http,
-        contract: ethabi::Contract,
-        operator_eth_addr: H160,
-        eth_signer: S,
-        contract_eth_addr: H160,
-        default_priority_fee_per_gas: U256,
-        chain_id: L1ChainId,
-    ) -> Self {
-        Self {
-            inner: Arc::new(ETHDirectClientInner {
-                sender_account: operator_eth_addr,
-                eth_signer,
-                contract_addr: contract_eth_addr,
-                chain_id,
-                contract,
-                default_priority_fee_per_gas,
-            }),
-            query_client: transport.into(),
-        }
-    }
 }
@@ -8,7 +8,9 @@ use std::{
 
 use async_trait::async_trait;
 use jsonrpc_core::types::error::Error as RpcError;
+use serde::Deserialize;
 use zksync_types::{
+    eth_sender::EthTxBlobSidecar,
     web3::{
         contract::{
             tokens::{Detokenize, Tokenize},
@@ -31,6 +33,7 @@ pub struct MockTx {
     pub hash: H256,
     pub nonce: u64,
     pub base_fee: U256,
+    pub priority_fee: U256,
 }
 
 impl From<Vec<u8>> for MockTx {
@@ -52,6 +55,7 @@ impl From<Vec<u8>> for MockTx {
             nonce,
             hash,
             base_fee,
+            priority_fee,
         }
     }
 }
@@ -62,6 +66,7 @@ pub struct MockEthereum {
     pub block_number: AtomicU64,
     pub max_fee_per_gas: U256,
     pub base_fee_history: RwLock<Vec<u64>>,
+    pub blob_base_fee_history: RwLock<Vec<u64>>,
     pub max_priority_fee_per_gas: U256,
     pub tx_statuses: RwLock<HashMap<H256, ExecutedTxStatus>>,
     pub sent_txs: RwLock<HashMap<H256, MockTx>>,
@@ -72,6 +77,10 @@ pub struct MockEthereum {
     /// This is useful for testing the cases when the transactions are executed out of order.
     pub non_ordering_confirmations: bool,
     pub multicall_address: Address,
+    /// L1 transactions returned by [`EthInterface::get_tx()`].
+    pub l1_transactions: RwLock<HashMap<H256, Transaction>>,
+    /// Logs returned by [`EthInterface::logs()`].
+    pub logs: RwLock<Vec<Log>>,
 }
 
 impl Default for MockEthereum {
@@ -81,6 +90,7 @@ impl Default for MockEthereum {
             max_priority_fee_per_gas: 10.into(),
             block_number: Default::default(),
             base_fee_history: Default::default(),
+            blob_base_fee_history: Default::default(),
             tx_statuses: Default::default(),
             sent_txs: Default::default(),
             current_nonce: Default::default(),
@@ -88,6 +98,8 @@ impl Default for MockEthereum {
             nonces: RwLock::new([(0, 0)].into()),
             non_ordering_confirmations: false,
             multicall_address: Address::default(),
+            l1_transactions: Default::default(),
+            logs: Default::default(),
         }
     }
 }
@@ -174,6 +186,37 @@ impl MockEthereum {
         })
     }
 
+    /// Same as [`Self::sign_prepared_tx()`], but additionally mixes blob-related params into the transaction.
+    pub fn sign_prepared_blob_tx(
+        &self,
+        mut raw_tx: Vec<u8>,
+        options: Options,
+        max_fee_per_blob_gas: U256,
+        blob_sidecar: &EthTxBlobSidecar,
+    ) -> Result<SignedCallResult, Error> {
+        raw_tx.append(&mut ethabi::encode(&max_fee_per_blob_gas.into_tokens()));
+        for versioned_hash in &blob_sidecar.versioned_hashes {
+            raw_tx.extend_from_slice(versioned_hash.as_bytes());
+        }
+        self.sign_prepared_tx(raw_tx, options)
+    }
+
+    /// Records an L1 transaction included in the current block together with the logs emitted by it.
+    /// Transaction and block information in the logs is filled in automatically.
+    pub fn add_l1_transaction(&self, mut tx: Transaction, logs: Vec<Log>) {
+        let block_number = self.block_number.load(Ordering::SeqCst);
+        tx.block_number = Some(block_number.into());
+        let mut all_logs = self.logs.write().unwrap();
+        for (i, mut log) in logs.into_iter().enumerate() {
+            log.block_number = Some(block_number.into());
+            log.transaction_hash = Some(tx.hash);
+            log.log_index = Some((all_logs.len() as u64).into());
+            log.transaction_log_index = Some((i as u64).into());
+            all_logs.push(log);
+        }
+        self.l1_transactions.write().unwrap().insert(tx.hash, tx);
+    }
+
     pub fn advance_block_number(&self, val: u64) -> u64 {
         self.block_number.fetch_add(val, Ordering::SeqCst) + val
     }
@@ -185,6 +228,13 @@ impl MockEthereum {
         }
     }
 
+    pub fn with_blob_fee_history(self, history: Vec<u64>) -> Self {
+        Self {
+            blob_base_fee_history: RwLock::new(history),
+            ..self
+        }
+    }
+
     pub fn with_non_ordering_confirmation(self, non_ordering_confirmations: bool) -> Self {
         Self {
             non_ordering_confirmations,
@@ -234,6 +284,15 @@ impl EthInterface for MockEthereum {
         Ok(mock_tx.hash)
     }
 
+    async fn send_raw_blob_tx(
+        &self,
+        tx: Vec<u8>,
+        _blob_sidecar: &EthTxBlobSidecar,
+    ) -> Result<H256, Error> {
+        // Blobs are already committed to in the mock transaction via their versioned hashes.
+        self.send_raw_tx(tx).await
+    }
+
     async fn nonce_at_for_account(
         &self,
         _account: Address,
@@ -258,6 +317,19 @@ impl EthInterface for MockEthereum {
             .to_vec())
     }
 
+    async fn blob_base_fee_history(
+        &self,
+        from_block: usize,
+        block_count: usize,
+        _component: &'static str,
+    ) -> Result<Vec<u64>, Error> {
+        let history = self.blob_base_fee_history.read().unwrap();
+        if history.is_empty() {
+            return Ok(vec![]);
+        }
+        Ok(history[from_block.saturating_sub(block_count - 1)..=from_block].to_vec())
+    }
+
     async fn get_pending_block_base_fee_per_gas(
         &self,
         _component: &'static str,
@@ -317,10 +389,10 @@ impl EthInterface for MockEthereum {
 
     async fn get_tx(
         &self,
-        _hash: H256,
+        hash: H256,
         _component: &'static str,
     ) -> Result<Option<Transaction>, Error> {
-        unimplemented!("Not needed right now")
+        Ok(self.l1_transactions.read().unwrap().get(&hash).cloned())
     }
 
     async fn tx_receipt(
@@ -339,8 +411,16 @@ impl EthInterface for MockEthereum {
         unimplemented!("Not needed right now")
     }
 
-    async fn logs(&self, _filter: Filter, _component: &'static str) -> Result<Vec<Log>, Error> {
-        unimplemented!("Not needed right now")
+    /// Returns recorded logs matching the filter. Only block range, address and topic criteria are supported.
+    async fn logs(&self, filter: Filter, _component: &'static str) -> Result<Vec<Log>, Error> {
+        let latest_block = self.block_number.load(Ordering::SeqCst);
+        let filter = MockLogFilter::new(&filter, latest_block);
+        let logs = self.logs.read().unwrap();
+        Ok(logs
+            .iter()
+            .filter(|log| filter.matches(log))
+            .cloned()
+            .collect())
     }
 
     async fn block(
@@ -380,6 +460,18 @@ impl BoundEthInterface for MockEthereum {
         self.sign_prepared_tx(data, options)
     }
 
+    async fn sign_prepared_blob_tx_for_addr(
+        &self,
+        data: Vec<u8>,
+        _contract_addr: H160,
+        options: Options,
+        max_fee_per_blob_gas: U256,
+        blob_sidecar: &EthTxBlobSidecar,
+        _component: &'static str,
+    ) -> Result<SignedCallResult, Error> {
+        self.sign_prepared_blob_tx(data, options, max_fee_per_blob_gas, blob_sidecar)
+    }
+
     async fn allowance_on_account(
         &self,
         _token_address: Address,
@@ -438,6 +530,17 @@ impl<T: AsRef<MockEthereum> + Send + Sync> EthInterface for T {
             .await
     }
 
+    async fn blob_base_fee_history(
+        &self,
+        from_block: usize,
+        block_count: usize,
+        component: &'static str,
+    ) -> Result<Vec<u64>, Error> {
+        self.as_ref()
+            .blob_base_fee_history(from_block, block_count, component)
+            .await
+    }
+
     async fn get_pending_block_base_fee_per_gas(
         &self,
         component: &'static str,
@@ -459,6 +562,14 @@ impl<T: AsRef<MockEthereum> + Send + Sync> EthInterface for T {
         self.as_ref().send_raw_tx(tx).await
     }
 
+    async fn send_raw_blob_tx(
+        &self,
+        tx: Vec<u8>,
+        blob_sidecar: &EthTxBlobSidecar,
+    ) -> Result<H256, Error> {
+        self.as_ref().send_raw_blob_tx(tx, blob_sidecar).await
+    }
+
     async fn failure_reason(&self, tx_hash: H256) -> Result<Option<FailureInfo>, Error> {
         self.as_ref().failure_reason(tx_hash).await
     }
@@ -564,6 +675,27 @@ impl<T: AsRef<MockEthereum> + Send + Sync> BoundEthInterface for T {
             .await
     }
 
+    async fn sign_prepared_blob_tx_for_addr(
+        &self,
+        data: Vec<u8>,
+        contract_addr: H160,
+        options: Options,
+        max_fee_per_blob_gas: U256,
+        blob_sidecar: &EthTxBlobSidecar,
+        component: &'static str,
+    ) -> Result<SignedCallResult, Error> {
+        self.as_ref()
+            .sign_prepared_blob_tx_for_addr(
+                data,
+                contract_addr,
+                options,
+                max_fee_per_blob_gas,
+                blob_sidecar,
+                component,
+            )
+            .await
+    }
+
     async fn allowance_on_account(
         &self,
         token_address: Address,
@@ -587,3 +719,82 @@ impl<T: AsRef<MockEthereum> + Send + Sync> BoundEthInterface for T {
         self.as_ref().current_nonce("").await
     }
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> From<OneOrMany<T>> for Vec<T> {
+    fn from(value: OneOrMany<T>) -> Self {
+        match value {
+            OneOrMany::One(value) => vec![value],
+            OneOrMany::Many(values) => values,
+        }
+    }
+}
+
+/// Log filter criteria extracted from [`Filter`], whose fields are private.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawLogFilter {
+    from_block: Option<String>,
+    to_block: Option<String>,
+    address: Option<OneOrMany<Address>>,
+    topics: Option<Vec<Option<OneOrMany<H256>>>>,
+}
+
+#[derive(Debug)]
+struct MockLogFilter {
+    from_block: u64,
+    to_block: u64,
+    addresses: Option<Vec<Address>>,
+    topics: Vec<Option<Vec<H256>>>,
+}
+
+impl MockLogFilter {
+    fn new(filter: &Filter, latest_block: u64) -> Self {
+        let filter = serde_json::to_value(filter).expect("failed serializing log filter");
+        let filter: RawLogFilter =
+            serde_json::from_value(filter).expect("failed deserializing log filter");
+        let resolve_block = |block: Option<String>| match block.as_deref() {
+            Some("earliest") => 0,
+            Some(number) if number.starts_with("0x") => {
+                u64::from_str_radix(&number[2..], 16).expect("invalid block number in log filter")
+            }
+            _ => latest_block,
+        };
+        Self {
+            from_block: resolve_block(filter.from_block),
+            to_block: resolve_block(filter.to_block),
+            addresses: filter.address.map(Vec::from),
+            topics: filter
+                .topics
+                .unwrap_or_default()
+                .into_iter()
+                .map(|topics| topics.map(Vec::from))
+                .collect(),
+        }
+    }
+
+    fn matches(&self, log: &Log) -> bool {
+        let is_in_range = log.block_number.map_or(false, |number| {
+            (self.from_block..=self.to_block).contains(&number.as_u64())
+        });
+        let address_matches = self
+            .addresses
+            .as_ref()
+            .map_or(true, |addresses| addresses.contains(&log.address));
+        let topics_match = self.topics.iter().enumerate().all(|(i, topics)| {
+            let Some(topics) = topics else {
+                return true;
+            };
+            log.topics
+                .get(i)
+                .map_or(false, |topic| topics.contains(topic))
+        });
+        is_in_range && address_matches && topics_match
+    }
+}
@@ -2,6 +2,7 @@
 
 use async_trait::async_trait;
 use zksync_types::{
+    eth_sender::EthTxBlobSidecar,
     web3::{
         contract::{
             tokens::{Detokenize, Tokenize},
@@ -58,6 +59,17 @@ pub trait EthInterface: Sync + Send {
         component: &'static str,
     ) -> Result<Vec<u64>, Error>;
 
+    /// Collects the blob base fee history (EIP-4844) for the specified block range.
+    ///
+    /// Has the same semantics as [`Self::base_fee_history()`], but returns an empty vector
+    /// if the L1 node doesn't report blob base fees (e.g., if EIP-4844 isn't activated on L1).
+    async fn blob_base_fee_history(
+        &self,
+        from_block: usize,
+        block_count: usize,
+        component: &'static str,
+    ) -> Result<Vec<u64>, Error>;
+
     /// Returns the `base_fee_per_gas` value for the currently pending L1 block.
     async fn get_pending_block_base_fee_per_gas(
         &self,
@@ -73,6 +85,14 @@ pub trait EthInterface: Sync + Send {
     /// Sends a transaction to the Ethereum network.
     async fn send_raw_tx(&self, tx: Vec<u8>) -> Result<H256, Error>;
 
+    /// Sends a signed EIP-4844 transaction to the Ethereum network, wrapping it together with the provided
+    /// blob sidecar.
+    async fn send_raw_blob_tx(
+        &self,
+        tx: Vec<u8>,
+        blob_sidecar: &EthTxBlobSidecar,
+    ) -> Result<H256, Error>;
+
     /// Fetches the transaction status for a specified transaction hash.
     ///
     /// Returns `Ok(None)` if the transaction is either not found or not executed yet.
@@ -183,6 +203,21 @@ pub trait BoundEthInterface: EthInterface {
         component: &'static str,
     ) -> Result<SignedCallResult, Error>;
 
+    /// Signs an EIP-4844 transaction carrying the blobs from the provided sidecar. Otherwise, works similarly
+    /// to [`Self::sign_prepared_tx_for_addr()`].
+    ///
+    /// The returned raw transaction doesn't include the sidecar; it should be sent to L1 using
+    /// [`EthInterface::send_raw_blob_tx()`].
+    async fn sign_prepared_blob_tx_for_addr(
+        &self,
+        data: Vec<u8>,
+        contract_addr: H160,
+        options: Options,
+        max_fee_per_blob_gas: U256,
+        blob_sidecar: &EthTxBlobSidecar,
+        component: &'static str,
+    ) -> Result<SignedCallResult, Error>;
+
     /// Returns the nonce of the `Self::sender_account()` at the specified block.
     async fn nonce_at(&self, block: BlockNumber, component: &'static str) -> Result<U256, Error> {
         self.nonce_at_for_account(self.sender_account(), block, component)
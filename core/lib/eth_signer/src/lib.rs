@@ -1,7 +1,9 @@
 use async_trait::async_trait;
 use error::SignerError;
 pub use json_rpc_signer::JsonRpcSigner;
+pub use operator_signer::OperatorSigner;
 pub use pk_signer::PrivateKeySigner;
+pub use remote_signer::{RemoteSigner, RemoteSignerTls};
 use zksync_types::{
     tx::primitives::PackedEthSignature, Address, EIP712TypedStructure, Eip712Domain,
 };
@@ -10,8 +12,10 @@ pub use crate::raw_ethereum_tx::TransactionParameters;
 
 pub mod error;
 pub mod json_rpc_signer;
+pub mod operator_signer;
 pub mod pk_signer;
 pub mod raw_ethereum_tx;
+pub mod remote_signer;
 
 #[async_trait]
 pub trait EthereumSigner: Send + Sync + Clone {
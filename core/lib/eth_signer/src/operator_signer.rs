@@ -0,0 +1,65 @@
+use zksync_types::{
+    tx::primitives::PackedEthSignature, Address, EIP712TypedStructure, Eip712Domain,
+};
+
+use crate::{
+    raw_ethereum_tx::TransactionParameters, EthereumSigner, PrivateKeySigner, RemoteSigner,
+    SignerError,
+};
+
+/// Signer for the operator account: either a local private key or a remote signing service.
+#[derive(Debug, Clone)]
+pub enum OperatorSigner {
+    PrivateKey(PrivateKeySigner),
+    Remote(RemoteSigner),
+}
+
+impl From<PrivateKeySigner> for OperatorSigner {
+    fn from(signer: PrivateKeySigner) -> Self {
+        Self::PrivateKey(signer)
+    }
+}
+
+impl From<RemoteSigner> for OperatorSigner {
+    fn from(signer: RemoteSigner) -> Self {
+        Self::Remote(signer)
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for OperatorSigner {
+    async fn sign_message(&self, message: &[u8]) -> Result<PackedEthSignature, SignerError> {
+        match self {
+            Self::PrivateKey(signer) => signer.sign_message(message).await,
+            Self::Remote(signer) => signer.sign_message(message).await,
+        }
+    }
+
+    async fn sign_typed_data<S: EIP712TypedStructure + Sync>(
+        &self,
+        domain: &Eip712Domain,
+        typed_struct: &S,
+    ) -> Result<PackedEthSignature, SignerError> {
+        match self {
+            Self::PrivateKey(signer) => signer.sign_typed_data(domain, typed_struct).await,
+            Self::Remote(signer) => signer.sign_typed_data(domain, typed_struct).await,
+        }
+    }
+
+    async fn sign_transaction(
+        &self,
+        raw_tx: TransactionParameters,
+    ) -> Result<Vec<u8>, SignerError> {
+        match self {
+            Self::PrivateKey(signer) => signer.sign_transaction(raw_tx).await,
+            Self::Remote(signer) => signer.sign_transaction(raw_tx).await,
+        }
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        match self {
+            Self::PrivateKey(signer) => signer.get_address().await,
+            Self::Remote(signer) => signer.get_address().await,
+        }
+    }
+}
@@ -77,6 +77,8 @@ impl EthereumSigner for PrivateKeySigner {
             transaction_type: raw_tx.transaction_type,
             access_list: raw_tx.access_list.unwrap_or_default(),
             max_priority_fee_per_gas,
+            max_fee_per_blob_gas: raw_tx.max_fee_per_blob_gas.unwrap_or_default(),
+            blob_versioned_hashes: raw_tx.blob_versioned_hashes.unwrap_or_default(),
         };
 
         let signed = tx.sign(&key, raw_tx.chain_id);
@@ -86,10 +88,13 @@ impl EthereumSigner for PrivateKeySigner {
 
 #[cfg(test)]
 mod test {
-    use zksync_types::{H160, H256, U256, U64};
+    use zksync_types::{eth_sender::EthTxBlobSidecar, H160, H256, U256, U64};
 
     use super::PrivateKeySigner;
-    use crate::{raw_ethereum_tx::TransactionParameters, EthereumSigner};
+    use crate::{
+        raw_ethereum_tx::{encode_blob_tx_with_sidecar, recover_tx_signer, TransactionParameters},
+        EthereumSigner,
+    };
 
     #[tokio::test]
     async fn test_generating_signed_raw_transaction() {
@@ -107,6 +112,8 @@ mod test {
             chain_id: 270,
             transaction_type: Some(U64::from(1u32)),
             access_list: None,
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
         };
         let raw_tx = signer
             .sign_transaction(raw_transaction.clone())
@@ -124,4 +131,85 @@ mod test {
         ];
         assert_eq!(raw_tx, precalculated_raw_tx);
     }
+
+    #[tokio::test]
+    async fn signing_blob_transaction() {
+        let signer = PrivateKeySigner::new(H256::from([5; 32]));
+        let versioned_hashes = vec![H256::repeat_byte(1), H256::repeat_byte(2)];
+        let raw_transaction = TransactionParameters {
+            nonce: U256::from(1u32),
+            to: Some(H160::repeat_byte(0x11)),
+            gas: U256::from(100_000u32),
+            max_fee_per_gas: U256::from(2u32),
+            max_priority_fee_per_gas: U256::from(1u32),
+            data: vec![1, 2, 3],
+            chain_id: 270,
+            transaction_type: Some(U64::from(3u32)),
+            max_fee_per_blob_gas: Some(U256::from(10u32)),
+            blob_versioned_hashes: Some(versioned_hashes.clone()),
+            ..TransactionParameters::default()
+        };
+        let raw_tx = signer.sign_transaction(raw_transaction).await.unwrap();
+        assert_eq!(raw_tx[0], 3);
+        let payload = rlp::Rlp::new(&raw_tx[1..]);
+        assert_eq!(payload.item_count().unwrap(), 14);
+        assert_eq!(payload.val_at::<U256>(9).unwrap(), U256::from(10u32));
+        assert_eq!(payload.list_at::<H256>(10).unwrap(), versioned_hashes);
+
+        let sidecar = EthTxBlobSidecar {
+            blobs: vec![vec![1; 32], vec![2; 32]],
+            commitments: vec![vec![3; 48], vec![4; 48]],
+            proofs: vec![vec![5; 48], vec![6; 48]],
+            versioned_hashes,
+        };
+        let network_tx = encode_blob_tx_with_sidecar(&raw_tx, &sidecar);
+        assert_eq!(network_tx[0], 3);
+        let wrapped = rlp::Rlp::new(&network_tx[1..]);
+        assert_eq!(wrapped.item_count().unwrap(), 4);
+        assert_eq!(wrapped.at(0).unwrap().as_raw(), &raw_tx[1..]);
+        assert_eq!(wrapped.list_at::<Vec<u8>>(1).unwrap(), sidecar.blobs);
+        assert_eq!(wrapped.list_at::<Vec<u8>>(2).unwrap(), sidecar.commitments);
+        assert_eq!(wrapped.list_at::<Vec<u8>>(3).unwrap(), sidecar.proofs);
+    }
+
+    #[tokio::test]
+    async fn recovering_transaction_signer() {
+        let signer = PrivateKeySigner::new(H256::from([5; 32]));
+        let address = signer.get_address().await.unwrap();
+        let raw_transaction = TransactionParameters {
+            nonce: U256::from(1u32),
+            to: Some(H160::repeat_byte(0x11)),
+            gas: U256::from(100_000u32),
+            max_fee_per_gas: U256::from(2u32),
+            max_priority_fee_per_gas: U256::from(1u32),
+            data: vec![1, 2, 3],
+            chain_id: 270,
+            ..TransactionParameters::default()
+        };
+
+        for tx_type in [None, Some(1_u32), Some(2), Some(3)] {
+            let mut tx = TransactionParameters {
+                transaction_type: tx_type.map(U64::from),
+                ..raw_transaction.clone()
+            };
+            if tx_type == Some(3) {
+                tx.max_fee_per_blob_gas = Some(U256::from(10u32));
+                tx.blob_versioned_hashes = Some(vec![H256::repeat_byte(1)]);
+            }
+            let raw_tx = signer.sign_transaction(tx).await.unwrap();
+            assert_eq!(recover_tx_signer(&raw_tx).unwrap(), address, "{tx_type:?}");
+
+            // Corrupting the transaction changes the recovered signer.
+            let mut corrupted_tx = raw_tx;
+            let data_pos = corrupted_tx
+                .windows(3)
+                .position(|window| window == [1, 2, 3])
+                .unwrap();
+            corrupted_tx[data_pos] = 0;
+            assert_ne!(recover_tx_signer(&corrupted_tx).ok(), Some(address));
+        }
+
+        assert!(recover_tx_signer(&[]).is_err());
+        assert!(recover_tx_signer(&[2, 0xc0]).is_err());
+    }
 }
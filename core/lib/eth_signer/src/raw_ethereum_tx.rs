@@ -9,19 +9,24 @@
 //! In the case where it will be possible to use only the web3 library without copy-paste, the changes will be small and simple
 //! Link to @Deniallugo's PR to web3: https://github.com/tomusdrw/rust-web3/pull/630
 
-use rlp::RlpStream;
+use rlp::{Rlp, RlpStream};
 use zksync_types::{
+    eth_sender::EthTxBlobSidecar,
     ethabi::Address,
+    tx::primitives::PackedEthSignature,
     web3::{
         signing::{self, Signature},
         types::{AccessList, SignedTransaction},
     },
-    U256, U64,
+    H256, U256, U64,
 };
 
+use crate::SignerError;
+
 const LEGACY_TX_ID: u64 = 0;
 const ACCESSLISTS_TX_ID: u64 = 1;
 const EIP1559_TX_ID: u64 = 2;
+const EIP4844_TX_ID: u64 = 3;
 
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct TransactionParameters {
@@ -47,6 +52,10 @@ pub struct TransactionParameters {
     pub max_fee_per_gas: U256,
     /// miner bribe
     pub max_priority_fee_per_gas: U256,
+    /// Max fee per blob gas (only for EIP-4844 transactions)
+    pub max_fee_per_blob_gas: Option<U256>,
+    /// Versioned hashes of blobs (only for EIP-4844 transactions)
+    pub blob_versioned_hashes: Option<Vec<H256>>,
 }
 
 /// A transaction used for RLP encoding, hashing and signing.
@@ -61,6 +70,8 @@ pub struct Transaction {
     pub transaction_type: Option<U64>,
     pub access_list: AccessList,
     pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_blob_gas: U256,
+    pub blob_versioned_hashes: Vec<H256>,
 }
 
 impl Transaction {
@@ -147,6 +158,35 @@ impl Transaction {
         stream
     }
 
+    fn encode_eip4844_payload(&self, chain_id: u64, signature: Option<&Signature>) -> RlpStream {
+        let mut stream = RlpStream::new();
+
+        let list_size = if signature.is_some() { 14 } else { 11 };
+        stream.begin_list(list_size);
+
+        stream.append(&chain_id);
+        stream.append(&self.nonce);
+        stream.append(&self.max_priority_fee_per_gas);
+        stream.append(&self.gas_price);
+        stream.append(&self.gas);
+        // EIP-4844 transactions cannot create contracts.
+        let to = self
+            .to
+            .expect("EIP-4844 transactions must have a recipient");
+        stream.append(&to);
+        stream.append(&self.value);
+        stream.append(&self.data);
+        self.rlp_append_access_list(&mut stream);
+        stream.append(&self.max_fee_per_blob_gas);
+        stream.append_list::<H256, _>(&self.blob_versioned_hashes);
+
+        if let Some(signature) = signature {
+            self.rlp_append_signature(&mut stream, signature);
+        }
+
+        stream
+    }
+
     fn rlp_append_signature(&self, stream: &mut RlpStream, signature: &Signature) {
         stream.append(&signature.v);
         stream.append(&U256::from_big_endian(signature.r.as_bytes()));
@@ -184,6 +224,12 @@ impl Transaction {
                 [&[tx_id], stream.as_raw()].concat()
             }
 
+            Some(EIP4844_TX_ID) => {
+                let tx_id: u8 = EIP4844_TX_ID as u8;
+                let stream = self.encode_eip4844_payload(chain_id, signature);
+                [&[tx_id], stream.as_raw()].concat()
+            }
+
             _ => {
                 panic!("Unsupported transaction type");
             }
@@ -222,3 +268,96 @@ impl Transaction {
         }
     }
 }
+
+/// Wraps a signed EIP-4844 transaction together with its blob sidecar into the network representation
+/// accepted by `eth_sendRawTransaction`: `0x03 || rlp([tx_payload_body, blobs, commitments, proofs])`.
+///
+/// The transaction hash is still computed over the signed transaction without the sidecar.
+pub fn encode_blob_tx_with_sidecar(signed_tx: &[u8], sidecar: &EthTxBlobSidecar) -> Vec<u8> {
+    assert_eq!(
+        signed_tx.first(),
+        Some(&(EIP4844_TX_ID as u8)),
+        "transaction is not an EIP-4844 transaction"
+    );
+
+    let mut stream = RlpStream::new_list(4);
+    stream.append_raw(&signed_tx[1..], 1);
+    for items in [&sidecar.blobs, &sidecar.commitments, &sidecar.proofs] {
+        stream.begin_list(items.len());
+        for item in items {
+            stream.append(item);
+        }
+    }
+    [&[EIP4844_TX_ID as u8], stream.as_raw()].concat()
+}
+
+/// Recovers the address of the account that signed the RLP-encoded transaction, as returned by
+/// [`EthereumSigner::sign_transaction()`](crate::EthereumSigner::sign_transaction()).
+/// EIP-4844 transactions must be passed without the blob sidecar.
+pub fn recover_tx_signer(signed_tx: &[u8]) -> Result<Address, SignerError> {
+    let decode_err = |err: rlp::DecoderError| SignerError::DecodeRawTxFailed(err.to_string());
+
+    let (tx_id, payload) = match signed_tx.first() {
+        None => {
+            return Err(SignerError::DecodeRawTxFailed(
+                "transaction is empty".to_string(),
+            ))
+        }
+        // Typed transactions start with a byte in `[0, 0x7f]`, while legacy ones start with an RLP list prefix.
+        Some(&tx_id) if tx_id <= 0x7f => (Some(tx_id), &signed_tx[1..]),
+        Some(_) => (None, signed_tx),
+    };
+    let rlp = Rlp::new(payload);
+    let item_count = rlp.item_count().map_err(decode_err)?;
+    if item_count < 3 {
+        return Err(SignerError::DecodeRawTxFailed(format!(
+            "transaction has {item_count} RLP items, which is too few for a signed transaction"
+        )));
+    }
+    let field_count = item_count - 3;
+    let v: u64 = rlp.val_at(field_count).map_err(decode_err)?;
+    let r: U256 = rlp.val_at(field_count + 1).map_err(decode_err)?;
+    let s: U256 = rlp.val_at(field_count + 2).map_err(decode_err)?;
+
+    let (recovery_id, chain_id) = if tx_id.is_some() {
+        let recovery_id = u8::try_from(v)
+            .ok()
+            .filter(|&v| v <= 1)
+            .ok_or_else(|| SignerError::RecoverAddress(format!("invalid y parity: {v}")))?;
+        (recovery_id, None)
+    } else {
+        PackedEthSignature::unpack_v(v)
+            .map_err(|err| SignerError::RecoverAddress(err.to_string()))?
+    };
+
+    // The signed message is the transaction encoding without the signature (with the EIP-155 chain ID suffix
+    // for replay-protected legacy transactions).
+    let mut stream = RlpStream::new_list(if chain_id.is_some() {
+        item_count
+    } else {
+        field_count
+    });
+    for i in 0..field_count {
+        stream.append_raw(rlp.at(i).map_err(decode_err)?.as_raw(), 1);
+    }
+    if let Some(chain_id) = chain_id {
+        stream.append(&chain_id);
+        stream.append(&0u8);
+        stream.append(&0u8);
+    }
+    let message = match tx_id {
+        Some(tx_id) => [&[tx_id], stream.as_raw()].concat(),
+        None => stream.out().to_vec(),
+    };
+    let message_hash = H256(signing::keccak256(&message));
+
+    let to_h256 = |value: U256| {
+        let mut bytes = [0_u8; 32];
+        value.to_big_endian(&mut bytes);
+        H256(bytes)
+    };
+    let signature = PackedEthSignature::from_rsv(&to_h256(r), &to_h256(s), recovery_id);
+    signature
+        .signature_recover_signer(&message_hash)
+        .map_err(|err| SignerError::RecoverAddress(err.to_string()))
+}
@@ -0,0 +1,552 @@
+//! Signer delegating to a remote signing service (e.g., Web3Signer) via the `eth_signTransaction` JSON-RPC protocol.
+//! The private key of the signing account is never exposed to the process using the signer.
+
+use std::time::Duration;
+
+use jsonrpc_core::types::response::Output;
+use zksync_types::{
+    eip712_signature::utils::get_eip712_json, tx::primitives::PackedEthSignature, Address,
+    EIP712TypedStructure, Eip712Domain, H256, U64,
+};
+
+use crate::{
+    error::{RpcSignerError, SignerError},
+    json_rpc_signer::{is_signature_from_address, messages::JsonRpcRequest},
+    raw_ethereum_tx::{recover_tx_signer, TransactionParameters},
+    EthereumSigner,
+};
+
+/// Timeout for a single request to the remote signer.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// TLS settings for connecting to the remote signer.
+#[derive(Clone)]
+pub struct RemoteSignerTls {
+    /// PEM-encoded client certificate (chain) used for TLS client authentication.
+    pub client_cert_pem: Vec<u8>,
+    /// PEM-encoded PKCS #8 private key for the client certificate.
+    pub client_key_pem: Vec<u8>,
+    /// PEM-encoded CA certificate used to verify the signer. If `None`, the system root certificates are used.
+    pub ca_cert_pem: Option<Vec<u8>>,
+}
+
+impl std::fmt::Debug for RemoteSignerTls {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteSignerTls")
+            .field("has_ca_cert", &self.ca_cert_pem.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Signer for a single account managed by a remote signing service. The service must support
+/// `eth_sign`, `eth_signTypedData` and `eth_signTransaction` JSON-RPC methods in the Web3Signer flavor
+/// (i.e., `eth_signTransaction` returns a hex-encoded signed transaction).
+///
+/// Message signatures and signed transactions returned by the service are checked to be produced by the configured account.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    rpc_addr: String,
+    client: reqwest::Client,
+    address: Address,
+}
+
+impl RemoteSigner {
+    /// Creates a signer for the specified account. If `tls` is provided, the signer will authenticate
+    /// with the client certificate on each request.
+    pub fn new(
+        rpc_addr: impl Into<String>,
+        address: Address,
+        tls: Option<&RemoteSignerTls>,
+    ) -> Result<Self, SignerError> {
+        let mut builder = reqwest::Client::builder().timeout(REQUEST_TIMEOUT);
+        if let Some(tls) = tls {
+            let identity =
+                reqwest::Identity::from_pkcs8_pem(&tls.client_cert_pem, &tls.client_key_pem)
+                    .map_err(|err| {
+                        SignerError::CustomError(format!("Invalid remote signer identity: {err}"))
+                    })?;
+            builder = builder.use_native_tls().identity(identity);
+            if let Some(ca_cert_pem) = &tls.ca_cert_pem {
+                let ca_cert = reqwest::Certificate::from_pem(ca_cert_pem).map_err(|err| {
+                    SignerError::CustomError(format!("Invalid remote signer CA certificate: {err}"))
+                })?;
+                builder = builder.add_root_certificate(ca_cert);
+            }
+        }
+        let client = builder.build().map_err(|err| {
+            SignerError::CustomError(format!("Cannot build remote signer client: {err}"))
+        })?;
+
+        Ok(Self {
+            rpc_addr: rpc_addr.into(),
+            client,
+            address,
+        })
+    }
+
+    fn transaction_json(&self, tx: &TransactionParameters) -> serde_json::Value {
+        let mut json = serde_json::json!({
+            "from": self.address,
+            "gas": tx.gas,
+            "value": tx.value,
+            "data": format!("0x{}", hex::encode(&tx.data)),
+            "nonce": tx.nonce,
+            "chainId": U64::from(tx.chain_id),
+        });
+        let fields = json.as_object_mut().unwrap();
+        if let Some(to) = tx.to {
+            fields.insert("to".to_owned(), serde_json::json!(to));
+        }
+        // Legacy and access list transactions use `gasPrice`; all later transaction types use EIP-1559 fees.
+        let is_legacy_pricing = tx.transaction_type.map_or(true, |ty| ty.as_u64() <= 1);
+        if is_legacy_pricing {
+            let gas_price = tx.gas_price.unwrap_or(tx.max_fee_per_gas);
+            fields.insert("gasPrice".to_owned(), serde_json::json!(gas_price));
+        } else {
+            fields.insert(
+                "maxFeePerGas".to_owned(),
+                serde_json::json!(tx.max_fee_per_gas),
+            );
+            fields.insert(
+                "maxPriorityFeePerGas".to_owned(),
+                serde_json::json!(tx.max_priority_fee_per_gas),
+            );
+        }
+        if let Some(tx_type) = tx.transaction_type {
+            fields.insert("type".to_owned(), serde_json::json!(tx_type));
+        }
+        if let Some(access_list) = &tx.access_list {
+            fields.insert("accessList".to_owned(), serde_json::json!(access_list));
+        }
+        if let Some(max_fee_per_blob_gas) = tx.max_fee_per_blob_gas {
+            fields.insert(
+                "maxFeePerBlobGas".to_owned(),
+                serde_json::json!(max_fee_per_blob_gas),
+            );
+        }
+        if let Some(hashes) = &tx.blob_versioned_hashes {
+            fields.insert("blobVersionedHashes".to_owned(), serde_json::json!(hashes));
+        }
+        json
+    }
+
+    fn check_signature(
+        &self,
+        signature: &PackedEthSignature,
+        signed_bytes: &H256,
+    ) -> Result<(), SignerError> {
+        if is_signature_from_address(signature, signed_bytes, self.address)? {
+            Ok(())
+        } else {
+            Err(SignerError::SigningFailed(
+                "Invalid signature from RemoteSigner".to_string(),
+            ))
+        }
+    }
+
+    /// Performs a JSON-RPC call to the signer, returning the result of a successful call.
+    async fn post(&self, message: &JsonRpcRequest) -> Result<serde_json::Value, RpcSignerError> {
+        let res = self
+            .client
+            .post(&self.rpc_addr)
+            .json(message)
+            .send()
+            .await
+            .map_err(|err| RpcSignerError::NetworkError(err.to_string()))?;
+        if res.status() != reqwest::StatusCode::OK {
+            let error = format!(
+                "Post query responded with a non-OK response: {}",
+                res.status()
+            );
+            return Err(RpcSignerError::NetworkError(error));
+        }
+        let reply: Output = res
+            .json()
+            .await
+            .map_err(|err| RpcSignerError::MalformedResponse(err.to_string()))?;
+
+        match reply {
+            Output::Success(success) => Ok(success.result),
+            Output::Failure(failure) => Err(RpcSignerError::RpcError(failure)),
+        }
+    }
+
+    async fn call<T: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Vec<serde_json::Value>,
+    ) -> Result<T, SignerError> {
+        let message = JsonRpcRequest::create(method, params);
+        let ret = self
+            .post(&message)
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        serde_json::from_value(ret).map_err(|err| SignerError::SigningFailed(err.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for RemoteSigner {
+    /// Signs the message using `eth_sign`; the signer is expected to add
+    /// the `\x19Ethereum Signed Message:\n` prefix.
+    async fn sign_message(&self, message: &[u8]) -> Result<PackedEthSignature, SignerError> {
+        let params = vec![
+            serde_json::json!(self.address),
+            serde_json::json!(format!("0x{}", hex::encode(message))),
+        ];
+        let signature: PackedEthSignature = self.call("eth_sign", params).await?;
+        let signed_bytes = PackedEthSignature::message_to_signed_bytes(message);
+        self.check_signature(&signature, &signed_bytes)?;
+        Ok(signature)
+    }
+
+    /// Signs typed struct by EIP-712 signature standard using `eth_signTypedData`.
+    async fn sign_typed_data<S: EIP712TypedStructure + Sync>(
+        &self,
+        domain: &Eip712Domain,
+        typed_struct: &S,
+    ) -> Result<PackedEthSignature, SignerError> {
+        let params = vec![
+            serde_json::json!(self.address),
+            get_eip712_json(domain, typed_struct),
+        ];
+        let signature: PackedEthSignature = self.call("eth_signTypedData", params).await?;
+        let signed_bytes = PackedEthSignature::typed_data_to_signed_bytes(domain, typed_struct);
+        self.check_signature(&signature, &signed_bytes)?;
+        Ok(signature)
+    }
+
+    /// Signs the transaction using `eth_signTransaction` and returns the RLP-encoded signed transaction.
+    /// The transaction signer is recovered and checked to be the configured account.
+    async fn sign_transaction(
+        &self,
+        raw_tx: TransactionParameters,
+    ) -> Result<Vec<u8>, SignerError> {
+        let params = vec![self.transaction_json(&raw_tx)];
+        let signed_tx: String = self.call("eth_signTransaction", params).await?;
+        let signed_tx = signed_tx.strip_prefix("0x").unwrap_or(&signed_tx);
+        let signed_tx = hex::decode(signed_tx)
+            .map_err(|err| SignerError::DecodeRawTxFailed(err.to_string()))?;
+        if signed_tx.is_empty() {
+            return Err(SignerError::DecodeRawTxFailed(
+                "remote signer returned an empty transaction".to_string(),
+            ));
+        }
+        let tx_signer = recover_tx_signer(&signed_tx)?;
+        if tx_signer != self.address {
+            return Err(SignerError::SigningFailed(format!(
+                "Transaction from RemoteSigner is signed by {tx_signer:?} instead of {:?}",
+                self.address
+            )));
+        }
+        Ok(signed_tx)
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        post,
+        web::{self, Data},
+        App, HttpResponse, HttpServer, Responder,
+    };
+    use std::{
+        fs,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        path::Path,
+        thread,
+    };
+
+    use futures::future::{AbortHandle, Abortable};
+    use jsonrpc_core::{Failure, Id, Output, Success, Version};
+    use openssl::ssl::{SslAcceptor, SslFiletype, SslMethod, SslVerifyMode};
+    use serde_json::json;
+    use zksync_types::U256;
+
+    use super::*;
+    use crate::PrivateKeySigner;
+
+    const PRIVATE_KEY: H256 = H256([5; 32]);
+
+    fn parse_transaction(tx: &serde_json::Value) -> TransactionParameters {
+        let quantity = |name: &str| -> Option<U256> {
+            tx.get(name)
+                .map(|value| serde_json::from_value(value.clone()).unwrap())
+        };
+        let data: String = serde_json::from_value(tx["data"].clone()).unwrap();
+        let chain_id: U64 = serde_json::from_value(tx["chainId"].clone()).unwrap();
+
+        TransactionParameters {
+            nonce: quantity("nonce").unwrap(),
+            to: tx
+                .get("to")
+                .map(|to| serde_json::from_value(to.clone()).unwrap()),
+            gas: quantity("gas").unwrap(),
+            gas_price: quantity("gasPrice"),
+            value: quantity("value").unwrap(),
+            data: hex::decode(&data[2..]).unwrap(),
+            chain_id: chain_id.as_u64(),
+            transaction_type: tx
+                .get("type")
+                .map(|ty| serde_json::from_value(ty.clone()).unwrap()),
+            access_list: None,
+            max_fee_per_gas: quantity("maxFeePerGas").unwrap_or_default(),
+            max_priority_fee_per_gas: quantity("maxPriorityFeePerGas").unwrap_or_default(),
+            max_fee_per_blob_gas: quantity("maxFeePerBlobGas"),
+            blob_versioned_hashes: tx
+                .get("blobVersionedHashes")
+                .map(|hashes| serde_json::from_value(hashes.clone()).unwrap()),
+        }
+    }
+
+    /// Mock Web3Signer signing with [`PRIVATE_KEY`].
+    #[post("/")]
+    async fn index(req: web::Json<JsonRpcRequest>) -> impl Responder {
+        HttpResponse::Ok().json(json!(handle_request(&req, PRIVATE_KEY).await))
+    }
+
+    async fn handle_request(req: &JsonRpcRequest, private_key: H256) -> Output {
+        let signer = PrivateKeySigner::new(private_key);
+        match req.method.as_str() {
+            "eth_sign" => {
+                let data: String = serde_json::from_value(req.params[1].clone()).unwrap();
+                let data = hex::decode(&data[2..]).unwrap();
+                let signature = signer.sign_message(&data).await.unwrap();
+                create_success(json!(signature))
+            }
+            "eth_signTransaction" => {
+                let tx = parse_transaction(&req.params[0]);
+                let signed_tx = signer.sign_transaction(tx).await.unwrap();
+                create_success(json!(format!("0x{}", hex::encode(signed_tx))))
+            }
+            _ => Output::Failure(Failure {
+                jsonrpc: Some(Version::V2),
+                error: jsonrpc_core::Error::method_not_found(),
+                id: Id::Num(1),
+            }),
+        }
+    }
+
+    fn create_success(result: serde_json::Value) -> Output {
+        Output::Success(Success {
+            jsonrpc: Some(Version::V2),
+            result,
+            id: Id::Num(1),
+        })
+    }
+
+    fn run_server() -> (String, AbortHandle) {
+        let server = HttpServer::new(|| App::new().service(index))
+            .bind("127.0.0.1:0")
+            .expect("Cannot bind mock signer server");
+        let address = format!("http://{}/", server.addrs()[0]);
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        tokio::spawn(Abortable::new(server.run(), abort_registration));
+        (address, abort_handle)
+    }
+
+    fn testdata_path(file_name: &str) -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/testdata");
+        path.join(file_name).to_str().unwrap().to_owned()
+    }
+
+    fn client_tls() -> RemoteSignerTls {
+        RemoteSignerTls {
+            client_cert_pem: fs::read(testdata_path("client.pem")).unwrap(),
+            client_key_pem: fs::read(testdata_path("client.key")).unwrap(),
+            ca_cert_pem: Some(fs::read(testdata_path("ca.pem")).unwrap()),
+        }
+    }
+
+    /// Runs a mock Web3Signer signing with the specified key over HTTPS. The server requires clients
+    /// to authenticate with a certificate issued by the test CA.
+    fn run_tls_server(private_key: H256) -> String {
+        let mut acceptor = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls()).unwrap();
+        acceptor
+            .set_private_key_file(testdata_path("server.key"), SslFiletype::PEM)
+            .unwrap();
+        acceptor
+            .set_certificate_chain_file(testdata_path("server.pem"))
+            .unwrap();
+        acceptor.set_ca_file(testdata_path("ca.pem")).unwrap();
+        acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+        let acceptor = acceptor.build();
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Cannot bind mock signer server");
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                // Handshake errors are expected for clients without a valid certificate.
+                let Ok(mut stream) = acceptor.accept(stream) else {
+                    continue;
+                };
+
+                let mut reader = BufReader::new(&mut stream);
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    let (name, value) = line.split_once(':').unwrap_or((line, ""));
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let req: JsonRpcRequest = serde_json::from_slice(&body).unwrap();
+                let resp = futures::executor::block_on(handle_request(&req, private_key));
+                let resp = serde_json::to_string(&resp).unwrap();
+                let resp = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{resp}",
+                    resp.len()
+                );
+                stream.write_all(resp.as_bytes()).unwrap();
+                stream.shutdown().ok();
+            }
+        });
+        format!("https://localhost:{port}/")
+    }
+
+    fn test_transaction() -> TransactionParameters {
+        TransactionParameters {
+            nonce: 1.into(),
+            to: Some(Address::repeat_byte(0x11)),
+            gas: 3_000_000.into(),
+            value: 0.into(),
+            data: vec![1, 2, 3],
+            chain_id: 9,
+            transaction_type: Some(U64::from(2)),
+            max_fee_per_gas: 100_000.into(),
+            max_priority_fee_per_gas: 1_000.into(),
+            ..TransactionParameters::default()
+        }
+    }
+
+    fn operator_address() -> Address {
+        PackedEthSignature::address_from_private_key(&PRIVATE_KEY).unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn signing_with_remote_signer() {
+        let (url, abort_handle) = run_server();
+        let signer = RemoteSigner::new(url, operator_address(), None).unwrap();
+        let local_signer = PrivateKeySigner::new(PRIVATE_KEY);
+
+        let message = b"some_text_message";
+        let signature = signer.sign_message(message).await.unwrap();
+        assert_eq!(signature, local_signer.sign_message(message).await.unwrap());
+
+        let tx = test_transaction();
+        let signed_tx = signer.sign_transaction(tx.clone()).await.unwrap();
+        assert_eq!(
+            signed_tx,
+            local_signer.sign_transaction(tx.clone()).await.unwrap()
+        );
+
+        let blob_tx = TransactionParameters {
+            transaction_type: Some(U64::from(3)),
+            max_fee_per_blob_gas: Some(10.into()),
+            blob_versioned_hashes: Some(vec![H256::repeat_byte(1)]),
+            ..tx
+        };
+        let signed_tx = signer.sign_transaction(blob_tx.clone()).await.unwrap();
+        assert_eq!(
+            signed_tx,
+            local_signer.sign_transaction(blob_tx).await.unwrap()
+        );
+
+        abort_handle.abort();
+    }
+
+    #[actix_rt::test]
+    async fn remote_signer_rejects_signature_from_other_account() {
+        let (url, abort_handle) = run_server();
+        let signer = RemoteSigner::new(url, Address::repeat_byte(1), None).unwrap();
+        let err = signer.sign_message(b"message").await.unwrap_err();
+        assert!(matches!(err, SignerError::SigningFailed(_)), "{err}");
+        abort_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn signing_with_remote_signer_over_tls() {
+        let url = run_tls_server(PRIVATE_KEY);
+        let signer = RemoteSigner::new(url, operator_address(), Some(&client_tls())).unwrap();
+        let local_signer = PrivateKeySigner::new(PRIVATE_KEY);
+
+        let message = b"some_text_message";
+        let signature = signer.sign_message(message).await.unwrap();
+        assert_eq!(signature, local_signer.sign_message(message).await.unwrap());
+        let signed_tx = signer.sign_transaction(test_transaction()).await.unwrap();
+        assert_eq!(
+            signed_tx,
+            local_signer
+                .sign_transaction(test_transaction())
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn tls_server_rejects_client_without_certificate() {
+        let url = run_tls_server(PRIVATE_KEY);
+        let tls = RemoteSignerTls {
+            ca_cert_pem: None,
+            ..client_tls()
+        };
+        // Without the CA certificate, the server certificate cannot be verified.
+        let signer = RemoteSigner::new(url.clone(), operator_address(), Some(&tls)).unwrap();
+        let err = signer.sign_message(b"message").await.unwrap_err();
+        assert!(matches!(err, SignerError::SigningFailed(_)), "{err}");
+
+        // Without the client certificate, the server rejects the connection.
+        let client = reqwest::Client::builder()
+            .use_native_tls()
+            .add_root_certificate(
+                reqwest::Certificate::from_pem(&client_tls().ca_cert_pem.unwrap()).unwrap(),
+            )
+            .build()
+            .unwrap();
+        let signer = RemoteSigner {
+            rpc_addr: url,
+            client,
+            address: operator_address(),
+        };
+        let err = signer.sign_message(b"message").await.unwrap_err();
+        assert!(matches!(err, SignerError::SigningFailed(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn remote_signer_rejects_transaction_signed_by_other_account() {
+        let url = run_tls_server(H256::repeat_byte(6));
+        let signer = RemoteSigner::new(url, operator_address(), Some(&client_tls())).unwrap();
+        let err = signer
+            .sign_transaction(test_transaction())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SignerError::SigningFailed(_)), "{err}");
+    }
+
+    #[test]
+    fn invalid_tls_identity_is_rejected() {
+        let tls = RemoteSignerTls {
+            client_cert_pem: b"not a certificate".to_vec(),
+            client_key_pem: b"not a key".to_vec(),
+            ca_cert_pem: None,
+        };
+        let err = RemoteSigner::new("https://localhost/", Address::zero(), Some(&tls)).unwrap_err();
+        assert!(matches!(err, SignerError::CustomError(_)), "{err}");
+    }
+}
@@ -0,0 +1,164 @@
+//! Utilities for posting pubdata in EIP-4844 blobs: packing pubdata into blobs and computing
+//! KZG commitments, proofs and versioned hashes for them.
+
+use std::path::Path;
+
+pub use c_kzg::KzgSettings;
+use sha2::{Digest, Sha256};
+use zksync_basic_types::H256;
+
+/// Number of field elements in a blob.
+pub const FIELD_ELEMENTS_PER_BLOB: usize = 4_096;
+/// Size of a serialized field element in bytes.
+pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
+/// Size of a blob in bytes.
+pub const BYTES_PER_BLOB: usize = FIELD_ELEMENTS_PER_BLOB * BYTES_PER_FIELD_ELEMENT;
+/// Maximum number of blobs that can be attached to a single L1 transaction.
+pub const MAX_BLOBS_PER_TX: usize = 6;
+
+/// Number of pubdata bytes packed into a single field element. The most significant byte
+/// of each field element is always zero, so that the element is less than the BLS12-381 scalar field modulus.
+const PAYLOAD_BYTES_PER_FIELD_ELEMENT: usize = BYTES_PER_FIELD_ELEMENT - 1;
+/// Maximum number of pubdata bytes packed into a single blob.
+pub const MAX_BLOB_PAYLOAD_SIZE: usize = FIELD_ELEMENTS_PER_BLOB * PAYLOAD_BYTES_PER_FIELD_ELEMENT;
+
+/// Version byte for versioned hashes of KZG commitments.
+const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// Errors that can occur when computing KZG commitments.
+#[derive(Debug, thiserror::Error)]
+pub enum KzgError {
+    #[error("failed loading KZG trusted setup: {0:?}")]
+    TrustedSetup(c_kzg::Error),
+    #[error("failed computing KZG commitment or proof: {0:?}")]
+    Commitment(c_kzg::Error),
+}
+
+/// Loads KZG trusted setup from a file in the format used by the reference implementation
+/// (i.e., `trusted_setup.txt` from the consensus specs).
+pub fn load_trusted_setup(path: &Path) -> Result<KzgSettings, KzgError> {
+    KzgSettings::load_trusted_setup_file(path).map_err(KzgError::TrustedSetup)
+}
+
+/// Returns the number of blobs necessary to post pubdata of the specified size.
+pub fn blob_count(pubdata_len: usize) -> usize {
+    (pubdata_len + MAX_BLOB_PAYLOAD_SIZE - 1) / MAX_BLOB_PAYLOAD_SIZE
+}
+
+/// Packs pubdata into blobs. Each field element holds [`PAYLOAD_BYTES_PER_FIELD_ELEMENT`] bytes of pubdata;
+/// the last blob is padded with zeros.
+pub fn pubdata_to_blobs(pubdata: &[u8]) -> Vec<Vec<u8>> {
+    pubdata
+        .chunks(MAX_BLOB_PAYLOAD_SIZE)
+        .map(|chunk| {
+            let mut blob = vec![0_u8; BYTES_PER_BLOB];
+            let field_elements = blob.chunks_mut(BYTES_PER_FIELD_ELEMENT);
+            for (element, bytes) in
+                field_elements.zip(chunk.chunks(PAYLOAD_BYTES_PER_FIELD_ELEMENT))
+            {
+                element[1..=bytes.len()].copy_from_slice(bytes);
+            }
+            blob
+        })
+        .collect()
+}
+
+/// Computes the versioned hash of a KZG commitment as defined in EIP-4844.
+pub fn kzg_to_versioned_hash(commitment: &[u8]) -> H256 {
+    let mut hash: [u8; 32] = Sha256::digest(commitment).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    H256(hash)
+}
+
+/// Computes the versioned hash of the provided blob. Unlike [`KzgInfo::new()`], doesn't compute the KZG proof.
+///
+/// # Panics
+///
+/// Panics if the blob has an incorrect size.
+pub fn blob_versioned_hash(settings: &KzgSettings, blob: &[u8]) -> Result<H256, KzgError> {
+    assert_eq!(blob.len(), BYTES_PER_BLOB, "incorrect blob size");
+
+    let kzg_blob = c_kzg::Blob::from_bytes(blob).map_err(KzgError::Commitment)?;
+    let commitment = c_kzg::KzgCommitment::blob_to_kzg_commitment(&kzg_blob, settings)
+        .map_err(KzgError::Commitment)?
+        .to_bytes();
+    Ok(kzg_to_versioned_hash(&commitment.into_inner()))
+}
+
+/// KZG commitment information for a single blob.
+#[derive(Debug, Clone)]
+pub struct KzgInfo {
+    pub blob: Vec<u8>,
+    pub commitment: [u8; 48],
+    pub proof: [u8; 48],
+    pub versioned_hash: H256,
+}
+
+impl KzgInfo {
+    /// Computes the commitment, proof and versioned hash for the provided blob.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the blob has an incorrect size.
+    pub fn new(settings: &KzgSettings, blob: Vec<u8>) -> Result<Self, KzgError> {
+        assert_eq!(blob.len(), BYTES_PER_BLOB, "incorrect blob size");
+
+        let kzg_blob = c_kzg::Blob::from_bytes(&blob).map_err(KzgError::Commitment)?;
+        let commitment = c_kzg::KzgCommitment::blob_to_kzg_commitment(&kzg_blob, settings)
+            .map_err(KzgError::Commitment)?
+            .to_bytes();
+        let proof = c_kzg::KzgProof::compute_blob_kzg_proof(&kzg_blob, &commitment, settings)
+            .map_err(KzgError::Commitment)?
+            .to_bytes();
+
+        let commitment = commitment.into_inner();
+        Ok(Self {
+            blob,
+            versioned_hash: kzg_to_versioned_hash(&commitment),
+            commitment,
+            proof: proof.into_inner(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packing_pubdata_into_blobs() {
+        assert!(pubdata_to_blobs(&[]).is_empty());
+        assert_eq!(blob_count(0), 0);
+
+        let pubdata: Vec<u8> = (0..=u8::MAX).cycle().take(100).collect();
+        let blobs = pubdata_to_blobs(&pubdata);
+        assert_eq!(blobs.len(), 1);
+        assert_eq!(blob_count(pubdata.len()), 1);
+        let blob = &blobs[0];
+        assert_eq!(blob.len(), BYTES_PER_BLOB);
+        assert_eq!(blob[0], 0);
+        assert_eq!(blob[1..32], pubdata[..31]);
+        assert_eq!(blob[32], 0);
+        assert_eq!(blob[33..64], pubdata[31..62]);
+        assert!(blob[4 * 32..].iter().all(|&byte| byte == 0));
+
+        let pubdata = vec![u8::MAX; MAX_BLOB_PAYLOAD_SIZE + 1];
+        let blobs = pubdata_to_blobs(&pubdata);
+        assert_eq!(blobs.len(), 2);
+        assert_eq!(blob_count(pubdata.len()), 2);
+        for element in blobs[0].chunks(BYTES_PER_FIELD_ELEMENT) {
+            assert_eq!(element[0], 0);
+            assert!(element[1..].iter().all(|&byte| byte == u8::MAX));
+        }
+        assert_eq!(blobs[1][..2], [0, u8::MAX]);
+        assert!(blobs[1][2..].iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn computing_versioned_hash() {
+        let commitment = [1_u8; 48];
+        let hash = kzg_to_versioned_hash(&commitment);
+        assert_eq!(hash[0], VERSIONED_HASH_VERSION_KZG);
+        assert_eq!(hash[1..], Sha256::digest(commitment)[1..]);
+    }
+}
@@ -1,15 +1,24 @@
-use std::collections::{hash_map, BTreeSet, HashMap, HashSet};
+use std::{
+    cmp::Reverse,
+    collections::{hash_map, BTreeSet, BinaryHeap, HashMap, HashSet},
+};
 
+use zksync_config::configs::chain::MempoolTxOrdering;
 use zksync_types::{
     l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, Transaction,
 };
 
-use crate::types::{AccountTransactions, L2TxFilter, MempoolScore};
+use crate::types::{
+    AccountLimits, AccountTransactions, InsertionError, L2TxFilter, MempoolScore, TxPriority,
+};
 
 #[derive(Debug)]
 pub struct MempoolInfo {
     pub stashed_accounts: Vec<Address>,
     pub purged_accounts: Vec<Address>,
+    /// L2 transactions (identified by the initiator address and nonce) that were evicted from
+    /// or rejected by the mempool and should be removed from the storage.
+    pub purged_transactions: Vec<(Address, Nonce)>,
 }
 
 #[derive(Debug)]
@@ -30,9 +39,15 @@ pub struct MempoolStore {
     /// Next priority operation
     next_priority_id: PriorityOpId,
     stashed_accounts: Vec<Address>,
+    /// L2 transactions evicted from or rejected by the mempool since the last `get_mempool_info()` call.
+    purged_transactions: Vec<(Address, Nonce)>,
     /// Number of L2 transactions in the mempool.
     size: u64,
+    /// Maximum number of L2 transactions in the mempool. If exceeded, transactions with the lowest score
+    /// are evicted.
     capacity: u64,
+    account_limits: AccountLimits,
+    tx_priority: TxPriority,
 }
 
 impl MempoolStore {
@@ -43,11 +58,40 @@ impl MempoolStore {
             l2_priority_queue: BTreeSet::new(),
             next_priority_id,
             stashed_accounts: vec![],
+            purged_transactions: vec![],
             size: 0,
             capacity,
+            account_limits: AccountLimits::default(),
+            tx_priority: TxPriority::default(),
         }
     }
 
+    /// Sets the ordering policy for L2 transactions of different accounts. By default, transactions
+    /// are ordered by the time they were received at (FIFO).
+    ///
+    /// With [`MempoolTxOrdering::PriorityFee`], transactions are prioritized by their effective tip
+    /// (`max_priority_fee_per_gas` capped by `max_fee_per_gas - fair_l2_gas_price`).
+    pub fn with_ordering(mut self, ordering: MempoolTxOrdering, fair_l2_gas_price: u64) -> Self {
+        self.tx_priority = TxPriority {
+            ordering,
+            fair_l2_gas_price,
+        };
+        self
+    }
+
+    /// Sets the minimum fee bump (in percent) required to replace a pending L2 transaction
+    /// with the same nonce. By default, a replacement transaction must not have lower fees.
+    pub fn with_min_replacement_fee_bump(mut self, percent: u64) -> Self {
+        self.account_limits.min_replacement_fee_bump_percent = percent;
+        self
+    }
+
+    /// Sets the maximum number of pending L2 transactions per account. By default, the number is not limited.
+    pub fn with_max_transactions_per_account(mut self, limit: usize) -> Self {
+        self.account_limits.max_transactions_per_account = Some(limit);
+        self
+    }
+
     /// Inserts batch of new transactions to mempool
     /// `initial_nonces` provides current committed nonce information to mempool
     /// variable is used only if account is not present in mempool yet and we have to bootstrap it
@@ -93,6 +137,7 @@ impl MempoolStore {
                 }
             }
         }
+        self.evict_excess_transactions();
     }
 
     fn insert_l2_transaction(
@@ -102,15 +147,40 @@ impl MempoolStore {
     ) {
         let account = transaction.initiator_account();
 
-        let metadata = match self.l2_transactions_per_account.entry(account) {
-            hash_map::Entry::Occupied(mut txs) => txs.get_mut().insert(transaction),
+        let nonce = transaction.common_data.nonce;
+        let limits = &self.account_limits;
+        let insertion_result = match self.l2_transactions_per_account.entry(account) {
+            hash_map::Entry::Occupied(mut txs) => txs.get_mut().insert(transaction, limits),
             hash_map::Entry::Vacant(entry) => {
                 let account_nonce = initial_nonces.get(&account).cloned().unwrap_or(Nonce(0));
                 entry
-                    .insert(AccountTransactions::new(account_nonce))
-                    .insert(transaction)
+                    .insert(AccountTransactions::new(account_nonce, self.tx_priority))
+                    .insert(transaction, limits)
+            }
+        };
+        let metadata = match insertion_result {
+            Ok(metadata) => metadata,
+            Err(InsertionError::UnderpricedReplacement) => {
+                // The pending transaction is kept; once it's executed, it will overwrite
+                // the replacement in the storage.
+                tracing::debug!(
+                    "rejected underpriced replacement of L2 transaction {account:?}:{nonce}"
+                );
+                return;
+            }
+            Err(InsertionError::AccountLimitReached) => {
+                tracing::debug!(
+                    "rejected L2 transaction {account:?}:{nonce}: account reached the limit \
+                     of pending transactions"
+                );
+                self.purged_transactions.push((account, nonce));
+                return;
             }
         };
+
+        if let Some(evicted_nonce) = metadata.evicted_nonce {
+            self.purged_transactions.push((account, evicted_nonce));
+        }
         if let Some(score) = metadata.previous_score {
             self.l2_priority_queue.remove(&score);
         }
@@ -122,6 +192,46 @@ impl MempoolStore {
         }
     }
 
+    /// Evicts L2 transactions with the lowest score while the mempool size exceeds its capacity.
+    /// Transactions are evicted starting from the greatest nonce for each account, so that
+    /// the remaining account transactions stay executable.
+    fn evict_excess_transactions(&mut self) {
+        if self.size <= self.capacity {
+            return;
+        }
+
+        let mut candidates: BinaryHeap<_> = self
+            .l2_transactions_per_account
+            .values()
+            .filter_map(|txs| txs.last_score().map(Reverse))
+            .collect();
+        while self.size > self.capacity {
+            let Some(Reverse(score)) = candidates.pop() else {
+                break;
+            };
+            let account = score.account;
+            let txs = self
+                .l2_transactions_per_account
+                .get_mut(&account)
+                .expect("mempool: dangling eviction candidate");
+            let (nonce, ready_score) = txs
+                .remove_last()
+                .expect("mempool: eviction candidate without transactions");
+            if let Some(ready_score) = ready_score {
+                self.l2_priority_queue.remove(&ready_score);
+            }
+            if let Some(next_score) = txs.last_score() {
+                candidates.push(Reverse(next_score));
+            } else {
+                self.l2_transactions_per_account.remove(&account);
+            }
+
+            tracing::debug!("evicted L2 transaction {account:?}:{nonce} from full mempool");
+            self.purged_transactions.push((account, nonce));
+            self.size -= 1;
+        }
+    }
+
     /// Returns `true` if there is a transaction in the mempool satisfying the filter.
     pub fn has_next(&self, filter: &L2TxFilter) -> bool {
         self.l1_transactions.get(&self.next_priority_id).is_some()
@@ -209,6 +319,7 @@ impl MempoolStore {
         MempoolInfo {
             stashed_accounts: std::mem::take(&mut self.stashed_accounts),
             purged_accounts: self.gc(),
+            purged_transactions: std::mem::take(&mut self.purged_transactions),
         }
     }
 
@@ -3,6 +3,7 @@ use std::{
     iter::FromIterator,
 };
 
+use zksync_config::configs::chain::MempoolTxOrdering;
 use zksync_types::{
     fee::Fee,
     helpers::unix_timestamp_ms,
@@ -365,6 +366,200 @@ fn mempool_capacity() {
     );
 }
 
+#[test]
+fn replacement_fee_bump() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_min_replacement_fee_bump(10);
+    let account = Address::random();
+    mempool.insert(
+        vec![gen_l2_tx_with_fee(account, Nonce(0), 100)],
+        HashMap::new(),
+    );
+
+    // Underpriced replacements are rejected.
+    for max_fee in [50, 100, 109] {
+        mempool.insert(
+            vec![gen_l2_tx_with_fee(account, Nonce(0), max_fee)],
+            HashMap::new(),
+        );
+    }
+    assert_eq!(mempool.stats().l2_transaction_count, 1);
+    assert!(mempool.get_mempool_info().purged_transactions.is_empty());
+    let tx = mempool.next_transaction(&L2TxFilter::default()).unwrap();
+    assert_eq!(fee_of(&tx).max_fee_per_gas, U256::from(100));
+
+    mempool.insert(
+        vec![gen_l2_tx_with_fee(account, Nonce(1), 100)],
+        HashMap::new(),
+    );
+    mempool.insert(
+        vec![gen_l2_tx_with_fee(account, Nonce(1), 110)],
+        HashMap::new(),
+    );
+    assert_eq!(mempool.stats().l2_transaction_count, 1);
+    let tx = mempool.next_transaction(&L2TxFilter::default()).unwrap();
+    assert_eq!(fee_of(&tx).max_fee_per_gas, U256::from(110));
+    assert_eq!(mempool.next_transaction(&L2TxFilter::default()), None);
+}
+
+#[test]
+fn max_transactions_per_account() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_max_transactions_per_account(3);
+    let account = Address::random();
+    let other_account = Address::random();
+    let transactions = vec![
+        gen_l2_tx(account, Nonce(1)),
+        gen_l2_tx(account, Nonce(2)),
+        gen_l2_tx(account, Nonce(3)),
+        gen_l2_tx(account, Nonce(4)),
+        gen_l2_tx(other_account, Nonce(0)),
+    ];
+    mempool.insert(transactions, HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 4);
+    assert_eq!(
+        mempool.get_mempool_info().purged_transactions,
+        [(account, Nonce(4))]
+    );
+
+    // Filling the nonce gap evicts the transaction with the greatest nonce.
+    mempool.insert(vec![gen_l2_tx(account, Nonce(0))], HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 4);
+    assert_eq!(
+        mempool.get_mempool_info().purged_transactions,
+        [(account, Nonce(3))]
+    );
+
+    let mut account_nonces = vec![];
+    while let Some(tx) = mempool.next_transaction(&L2TxFilter::default()) {
+        if tx.initiator_account() == account {
+            account_nonces.push(tx.nonce().unwrap().0);
+        }
+    }
+    assert_eq!(account_nonces, [0, 1, 2]);
+}
+
+#[test]
+fn evicting_transactions_on_capacity_overflow() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 4);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let now = unix_timestamp_ms();
+    let transactions = vec![
+        gen_l2_tx_with_timestamp(account0, Nonce(0), now),
+        gen_l2_tx_with_timestamp(account0, Nonce(1), now + 1),
+        gen_l2_tx_with_timestamp(account0, Nonce(2), now + 2),
+        gen_l2_tx_with_timestamp(account1, Nonce(0), now + 3),
+    ];
+    mempool.insert(transactions, HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 4);
+
+    // The newest transactions (i.e., ones with the lowest score) are evicted first.
+    mempool.insert(
+        vec![
+            gen_l2_tx_with_timestamp(account1, Nonce(1), now + 4),
+            gen_l2_tx_with_timestamp(account1, Nonce(2), now + 5),
+        ],
+        HashMap::new(),
+    );
+    assert_eq!(mempool.stats().l2_transaction_count, 4);
+    let info = mempool.get_mempool_info();
+    assert_eq!(
+        info.purged_transactions,
+        [(account1, Nonce(2)), (account1, Nonce(1))]
+    );
+    assert!(info.purged_accounts.is_empty());
+
+    mempool.insert(
+        vec![gen_l2_tx_with_timestamp(account0, Nonce(3), now + 6)],
+        HashMap::new(),
+    );
+    assert_eq!(
+        mempool.get_mempool_info().purged_transactions,
+        [(account0, Nonce(3))]
+    );
+
+    // Evicting the only transaction of an account removes the account from the mempool.
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 1);
+    mempool.insert(
+        vec![
+            gen_l2_tx_with_timestamp(account0, Nonce(0), now),
+            gen_l2_tx_with_timestamp(account1, Nonce(0), now + 1),
+        ],
+        HashMap::new(),
+    );
+    assert_eq!(mempool.stats().l2_priority_queue_size, 1);
+    assert_eq!(
+        mempool.get_mempool_info().purged_transactions,
+        [(account1, Nonce(0))]
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account0, 0)
+    );
+    assert_eq!(mempool.next_transaction(&L2TxFilter::default()), None);
+}
+
+#[test]
+fn priority_fee_ordering() {
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let account2 = Address::random();
+    let now = unix_timestamp_ms();
+    let transactions = || {
+        vec![
+            gen_l2_tx_with_priority_fee(account0, Nonce(0), now, 1_000, 1),
+            // Higher priority fee doesn't allow to execute a transaction before its predecessors.
+            gen_l2_tx_with_priority_fee(account0, Nonce(1), now + 1, 1_000, 100),
+            gen_l2_tx_with_priority_fee(account1, Nonce(0), now + 2, 1_000, 10),
+            // Priority fee is capped by the max fee.
+            gen_l2_tx_with_priority_fee(account2, Nonce(0), now + 3, 5, 50),
+        ]
+    };
+
+    let mut mempool =
+        MempoolStore::new(PriorityOpId(0), 100).with_ordering(MempoolTxOrdering::PriorityFee, 0);
+    mempool.insert(transactions(), HashMap::new());
+    let filter = L2TxFilter::default();
+    assert_eq!(view(mempool.next_transaction(&filter)), (account1, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account2, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 1));
+    assert_eq!(mempool.next_transaction(&filter), None);
+
+    // Transactions with a higher priority not matching the filter are stashed.
+    let mut mempool =
+        MempoolStore::new(PriorityOpId(0), 100).with_ordering(MempoolTxOrdering::PriorityFee, 0);
+    mempool.insert(transactions(), HashMap::new());
+    let filter = L2TxFilter {
+        l1_gas_price: 0,
+        fee_per_gas: 6,
+        gas_per_pubdata: 0,
+    };
+    assert_eq!(view(mempool.next_transaction(&filter)), (account1, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 0));
+    assert_eq!(mempool.get_mempool_info().stashed_accounts, [account2]);
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 1));
+    assert_eq!(mempool.next_transaction(&filter), None);
+
+    // The tip is capped by the part of the max fee exceeding the fair L2 gas price.
+    let mut mempool =
+        MempoolStore::new(PriorityOpId(0), 100).with_ordering(MempoolTxOrdering::PriorityFee, 995);
+    mempool.insert(transactions(), HashMap::new());
+    let filter = L2TxFilter::default();
+    assert_eq!(view(mempool.next_transaction(&filter)), (account1, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 1));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account2, 0));
+
+    // FIFO ordering ignores priority fees.
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    mempool.insert(transactions(), HashMap::new());
+    let filter = L2TxFilter::default();
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 1));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account1, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account2, 0));
+}
+
 fn gen_l2_tx(address: Address, nonce: Nonce) -> Transaction {
     gen_l2_tx_with_timestamp(address, nonce, unix_timestamp_ms())
 }
@@ -384,6 +579,42 @@ fn gen_l2_tx_with_timestamp(address: Address, nonce: Nonce, received_at_ms: u64)
     txn.into()
 }
 
+fn gen_l2_tx_with_fee(address: Address, nonce: Nonce, max_fee_per_gas: u64) -> Transaction {
+    let mut tx = gen_l2_tx(address, nonce);
+    match &mut tx.common_data {
+        ExecuteTransactionCommon::L2(data) => {
+            data.fee.max_fee_per_gas = U256::from(max_fee_per_gas);
+        }
+        _ => unreachable!(),
+    }
+    tx
+}
+
+fn gen_l2_tx_with_priority_fee(
+    address: Address,
+    nonce: Nonce,
+    received_at_ms: u64,
+    max_fee_per_gas: u64,
+    max_priority_fee_per_gas: u64,
+) -> Transaction {
+    let mut tx = gen_l2_tx_with_timestamp(address, nonce, received_at_ms);
+    match &mut tx.common_data {
+        ExecuteTransactionCommon::L2(data) => {
+            data.fee.max_fee_per_gas = U256::from(max_fee_per_gas);
+            data.fee.max_priority_fee_per_gas = U256::from(max_priority_fee_per_gas);
+        }
+        _ => unreachable!(),
+    }
+    tx
+}
+
+fn fee_of(tx: &Transaction) -> &Fee {
+    match &tx.common_data {
+        ExecuteTransactionCommon::L2(data) => &data.fee,
+        _ => unreachable!(),
+    }
+}
+
 fn gen_l1_tx(priority_id: PriorityOpId) -> Transaction {
     let execute = Execute {
         contract_address: Address::repeat_byte(0x11),
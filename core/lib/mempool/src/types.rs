@@ -1,5 +1,6 @@
 use std::{cmp::Ordering, collections::HashMap};
 
+use zksync_config::configs::chain::MempoolTxOrdering;
 use zksync_types::{fee::Fee, l2::L2Tx, Address, Nonce, Transaction, U256};
 
 /// Pending mempool transactions of account
@@ -10,35 +11,63 @@ pub(crate) struct AccountTransactions {
     /// account nonce in mempool
     /// equals to committed nonce in db + number of transactions sent to state keeper
     nonce: Nonce,
+    /// ordering policy used to compute transaction scores
+    tx_priority: TxPriority,
 }
 
 impl AccountTransactions {
-    pub fn new(nonce: Nonce) -> Self {
+    pub fn new(nonce: Nonce, tx_priority: TxPriority) -> Self {
         Self {
             transactions: HashMap::new(),
             nonce,
+            tx_priority,
         }
     }
 
-    /// Inserts new transaction for given account. Returns insertion metadata
-    pub fn insert(&mut self, transaction: L2Tx) -> InsertionMetadata {
+    /// Inserts new transaction for given account. Returns insertion metadata, or an error
+    /// if the transaction violates replacement rules or per-account limits.
+    pub fn insert(
+        &mut self,
+        transaction: L2Tx,
+        limits: &AccountLimits,
+    ) -> Result<InsertionMetadata, InsertionError> {
         let mut metadata = InsertionMetadata::default();
         let nonce = transaction.common_data.nonce;
         // skip insertion if transaction is old
         if nonce < self.nonce {
-            return metadata;
+            return Ok(metadata);
         }
-        let new_score = Self::score_for_transaction(&transaction);
+
+        if let Some(existing) = self.transactions.get(&nonce) {
+            let existing_fee = &existing.common_data.fee;
+            let new_fee = &transaction.common_data.fee;
+            if !limits.is_sufficient_replacement(existing_fee, new_fee) {
+                return Err(InsertionError::UnderpricedReplacement);
+            }
+        } else if let Some(max_transactions) = limits.max_transactions_per_account {
+            if self.transactions.len() >= max_transactions {
+                // Allow filling nonce gaps by evicting the transaction with the greatest nonce;
+                // otherwise, the account could get stuck forever.
+                let last_nonce = self.last_nonce().filter(|&last_nonce| last_nonce > nonce);
+                let Some(last_nonce) = last_nonce else {
+                    return Err(InsertionError::AccountLimitReached);
+                };
+                self.transactions.remove(&last_nonce);
+                metadata.evicted_nonce = Some(last_nonce);
+            }
+        }
+
+        let new_score = self.score_for_transaction(&transaction);
         let previous_score = self
             .transactions
             .insert(nonce, transaction)
-            .map(|tx| Self::score_for_transaction(&tx));
-        metadata.is_new = previous_score.is_none();
+            .map(|tx| self.score_for_transaction(&tx));
+        metadata.is_new = previous_score.is_none() && metadata.evicted_nonce.is_none();
         if nonce == self.nonce {
             metadata.new_score = Some(new_score);
             metadata.previous_score = previous_score;
         }
-        metadata
+        Ok(metadata)
     }
 
     /// Returns next transaction to be included in block and optional score of its successor
@@ -52,7 +81,7 @@ impl AccountTransactions {
         let score = self
             .transactions
             .get(&self.nonce)
-            .map(Self::score_for_transaction);
+            .map(|tx| self.score_for_transaction(tx));
         (transaction, score)
     }
 
@@ -65,27 +94,77 @@ impl AccountTransactions {
         self.nonce = self.nonce.min(tx_nonce);
         self.transactions
             .get(&(tx_nonce + 1))
-            .map(Self::score_for_transaction)
+            .map(|tx| self.score_for_transaction(tx))
+    }
+
+    /// Removes the transaction with the greatest nonce. Returns its nonce, and its score
+    /// if the transaction was ready for execution.
+    pub fn remove_last(&mut self) -> Option<(Nonce, Option<MempoolScore>)> {
+        let last_nonce = self.last_nonce()?;
+        let transaction = self.transactions.remove(&last_nonce)?;
+        let score = (last_nonce == self.nonce).then(|| self.score_for_transaction(&transaction));
+        Some((last_nonce, score))
+    }
+
+    /// Returns score of the transaction with the greatest nonce. This transaction is evicted first
+    /// if the account has to make room for transactions of other accounts.
+    pub fn last_score(&self) -> Option<MempoolScore> {
+        let last_nonce = self.last_nonce()?;
+        self.transactions
+            .get(&last_nonce)
+            .map(|tx| self.score_for_transaction(tx))
+    }
+
+    fn last_nonce(&self) -> Option<Nonce> {
+        self.transactions.keys().max().copied()
     }
 
     pub fn len(&self) -> usize {
         self.transactions.len()
     }
 
-    fn score_for_transaction(transaction: &L2Tx) -> MempoolScore {
+    fn score_for_transaction(&self, transaction: &L2Tx) -> MempoolScore {
+        let fee = &transaction.common_data.fee;
         MempoolScore {
             account: transaction.initiator_account(),
+            priority: self.tx_priority.priority(fee),
             received_at_ms: transaction.received_timestamp_ms,
-            fee_data: transaction.common_data.fee.clone(),
+            fee_data: fee.clone(),
+        }
+    }
+}
+
+/// Priority of L2 transactions from different accounts in mempool, which is determined by the ordering policy.
+/// Transactions of the same account are always ordered by nonce.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct TxPriority {
+    pub ordering: MempoolTxOrdering,
+    /// L2 gas price charged by the operator; the remaining part of `max_fee_per_gas` is the maximum tip
+    /// a transaction can pay.
+    pub fair_l2_gas_price: u64,
+}
+
+impl TxPriority {
+    fn priority(self, fee: &Fee) -> U256 {
+        match self.ordering {
+            MempoolTxOrdering::Fifo => U256::zero(),
+            MempoolTxOrdering::PriorityFee => {
+                let max_tip = fee
+                    .max_fee_per_gas
+                    .saturating_sub(self.fair_l2_gas_price.into());
+                fee.max_priority_fee_per_gas.min(max_tip)
+            }
         }
     }
 }
 
 /// Mempool score of transaction. Used to prioritize L2 transactions in mempool
-/// Currently trivial ordering is used based on received at timestamp
+/// Transactions are ordered by priority (which is determined by [`TxPriority`]; it is always zero for FIFO ordering),
+/// then by received at timestamp.
 #[derive(Eq, PartialEq, Clone, Debug, Hash)]
 pub struct MempoolScore {
     pub account: Address,
+    pub priority: U256,
     pub received_at_ms: u64,
     // Not used for actual scoring, but state keeper would request
     // transactions that have acceptable fee values (so transactions
@@ -103,6 +182,10 @@ impl MempoolScore {
 
 impl Ord for MempoolScore {
     fn cmp(&self, other: &MempoolScore) -> Ordering {
+        match self.priority.cmp(&other.priority) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
         match self.received_at_ms.cmp(&other.received_at_ms).reverse() {
             Ordering::Equal => {}
             ordering => return ordering,
@@ -122,6 +205,37 @@ pub(crate) struct InsertionMetadata {
     pub new_score: Option<MempoolScore>,
     pub previous_score: Option<MempoolScore>,
     pub is_new: bool,
+    /// Nonce of the transaction evicted to make room for the inserted one.
+    pub evicted_nonce: Option<Nonce>,
+}
+
+/// Reasons for rejecting an L2 transaction on insertion into mempool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum InsertionError {
+    /// Transaction replaces a pending transaction with the same nonce, but doesn't bump fees enough.
+    UnderpricedReplacement,
+    /// Account has reached the limit of pending transactions.
+    AccountLimitReached,
+}
+
+/// Per-account limits applied by mempool on L2 transaction insertion.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AccountLimits {
+    /// Minimum fee bump (in percent) required to replace a pending transaction with the same nonce.
+    /// Both `max_fee_per_gas` and `max_priority_fee_per_gas` must be bumped.
+    pub min_replacement_fee_bump_percent: u64,
+    /// Maximum number of pending transactions per account. `None` means no limit.
+    pub max_transactions_per_account: Option<usize>,
+}
+
+impl AccountLimits {
+    fn is_sufficient_replacement(&self, existing_fee: &Fee, new_fee: &Fee) -> bool {
+        let bump_percent = U256::from(self.min_replacement_fee_bump_percent);
+        let min_fee =
+            |fee: U256| fee.saturating_mul(U256::from(100) + bump_percent) / U256::from(100);
+        new_fee.max_fee_per_gas >= min_fee(existing_fee.max_fee_per_gas)
+            && new_fee.max_priority_fee_per_gas >= min_fee(existing_fee.max_priority_fee_per_gas)
+    }
 }
 
 /// Structure that can be used by state keeper to describe
@@ -157,6 +271,7 @@ mod tests {
 
         let score = MempoolScore {
             account: Address::random(),
+            priority: Default::default(),       // Not important
             received_at_ms: Default::default(), // Not important
             fee_data: Fee {
                 gas_limit: Default::default(), // Not important
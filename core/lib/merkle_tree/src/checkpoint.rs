@@ -0,0 +1,522 @@
+//! Portable tree checkpoints.
+//!
+//! # Overview
+//!
+//! A **checkpoint** is a serialized snapshot of all tree entries at a specific tree version.
+//! Unlike copying RocksDB directories, checkpoints do not depend on the storage layout or RocksDB options,
+//! and can be used to move a tree between machines. A checkpoint is created using
+//! [`MerkleTree::export_checkpoint()`] and is restored using [`MerkleTreeRecovery::import_checkpoint()`].
+//!
+//! # Format
+//!
+//! A checkpoint consists of a header followed by a sequence of chunks. All integers are big-endian.
+//!
+//! - **Header:** magic bytes [`MAGIC`], format version (1 byte), tree version (8 bytes), root hash
+//!   (32 bytes) and leaf count (8 bytes).
+//! - **Chunk:** number of entries (4 bytes), entries, and the Blake2s-256 hash of the serialized entries
+//!   (32 bytes). Each entry consists of the tree key (32 bytes), value hash (32 bytes) and leaf index (8 bytes).
+//!   Entries are ordered by increasing key across all chunks, so that they can be fed to
+//!   [`MerkleTreeRecovery::extend_linear()`] as is.
+//! - **Terminator:** a chunk header with zero entries (and no hash).
+//!
+//! Chunks may contain at most [`MAX_CHUNK_SIZE`] entries; larger chunks are rejected on import
+//! without allocating memory for them.
+//!
+//! Chunk hashes only protect against data corruption. The integrity of the checkpoint as a whole
+//! is established by comparing the root hash of the restored tree with the one in the header, which
+//! should be authenticated by external means.
+
+use std::io::{self, Read, Write};
+
+use zksync_crypto::hasher::{blake2::Blake2Hasher, Hasher};
+
+use crate::{
+    errors::DeserializeError,
+    hasher::HashTree,
+    recovery::MerkleTreeRecovery,
+    storage::PruneDatabase,
+    types::{Nibbles, Node, NodeKey, Root, TreeEntry, KEY_SIZE},
+    Database, Key, MerkleTree, ValueHash,
+};
+
+/// Magic bytes at the start of a checkpoint.
+pub const MAGIC: &[u8; 8] = b"ZKSTREE\0";
+/// Maximum number of entries in a single checkpoint chunk (~72 MiB of serialized entries).
+pub const MAX_CHUNK_SIZE: usize = 1 << 20;
+const FORMAT_VERSION: u8 = 1;
+const ENTRY_SIZE: usize = KEY_SIZE + 32 + 8;
+
+/// Errors that can occur when exporting or importing a tree checkpoint.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum CheckpointError {
+    /// I/O error reading or writing the checkpoint.
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    /// Error deserializing tree data from the database.
+    #[error("failed deserializing node from DB: {0}")]
+    Deserialize(#[from] DeserializeError),
+    /// Exported tree version does not exist.
+    #[error("tree version {0} does not exist")]
+    MissingVersion(u64),
+    /// Tree node is missing in the database (e.g., because it was pruned).
+    #[error("missing tree node at {0}")]
+    MissingNode(NodeKey),
+    /// Checkpoint does not start with the expected magic bytes.
+    #[error("input is not a Merkle tree checkpoint")]
+    InvalidMagic,
+    /// Checkpoint has an unsupported format version.
+    #[error("unsupported checkpoint format version: {0}")]
+    UnsupportedFormat(u8),
+    /// Hash of a checkpoint chunk doesn't match the hash recorded in the checkpoint.
+    #[error("hash mismatch for checkpoint chunk #{index}: expected {expected:?}, got {actual:?}")]
+    ChunkHashMismatch {
+        /// 0-based index of the chunk.
+        index: usize,
+        /// Hash recorded in the checkpoint.
+        expected: ValueHash,
+        /// Hash computed from the chunk entries.
+        actual: ValueHash,
+    },
+    /// Checkpoint chunk declares more entries than [`MAX_CHUNK_SIZE`].
+    #[error(
+        "checkpoint chunk #{index} has {size} entries, which exceeds the limit {MAX_CHUNK_SIZE}"
+    )]
+    ChunkTooLarge {
+        /// 0-based index of the chunk.
+        index: usize,
+        /// Number of entries declared in the chunk header.
+        size: usize,
+    },
+    /// Number of imported leaves doesn't match the leaf count in the checkpoint header.
+    #[error("leaf count mismatch for imported tree: expected {expected}, got {actual}")]
+    LeafCountMismatch {
+        /// Leaf count from the checkpoint header.
+        expected: u64,
+        /// Number of leaves in the checkpoint chunks.
+        actual: u64,
+    },
+    /// Root hash of the imported tree doesn't match the hash in the checkpoint header.
+    #[error("root hash mismatch for imported tree: expected {expected:?}, got {actual:?}")]
+    RootHashMismatch {
+        /// Root hash from the checkpoint header.
+        expected: ValueHash,
+        /// Root hash of the imported tree.
+        actual: ValueHash,
+    },
+}
+
+/// Header of a tree checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckpointHeader {
+    /// Tree version the checkpoint was created for.
+    pub version: u64,
+    /// Root hash of the tree at [`Self::version`].
+    pub root_hash: ValueHash,
+    /// Number of leaves in the tree at [`Self::version`].
+    pub leaf_count: u64,
+}
+
+impl CheckpointHeader {
+    fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION])?;
+        writer.write_all(&self.version.to_be_bytes())?;
+        writer.write_all(self.root_hash.as_bytes())?;
+        writer.write_all(&self.leaf_count.to_be_bytes())
+    }
+
+    fn read_from(reader: &mut impl Read) -> Result<Self, CheckpointError> {
+        let mut magic = [0_u8; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != *MAGIC {
+            return Err(CheckpointError::InvalidMagic);
+        }
+        let mut format_version = [0_u8];
+        reader.read_exact(&mut format_version)?;
+        if format_version[0] != FORMAT_VERSION {
+            return Err(CheckpointError::UnsupportedFormat(format_version[0]));
+        }
+
+        let version = read_u64(reader)?;
+        let mut root_hash = ValueHash::zero();
+        reader.read_exact(root_hash.as_bytes_mut())?;
+        let leaf_count = read_u64(reader)?;
+        Ok(Self {
+            version,
+            root_hash,
+            leaf_count,
+        })
+    }
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut buffer = [0_u8; 8];
+    reader.read_exact(&mut buffer)?;
+    Ok(u64::from_be_bytes(buffer))
+}
+
+/// Writer of checkpoint chunks.
+#[derive(Debug)]
+struct ChunkWriter<W> {
+    writer: W,
+    chunk_size: usize,
+    buffer: Vec<u8>,
+    entry_count: usize,
+}
+
+impl<W: Write> ChunkWriter<W> {
+    fn new(writer: W, chunk_size: usize) -> Self {
+        Self {
+            writer,
+            chunk_size,
+            buffer: Vec::with_capacity(chunk_size * ENTRY_SIZE),
+            entry_count: 0,
+        }
+    }
+
+    fn push(&mut self, entry: TreeEntry) -> io::Result<()> {
+        let mut key_bytes = [0_u8; KEY_SIZE];
+        entry.key.to_big_endian(&mut key_bytes);
+        self.buffer.extend_from_slice(&key_bytes);
+        self.buffer.extend_from_slice(entry.value.as_bytes());
+        self.buffer
+            .extend_from_slice(&entry.leaf_index.to_be_bytes());
+        self.entry_count += 1;
+        if self.entry_count == self.chunk_size {
+            self.flush_chunk()?;
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::cast_possible_truncation)] // chunk size is checked on creation
+    fn flush_chunk(&mut self) -> io::Result<()> {
+        if self.entry_count == 0 {
+            return Ok(());
+        }
+        let hash = Blake2Hasher.hash_bytes(&self.buffer);
+        self.writer
+            .write_all(&(self.entry_count as u32).to_be_bytes())?;
+        self.writer.write_all(&self.buffer)?;
+        self.writer.write_all(hash.as_bytes())?;
+        self.buffer.clear();
+        self.entry_count = 0;
+        Ok(())
+    }
+
+    fn finish(mut self) -> io::Result<()> {
+        self.flush_chunk()?;
+        self.writer.write_all(&0_u32.to_be_bytes())?;
+        self.writer.flush()
+    }
+}
+
+/// Reader of checkpoint chunks.
+#[derive(Debug)]
+struct ChunkReader<R> {
+    reader: R,
+    chunk_index: usize,
+}
+
+impl<R: Read> ChunkReader<R> {
+    fn read_chunk(&mut self) -> Result<Option<Vec<TreeEntry>>, CheckpointError> {
+        let mut entry_count = [0_u8; 4];
+        self.reader.read_exact(&mut entry_count)?;
+        let entry_count = u32::from_be_bytes(entry_count) as usize;
+        if entry_count == 0 {
+            return Ok(None);
+        }
+        if entry_count > MAX_CHUNK_SIZE {
+            return Err(CheckpointError::ChunkTooLarge {
+                index: self.chunk_index,
+                size: entry_count,
+            });
+        }
+
+        let mut buffer = vec![0_u8; entry_count * ENTRY_SIZE];
+        self.reader.read_exact(&mut buffer)?;
+        let mut expected_hash = ValueHash::zero();
+        self.reader.read_exact(expected_hash.as_bytes_mut())?;
+        let actual_hash = Blake2Hasher.hash_bytes(&buffer);
+        if actual_hash != expected_hash {
+            return Err(CheckpointError::ChunkHashMismatch {
+                index: self.chunk_index,
+                expected: expected_hash,
+                actual: actual_hash,
+            });
+        }
+        self.chunk_index += 1;
+
+        let entries = buffer.chunks_exact(ENTRY_SIZE).map(|entry| {
+            let (key, rest) = entry.split_at(KEY_SIZE);
+            let (value, leaf_index) = rest.split_at(32);
+            TreeEntry {
+                key: Key::from_big_endian(key),
+                value: ValueHash::from_slice(value),
+                leaf_index: u64::from_be_bytes(leaf_index.try_into().unwrap()),
+                // ^ `unwrap()` is safe by construction
+            }
+        });
+        Ok(Some(entries.collect()))
+    }
+}
+
+impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
+    /// Exports all tree entries at the specified `version` as a checkpoint. Entries are split
+    /// into chunks containing at most `chunk_size` entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version doesn't exist, the tree data cannot be read (e.g., because
+    /// the version is pruned), or on I/O errors writing to `writer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero or exceeds [`MAX_CHUNK_SIZE`].
+    pub fn export_checkpoint<W: Write>(
+        &self,
+        version: u64,
+        mut writer: W,
+        chunk_size: usize,
+    ) -> Result<CheckpointHeader, CheckpointError> {
+        assert!(chunk_size > 0, "Chunk size must be positive");
+        assert!(
+            chunk_size <= MAX_CHUNK_SIZE,
+            "Chunk size must not exceed {MAX_CHUNK_SIZE}"
+        );
+
+        let root = self
+            .db
+            .try_root(version)?
+            .ok_or(CheckpointError::MissingVersion(version))?;
+        let header = CheckpointHeader {
+            version,
+            root_hash: self.root_hash(version).unwrap(),
+            // ^ `unwrap()` is safe: we've checked that the root exists
+            leaf_count: root.leaf_count(),
+        };
+        header.write_to(&mut writer)?;
+
+        let mut chunk_writer = ChunkWriter::new(writer, chunk_size);
+        if let Root::Filled { node, .. } = root {
+            let root_key = Nibbles::EMPTY.with_version(version);
+            self.export_node(node, root_key, &mut chunk_writer)?;
+        }
+        chunk_writer.finish()?;
+        Ok(header)
+    }
+
+    fn export_node<W: Write>(
+        &self,
+        node: Node,
+        key: NodeKey,
+        chunk_writer: &mut ChunkWriter<W>,
+    ) -> Result<(), CheckpointError> {
+        match node {
+            Node::Leaf(leaf) => chunk_writer.push(leaf.into())?,
+            Node::Internal(node) => {
+                // Children are iterated in the increasing nibble order, so leaves are visited
+                // in the increasing key order.
+                for (nibble, child_ref) in node.children() {
+                    let child_key = key
+                        .nibbles
+                        .push(nibble)
+                        .ok_or(CheckpointError::MissingNode(key))?;
+                    let child_key = child_key.with_version(child_ref.version);
+                    let child = self
+                        .db
+                        .try_tree_node(&child_key, child_ref.is_leaf)?
+                        .ok_or(CheckpointError::MissingNode(child_key))?;
+                    // Recursion here is OK; the tree isn't that deep.
+                    self.export_node(child, child_key, chunk_writer)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<DB: PruneDatabase> MerkleTreeRecovery<DB> {
+    /// Restores a tree from a checkpoint created by [`MerkleTree::export_checkpoint()`] and finalizes
+    /// the recovery. Returns the header of the imported checkpoint together with the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint is malformed or corrupted, or if the number of leaves or the root hash
+    /// of the restored tree don't match the checkpoint header. In the latter cases, the database is left
+    /// in the recovery state.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same situations as [`Self::new()`].
+    pub fn import_checkpoint<R: Read>(
+        db: DB,
+        mut reader: R,
+    ) -> Result<(DB, CheckpointHeader), CheckpointError> {
+        let header = CheckpointHeader::read_from(&mut reader)?;
+        let mut recovery = Self::new(db, header.version);
+        let mut chunk_reader = ChunkReader {
+            reader,
+            chunk_index: 0,
+        };
+        let mut leaf_count = 0_u64;
+        while let Some(entries) = chunk_reader.read_chunk()? {
+            leaf_count += entries.len() as u64;
+            recovery.extend_linear(entries);
+        }
+
+        if leaf_count != header.leaf_count {
+            return Err(CheckpointError::LeafCountMismatch {
+                expected: header.leaf_count,
+                actual: leaf_count,
+            });
+        }
+
+        let root_hash = recovery.root_hash();
+        if root_hash != header.root_hash {
+            return Err(CheckpointError::RootHashMismatch {
+                expected: header.root_hash,
+                actual: root_hash,
+            });
+        }
+        Ok((recovery.finalize(), header))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PatchSet;
+
+    fn test_entries(count: u64) -> Vec<TreeEntry> {
+        (1..=count)
+            .map(|i| TreeEntry::new(Key::from(i * 7919), i, ValueHash::from_low_u64_be(i)))
+            .collect()
+    }
+
+    fn export(tree: &MerkleTree<PatchSet>, version: u64, chunk_size: usize) -> Vec<u8> {
+        let mut buffer = vec![];
+        tree.export_checkpoint(version, &mut buffer, chunk_size)
+            .unwrap();
+        buffer
+    }
+
+    #[test]
+    fn checkpoint_roundtrip() {
+        let mut tree = MerkleTree::new(PatchSet::default());
+        tree.extend(test_entries(50));
+        let output = tree.extend(test_entries(100));
+
+        for chunk_size in [1, 7, 100, 1_000] {
+            let checkpoint = export(&tree, 1, chunk_size);
+            let (db, header) =
+                MerkleTreeRecovery::import_checkpoint(PatchSet::default(), checkpoint.as_slice())
+                    .unwrap();
+            assert_eq!(header.version, 1);
+            assert_eq!(header.root_hash, output.root_hash);
+            assert_eq!(header.leaf_count, 100);
+
+            let imported_tree = MerkleTree::new(db);
+            assert_eq!(imported_tree.root_hash(1), Some(output.root_hash));
+            imported_tree.verify_consistency(1, true).unwrap();
+        }
+    }
+
+    #[test]
+    fn exporting_empty_tree() {
+        let mut tree = MerkleTree::new(PatchSet::default());
+        let output = tree.extend(vec![]);
+        let checkpoint = export(&tree, 0, 10);
+
+        let (db, header) =
+            MerkleTreeRecovery::import_checkpoint(PatchSet::default(), checkpoint.as_slice())
+                .unwrap();
+        assert_eq!(header.root_hash, output.root_hash);
+        assert_eq!(MerkleTree::new(db).root_hash(0), Some(output.root_hash));
+    }
+
+    #[test]
+    fn exporting_missing_version() {
+        let mut tree = MerkleTree::new(PatchSet::default());
+        tree.extend(test_entries(10));
+        let err = tree.export_checkpoint(1, io::sink(), 10).unwrap_err();
+        assert!(matches!(err, CheckpointError::MissingVersion(1)), "{err}");
+    }
+
+    #[test]
+    fn corrupted_chunk_is_detected() {
+        let mut tree = MerkleTree::new(PatchSet::default());
+        tree.extend(test_entries(10));
+        let mut checkpoint = export(&tree, 0, 5);
+        // Flip a bit in the first entry of the first chunk.
+        let header_len = MAGIC.len() + 1 + 8 + 32 + 8;
+        checkpoint[header_len + 4] ^= 1;
+
+        let err = MerkleTreeRecovery::import_checkpoint(PatchSet::default(), checkpoint.as_slice())
+            .unwrap_err();
+        assert!(
+            matches!(err, CheckpointError::ChunkHashMismatch { index: 0, .. }),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn oversized_chunk_is_rejected() {
+        let mut tree = MerkleTree::new(PatchSet::default());
+        tree.extend(test_entries(10));
+        let mut checkpoint = export(&tree, 0, 5);
+        // Overwrite the entry count of the first chunk. The import must fail before trying to allocate
+        // a buffer for the declared entries.
+        let header_len = MAGIC.len() + 1 + 8 + 32 + 8;
+        checkpoint[header_len..header_len + 4].copy_from_slice(&u32::MAX.to_be_bytes());
+
+        let err = MerkleTreeRecovery::import_checkpoint(PatchSet::default(), checkpoint.as_slice())
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                CheckpointError::ChunkTooLarge { index: 0, size } if size == u32::MAX as usize
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn leaf_count_mismatch_is_detected() {
+        let mut tree = MerkleTree::new(PatchSet::default());
+        tree.extend(test_entries(10));
+        let mut checkpoint = export(&tree, 0, 5);
+        // Overwrite the leaf count in the header.
+        let leaf_count_offset = MAGIC.len() + 1 + 8 + 32;
+        checkpoint[leaf_count_offset..leaf_count_offset + 8].copy_from_slice(&11_u64.to_be_bytes());
+
+        let err = MerkleTreeRecovery::import_checkpoint(PatchSet::default(), checkpoint.as_slice())
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                CheckpointError::LeafCountMismatch {
+                    expected: 11,
+                    actual: 10
+                }
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn root_hash_mismatch_is_detected() {
+        let mut tree = MerkleTree::new(PatchSet::default());
+        tree.extend(test_entries(10));
+        let mut checkpoint = export(&tree, 0, 5);
+        // Corrupt the root hash in the header.
+        checkpoint[MAGIC.len() + 1 + 8] ^= 1;
+
+        let err = MerkleTreeRecovery::import_checkpoint(PatchSet::default(), checkpoint.as_slice())
+            .unwrap_err();
+        assert!(
+            matches!(err, CheckpointError::RootHashMismatch { .. }),
+            "{err}"
+        );
+    }
+}
@@ -1,5 +1,7 @@
 //! Tying the Merkle tree implementation to the problem domain.
 
+use std::io;
+
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use zksync_crypto::hasher::blake2::Blake2Hasher;
 use zksync_types::{
@@ -10,6 +12,8 @@ use zksync_types::{
 use zksync_utils::h256_to_u256;
 
 use crate::{
+    checkpoint::{CheckpointError, CheckpointHeader},
+    recovery::MerkleTreeRecovery,
     storage::{PatchSet, Patched, RocksDBWrapper},
     types::{
         Key, Root, TreeEntry, TreeEntryWithProof, TreeInstruction, TreeLogEntry, ValueHash,
@@ -57,6 +61,9 @@ pub struct ZkSyncTree {
 }
 
 impl ZkSyncTree {
+    /// Number of entries in a single chunk of exported checkpoints.
+    const CHECKPOINT_CHUNK_SIZE: usize = 10_000;
+
     fn create_thread_pool(thread_count: usize) -> ThreadPool {
         ThreadPoolBuilder::new()
             .thread_name(|idx| format!("new-merkle-tree-{idx}"))
@@ -178,6 +185,46 @@ impl ZkSyncTree {
             });
     }
 
+    /// Exports the tree state after the specified L1 batch as a checkpoint; see the [`checkpoint`](crate::checkpoint)
+    /// module for the format description.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree version for the L1 batch is missing or pruned, or on I/O errors.
+    pub fn export<W: io::Write>(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        writer: W,
+    ) -> Result<CheckpointHeader, CheckpointError> {
+        let version = u64::from(l1_batch_number.0);
+        self.tree
+            .export_checkpoint(version, writer, Self::CHECKPOINT_CHUNK_SIZE)
+    }
+
+    /// Imports a tree from a checkpoint created by [`Self::export()`] into an empty RocksDB instance
+    /// and creates a tree with the full processing mode. The root hash of the imported tree is checked
+    /// against the checkpoint header.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint is malformed or corrupted, or on I/O errors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `db` contains a tree which is not being recovered, or is being recovered
+    /// for another L1 batch.
+    pub fn import<R: io::Read>(db: RocksDBWrapper, reader: R) -> Result<Self, CheckpointError> {
+        let (db, header) = MerkleTreeRecovery::import_checkpoint(db, reader)?;
+        tracing::info!(
+            "Imported Merkle tree checkpoint for L1 batch #{version} with root hash {root_hash:?} \
+             and {leaf_count} leaves",
+            version = header.version,
+            root_hash = header.root_hash,
+            leaf_count = header.leaf_count
+        );
+        Ok(Self::new(db))
+    }
+
     /// Processes an iterator of storage logs comprising a single L1 batch.
     pub fn process_l1_batch(
         &mut self,
@@ -63,6 +63,7 @@ pub use crate::{
 };
 use crate::{hasher::HasherWithStats, storage::Storage, types::Root};
 
+pub mod checkpoint;
 mod consistency;
 pub mod domain;
 mod errors;
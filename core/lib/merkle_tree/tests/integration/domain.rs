@@ -271,6 +271,31 @@ fn reset_tree() {
     });
 }
 
+#[test]
+fn exporting_and_importing_tree() {
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let logs = gen_storage_logs();
+    let db = RocksDB::new(temp_dir.as_ref());
+    let mut tree = ZkSyncTree::new(db.into());
+    for chunk in logs.chunks(25) {
+        tree.process_l1_batch(chunk);
+    }
+    tree.save();
+
+    let mut checkpoint = vec![];
+    let header = tree.export(L1BatchNumber(3), &mut checkpoint).unwrap();
+    assert_eq!(header.version, 3);
+    assert_eq!(header.root_hash, tree.root_hash());
+    assert_eq!(header.leaf_count, logs.len() as u64);
+
+    let imported_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let db = RocksDB::new(imported_dir.as_ref());
+    let imported_tree = ZkSyncTree::import(db.into(), checkpoint.as_slice()).unwrap();
+    assert_eq!(imported_tree.root_hash(), tree.root_hash());
+    assert_eq!(imported_tree.next_l1_batch_number(), L1BatchNumber(4));
+    imported_tree.verify_consistency(L1BatchNumber(3));
+}
+
 #[test]
 fn read_logs() {
     let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
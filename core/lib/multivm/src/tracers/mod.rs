@@ -1,8 +1,10 @@
 pub mod call_tracer;
 mod multivm_dispatcher;
+pub mod prestate_tracer;
 pub mod storage_invocation;
 pub mod validator;
 
 pub use call_tracer::CallTracer;
 pub use multivm_dispatcher::TracerDispatcher;
+pub use prestate_tracer::PrestateTracer;
 pub use storage_invocation::StorageInvocations;
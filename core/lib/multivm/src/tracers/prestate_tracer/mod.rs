@@ -0,0 +1,68 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::Arc,
+};
+
+use once_cell::sync::OnceCell;
+use zksync_types::{vm_trace::TouchedStorageSlot, AccountTreeId, LogQuery, StorageKey};
+use zksync_utils::u256_to_h256;
+
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Tracer collecting all storage slots touched during the VM execution, together with their values
+/// before and after the execution.
+///
+/// Only the storage accesses made during the traced execution are collected, so the tracer can be used
+/// to trace a single transaction in a VM that has already executed other transactions in the batch.
+#[derive(Debug, Clone)]
+pub struct PrestateTracer {
+    result: Arc<OnceCell<Vec<TouchedStorageSlot>>>,
+    /// VM timestamp at the start of the traced execution.
+    initial_timestamp: u32,
+}
+
+impl PrestateTracer {
+    pub fn new(result: Arc<OnceCell<Vec<TouchedStorageSlot>>>) -> Self {
+        Self {
+            result,
+            initial_timestamp: 0,
+        }
+    }
+
+    fn store_result<'a>(&mut self, logs: impl Iterator<Item = &'a LogQuery>) {
+        let mut slots: Vec<TouchedStorageSlot> = vec![];
+        let mut slot_indices = HashMap::new();
+        for log in logs {
+            let key = StorageKey::new(AccountTreeId::new(log.address), u256_to_h256(log.key));
+            let slot_index = match slot_indices.entry(key) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => {
+                    // The first access to a slot always reads its value from the storage.
+                    let pre_value = u256_to_h256(log.read_value);
+                    slots.push(TouchedStorageSlot {
+                        key,
+                        pre_value,
+                        post_value: pre_value,
+                    });
+                    *entry.insert(slots.len() - 1)
+                }
+            };
+
+            if log.rw_flag {
+                // Rolled back writes are recorded in the reverse order, so that restoring the read value
+                // is sufficient to undo them.
+                let value = if log.rollback {
+                    log.read_value
+                } else {
+                    log.written_value
+                };
+                slots[slot_index].post_value = u256_to_h256(value);
+            }
+        }
+
+        let cell = self.result.as_ref();
+        cell.set(slots).unwrap();
+    }
+}
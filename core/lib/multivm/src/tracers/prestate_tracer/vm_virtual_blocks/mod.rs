@@ -0,0 +1,37 @@
+use zk_evm_1_3_3::aux_structures::Timestamp;
+use zksync_state::WriteStorage;
+
+use crate::{
+    interface::{dyn_tracers::vm_1_3_3::DynTracer, tracer::VmExecutionStopReason},
+    tracers::prestate_tracer::PrestateTracer,
+    vm_virtual_blocks::{
+        BootloaderState, ExecutionEndTracer, ExecutionProcessing, HistoryMode, SimpleMemory,
+        VmTracer, ZkSyncVmState,
+    },
+};
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for PrestateTracer {}
+
+impl<H: HistoryMode> ExecutionEndTracer<H> for PrestateTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> ExecutionProcessing<S, H> for PrestateTracer {
+    fn initialize_tracer(&mut self, state: &mut ZkSyncVmState<S, H>) {
+        self.initial_timestamp = state.local_state.timestamp;
+    }
+
+    fn after_vm_execution(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        let logs = state
+            .storage
+            .storage_log_queries_after_timestamp(Timestamp(self.initial_timestamp))
+            .iter()
+            .map(|log| &log.log_query);
+        self.store_result(logs);
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for PrestateTracer {}
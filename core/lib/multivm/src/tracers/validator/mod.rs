@@ -1,4 +1,8 @@
-use std::{collections::HashSet, marker::PhantomData, sync::Arc};
+use std::{
+    collections::HashSet,
+    marker::PhantomData,
+    sync::{atomic::Ordering, Arc},
+};
 
 use once_cell::sync::OnceCell;
 use zksync_state::{StoragePtr, WriteStorage};
@@ -7,13 +11,16 @@ use zksync_system_constants::{
     L2_ETH_TOKEN_ADDRESS, MSG_VALUE_SIMULATOR_ADDRESS, SYSTEM_CONTEXT_ADDRESS,
 };
 use zksync_types::{
-    vm_trace::ViolatedValidationRule, web3::signing::keccak256, AccountTreeId, Address, StorageKey,
-    H256, U256,
+    vm_trace::{ValidationCallFrame, ViolatedValidationRule, ViolatedValidationRuleContext},
+    web3::signing::keccak256,
+    AccountTreeId, Address, StorageKey, H256, U256,
 };
 use zksync_utils::{be_bytes_to_safe_address, u256_to_account_address, u256_to_h256};
 
 use crate::tracers::validator::types::{NewTrustedValidationItems, ValidationTracerMode};
-pub use crate::tracers::validator::types::{ValidationError, ValidationTracerParams};
+pub use crate::tracers::validator::types::{
+    ValidationError, ValidationTracerDiagnostics, ValidationTracerParams,
+};
 
 mod types;
 mod vm_latest;
@@ -37,6 +44,7 @@ pub struct ValidationTracer<H> {
     computational_gas_used: u32,
     computational_gas_limit: u32,
     pub result: Arc<OnceCell<ViolatedValidationRule>>,
+    diagnostics: Arc<ValidationTracerDiagnostics>,
     _marker: PhantomData<fn(H) -> H>,
 }
 
@@ -59,13 +67,26 @@ impl<H> ValidationTracer<H> {
                 computational_gas_used: 0,
                 computational_gas_limit: params.computational_gas_limit,
                 result: result.clone(),
+                diagnostics: Arc::default(),
                 _marker: Default::default(),
             },
             result,
         )
     }
 
-    fn process_validation_round_result(&mut self, result: ValidationRoundResult) {
+    /// Returns a handle to diagnostic information collected by this tracer.
+    pub fn diagnostics(&self) -> Arc<ValidationTracerDiagnostics> {
+        self.diagnostics.clone()
+    }
+
+    fn process_validation_round_result(
+        &mut self,
+        result: ValidationRoundResult,
+        violation_context: impl FnOnce() -> (String, Vec<ValidationCallFrame>),
+    ) {
+        self.diagnostics
+            .computational_gas_used
+            .store(self.computational_gas_used, Ordering::Relaxed);
         match result {
             Ok(NewTrustedValidationItems {
                 new_allowed_slots,
@@ -79,6 +100,14 @@ impl<H> ValidationTracer<H> {
                     tracing::trace!("Validation error is already set, skipping");
                     return;
                 }
+                let (opcode, call_stack) = violation_context();
+                let context = ViolatedValidationRuleContext {
+                    rule: err.clone(),
+                    opcode,
+                    call_stack,
+                    computational_gas_used: self.computational_gas_used,
+                };
+                self.diagnostics.violation.set(context).ok();
                 self.result.set(err).expect("Result should be empty");
             }
         }
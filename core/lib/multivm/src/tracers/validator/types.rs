@@ -1,6 +1,14 @@
-use std::{collections::HashSet, fmt::Display};
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    sync::atomic::{AtomicU32, Ordering},
+};
 
-use zksync_types::{vm_trace::ViolatedValidationRule, Address, H256, U256};
+use once_cell::sync::OnceCell;
+use zksync_types::{
+    vm_trace::{ViolatedValidationRule, ViolatedValidationRuleContext},
+    Address, H256, U256,
+};
 
 use crate::interface::Halt;
 
@@ -37,6 +45,26 @@ pub struct ValidationTracerParams {
     pub computational_gas_limit: u32,
 }
 
+/// Diagnostic information collected by the validation tracer. Can be inspected after the tracer
+/// is consumed by the VM.
+#[derive(Debug, Default)]
+pub struct ValidationTracerDiagnostics {
+    pub(super) computational_gas_used: AtomicU32,
+    pub(super) violation: OnceCell<ViolatedValidationRuleContext>,
+}
+
+impl ValidationTracerDiagnostics {
+    /// Returns the computational gas used by account validation.
+    pub fn computational_gas_used(&self) -> u32 {
+        self.computational_gas_used.load(Ordering::Relaxed)
+    }
+
+    /// Returns the context of the first violated validation rule, if any.
+    pub fn violation(&self) -> Option<&ViolatedValidationRuleContext> {
+        self.violation.get()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ValidationError {
     FailedTx(Halt),
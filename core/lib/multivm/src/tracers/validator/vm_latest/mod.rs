@@ -5,7 +5,9 @@ use zk_evm_1_4_0::{
 use zksync_state::{StoragePtr, WriteStorage};
 use zksync_system_constants::KECCAK256_PRECOMPILE_ADDRESS;
 use zksync_types::{
-    get_code_key, vm_trace::ViolatedValidationRule, AccountTreeId, StorageKey, H256,
+    get_code_key,
+    vm_trace::{ValidationCallFrame, ViolatedValidationRule},
+    AccountTreeId, Address, StorageKey, H256,
 };
 use zksync_utils::{h256_to_account_address, u256_to_account_address, u256_to_h256};
 
@@ -145,7 +147,10 @@ impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H::VmBoojumInteg
 
             let validation_round_result =
                 self.check_user_restrictions_vm_latest(state, data, memory, storage);
-            self.process_validation_round_result(validation_round_result);
+            self.process_validation_round_result(validation_round_result, || {
+                let opcode = format!("{:?}", data.opcode.variant.opcode);
+                (opcode, validation_call_stack(&state))
+            });
         }
 
         let hook = VmHook::from_opcode_memory(&state, &data);
@@ -199,3 +204,20 @@ impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H::VmBoojumIntegration> for Va
         TracerExecutionStatus::Continue
     }
 }
+
+/// Collects far call frames from the outermost to the innermost one.
+fn validation_call_stack(state: &VmLocalStateData<'_>) -> Vec<ValidationCallFrame> {
+    let callstack = &state.vm_local_state.callstack;
+    callstack
+        .inner
+        .iter()
+        .chain(std::iter::once(&callstack.current))
+        // The outermost entry is an empty context preceding the bootloader frame.
+        .filter(|frame| !frame.is_local_frame && frame.this_address != Address::zero())
+        .map(|frame| ValidationCallFrame {
+            this_address: frame.this_address,
+            code_address: frame.code_address,
+            msg_sender: frame.msg_sender,
+        })
+        .collect()
+}
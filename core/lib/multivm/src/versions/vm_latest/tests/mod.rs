@@ -18,3 +18,4 @@ mod tester;
 mod tracing_execution_error;
 mod upgrade;
 mod utils;
+mod validation_tracer;
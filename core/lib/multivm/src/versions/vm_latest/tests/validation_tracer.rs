@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+
+use zksync_system_constants::{BOOTLOADER_ADDRESS, NONCE_HOLDER_ADDRESS};
+use zksync_types::{vm_trace::ViolatedValidationRule, Address, Execute, Nonce};
+
+use crate::{
+    interface::{ExecutionResult, Halt, TxExecutionMode, VmExecutionMode, VmInterface},
+    tracers::validator::{ValidationTracer, ValidationTracerParams},
+    vm_latest::{
+        tests::{
+            nonce_holder::NonceHolderTestMode,
+            tester::{Account, VmTesterBuilder},
+            utils::read_nonce_holder_tester,
+        },
+        types::internals::TransactionData,
+        HistoryEnabled, ToTracerPointer,
+    },
+};
+
+#[test]
+fn test_validation_tracer_diagnostics() {
+    let mut account = Account::random();
+
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_deployer()
+        .with_custom_contracts(vec![(
+            read_nonce_holder_tester().to_vec(),
+            account.address,
+            true,
+        )])
+        .with_rich_accounts(vec![account.clone()])
+        .build();
+
+    let mut transaction_data: TransactionData = account
+        .get_l2_tx_for_execute_with_nonce(
+            Execute {
+                contract_address: account.address,
+                calldata: vec![12],
+                value: Default::default(),
+                factory_deps: None,
+            },
+            None,
+            Nonce(0),
+        )
+        .into();
+    // The account calls `NonceHolder.increaseMinNonce()` during validation.
+    transaction_data.signature = vec![NonceHolderTestMode::IncreaseMinNonceBy1.into()];
+    vm.vm.push_raw_transaction(transaction_data, 0, 0, true);
+
+    // Validate the transaction on behalf of an unrelated user, so that nonce holder slots
+    // of the account are not allowed to be touched.
+    let params = ValidationTracerParams {
+        user_address: Address::repeat_byte(0x42),
+        paymaster_address: Address::zero(),
+        trusted_slots: HashSet::new(),
+        trusted_addresses: HashSet::new(),
+        trusted_address_slots: HashSet::new(),
+        computational_gas_limit: u32::MAX,
+    };
+    let (tracer, validation_result) = ValidationTracer::<HistoryEnabled>::new(params);
+    let diagnostics = tracer.diagnostics();
+    let result = vm
+        .vm
+        .inspect(tracer.into_tracer_pointer().into(), VmExecutionMode::OneTx);
+
+    let ExecutionResult::Halt { reason } = result.result else {
+        panic!("Expected halt, got {:?}", result.result);
+    };
+    assert!(
+        matches!(reason, Halt::TracerCustom(_)),
+        "Unexpected halt reason: {reason:?}"
+    );
+
+    let rule = validation_result.get().expect("no violated rule");
+    assert!(
+        matches!(
+            rule,
+            ViolatedValidationRule::TouchedUnallowedStorageSlots(address, _)
+                if *address == NONCE_HOLDER_ADDRESS
+        ),
+        "Unexpected violated rule: {rule:?}"
+    );
+
+    let violation = diagnostics.violation().expect("no violation context");
+    assert_eq!(violation.rule.to_string(), rule.to_string());
+    assert!(
+        violation.opcode.contains("StorageRead"),
+        "Unexpected opcode: {}",
+        violation.opcode
+    );
+    let call_stack: Vec<_> = violation
+        .call_stack
+        .iter()
+        .map(|frame| frame.this_address)
+        .collect();
+    assert_eq!(
+        call_stack,
+        [BOOTLOADER_ADDRESS, account.address, NONCE_HOLDER_ADDRESS]
+    );
+    let nonce_holder_frame = violation.call_stack.last().unwrap();
+    assert_eq!(nonce_holder_frame.msg_sender, account.address);
+    assert!(violation.computational_gas_used > 0);
+    assert_eq!(
+        diagnostics.computational_gas_used(),
+        violation.computational_gas_used
+    );
+}
@@ -0,0 +1,243 @@
+//! Versioned envelope wrapping serialized objects stored via `dyn ObjectStore::put()`.
+//!
+//! # Format
+//!
+//! An envelope consists of a header followed by the (possibly compressed) payload:
+//!
+//! - Magic bytes [`MAGIC`] (4 bytes)
+//! - Format version used by the writer (1 byte; currently 1)
+//! - Minimum format version a reader must support to open the envelope (1 byte; currently 1)
+//! - Header length in bytes, including the magic bytes (2 bytes, little-endian)
+//! - Compression method (1 byte; 0 = none, 1 = zstd)
+//! - Length of the uncompressed payload (8 bytes, little-endian)
+//! - SHA-256 checksum of the uncompressed payload (32 bytes)
+//!
+//! Future format versions may append fields to the header. As long as such a version is backward-compatible,
+//! writers keep the minimum reader version intact, and older readers skip unknown header fields using
+//! the header length. Readers only reject envelopes with the minimum reader version exceeding [`FORMAT_VERSION`].
+//!
+//! Objects not starting with the magic bytes are considered to be written before the envelope
+//! was introduced, and are returned as is. The magic bytes are chosen so that they cannot realistically
+//! start a legacy bincode / gzip blob.
+
+use std::{error, fmt};
+
+use sha2::{Digest, Sha256};
+
+const MAGIC: [u8; 4] = [0xff, b'Z', b'K', b'E'];
+const FORMAT_VERSION: u8 = 1;
+const MIN_READER_VERSION: u8 = 1;
+/// Length of the header fields known to this version (including the magic bytes).
+const HEADER_LEN: usize = MAGIC.len() + 4 + 1 + 8 + 32;
+const ZSTD_LEVEL: i32 = 3;
+
+const COMPRESSION_NONE: u8 = 0;
+const COMPRESSION_ZSTD: u8 = 1;
+
+/// Errors that can occur when opening an envelope.
+#[derive(Debug)]
+pub(crate) enum EnvelopeError {
+    Truncated,
+    UnsupportedVersion { version: u8, min_reader_version: u8 },
+    UnsupportedCompression(u8),
+    Decompression(std::io::Error),
+    LengthMismatch { expected: u64, actual: u64 },
+    ChecksumMismatch,
+}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => formatter.write_str("object envelope is truncated"),
+            Self::UnsupportedVersion {
+                version,
+                min_reader_version,
+            } => write!(
+                formatter,
+                "unsupported object envelope version {version}: reader must support version \
+                 {min_reader_version}, but only supports {FORMAT_VERSION}"
+            ),
+            Self::UnsupportedCompression(method) => {
+                write!(formatter, "unsupported object compression method: {method}")
+            }
+            Self::Decompression(err) => write!(formatter, "failed decompressing object: {err}"),
+            Self::LengthMismatch { expected, actual } => write!(
+                formatter,
+                "object length mismatch: expected {expected} bytes, got {actual} bytes"
+            ),
+            Self::ChecksumMismatch => formatter.write_str("object checksum mismatch"),
+        }
+    }
+}
+
+impl error::Error for EnvelopeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Decompression(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Wraps the serialized object into an envelope. The payload is compressed unless compression
+/// doesn't reduce its size (e.g., for objects that are already compressed).
+pub(crate) fn seal(payload: &[u8]) -> Vec<u8> {
+    let compressed = zstd::stream::encode_all(payload, ZSTD_LEVEL)
+        .ok()
+        .filter(|compressed| compressed.len() < payload.len());
+    let (compression, stored_payload) = match &compressed {
+        Some(compressed) => (COMPRESSION_ZSTD, compressed.as_slice()),
+        None => (COMPRESSION_NONE, payload),
+    };
+
+    let mut envelope = Vec::with_capacity(HEADER_LEN + stored_payload.len());
+    envelope.extend_from_slice(&MAGIC);
+    envelope.push(FORMAT_VERSION);
+    envelope.push(MIN_READER_VERSION);
+    envelope.extend_from_slice(&(HEADER_LEN as u16).to_le_bytes());
+    envelope.push(compression);
+    envelope.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    envelope.extend_from_slice(&Sha256::digest(payload));
+    envelope.extend_from_slice(stored_payload);
+    envelope
+}
+
+/// Unwraps the serialized object from an envelope, checking its integrity. Legacy objects
+/// without an envelope are returned as is.
+pub(crate) fn open(bytes: Vec<u8>) -> Result<Vec<u8>, EnvelopeError> {
+    if !bytes.starts_with(&MAGIC) {
+        return Ok(bytes);
+    }
+    // Fields up to and including the header length are present in all format versions.
+    let header_len_start = MAGIC.len() + 2;
+    if bytes.len() < header_len_start + 2 {
+        return Err(EnvelopeError::Truncated);
+    }
+    let version = bytes[MAGIC.len()];
+    let min_reader_version = bytes[MAGIC.len() + 1];
+    if min_reader_version > FORMAT_VERSION {
+        return Err(EnvelopeError::UnsupportedVersion {
+            version,
+            min_reader_version,
+        });
+    }
+    let header_len = u16::from_le_bytes([bytes[header_len_start], bytes[header_len_start + 1]]);
+    let header_len = usize::from(header_len);
+    // Header fields appended by newer versions are skipped; the header cannot be shorter than the known fields.
+    if header_len < HEADER_LEN || bytes.len() < header_len {
+        return Err(EnvelopeError::Truncated);
+    }
+
+    let (header, stored_payload) = bytes.split_at(header_len);
+    let compression = header[header_len_start + 2];
+    let len_start = header_len_start + 3;
+    let expected_len = u64::from_le_bytes(header[len_start..len_start + 8].try_into().unwrap());
+    let expected_checksum = &header[len_start + 8..HEADER_LEN];
+
+    let payload = match compression {
+        COMPRESSION_NONE => stored_payload.to_vec(),
+        COMPRESSION_ZSTD => {
+            zstd::stream::decode_all(stored_payload).map_err(EnvelopeError::Decompression)?
+        }
+        _ => return Err(EnvelopeError::UnsupportedCompression(compression)),
+    };
+
+    if payload.len() as u64 != expected_len {
+        return Err(EnvelopeError::LengthMismatch {
+            expected: expected_len,
+            actual: payload.len() as u64,
+        });
+    }
+    if Sha256::digest(&payload).as_slice() != expected_checksum {
+        return Err(EnvelopeError::ChecksumMismatch);
+    }
+    Ok(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPRESSION_OFFSET: usize = MAGIC.len() + 4;
+
+    #[test]
+    fn sealing_and_opening_compressible_object() {
+        let payload = vec![42_u8; 10_000];
+        let envelope = seal(&payload);
+        assert!(envelope.len() < 1_000, "{}", envelope.len());
+        assert_eq!(envelope[COMPRESSION_OFFSET], COMPRESSION_ZSTD);
+        assert_eq!(open(envelope).unwrap(), payload);
+    }
+
+    #[test]
+    fn sealing_and_opening_incompressible_object() {
+        let payload: Vec<u8> = (0..=255).collect();
+        let envelope = seal(&payload);
+        assert_eq!(envelope.len(), HEADER_LEN + payload.len());
+        assert_eq!(envelope[COMPRESSION_OFFSET], COMPRESSION_NONE);
+        assert_eq!(open(envelope).unwrap(), payload);
+
+        let envelope = seal(&[]);
+        assert_eq!(open(envelope).unwrap(), [] as [u8; 0]);
+    }
+
+    #[test]
+    fn opening_legacy_object() {
+        let payload = vec![1, 2, 3, 4];
+        assert_eq!(open(payload.clone()).unwrap(), payload);
+        assert_eq!(open(vec![]).unwrap(), [] as [u8; 0]);
+    }
+
+    #[test]
+    fn detecting_corruption() {
+        let payload: Vec<u8> = (0..=255).collect();
+        let mut envelope = seal(&payload);
+        *envelope.last_mut().unwrap() ^= 1;
+        let err = open(envelope).unwrap_err();
+        assert!(matches!(err, EnvelopeError::ChecksumMismatch), "{err}");
+
+        let envelope = seal(&vec![42_u8; 10_000]);
+        let truncated_envelope = envelope[..envelope.len() - 1].to_vec();
+        assert!(open(truncated_envelope).is_err());
+        let err = open(envelope[..10].to_vec()).unwrap_err();
+        assert!(matches!(err, EnvelopeError::Truncated), "{err}");
+
+        let mut envelope = seal(&payload);
+        envelope[MAGIC.len() + 2..MAGIC.len() + 4].copy_from_slice(&10_u16.to_le_bytes());
+        let err = open(envelope).unwrap_err();
+        assert!(matches!(err, EnvelopeError::Truncated), "{err}");
+    }
+
+    #[test]
+    fn opening_envelope_from_compatible_newer_version() {
+        let payload = vec![42_u8; 10_000];
+        let envelope = seal(&payload);
+        // Emulate a newer version appending a field to the header.
+        let mut newer_envelope = envelope[..HEADER_LEN].to_vec();
+        newer_envelope[MAGIC.len()] = FORMAT_VERSION + 1;
+        let header_len = (HEADER_LEN + 3) as u16;
+        newer_envelope[MAGIC.len() + 2..MAGIC.len() + 4].copy_from_slice(&header_len.to_le_bytes());
+        newer_envelope.extend_from_slice(&[1, 2, 3]);
+        newer_envelope.extend_from_slice(&envelope[HEADER_LEN..]);
+
+        assert_eq!(open(newer_envelope).unwrap(), payload);
+    }
+
+    #[test]
+    fn rejecting_envelope_from_incompatible_newer_version() {
+        let mut envelope = seal(&[1, 2, 3]);
+        envelope[MAGIC.len()] = FORMAT_VERSION + 1;
+        envelope[MAGIC.len() + 1] = FORMAT_VERSION + 1;
+        let err = open(envelope).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                EnvelopeError::UnsupportedVersion {
+                    version: 2,
+                    min_reader_version: 2
+                }
+            ),
+            "{err}"
+        );
+    }
+}
@@ -71,6 +71,28 @@ impl ObjectStore for FileBackedObjectStore {
         fs::remove_file(filename).await.map_err(From::from)
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        let mut entries = fs::read_dir(format!("{}/{bucket}", self.base_dir)).await?;
+        let mut keys = vec![];
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry.file_type().await?.is_file() {
+                continue;
+            }
+            // Non-UTF-8 file names cannot correspond to keys, so they are skipped.
+            if let Ok(key) = entry.file_name().into_string() {
+                if key.starts_with(prefix) {
+                    keys.push(key);
+                }
+            }
+        }
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!("{}/{}", self.base_dir, bucket)
     }
@@ -125,4 +147,30 @@ mod test {
             .await;
         assert!(result.is_ok(), "result must be OK");
     }
+
+    #[tokio::test]
+    async fn test_list() {
+        let dir = TempDir::new("test-data").unwrap();
+        let path = dir.into_path().into_os_string().into_string().unwrap();
+        let object_store = FileBackedObjectStore::new(path).await;
+        for key in ["2_1.bin", "1_2.bin", "1_1.bin", "10_1.bin"] {
+            object_store
+                .put_raw(Bucket::ProverJobsFri, key, vec![0, 1])
+                .await
+                .unwrap();
+        }
+
+        let keys = object_store
+            .list_raw(Bucket::ProverJobsFri, "1")
+            .await
+            .unwrap();
+        assert_eq!(keys, ["10_1.bin", "1_1.bin", "1_2.bin"]);
+        let keys = object_store
+            .list_raw(Bucket::ProverJobsFri, "1_")
+            .await
+            .unwrap();
+        assert_eq!(keys, ["1_1.bin", "1_2.bin"]);
+        let keys = object_store.list_raw(Bucket::ProofsFri, "").await.unwrap();
+        assert!(keys.is_empty());
+    }
 }
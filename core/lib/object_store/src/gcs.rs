@@ -11,6 +11,7 @@ use google_cloud_storage::{
             delete::DeleteObjectRequest,
             download::Range,
             get::GetObjectRequest,
+            list::ListObjectsRequest,
             upload::{Media, UploadObjectRequest, UploadType},
         },
         Error as HttpError,
@@ -19,11 +20,11 @@ use google_cloud_storage::{
 use http::StatusCode;
 
 use crate::{
-    metrics::GCS_METRICS,
+    metrics::OBJECT_STORE_METRICS,
     raw::{Bucket, ObjectStore, ObjectStoreError},
 };
 
-async fn retry<T, E, Fut, F>(max_retries: u16, mut f: F) -> Result<T, E>
+pub(crate) async fn retry<T, E, Fut, F>(max_retries: u16, mut f: F) -> Result<T, E>
 where
     E: fmt::Display,
     Fut: Future<Output = Result<T, E>>,
@@ -35,7 +36,7 @@ where
         match f().await {
             Ok(result) => return Ok(result),
             Err(err) => {
-                tracing::warn!(%err, "Failed GCS request {retries}/{max_retries}, retrying.");
+                tracing::warn!(%err, "Failed object store request {retries}/{max_retries}, retrying.");
                 if retries > max_retries {
                     return Err(err);
                 }
@@ -148,7 +149,7 @@ impl From<HttpError> for ObjectStoreError {
 #[async_trait]
 impl ObjectStore for GoogleCloudStorage {
     async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
-        let fetch_latency = GCS_METRICS.start_fetch(bucket);
+        let fetch_latency = OBJECT_STORE_METRICS.start_fetch(bucket);
         let filename = Self::filename(bucket.as_str(), key);
         tracing::trace!(
             "Fetching data from GCS for key {filename} from bucket {}",
@@ -179,7 +180,7 @@ impl ObjectStore for GoogleCloudStorage {
         key: &str,
         value: Vec<u8>,
     ) -> Result<(), ObjectStoreError> {
-        let store_latency = GCS_METRICS.start_store(bucket);
+        let store_latency = OBJECT_STORE_METRICS.start_store(bucket);
         let filename = Self::filename(bucket.as_str(), key);
         tracing::trace!(
             "Storing data to GCS for key {filename} from bucket {}",
@@ -208,6 +209,39 @@ impl ObjectStore for GoogleCloudStorage {
         self.remove_inner(bucket.as_str(), key).await
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        let bucket_prefix = Self::filename(bucket.as_str(), "");
+        tracing::trace!(
+            "Listing keys in GCS with prefix {bucket_prefix}{prefix} from bucket {}",
+            self.bucket_prefix
+        );
+
+        let mut request = ListObjectsRequest {
+            bucket: self.bucket_prefix.clone(),
+            prefix: Some(format!("{bucket_prefix}{prefix}")),
+            ..ListObjectsRequest::default()
+        };
+        let mut keys = vec![];
+        loop {
+            let response = retry(self.max_retries, || self.client.list_objects(&request)).await?;
+            let objects = response.items.unwrap_or_default();
+            keys.extend(
+                objects.into_iter().filter_map(|object| {
+                    object.name.strip_prefix(&bucket_prefix).map(str::to_owned)
+                }),
+            );
+            if response.next_page_token.is_none() {
+                break;
+            }
+            request.page_token = response.next_page_token;
+        }
+        Ok(keys)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!(
             "https://storage.googleapis.com/{}/{}",
@@ -3,6 +3,7 @@
 //!
 //! - File-based storage saving blobs as separate files in the local filesystem
 //! - GCS-based storage
+//! - S3-compatible storage (AWS S3, MinIO etc.)
 //!
 //! These implementations are not exposed externally. Instead, a store trait object
 //! can be constructed using an [`ObjectStoreFactory`] based on the configuration.
@@ -12,7 +13,8 @@
 //! Besides the lower-level storage abstraction, the crate provides high-level
 //! typesafe `<dyn ObjectStore>::get()` and `<dyn ObjectStore>::put()` methods
 //! to store [(de)serializable objects](StoredObject). Prefer using these methods
-//! whenever possible.
+//! whenever possible. Besides type safety, these methods compress stored objects and check their
+//! integrity on retrieval.
 
 // Linter settings.
 #![warn(missing_debug_implementations, bare_trait_objects)]
@@ -23,12 +25,14 @@
     clippy::doc_markdown
 )]
 
+mod envelope;
 mod file;
 mod gcs;
 mod metrics;
 mod mock;
 mod objects;
 mod raw;
+mod s3;
 
 // Re-export `bincode` crate so that client binaries can conveniently use it.
 pub use bincode;
@@ -27,7 +27,10 @@ use zksync_types::{
     L1BatchNumber,
 };
 
-use crate::raw::{BoxedError, Bucket, ObjectStore, ObjectStoreError};
+use crate::{
+    envelope,
+    raw::{BoxedError, Bucket, ObjectStore, ObjectStoreError},
+};
 
 /// Object that can be stored in an [`ObjectStore`].
 pub trait StoredObject: Sized {
@@ -282,21 +285,27 @@ impl StoredObject for L1BatchProofForL1 {
 }
 
 impl dyn ObjectStore + '_ {
-    /// Fetches the value for the given key if it exists.
+    /// Fetches the value for the given key if it exists. Objects stored before the introduction
+    /// of the checksummed envelope (see [`Self::put()`]) are supported as well.
     ///
     /// # Errors
     ///
     /// Returns an error if an object with the `key` does not exist, cannot be accessed,
-    /// or cannot be deserialized.
+    /// is corrupted, or cannot be deserialized.
     pub async fn get<V: StoredObject>(&self, key: V::Key<'_>) -> Result<V, ObjectStoreError> {
         let key = V::encode_key(key);
         let bytes = self.get_raw(V::BUCKET, &key).await?;
+        let bytes = envelope::open(bytes).map_err(|err| ObjectStoreError::Corrupted(err.into()))?;
         V::deserialize(bytes).map_err(ObjectStoreError::Serialization)
     }
 
     /// Stores the value associating it with the key. If the key already exists,
     /// the value is replaced.
     ///
+    /// The serialized value is wrapped in a versioned envelope containing a checksum of the value,
+    /// and is compressed using zstd if this reduces its size. Thus, the raw stored bytes
+    /// (e.g., returned by [`ObjectStore::get_raw()`]) differ from [`StoredObject::serialize()`] output.
+    ///
     /// # Errors
     ///
     /// Returns an error if serialization or the insertion / replacement operation fails.
@@ -304,6 +313,26 @@ impl dyn ObjectStore + '_ {
         &self,
         key: V::Key<'_>,
         value: &V,
+    ) -> Result<String, ObjectStoreError> {
+        let key = V::encode_key(key);
+        let bytes = value.serialize().map_err(ObjectStoreError::Serialization)?;
+        self.put_raw(V::BUCKET, &key, envelope::seal(&bytes))
+            .await?;
+        Ok(key)
+    }
+
+    /// Stores the value associating it with the key without wrapping it in an envelope, i.e.,
+    /// the raw stored bytes are equal to [`StoredObject::serialize()`] output. This should be used
+    /// for objects read by third parties (e.g., ones in public buckets), which are not aware of the envelope.
+    /// The stored value can still be read using [`Self::get()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or the insertion / replacement operation fails.
+    pub async fn put_unwrapped<V: StoredObject>(
+        &self,
+        key: V::Key<'_>,
+        value: &V,
     ) -> Result<String, ObjectStoreError> {
         let key = V::encode_key(key);
         let bytes = value.serialize().map_err(ObjectStoreError::Serialization)?;
@@ -356,7 +385,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_storage_logs_can_be_serialized_and_deserialized() {
-        let store = ObjectStoreFactory::mock().create_store().await;
+        let store = ObjectStoreFactory::mock().create_store().await.unwrap();
         let key = SnapshotStorageLogsStorageKey {
             l1_batch_number: L1BatchNumber(567),
             chunk_id: 5,
@@ -384,7 +413,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_factory_deps_can_be_serialized_and_deserialized() {
-        let store = ObjectStoreFactory::mock().create_store().await;
+        let store = ObjectStoreFactory::mock().create_store().await.unwrap();
         let key = L1BatchNumber(123);
         let factory_deps = SnapshotFactoryDependencies {
             factory_deps: vec![
@@ -400,4 +429,61 @@ mod tests {
         let reconstructed_factory_deps = store.get(key).await.unwrap();
         assert_eq!(factory_deps, reconstructed_factory_deps);
     }
+
+    #[tokio::test]
+    async fn legacy_and_corrupted_objects() {
+        let store = ObjectStoreFactory::mock().create_store().await.unwrap();
+        let key = L1BatchNumber(123);
+        let factory_deps = SnapshotFactoryDependencies {
+            factory_deps: vec![SnapshotFactoryDependency {
+                bytecode: Bytes(vec![1; 64]),
+            }],
+        };
+
+        // Objects written without an envelope should be readable.
+        let filename = SnapshotFactoryDependencies::encode_key(key);
+        let legacy_bytes = factory_deps.serialize().unwrap();
+        store
+            .put_raw(Bucket::StorageSnapshot, &filename, legacy_bytes)
+            .await
+            .unwrap();
+        let reconstructed_factory_deps = store.get(key).await.unwrap();
+        assert_eq!(factory_deps, reconstructed_factory_deps);
+
+        store.put(key, &factory_deps).await.unwrap();
+        let mut stored_bytes = store
+            .get_raw(Bucket::StorageSnapshot, &filename)
+            .await
+            .unwrap();
+        *stored_bytes.last_mut().unwrap() ^= 1;
+        store
+            .put_raw(Bucket::StorageSnapshot, &filename, stored_bytes)
+            .await
+            .unwrap();
+        let err = store
+            .get::<SnapshotFactoryDependencies>(key)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::Corrupted(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn unwrapped_objects() {
+        let store = ObjectStoreFactory::mock().create_store().await.unwrap();
+        let key = L1BatchNumber(123);
+        let factory_deps = SnapshotFactoryDependencies {
+            factory_deps: vec![SnapshotFactoryDependency {
+                bytecode: Bytes(vec![1; 64]),
+            }],
+        };
+
+        let filename = store.put_unwrapped(key, &factory_deps).await.unwrap();
+        let stored_bytes = store
+            .get_raw(Bucket::StorageSnapshot, &filename)
+            .await
+            .unwrap();
+        assert_eq!(stored_bytes, factory_deps.serialize().unwrap());
+        let reconstructed_factory_deps = store.get(key).await.unwrap();
+        assert_eq!(factory_deps, reconstructed_factory_deps);
+    }
 }
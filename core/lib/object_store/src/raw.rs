@@ -1,9 +1,15 @@
 use std::{error, fmt, sync::Arc};
 
+use anyhow::Context as _;
 use async_trait::async_trait;
 use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
 
-use crate::{file::FileBackedObjectStore, gcs::GoogleCloudStorage, mock::MockStore};
+use crate::{
+    file::FileBackedObjectStore,
+    gcs::GoogleCloudStorage,
+    mock::MockStore,
+    s3::{S3Credentials, S3Storage},
+};
 
 /// Bucket for [`ObjectStore`] in which objects can be placed.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -56,6 +62,8 @@ pub enum ObjectStoreError {
     KeyNotFound(BoxedError),
     /// Object (de)serialization failed.
     Serialization(BoxedError),
+    /// Object is corrupted (e.g., its checksum doesn't match its contents), or is truncated.
+    Corrupted(BoxedError),
     /// Other error has occurred when accessing the store (e.g., a network error).
     Other(BoxedError),
 }
@@ -65,6 +73,7 @@ impl fmt::Display for ObjectStoreError {
         match self {
             Self::KeyNotFound(err) => write!(formatter, "key not found: {err}"),
             Self::Serialization(err) => write!(formatter, "serialization error: {err}"),
+            Self::Corrupted(err) => write!(formatter, "object is corrupted: {err}"),
             Self::Other(err) => write!(formatter, "other error: {err}"),
         }
     }
@@ -73,9 +82,10 @@ impl fmt::Display for ObjectStoreError {
 impl error::Error for ObjectStoreError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
-            Self::KeyNotFound(err) | Self::Serialization(err) | Self::Other(err) => {
-                Some(err.as_ref())
-            }
+            Self::KeyNotFound(err)
+            | Self::Serialization(err)
+            | Self::Corrupted(err)
+            | Self::Other(err) => Some(err.as_ref()),
         }
     }
 }
@@ -116,6 +126,15 @@ pub trait ObjectStore: fmt::Debug + Send + Sync {
     /// Returns an error if removal fails.
     async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError>;
 
+    /// Lists keys in the given bucket starting with the specified `prefix`. Keys are returned
+    /// in the lexicographic order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if listing fails.
+    async fn list_raw(&self, bucket: Bucket, prefix: &str)
+        -> Result<Vec<String>, ObjectStoreError>;
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String;
 }
 
@@ -138,6 +157,14 @@ impl<T: ObjectStore + ?Sized> ObjectStore for Arc<T> {
         (**self).remove_raw(bucket, key).await
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        (**self).list_raw(bucket, prefix).await
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         (**self).storage_prefix_raw(bucket)
     }
@@ -178,14 +205,20 @@ impl ObjectStoreFactory {
     }
 
     /// Creates an [`ObjectStore`].
-    pub async fn create_store(&self) -> Box<dyn ObjectStore> {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store configuration is invalid (e.g., S3 credentials are not specified).
+    pub async fn create_store(&self) -> anyhow::Result<Box<dyn ObjectStore>> {
         match &self.origin {
             ObjectStoreOrigin::Config(config) => Self::create_from_config(config).await,
-            ObjectStoreOrigin::Mock(store) => Box::new(Arc::clone(store)),
+            ObjectStoreOrigin::Mock(store) => Ok(Box::new(Arc::clone(store))),
         }
     }
 
-    async fn create_from_config(config: &ObjectStoreConfig) -> Box<dyn ObjectStore> {
+    async fn create_from_config(
+        config: &ObjectStoreConfig,
+    ) -> anyhow::Result<Box<dyn ObjectStore>> {
         let gcs_credential_file_path = match config.mode {
             ObjectStoreMode::GCSWithCredentialFile => Some(config.gcs_credential_file_path.clone()),
             _ => None,
@@ -201,7 +234,7 @@ impl ObjectStoreFactory {
                     config.max_retries,
                 )
                 .await;
-                Box::new(store)
+                Ok(Box::new(store))
             }
             ObjectStoreMode::GCSWithCredentialFile => {
                 tracing::trace!("Initialized GoogleCloudStorage Object store with credential file");
@@ -211,12 +244,33 @@ impl ObjectStoreFactory {
                     config.max_retries,
                 )
                 .await;
-                Box::new(store)
+                Ok(Box::new(store))
             }
             ObjectStoreMode::FileBacked => {
                 tracing::trace!("Initialized FileBacked Object store");
                 let store = FileBackedObjectStore::new(config.file_backed_base_path.clone()).await;
-                Box::new(store)
+                Ok(Box::new(store))
+            }
+            ObjectStoreMode::S3 => {
+                tracing::trace!("Initialized S3 Object store");
+                let credentials = S3Credentials {
+                    access_key_id: config
+                        .s3_access_key_id
+                        .clone()
+                        .context("S3 access key ID is not specified")?,
+                    secret_access_key: config
+                        .s3_secret_access_key
+                        .clone()
+                        .context("S3 secret access key is not specified")?,
+                };
+                let store = S3Storage::new(
+                    config.s3_endpoint.as_deref(),
+                    config.s3_region.as_deref(),
+                    credentials,
+                    config.bucket_base_url.clone(),
+                    config.max_retries,
+                )?;
+                Ok(Box::new(store))
             }
         }
     }
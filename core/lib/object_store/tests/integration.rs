@@ -16,7 +16,7 @@ async fn prepare_basic_circuits_job_serialization() {
     let snapshot = fs::read("./tests/snapshots/prepare-basic-circuits-job-full.bin")
         .await
         .unwrap();
-    let store = ObjectStoreFactory::mock().create_store().await;
+    let store = ObjectStoreFactory::mock().create_store().await.unwrap();
     store
         .put_raw(
             Bucket::WitnessInput,
@@ -28,9 +28,16 @@ async fn prepare_basic_circuits_job_serialization() {
 
     let job: PrepareBasicCircuitsJob = store.get(L1BatchNumber(1)).await.unwrap();
 
+    assert_eq!(bincode::serialize(&job).unwrap(), snapshot);
+
+    // The stored object is wrapped in a compressed envelope, but should be deserialized to the same job.
     let key = store.put(L1BatchNumber(2), &job).await.unwrap();
-    let serialized_job = store.get_raw(Bucket::WitnessInput, &key).await.unwrap();
-    assert_eq!(serialized_job, snapshot);
+    let stored_job = store.get_raw(Bucket::WitnessInput, &key).await.unwrap();
+    assert_ne!(stored_job, snapshot);
+    assert!(stored_job.len() < snapshot.len());
+    let restored_job: PrepareBasicCircuitsJob = store.get(L1BatchNumber(2)).await.unwrap();
+    assert_eq!(bincode::serialize(&restored_job).unwrap(), snapshot);
+
     assert_job_integrity(
         job.next_enumeration_index(),
         job.into_merkle_paths().collect(),
@@ -19,13 +19,15 @@
 //! | Contracts    | address (20 bytes)              | `Vec<u8>`                       | Contract contents                         |
 //! | Factory deps | hash (32 bytes)                 | `Vec<u8>`                       | Bytecodes for new contracts that a certain contract may deploy. |
 
-use std::{collections::HashMap, convert::TryInto, mem, path::Path, time::Instant};
+use std::{collections::HashMap, convert::TryInto, mem, ops, path::Path, time::Instant};
 
 use itertools::{Either, Itertools};
 use zksync_dal::StorageProcessor;
 use zksync_storage::{db::NamedColumnFamily, RocksDB};
-use zksync_types::{L1BatchNumber, StorageKey, StorageValue, H256, U256};
-use zksync_utils::{h256_to_u256, u256_to_h256};
+use zksync_types::{
+    snapshots::SnapshotRecoveryStatus, L1BatchNumber, StorageKey, StorageValue, H256, U256,
+};
+use zksync_utils::{ceil_div, h256_to_u256, u256_to_h256};
 
 use self::metrics::METRICS;
 use crate::{InMemoryStorage, ReadStorage};
@@ -107,6 +109,8 @@ pub struct RocksdbStorage {
 impl RocksdbStorage {
     const BLOCK_NUMBER_KEY: &'static [u8] = b"block_number";
     const ENUM_INDEX_MIGRATION_CURSOR: &'static [u8] = b"enum_index_migration_cursor";
+    /// Desired number of storage logs loaded from Postgres at once when recovering from a snapshot.
+    const DESIRED_SNAPSHOT_CHUNK_SIZE: u64 = 200_000;
 
     fn is_special_key(key: &[u8]) -> bool {
         key == Self::BLOCK_NUMBER_KEY || key == Self::ENUM_INDEX_MIGRATION_CURSOR
@@ -122,6 +126,16 @@ impl RocksdbStorage {
         }
     }
 
+    /// Opens an existing storage at the provided RocksDB `path` in the read-only mode. The returned storage
+    /// can be used to inspect the cache state; any attempts to update it will panic.
+    pub fn read_only(path: &Path) -> Self {
+        Self {
+            db: RocksDB::read_only(path),
+            pending_patch: InMemoryStorage::default(),
+            enum_index_migration_chunk_size: 100,
+        }
+    }
+
     /// Enables enum indices migration.
     pub fn enable_enum_index_migration(&mut self, chunk_size: usize) {
         self.enum_index_migration_chunk_size = chunk_size;
@@ -146,6 +160,17 @@ impl RocksdbStorage {
         );
 
         let mut current_l1_batch_number = self.l1_batch_number().0;
+        if current_l1_batch_number == 0 {
+            let snapshot_recovery = conn
+                .snapshot_recovery_dal()
+                .get_applied_snapshot_status()
+                .await
+                .unwrap();
+            if let Some(snapshot_recovery) = snapshot_recovery {
+                self.recover_from_snapshot(conn, &snapshot_recovery).await;
+                current_l1_batch_number = self.l1_batch_number().0;
+            }
+        }
         assert!(
             current_l1_batch_number <= latest_l1_batch_number.0 + 1,
             "L1 batch number in state keeper cache ({current_l1_batch_number}) is greater than \
@@ -192,6 +217,108 @@ impl RocksdbStorage {
         }
     }
 
+    /// Recovers the state from the snapshot applied to Postgres. Unlike catching up with Postgres L1 batch by L1 batch,
+    /// storage logs are loaded in chunks by hashed key ranges, so that the entire state isn't loaded into memory.
+    async fn recover_from_snapshot(
+        &mut self,
+        conn: &mut StorageProcessor<'_>,
+        snapshot_recovery: &SnapshotRecoveryStatus,
+    ) {
+        let l1_batch_number = snapshot_recovery.l1_batch_number;
+        let miniblock_number = snapshot_recovery.miniblock_number;
+        assert!(
+            snapshot_recovery.is_finished(),
+            "Snapshot recovery for L1 batch #{l1_batch_number} is not finished in Postgres"
+        );
+        let started_at = Instant::now();
+        let log_count = conn
+            .storage_logs_dal()
+            .count_miniblock_storage_logs(miniblock_number)
+            .await
+            .unwrap();
+        let chunk_count = ceil_div(log_count, Self::DESIRED_SNAPSHOT_CHUNK_SIZE).max(1);
+        tracing::info!(
+            "Recovering secondary storage from snapshot at L1 batch #{l1_batch_number} with {log_count} storage logs \
+             in {chunk_count} chunks"
+        );
+
+        for (i, key_range) in Self::hashed_key_ranges(chunk_count).enumerate() {
+            let entries = conn
+                .storage_logs_dal()
+                .get_tree_entries_for_miniblock(miniblock_number, key_range)
+                .await
+                .unwrap();
+            let entry_count = entries.len();
+
+            let db = self.db.clone();
+            let save_task = tokio::task::spawn_blocking(move || {
+                let mut batch = db.new_write_batch();
+                for entry in entries {
+                    // Tree entry keys are hashed keys interpreted as little-endian numbers.
+                    let mut hashed_key = [0_u8; 32];
+                    entry.key.to_little_endian(&mut hashed_key);
+                    batch.put_cf(
+                        StateKeeperColumnFamily::State,
+                        &hashed_key,
+                        &StateValue::new(entry.value, Some(entry.leaf_index)).serialize(),
+                    );
+                }
+                db.write(batch)
+                    .expect("failed to save state data into rocksdb");
+            });
+            save_task.await.unwrap();
+            tracing::debug!(
+                "Recovered {entry_count} storage logs from chunk {} / {chunk_count}",
+                i + 1
+            );
+        }
+
+        let factory_deps = conn
+            .blocks_dal()
+            .get_l1_batch_factory_deps(l1_batch_number)
+            .await
+            .unwrap();
+        for (hash, bytecode) in factory_deps {
+            self.store_factory_dep(hash, bytecode);
+        }
+        // All recovered entries have enum indices, so there's nothing to migrate.
+        let mut batch = self.db.new_write_batch();
+        batch.put_cf(
+            StateKeeperColumnFamily::State,
+            Self::ENUM_INDEX_MIGRATION_CURSOR,
+            &[],
+        );
+        self.db
+            .write(batch)
+            .expect("failed to save state data into rocksdb");
+        self.save(l1_batch_number + 1).await;
+        tracing::info!(
+            "Recovered secondary storage from snapshot at L1 batch #{l1_batch_number} in {:?}",
+            started_at.elapsed()
+        );
+    }
+
+    /// Splits the hashed key space into `count` contiguous ranges of equal size.
+    fn hashed_key_ranges(count: u64) -> impl Iterator<Item = ops::RangeInclusive<H256>> {
+        assert!(count > 0);
+        let mut stride = U256::MAX / count;
+        let stride_minus_one = if stride < U256::MAX {
+            stride += U256::one();
+            stride - 1
+        } else {
+            stride // `stride` is really 1 << 256 == U256::MAX + 1
+        };
+
+        (0..count).map(move |i| {
+            let start = stride * i;
+            let (mut end, is_overflow) = stride_minus_one.overflowing_add(start);
+            if is_overflow {
+                end = U256::MAX;
+            }
+            u256_to_h256(start)..=u256_to_h256(end)
+        })
+    }
+
     async fn apply_storage_logs(
         &mut self,
         storage_logs: HashMap<StorageKey, H256>,
@@ -654,6 +781,76 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn rocksdb_storage_recovery_from_snapshot() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        prepare_postgres(&mut conn).await;
+        let snapshot_storage_logs = gen_storage_logs(20..40);
+        create_miniblock(&mut conn, MiniblockNumber(1), snapshot_storage_logs.clone()).await;
+        insert_factory_deps(&mut conn, MiniblockNumber(1), 0..2).await;
+        create_l1_batch(&mut conn, L1BatchNumber(1), &snapshot_storage_logs).await;
+        let snapshot_recovery = SnapshotRecoveryStatus {
+            l1_batch_number: L1BatchNumber(1),
+            l1_batch_root_hash: H256::zero(),
+            miniblock_number: MiniblockNumber(1),
+            miniblock_hash: H256::zero(),
+            storage_logs_chunks_processed: vec![true; 3],
+        };
+        conn.snapshot_recovery_dal()
+            .set_applied_snapshot_status(&snapshot_recovery)
+            .await
+            .unwrap();
+
+        let new_storage_logs = gen_storage_logs(50..60);
+        create_miniblock(&mut conn, MiniblockNumber(2), new_storage_logs.clone()).await;
+        create_l1_batch(&mut conn, L1BatchNumber(2), &new_storage_logs).await;
+        let enum_indices: HashMap<_, _> = conn
+            .storage_logs_dedup_dal()
+            .initial_writes_for_batch(L1BatchNumber(1))
+            .await
+            .into_iter()
+            .collect();
+
+        let dir = TempDir::new().expect("cannot create temporary dir for state keeper");
+        let mut storage = RocksdbStorage::new(dir.path());
+        storage.update_from_postgres(&mut conn).await;
+
+        assert_eq!(storage.l1_batch_number(), L1BatchNumber(3));
+        assert_eq!(storage.enum_migration_start_from(), None);
+        for log in &snapshot_storage_logs {
+            let state_value = storage.read_state_value(&log.key).unwrap();
+            assert_eq!(state_value.value, log.value);
+            assert_eq!(
+                state_value.enum_index,
+                Some(enum_indices[&log.key.hashed_key()])
+            );
+        }
+        for log in &new_storage_logs {
+            assert_eq!(storage.read_value(&log.key), log.value);
+        }
+        for i in 0..2 {
+            assert_eq!(
+                storage.load_factory_dep(H256::repeat_byte(i)).unwrap(),
+                [i; 64]
+            );
+        }
+    }
+
+    #[test]
+    fn hashed_key_ranges_cover_entire_key_space() {
+        for count in [1, 3, 8] {
+            let ranges: Vec<_> = RocksdbStorage::hashed_key_ranges(count).collect();
+            assert_eq!(ranges.len() as u64, count);
+            assert_eq!(*ranges[0].start(), H256::zero());
+            assert_eq!(*ranges.last().unwrap().end(), H256::repeat_byte(0xff));
+            for window in ranges.windows(2) {
+                let prev_end = h256_to_u256(*window[0].end());
+                assert_eq!(h256_to_u256(*window[1].start()), prev_end + 1);
+            }
+        }
+    }
+
     #[tokio::test]
     async fn rocksdb_enum_index_migration() {
         let pool = ConnectionPool::test_pool().await;
@@ -274,6 +274,9 @@ pub struct RocksDBOptions {
     /// Timeout to wait for the database to run compaction on stalled writes during startup or
     /// when the corresponding RocksDB error is encountered.
     pub stalled_writes_retries: StalledWritesRetries,
+    /// Opens the database in the read-only mode. Write attempts for such a database will result in an error.
+    /// Column families are not created if they are missing.
+    pub read_only: bool,
 }
 
 impl Default for RocksDBOptions {
@@ -282,6 +285,7 @@ impl Default for RocksDBOptions {
             block_cache_capacity: None,
             large_memtable_capacity: None,
             stalled_writes_retries: StalledWritesRetries::new(Duration::from_secs(10)),
+            read_only: false,
         }
     }
 }
@@ -302,6 +306,15 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
         Self::with_options(path, RocksDBOptions::default())
     }
 
+    /// Opens an existing database in the read-only mode.
+    pub fn read_only(path: &Path) -> Self {
+        let options = RocksDBOptions {
+            read_only: true,
+            ..RocksDBOptions::default()
+        };
+        Self::with_options(path, options)
+    }
+
     pub fn with_options(path: &Path, options: RocksDBOptions) -> Self {
         let caches = RocksDBCaches::new(options.block_cache_capacity);
         let db_options = Self::rocksdb_options(None, None);
@@ -354,7 +367,12 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             ColumnFamilyDescriptor::new(cf_name, cf_options)
         });
 
-        let db = DB::open_cf_descriptors(&db_options, path, cfs).expect("failed to init rocksdb");
+        let db = if options.read_only {
+            DB::open_cf_descriptors_read_only(&db_options, path, cfs, false)
+        } else {
+            DB::open_cf_descriptors(&db_options, path, cfs)
+        };
+        let db = db.expect("failed to init rocksdb");
         let inner = Arc::new(RocksDBInner {
             db,
             db_name: CF::DB_NAME,
@@ -370,7 +388,9 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             path.display()
         );
 
-        inner.wait_for_writes_to_resume(&options.stalled_writes_retries);
+        if !options.read_only {
+            inner.wait_for_writes_to_resume(&options.stalled_writes_retries);
+        }
         Self {
             inner,
             sync_writes: false,
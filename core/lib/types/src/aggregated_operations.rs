@@ -9,7 +9,11 @@ use zkevm_test_harness::{
 };
 use zksync_basic_types::{ethabi::Token, L1BatchNumber};
 
-use crate::{commitment::L1BatchWithMetadata, ProtocolVersionId, U256};
+use crate::{
+    commitment::{L1BatchWithMetadata, PubdataBlob},
+    eth_sender::EthTxBlobSidecar,
+    ProtocolVersionId, U256,
+};
 
 fn l1_batch_range_from_batches(
     batches: &[L1BatchWithMetadata],
@@ -29,20 +33,48 @@ fn l1_batch_range_from_batches(
 pub struct L1BatchCommitOperation {
     pub last_committed_l1_batch: L1BatchWithMetadata,
     pub l1_batches: Vec<L1BatchWithMetadata>,
+    /// Blobs with pubdata for each of `l1_batches`, or `None` if pubdata is posted as calldata.
+    pub pubdata_blobs: Option<Vec<Vec<PubdataBlob>>>,
 }
 
 impl L1BatchCommitOperation {
     pub fn get_eth_tx_args(&self) -> Vec<Token> {
         let stored_batch_info = self.last_committed_l1_batch.l1_header_data();
-        let l1_batches_to_commit = self
-            .l1_batches
-            .iter()
-            .map(L1BatchWithMetadata::l1_commit_data)
-            .collect();
+        let l1_batches_to_commit = if let Some(pubdata_blobs) = &self.pubdata_blobs {
+            assert_eq!(pubdata_blobs.len(), self.l1_batches.len());
+            self.l1_batches
+                .iter()
+                .zip(pubdata_blobs)
+                .map(|(l1_batch, blobs)| l1_batch.l1_commit_data_with_blobs(blobs))
+                .collect()
+        } else {
+            self.l1_batches
+                .iter()
+                .map(L1BatchWithMetadata::l1_commit_data)
+                .collect()
+        };
 
         vec![stored_batch_info, Token::Array(l1_batches_to_commit)]
     }
 
+    /// Returns the blob sidecar for the commit transaction, or `None` if pubdata is posted as calldata.
+    pub fn blob_sidecar(&self) -> Option<EthTxBlobSidecar> {
+        let blobs = self.pubdata_blobs.as_ref()?.iter().flatten();
+        let mut sidecar = EthTxBlobSidecar {
+            blobs: vec![],
+            commitments: vec![],
+            proofs: vec![],
+            versioned_hashes: vec![],
+        };
+        for blob in blobs {
+            sidecar.blobs.push(blob.blob.clone());
+            sidecar.commitments.push(blob.commitment.clone());
+            sidecar.proofs.push(blob.proof.clone());
+            sidecar.versioned_hashes.push(blob.versioned_hash);
+        }
+        Some(sidecar)
+    }
+
     pub fn l1_batch_range(&self) -> ops::RangeInclusive<L1BatchNumber> {
         l1_batch_range_from_batches(&self.l1_batches)
     }
@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
 use chrono::{DateTime, Utc};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use strum::Display;
@@ -6,15 +8,21 @@ use zksync_basic_types::{
     L1BatchNumber,
 };
 use zksync_contracts::BaseSystemContractsHashes;
+use zksync_utils::{h256_to_account_address, h256_to_u256};
 
 pub use crate::transaction_request::{
     Eip712Meta, SerializationTransactionError, TransactionRequest,
 };
 use crate::{
+    get_nonce_key,
     protocol_version::L1VerifierConfig,
-    vm_trace::{Call, CallType},
+    utils::{decompose_full_nonce, storage_key_for_eth_balance},
+    vm_trace::{
+        Call, CallType, TouchedStorageSlot, ValidationCallFrame, ViolatedValidationRuleContext,
+    },
     web3::types::{AccessList, Index, H2048},
-    Address, MiniblockNumber, ProtocolVersionId,
+    Address, MiniblockNumber, ProtocolVersionId, ACCOUNT_CODE_STORAGE_ADDRESS, BOOTLOADER_ADDRESS,
+    L2_ETH_TOKEN_ADDRESS, NONCE_HOLDER_ADDRESS,
 };
 
 pub mod en;
@@ -622,16 +630,22 @@ pub struct ProtocolVersion {
     pub l2_system_upgrade_tx_hash: Option<H256>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum SupportedTracers {
     CallTracer,
+    PrestateTracer,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct CallTracerConfig {
+    #[serde(default)]
     pub only_top_call: bool,
+    /// Makes `prestateTracer` return both pre- and post-execution state of the modified accounts.
+    /// Ignored by other tracers.
+    #[serde(default)]
+    pub diff_mode: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -642,6 +656,279 @@ pub struct TracerConfig {
     pub tracer_config: CallTracerConfig,
 }
 
+/// Options accepted by `debug_traceCall`: tracer configuration optionally extended with state overrides.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceCallConfig {
+    #[serde(flatten)]
+    pub options: TracerConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+}
+
+/// Overrides of an account state applied before executing a call (cf. `stateOverride` in Geth).
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OverrideAccount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U256>,
+    /// Bytecode of the account; must be a valid zkEVM bytecode. Empty bytecode turns the account into an EOA.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// Full replacement of the account storage; slots not mentioned here are considered to be zero.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<HashMap<H256, H256>>,
+    /// Partial replacement of the account storage; slots not mentioned here retain their values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<HashMap<H256, H256>>,
+}
+
+/// State overrides for `eth_call`-like methods keyed by the account address.
+pub type StateOverride = HashMap<Address, OverrideAccount>;
+
+/// Options accepted by `zks_simulateBundle`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleSimulationConfig {
+    /// Whether to return a call trace for each simulated call.
+    #[serde(default)]
+    pub with_call_traces: bool,
+    /// State overrides applied before executing the first call in the bundle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+}
+
+/// Result of a single call from a bundle simulated by `zks_simulateBundle`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleCallResult {
+    /// Returned data for successful calls, or revert data for reverted ones.
+    pub output: Bytes,
+    /// Error message if the call was halted (e.g., ran out of gas or failed validation), or was not executed
+    /// because a preceding call in the bundle was halted.
+    pub error: Option<String>,
+    /// Revert reason if the call was reverted by a contract.
+    pub revert_reason: Option<String>,
+    pub gas_used: U256,
+    /// Events emitted by the call. Block-related fields are not set since the call is not included in a block.
+    pub logs: Vec<Log>,
+    /// Call trace; only present if requested in [`BundleSimulationConfig`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<DebugCall>,
+}
+
+/// Diagnostics of the account validation phase of a transaction. Returned by `zks_debugValidation`
+/// and attached as error data when a transaction is rejected because of a validation failure.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationDiagnostics {
+    /// Human-readable validation error, or `None` if validation succeeded.
+    pub error: Option<String>,
+    /// Details of the violated validation rule, if any.
+    pub violated_rule: Option<ViolatedRuleDiagnostics>,
+    /// Computational gas used during validation.
+    pub computational_gas_used: u32,
+    pub computational_gas_limit: u32,
+}
+
+impl ValidationDiagnostics {
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Details of a violated validation rule.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ViolatedRuleDiagnostics {
+    /// Name of the rule, e.g. `touchedUnallowedStorageSlots`.
+    pub rule: String,
+    /// Contract that violated the rule.
+    pub contract: Option<Address>,
+    /// Storage slot that was accessed, for storage-related rules.
+    pub slot: Option<H256>,
+    /// Opcode executed at the violation point, e.g. `Log(StorageRead)`.
+    pub opcode: String,
+    /// Far call frames at the violation point, from the outermost to the innermost one.
+    pub call_stack: Vec<ValidationCallFrame>,
+}
+
+impl From<ViolatedValidationRuleContext> for ViolatedRuleDiagnostics {
+    fn from(context: ViolatedValidationRuleContext) -> Self {
+        let innermost_contract = context.call_stack.last().map(|frame| frame.this_address);
+        Self {
+            rule: context.rule.name().to_owned(),
+            contract: context.rule.contract().or(innermost_contract),
+            slot: context.rule.slot(),
+            opcode: context.opcode,
+            call_stack: context.call_stack,
+        }
+    }
+}
+
+/// Account state reported by `prestateTracer`. Only the fields touched during execution are present.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestateAccount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployment_nonce: Option<U256>,
+    /// Versioned hash of the account bytecode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_hash: Option<H256>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<H256, H256>,
+}
+
+/// Output of `prestateTracer`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+    /// State of the modified accounts before and after execution (`diffMode: true`).
+    Diff {
+        pre: BTreeMap<Address, PrestateAccount>,
+        post: BTreeMap<Address, PrestateAccount>,
+    },
+    /// State of all touched accounts before execution.
+    Prestate(BTreeMap<Address, PrestateAccount>),
+}
+
+impl PrestateTrace {
+    /// Groups touched storage slots by account. Balance and nonce slots are keyed by hashes in the system contracts,
+    /// so they can only be attributed to an account if its address is known, i.e., if it is one of `accounts`
+    /// or the address of a touched slot. Otherwise, such slots are reported as the storage of the system contract.
+    pub fn new(
+        slots: &[TouchedStorageSlot],
+        accounts: impl IntoIterator<Item = Address>,
+        diff_mode: bool,
+    ) -> Self {
+        let mut known_accounts: HashSet<_> = accounts.into_iter().collect();
+        known_accounts.insert(BOOTLOADER_ADDRESS);
+        for slot in slots {
+            known_accounts.insert(*slot.key.address());
+            if *slot.key.address() == ACCOUNT_CODE_STORAGE_ADDRESS {
+                known_accounts.insert(h256_to_account_address(slot.key.key()));
+            }
+        }
+        let balance_keys: HashMap<_, _> = known_accounts
+            .iter()
+            .map(|&account| (*storage_key_for_eth_balance(&account).key(), account))
+            .collect();
+        let nonce_keys: HashMap<_, _> = known_accounts
+            .iter()
+            .map(|&account| (*get_nonce_key(&account).key(), account))
+            .collect();
+        let group = |slots: &[TouchedStorageSlot], get_value: fn(&TouchedStorageSlot) -> H256| {
+            Self::group_by_account(slots, &balance_keys, &nonce_keys, get_value)
+        };
+
+        if diff_mode {
+            let modified_slots: Vec<_> = slots
+                .iter()
+                .filter(|slot| slot.is_modified())
+                .copied()
+                .collect();
+            Self::Diff {
+                pre: group(&modified_slots, |slot| slot.pre_value),
+                post: group(&modified_slots, |slot| slot.post_value),
+            }
+        } else {
+            Self::Prestate(group(slots, |slot| slot.pre_value))
+        }
+    }
+
+    fn group_by_account(
+        slots: &[TouchedStorageSlot],
+        balance_keys: &HashMap<H256, Address>,
+        nonce_keys: &HashMap<H256, Address>,
+        get_value: fn(&TouchedStorageSlot) -> H256,
+    ) -> BTreeMap<Address, PrestateAccount> {
+        let mut accounts = BTreeMap::<_, PrestateAccount>::new();
+        for slot in slots {
+            let (contract, key) = (*slot.key.address(), *slot.key.key());
+            let value = get_value(slot);
+
+            if contract == L2_ETH_TOKEN_ADDRESS {
+                if let Some(&account) = balance_keys.get(&key) {
+                    accounts.entry(account).or_default().balance = Some(h256_to_u256(value));
+                    continue;
+                }
+            } else if contract == NONCE_HOLDER_ADDRESS {
+                if let Some(&account) = nonce_keys.get(&key) {
+                    let (nonce, deployment_nonce) = decompose_full_nonce(h256_to_u256(value));
+                    let account = accounts.entry(account).or_default();
+                    account.nonce = Some(nonce);
+                    account.deployment_nonce = Some(deployment_nonce);
+                    continue;
+                }
+            } else if contract == ACCOUNT_CODE_STORAGE_ADDRESS {
+                let account = h256_to_account_address(&key);
+                accounts.entry(account).or_default().code_hash = Some(value);
+                continue;
+            }
+            accounts
+                .entry(contract)
+                .or_default()
+                .storage
+                .insert(key, value);
+        }
+        accounts
+    }
+}
+
+/// Result of tracing a single transaction or call; its shape depends on the requested tracer.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum DebugTrace {
+    Call(DebugCall),
+    Prestate(PrestateTrace),
+}
+
+impl From<DebugCall> for DebugTrace {
+    fn from(call: DebugCall) -> Self {
+        Self::Call(call)
+    }
+}
+
+/// Trace of a transaction in a block. Like [`ResultDebugCall`], but the trace shape depends on the requested tracer.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultDebugTrace {
+    pub result: DebugTrace,
+}
+
+/// Trace of a transaction obtained by re-executing the L1 batch it belongs to.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchTransactionTrace {
+    pub tx_hash: H256,
+    pub miniblock_number: MiniblockNumber,
+    pub result: DebugCall,
+    /// Gas refunded to the transaction initiator by the bootloader.
+    pub gas_refunded: u32,
+    /// Refund suggested by the operator; may differ from `gas_refunded` computed by the bootloader.
+    pub operator_suggested_refund: u32,
+    /// Amount of pubdata (in bytes) published by the transaction.
+    pub pubdata_published: u32,
+}
+
+/// Result of re-executing all transactions in an L1 batch.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchTrace {
+    pub l1_batch_number: L1BatchNumber,
+    pub transactions: Vec<L1BatchTransactionTrace>,
+    /// Gas used by the bootloader after the last transaction to finalize the batch.
+    pub block_tip_gas_used: u32,
+    /// Amount of pubdata (in bytes) published by the bootloader when finalizing the batch.
+    pub block_tip_pubdata_published: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum BlockStatus {
@@ -679,6 +966,31 @@ pub struct BlockDetails {
     pub protocol_version: Option<ProtocolVersionId>,
 }
 
+/// Lifecycle stage of an L1 batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum L1BatchStatus {
+    Sealed,
+    Committed,
+    Proven,
+    Executed,
+}
+
+impl L1BatchStatus {
+    pub const ALL: [Self; 4] = [Self::Sealed, Self::Committed, Self::Proven, Self::Executed];
+}
+
+/// Notification about an L1 batch reaching a certain lifecycle stage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchStatusUpdate {
+    pub l1_batch_number: L1BatchNumber,
+    pub status: L1BatchStatus,
+    /// Hash of the confirmed L1 transaction that has moved the batch to this stage.
+    /// Always `None` for the `sealed` status.
+    pub l1_tx_hash: Option<H256>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct L1BatchDetails {
@@ -702,3 +1014,210 @@ pub struct Proof {
     pub address: Address,
     pub storage_proof: Vec<StorageProof>,
 }
+
+/// Storage slot proof in the `eth_getProof` format (EIP-1186).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthStorageProof {
+    pub key: H256,
+    pub value: U256,
+    /// Root-to-leaf Merkle path. For empty slots, the path proves that the slot is not present in the tree.
+    pub proof: Vec<H256>,
+}
+
+/// Account proof in the `eth_getProof` format (EIP-1186).
+///
+/// zkSync uses a single Merkle tree for the entire state, so account data is stored in slots of system contracts
+/// (`AccountCodeStorage`, `L2EthToken` and `NonceHolder`) and is proven like any other storage slot.
+/// Correspondingly, `storage_hash` is the root hash of the tree rather than a per-account hash.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthProof {
+    pub address: Address,
+    /// Merkle path for the account code hash.
+    pub account_proof: Vec<H256>,
+    pub balance: U256,
+    /// Merkle path for the account balance (zkSync-specific).
+    pub balance_proof: Vec<H256>,
+    pub nonce: U256,
+    /// Merkle path for the account nonce (zkSync-specific).
+    pub nonce_proof: Vec<H256>,
+    pub code_hash: H256,
+    pub storage_hash: H256,
+    pub storage_proof: Vec<EthStorageProof>,
+    /// L1 batch for which the proofs are generated (zkSync-specific).
+    pub l1_batch_number: L1BatchNumber,
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_utils::u256_to_h256;
+
+    use super::*;
+    use crate::{get_code_key, AccountTreeId, StorageKey};
+
+    #[test]
+    fn prestate_trace_groups_slots_by_account() {
+        let account = Address::repeat_byte(1);
+        let contract = Address::repeat_byte(2);
+        let slots = [
+            TouchedStorageSlot {
+                key: storage_key_for_eth_balance(&account),
+                pre_value: u256_to_h256(100.into()),
+                post_value: u256_to_h256(90.into()),
+            },
+            TouchedStorageSlot {
+                key: get_nonce_key(&account),
+                pre_value: u256_to_h256(3.into()),
+                post_value: u256_to_h256(4.into()),
+            },
+            TouchedStorageSlot {
+                key: get_code_key(&contract),
+                pre_value: H256::repeat_byte(0xc0),
+                post_value: H256::repeat_byte(0xc0),
+            },
+            TouchedStorageSlot {
+                key: StorageKey::new(AccountTreeId::new(contract), H256::zero()),
+                pre_value: H256::zero(),
+                post_value: H256::repeat_byte(0xff),
+            },
+        ];
+
+        let PrestateTrace::Prestate(prestate) = PrestateTrace::new(&slots, [account], false) else {
+            panic!("unexpected trace shape");
+        };
+        assert_eq!(prestate.len(), 2);
+        assert_eq!(prestate[&account].balance, Some(100.into()));
+        assert_eq!(prestate[&account].nonce, Some(3.into()));
+        assert_eq!(prestate[&account].deployment_nonce, Some(0.into()));
+        assert_eq!(prestate[&contract].code_hash, Some(H256::repeat_byte(0xc0)));
+        assert_eq!(prestate[&contract].storage[&H256::zero()], H256::zero());
+
+        let PrestateTrace::Diff { pre, post } = PrestateTrace::new(&slots, [account], true) else {
+            panic!("unexpected trace shape");
+        };
+        assert_eq!(pre[&account].balance, Some(100.into()));
+        assert_eq!(post[&account].balance, Some(90.into()));
+        assert_eq!(post[&account].nonce, Some(4.into()));
+        // Code hash is not modified, so it should not be present in the diff.
+        assert_eq!(pre[&contract].code_hash, None);
+        assert_eq!(
+            post[&contract].storage[&H256::zero()],
+            H256::repeat_byte(0xff)
+        );
+    }
+
+    #[test]
+    fn deserializing_state_override() {
+        let json = serde_json::json!({
+            "0x0101010101010101010101010101010101010101": {
+                "balance": "0x64",
+                "nonce": "0x3",
+                "stateDiff": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000001":
+                        "0x00000000000000000000000000000000000000000000000000000000000000ff",
+                },
+            },
+        });
+        let overrides: StateOverride = serde_json::from_value(json).unwrap();
+        let account = &overrides[&Address::repeat_byte(1)];
+        assert_eq!(account.balance, Some(100.into()));
+        assert_eq!(account.nonce, Some(3.into()));
+        assert_eq!(account.code, None);
+        assert_eq!(account.state, None);
+        let state_diff = account.state_diff.as_ref().unwrap();
+        assert_eq!(
+            state_diff[&H256::from_low_u64_be(1)],
+            H256::from_low_u64_be(0xff)
+        );
+
+        let json = serde_json::json!({
+            "tracer": "callTracer",
+            "stateOverrides": {
+                "0x0101010101010101010101010101010101010101": { "code": "0x" },
+            },
+        });
+        let config: TraceCallConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config.options.tracer, SupportedTracers::CallTracer);
+        let overrides = config.state_overrides.unwrap();
+        assert_eq!(
+            overrides[&Address::repeat_byte(1)].code,
+            Some(Bytes::default())
+        );
+    }
+
+    #[test]
+    fn serializing_prestate_trace() {
+        let account = Address::repeat_byte(1);
+        let slots = [TouchedStorageSlot {
+            key: storage_key_for_eth_balance(&account),
+            pre_value: u256_to_h256(1.into()),
+            post_value: u256_to_h256(2.into()),
+        }];
+
+        let trace = DebugTrace::Prestate(PrestateTrace::new(&slots, [account], true));
+        let json = serde_json::to_value(&trace).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "pre": { "0x0101010101010101010101010101010101010101": { "balance": "0x1" } },
+                "post": { "0x0101010101010101010101010101010101010101": { "balance": "0x2" } },
+            })
+        );
+        let restored: PrestateTrace = serde_json::from_value(json).unwrap();
+        assert!(matches!(restored, PrestateTrace::Diff { .. }));
+    }
+
+    #[test]
+    fn converting_violated_rule_context() {
+        use crate::vm_trace::ViolatedValidationRule;
+
+        let account = Address::repeat_byte(1);
+        let contract = Address::repeat_byte(2);
+        let call_stack = vec![
+            ValidationCallFrame {
+                this_address: BOOTLOADER_ADDRESS,
+                code_address: BOOTLOADER_ADDRESS,
+                msg_sender: Address::zero(),
+            },
+            ValidationCallFrame {
+                this_address: account,
+                code_address: account,
+                msg_sender: BOOTLOADER_ADDRESS,
+            },
+            ValidationCallFrame {
+                this_address: contract,
+                code_address: contract,
+                msg_sender: account,
+            },
+        ];
+        let context = ViolatedValidationRuleContext {
+            rule: ViolatedValidationRule::TouchedUnallowedStorageSlots(contract, 5.into()),
+            opcode: "Log(StorageRead)".to_owned(),
+            call_stack: call_stack.clone(),
+            computational_gas_used: 1_000,
+        };
+        let diagnostics = ViolatedRuleDiagnostics::from(context);
+        assert_eq!(diagnostics.rule, "touchedUnallowedStorageSlots");
+        assert_eq!(diagnostics.contract, Some(contract));
+        assert_eq!(diagnostics.slot, Some(H256::from_low_u64_be(5)));
+        assert_eq!(diagnostics.call_stack, call_stack);
+
+        let context = ViolatedValidationRuleContext {
+            rule: ViolatedValidationRule::TouchedUnallowedContext,
+            opcode: "Context(Meta)".to_owned(),
+            call_stack: call_stack[..2].to_vec(),
+            computational_gas_used: 1_000,
+        };
+        let diagnostics = ViolatedRuleDiagnostics::from(context);
+        assert_eq!(diagnostics.contract, Some(account));
+        assert_eq!(diagnostics.slot, None);
+
+        let json = serde_json::to_value(&diagnostics).unwrap();
+        assert_eq!(json["rule"], "touchedUnallowedContext");
+        assert_eq!(
+            json["callStack"][1]["msgSender"],
+            serde_json::json!(BOOTLOADER_ADDRESS)
+        );
+    }
+}
@@ -6,8 +6,13 @@
 //! required for the rollup to execute L1 batches, it's needed for the proof generation and the Ethereum
 //! transactions, thus the calculations are done separately and asynchronously.
 
-use std::{collections::HashMap, convert::TryFrom};
+use std::{
+    collections::HashMap,
+    convert::{TryFrom, TryInto},
+    fmt,
+};
 
+use anyhow::Context;
 use serde::{Deserialize, Serialize};
 use zksync_mini_merkle_tree::MiniMerkleTree;
 use zksync_system_constants::{
@@ -21,10 +26,10 @@ use crate::{
     l2_to_l1_log::{L2ToL1Log, SystemL2ToL1Log, UserL2ToL1Log},
     web3::signing::keccak256,
     writes::{
-        compress_state_diffs, InitialStorageWrite, RepeatedStorageWrite, StateDiffRecord,
-        PADDED_ENCODED_STORAGE_DIFF_LEN_BYTES,
+        compress_state_diffs, decompress_state_diffs, CompressedStateDiff, InitialStorageWrite,
+        RepeatedStorageWrite, StateDiffRecord, PADDED_ENCODED_STORAGE_DIFF_LEN_BYTES,
     },
-    H256, KNOWN_CODES_STORAGE_ADDRESS, U256,
+    L1BatchNumber, H256, KNOWN_CODES_STORAGE_ADDRESS, U256,
 };
 
 /// Type that can be serialized for commitment.
@@ -87,6 +92,28 @@ pub struct L1BatchMetadata {
     pub state_diffs_compressed: Vec<u8>,
 }
 
+/// Marker of pubdata posted in EIP-4844 blobs, used as the first byte of the pubdata commitments
+/// in the L1 batch commit data.
+pub const PUBDATA_SOURCE_BLOBS: u8 = 1;
+
+/// EIP-4844 blob carrying (a part of) pubdata of an L1 batch together with its KZG commitment and proof.
+#[derive(Clone, PartialEq)]
+pub struct PubdataBlob {
+    pub blob: Vec<u8>,
+    pub commitment: Vec<u8>,
+    pub proof: Vec<u8>,
+    pub versioned_hash: H256,
+}
+
+impl fmt::Debug for PubdataBlob {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("PubdataBlob")
+            .field("versioned_hash", &self.versioned_hash)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct L1BatchWithMetadata {
     pub header: L1BatchHeader,
@@ -182,75 +209,282 @@ impl L1BatchWithMetadata {
                 ),
             ])
         } else {
-            Token::Tuple(vec![
-                Token::Uint(U256::from(self.header.number.0)),
-                Token::Uint(U256::from(self.header.timestamp)),
-                Token::Uint(U256::from(self.metadata.rollup_last_leaf_index)),
-                Token::FixedBytes(self.metadata.merkle_root_hash.as_bytes().to_vec()),
-                Token::Uint(U256::from(self.header.l1_tx_count)),
-                Token::FixedBytes(
-                    self.header
-                        .priority_ops_onchain_data_hash()
-                        .as_bytes()
-                        .to_vec(),
-                ),
-                Token::FixedBytes(
-                    self.metadata
-                        .bootloader_initial_content_commitment
-                        .unwrap()
-                        .as_bytes()
-                        .to_vec(),
-                ),
-                Token::FixedBytes(
-                    self.metadata
-                        .events_queue_commitment
-                        .unwrap()
-                        .as_bytes()
-                        .to_vec(),
-                ),
-                Token::Bytes(self.metadata.l2_l1_messages_compressed.clone()),
-                Token::Bytes(self.construct_pubdata()),
-            ])
+            self.post_boojum_l1_commit_data(self.construct_pubdata())
+        }
+    }
+
+    /// Same as [`Self::l1_commit_data()`], but for an L1 batch with pubdata posted in EIP-4844 blobs.
+    /// Instead of pubdata, the commit data contains [`PUBDATA_SOURCE_BLOBS`] followed by the versioned hash,
+    /// KZG commitment and proof of each blob in the order the blobs are attached to the transaction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the L1 batch is pre-boojum; pre-boojum L1 batches cannot be committed using blobs.
+    pub fn l1_commit_data_with_blobs(&self, blobs: &[PubdataBlob]) -> Token {
+        assert!(
+            !self.header.protocol_version.unwrap().is_pre_boojum(),
+            "Pre-boojum L1 batch #{} cannot be committed using blobs",
+            self.header.number
+        );
+        let mut pubdata_commitments = vec![PUBDATA_SOURCE_BLOBS];
+        for blob in blobs {
+            pubdata_commitments.extend_from_slice(blob.versioned_hash.as_bytes());
+            pubdata_commitments.extend_from_slice(&blob.commitment);
+            pubdata_commitments.extend_from_slice(&blob.proof);
         }
+        self.post_boojum_l1_commit_data(pubdata_commitments)
+    }
+
+    fn post_boojum_l1_commit_data(&self, pubdata: Vec<u8>) -> Token {
+        Token::Tuple(vec![
+            Token::Uint(U256::from(self.header.number.0)),
+            Token::Uint(U256::from(self.header.timestamp)),
+            Token::Uint(U256::from(self.metadata.rollup_last_leaf_index)),
+            Token::FixedBytes(self.metadata.merkle_root_hash.as_bytes().to_vec()),
+            Token::Uint(U256::from(self.header.l1_tx_count)),
+            Token::FixedBytes(
+                self.header
+                    .priority_ops_onchain_data_hash()
+                    .as_bytes()
+                    .to_vec(),
+            ),
+            Token::FixedBytes(
+                self.metadata
+                    .bootloader_initial_content_commitment
+                    .unwrap()
+                    .as_bytes()
+                    .to_vec(),
+            ),
+            Token::FixedBytes(
+                self.metadata
+                    .events_queue_commitment
+                    .unwrap()
+                    .as_bytes()
+                    .to_vec(),
+            ),
+            Token::Bytes(self.metadata.l2_l1_messages_compressed.clone()),
+            Token::Bytes(pubdata),
+        ])
     }
 
     pub fn l1_commit_data_size(&self) -> usize {
         crate::ethabi::encode(&[Token::Array(vec![self.l1_commit_data()])]).len()
     }
 
+    /// Returns the size of commit data (see [`Self::l1_commit_data_with_blobs()`]) for an L1 batch
+    /// with pubdata posted in the specified number of blobs.
+    pub fn l1_commit_data_size_with_blobs(&self, blob_count: usize) -> usize {
+        // Versioned hash, KZG commitment and KZG proof for each blob.
+        const BLOB_COMMITMENT_DATA_SIZE: usize = 32 + 48 + 48;
+
+        let pubdata_commitments = vec![0; 1 + blob_count * BLOB_COMMITMENT_DATA_SIZE];
+        let commit_data = self.post_boojum_l1_commit_data(pubdata_commitments);
+        crate::ethabi::encode(&[Token::Array(vec![commit_data])]).len()
+    }
+
     /// Packs all pubdata needed for batch commitment in boojum into one bytes array. The packing contains the
     /// following: logs, messages, bytecodes, and compressed state diffs.
-    /// This data is currently part of calldata but will be submitted as part of the blob section post EIP-4844.
+    /// This data is either a part of calldata, or is packed into EIP-4844 blobs (see [`Self::l1_commit_data_with_blobs()`]).
     pub fn construct_pubdata(&self) -> Vec<u8> {
+        let factory_deps: Vec<_> = self.factory_deps.iter().map(Vec::as_slice).collect();
+        Self::pubdata(
+            &self.header,
+            &factory_deps,
+            &self.metadata.state_diffs_compressed,
+        )
+    }
+
+    /// Packs pubdata of an L1 batch. Unlike [`Self::construct_pubdata()`], doesn't require L1 batch metadata
+    /// to be computed; `factory_deps` must be in the order of their appearance in the L1 batch.
+    pub fn pubdata(
+        header: &L1BatchHeader,
+        factory_deps: &[&[u8]],
+        state_diffs_compressed: &[u8],
+    ) -> Vec<u8> {
         let mut res: Vec<u8> = vec![];
 
         // Process and Pack Logs
-        res.extend((self.header.l2_to_l1_logs.len() as u32).to_be_bytes());
-        for l2_to_l1_log in &self.header.l2_to_l1_logs {
+        res.extend((header.l2_to_l1_logs.len() as u32).to_be_bytes());
+        for l2_to_l1_log in &header.l2_to_l1_logs {
             res.extend(l2_to_l1_log.0.to_bytes());
         }
 
         // Process and Pack Msgs
-        res.extend((self.header.l2_to_l1_messages.len() as u32).to_be_bytes());
-        for msg in &self.header.l2_to_l1_messages {
+        res.extend((header.l2_to_l1_messages.len() as u32).to_be_bytes());
+        for msg in &header.l2_to_l1_messages {
             res.extend((msg.len() as u32).to_be_bytes());
             res.extend(msg);
         }
 
         // Process and Pack Bytecodes
-        res.extend((self.factory_deps.len() as u32).to_be_bytes());
-        for bytecode in &self.factory_deps {
+        res.extend((factory_deps.len() as u32).to_be_bytes());
+        for bytecode in factory_deps {
             res.extend((bytecode.len() as u32).to_be_bytes());
-            res.extend(bytecode);
+            res.extend(*bytecode);
         }
 
         // Extend with Compressed StateDiffs
-        res.extend(&self.metadata.state_diffs_compressed);
+        res.extend(state_diffs_compressed);
 
         res
     }
 }
 
+/// Post-boojum L1 batch commit data as published in the `commitBatches` calldata.
+/// The inverse of [`L1BatchWithMetadata::l1_commit_data()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1BatchCommitData {
+    pub number: L1BatchNumber,
+    pub timestamp: u64,
+    pub rollup_last_leaf_index: u64,
+    pub merkle_root_hash: H256,
+    pub l1_tx_count: u16,
+    pub priority_operations_hash: H256,
+    pub bootloader_initial_content_commitment: H256,
+    pub events_queue_commitment: H256,
+    pub l2_l1_messages_compressed: Vec<u8>,
+    /// Either the pubdata of the batch, or the pubdata commitments if it was posted in EIP-4844 blobs.
+    pub pubdata: Vec<u8>,
+}
+
+impl L1BatchCommitData {
+    /// Parses commit data from a single element of the `commitBatches` calldata.
+    pub fn from_token(token: Token) -> anyhow::Result<Self> {
+        let Token::Tuple(tokens) = token else {
+            anyhow::bail!("commit data is not a tuple: {token:?}");
+        };
+        anyhow::ensure!(
+            tokens.len() == 10,
+            "unexpected number of tokens in commit data: {}",
+            tokens.len()
+        );
+        let mut tokens = tokens.into_iter();
+        let mut next = || tokens.next().unwrap();
+
+        let uint = |token: Token, name: &str| -> anyhow::Result<u64> {
+            let value = token
+                .into_uint()
+                .ok_or_else(|| anyhow::anyhow!("`{name}` is not a uint"))?;
+            anyhow::ensure!(value <= U256::from(u64::MAX), "`{name}` overflows u64");
+            Ok(value.as_u64())
+        };
+        let hash = |token: Token, name: &str| match token {
+            Token::FixedBytes(bytes) if bytes.len() == 32 => Ok(H256::from_slice(&bytes)),
+            _ => Err(anyhow::anyhow!("`{name}` is not a 32-byte hash")),
+        };
+        let bytes = |token: Token, name: &str| {
+            token
+                .into_bytes()
+                .ok_or_else(|| anyhow::anyhow!("`{name}` is not bytes"))
+        };
+
+        let number = uint(next(), "batchNumber")?;
+        let timestamp = uint(next(), "timestamp")?;
+        let rollup_last_leaf_index = uint(next(), "indexRepeatedStorageChanges")?;
+        let merkle_root_hash = hash(next(), "newStateRoot")?;
+        let l1_tx_count = uint(next(), "numberOfLayer1Txs")?;
+        Ok(Self {
+            number: L1BatchNumber(u32::try_from(number).context("batchNumber")?),
+            timestamp,
+            rollup_last_leaf_index,
+            merkle_root_hash,
+            l1_tx_count: u16::try_from(l1_tx_count).context("numberOfLayer1Txs")?,
+            priority_operations_hash: hash(next(), "priorityOperationsHash")?,
+            bootloader_initial_content_commitment: hash(
+                next(),
+                "bootloaderHeapInitialContentsHash",
+            )?,
+            events_queue_commitment: hash(next(), "eventsQueueStateHash")?,
+            l2_l1_messages_compressed: bytes(next(), "systemLogs")?,
+            pubdata: bytes(next(), "pubdata")?,
+        })
+    }
+
+    /// Checks whether the pubdata of this batch was posted in EIP-4844 blobs rather than in calldata.
+    pub fn is_pubdata_in_blobs(&self) -> bool {
+        self.pubdata.first() == Some(&PUBDATA_SOURCE_BLOBS)
+    }
+}
+
+/// Pubdata of an L1 batch published in calldata. The inverse of [`L1BatchWithMetadata::construct_pubdata()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1BatchPubdata {
+    pub l2_to_l1_logs: Vec<UserL2ToL1Log>,
+    pub l2_to_l1_messages: Vec<Vec<u8>>,
+    pub published_bytecodes: Vec<Vec<u8>>,
+    pub state_diffs: Vec<CompressedStateDiff>,
+}
+
+impl L1BatchPubdata {
+    pub fn parse(pubdata: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = PubdataReader(pubdata);
+
+        let logs_count = reader.read_u32().context("logs count")?;
+        let l2_to_l1_logs = (0..logs_count)
+            .map(|i| {
+                let bytes = reader
+                    .read_bytes(L2ToL1Log::SERIALIZED_SIZE)
+                    .with_context(|| format!("log #{i}"))?;
+                Ok(UserL2ToL1Log(L2ToL1Log::from_slice(bytes)))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        let l2_to_l1_messages = reader
+            .read_length_prefixed_items()
+            .context("L2-to-L1 messages")?;
+        let published_bytecodes = reader
+            .read_length_prefixed_items()
+            .context("published bytecodes")?;
+
+        let (state_diffs, consumed_len) =
+            decompress_state_diffs(reader.0).context("state diffs")?;
+        anyhow::ensure!(
+            consumed_len == reader.0.len(),
+            "{} unexpected trailing bytes in pubdata",
+            reader.0.len() - consumed_len
+        );
+
+        Ok(Self {
+            l2_to_l1_logs,
+            l2_to_l1_messages,
+            published_bytecodes,
+            state_diffs,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct PubdataReader<'a>(&'a [u8]);
+
+impl<'a> PubdataReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        anyhow::ensure!(self.0.len() >= len, "pubdata is truncated");
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self) -> anyhow::Result<u32> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn read_length_prefixed_items(&mut self) -> anyhow::Result<Vec<Vec<u8>>> {
+        let count = self.read_u32().context("items count")?;
+        (0..count)
+            .map(|i| {
+                let len = self
+                    .read_u32()
+                    .with_context(|| format!("length of item #{i}"))?;
+                let bytes = self
+                    .read_bytes(len as usize)
+                    .with_context(|| format!("item #{i}"))?;
+                Ok(bytes.to_vec())
+            })
+            .collect()
+    }
+}
+
 impl SerializeCommitment for L2ToL1Log {
     const SERIALIZED_SIZE: usize = 88;
 
@@ -340,6 +574,8 @@ struct L1BatchAuxiliaryOutput {
     bootloader_heap_hash: H256,
     #[allow(dead_code)]
     events_state_queue_hash: H256,
+    /// Versioned hashes of EIP-4844 blobs with the L1 batch pubdata; empty if pubdata is posted as calldata.
+    blob_versioned_hashes: Vec<H256>,
     is_pre_boojum: bool,
 }
 
@@ -353,8 +589,13 @@ impl L1BatchAuxiliaryOutput {
         state_diffs: Vec<StateDiffRecord>,
         bootloader_heap_hash: H256,
         events_state_queue_hash: H256,
+        blob_versioned_hashes: Vec<H256>,
         is_pre_boojum: bool,
     ) -> Self {
+        assert!(
+            blob_versioned_hashes.is_empty() || !is_pre_boojum,
+            "Pre-boojum L1 batches cannot post pubdata in blobs"
+        );
         let state_diff_hash_from_logs = system_logs.iter().find_map(|log| {
             if log.0.key == u256_to_h256(STATE_DIFF_HASH_KEY.into()) {
                 Some(log.0.value)
@@ -452,14 +693,15 @@ impl L1BatchAuxiliaryOutput {
 
             bootloader_heap_hash,
             events_state_queue_hash,
+            blob_versioned_hashes,
             is_pre_boojum,
         }
     }
 
     pub fn to_bytes(&self) -> Vec<u8> {
-        // 4 H256 values
-        const SERIALIZED_SIZE: usize = 128;
-        let mut result = Vec::with_capacity(SERIALIZED_SIZE);
+        // 4 H256 values + versioned hashes of blobs (if any)
+        let serialized_size = 128 + 32 * self.blob_versioned_hashes.len();
+        let mut result = Vec::with_capacity(serialized_size);
 
         if self.is_pre_boojum {
             result.extend(self.l2_l1_logs_merkle_root.as_bytes());
@@ -471,6 +713,9 @@ impl L1BatchAuxiliaryOutput {
             result.extend(self.state_diffs_hash.as_bytes());
             result.extend(self.bootloader_heap_hash.as_bytes());
             result.extend(self.events_state_queue_hash.as_bytes());
+            for versioned_hash in &self.blob_versioned_hashes {
+                result.extend(versioned_hash.as_bytes());
+            }
         }
         result
     }
@@ -565,6 +810,7 @@ impl L1BatchCommitment {
         state_diffs: Vec<StateDiffRecord>,
         bootloader_heap_hash: H256,
         events_state_queue_hash: H256,
+        blob_versioned_hashes: Vec<H256>,
         is_pre_boojum: bool,
     ) -> Self {
         let meta_parameters = L1BatchMetaParameters {
@@ -595,6 +841,7 @@ impl L1BatchCommitment {
                 state_diffs,
                 bootloader_heap_hash,
                 events_state_queue_hash,
+                blob_versioned_hashes,
                 is_pre_boojum,
             ),
             meta_parameters,
@@ -665,14 +912,21 @@ mod tests {
     use serde::{Deserialize, Serialize};
     use serde_with::serde_as;
 
+    use zksync_contracts::BaseSystemContractsHashes;
+
     use crate::{
+        block::L1BatchHeader,
         commitment::{
-            L1BatchAuxiliaryOutput, L1BatchCommitment, L1BatchMetaParameters,
-            L1BatchPassThroughData,
+            L1BatchAuxiliaryOutput, L1BatchCommitData, L1BatchCommitment, L1BatchMetaParameters,
+            L1BatchMetadata, L1BatchPassThroughData, L1BatchPubdata, L1BatchWithMetadata,
+            PubdataBlob,
         },
         l2_to_l1_log::{L2ToL1Log, UserL2ToL1Log},
-        writes::{InitialStorageWrite, RepeatedStorageWrite},
-        H256, U256,
+        writes::{
+            compress_state_diffs, InitialStorageWrite, RepeatedStorageWrite, StateDiffKey,
+            StateDiffRecord,
+        },
+        Address, L1BatchNumber, ProtocolVersionId, H256, U256,
     };
 
     #[serde_as]
@@ -754,6 +1008,7 @@ mod tests {
             vec![],
             H256::zero(),
             H256::zero(),
+            vec![],
             false,
         );
 
@@ -835,4 +1090,150 @@ mod tests {
             commitment_test.expected_outputs.commitment_hash
         );
     }
+
+    #[test]
+    fn blob_versioned_hashes_are_committed_to() {
+        let aux_output = |blob_versioned_hashes| {
+            L1BatchAuxiliaryOutput::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                H256::repeat_byte(1),
+                H256::repeat_byte(2),
+                blob_versioned_hashes,
+                false,
+            )
+        };
+
+        let calldata_output = aux_output(vec![]);
+        assert_eq!(calldata_output.to_bytes().len(), 128);
+        let versioned_hashes = vec![H256::repeat_byte(3), H256::repeat_byte(4)];
+        let blobs_output = aux_output(versioned_hashes.clone());
+        let serialized = blobs_output.to_bytes();
+        assert_eq!(serialized.len(), 192);
+        assert_eq!(serialized[..128], calldata_output.to_bytes());
+        assert_eq!(&serialized[128..160], versioned_hashes[0].as_bytes());
+        assert_eq!(&serialized[160..], versioned_hashes[1].as_bytes());
+        assert_ne!(blobs_output.hash(), calldata_output.hash());
+    }
+
+    #[test]
+    fn parsing_commit_data_and_pubdata() {
+        let mut header = L1BatchHeader::new(
+            L1BatchNumber(5),
+            100,
+            Address::zero(),
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+        );
+        header.l1_tx_count = 2;
+        let user_log = UserL2ToL1Log(L2ToL1Log {
+            shard_id: 0,
+            is_service: true,
+            tx_number_in_block: 1,
+            sender: Address::repeat_byte(1),
+            key: H256::repeat_byte(2),
+            value: H256::repeat_byte(3),
+        });
+        header.l2_to_l1_logs = vec![user_log.clone()];
+        header.l2_to_l1_messages = vec![vec![1, 2, 3], vec![]];
+
+        let state_diffs = vec![
+            StateDiffRecord {
+                address: Address::repeat_byte(1),
+                key: U256::one(),
+                derived_key: [1; 32],
+                enumeration_index: 0,
+                initial_value: U256::zero(),
+                final_value: U256::from(100),
+            },
+            StateDiffRecord {
+                address: Address::repeat_byte(2),
+                key: U256::one(),
+                derived_key: [2; 32],
+                enumeration_index: 3,
+                initial_value: U256::from(100),
+                final_value: U256::from(42),
+            },
+        ];
+        let metadata = L1BatchMetadata {
+            root_hash: H256::repeat_byte(4),
+            rollup_last_leaf_index: 10,
+            merkle_root_hash: H256::repeat_byte(4),
+            initial_writes_compressed: vec![],
+            repeated_writes_compressed: vec![],
+            commitment: H256::zero(),
+            l2_l1_messages_compressed: vec![5; 10],
+            l2_l1_merkle_root: H256::zero(),
+            block_meta_params: L1BatchMetaParameters {
+                zkporter_is_available: false,
+                bootloader_code_hash: H256::zero(),
+                default_aa_code_hash: H256::zero(),
+            },
+            aux_data_hash: H256::zero(),
+            meta_parameters_hash: H256::zero(),
+            pass_through_data_hash: H256::zero(),
+            events_queue_commitment: Some(H256::repeat_byte(6)),
+            bootloader_initial_content_commitment: Some(H256::repeat_byte(7)),
+            state_diffs_compressed: compress_state_diffs(state_diffs),
+        };
+        let l1_batch = L1BatchWithMetadata {
+            header,
+            metadata,
+            factory_deps: vec![vec![0xfe; 64]],
+        };
+
+        let commit_data = L1BatchCommitData::from_token(l1_batch.l1_commit_data()).unwrap();
+        assert_eq!(commit_data.number, L1BatchNumber(5));
+        assert_eq!(commit_data.timestamp, 100);
+        assert_eq!(commit_data.rollup_last_leaf_index, 10);
+        assert_eq!(commit_data.merkle_root_hash, H256::repeat_byte(4));
+        assert_eq!(commit_data.l1_tx_count, 2);
+        assert_eq!(commit_data.events_queue_commitment, H256::repeat_byte(6));
+        assert_eq!(
+            commit_data.bootloader_initial_content_commitment,
+            H256::repeat_byte(7)
+        );
+        assert_eq!(commit_data.l2_l1_messages_compressed, [5; 10]);
+        assert!(!commit_data.is_pubdata_in_blobs());
+
+        let pubdata = L1BatchPubdata::parse(&commit_data.pubdata).unwrap();
+        assert_eq!(pubdata.l2_to_l1_logs, [user_log]);
+        assert_eq!(pubdata.l2_to_l1_messages, l1_batch.header.l2_to_l1_messages);
+        assert_eq!(pubdata.published_bytecodes, l1_batch.factory_deps);
+        let state_diffs: Vec<_> = pubdata
+            .state_diffs
+            .iter()
+            .map(|diff| {
+                let prev_value = match diff.key {
+                    StateDiffKey::Initial(_) => U256::zero(),
+                    StateDiffKey::Repeated(_) => U256::from(100),
+                };
+                (diff.key, diff.final_value(prev_value))
+            })
+            .collect();
+        assert_eq!(
+            state_diffs,
+            [
+                (StateDiffKey::Initial([1; 32]), U256::from(100)),
+                (StateDiffKey::Repeated(3), U256::from(42)),
+            ]
+        );
+
+        let mut truncated_pubdata = commit_data.pubdata.clone();
+        truncated_pubdata.pop();
+        L1BatchPubdata::parse(&truncated_pubdata).unwrap_err();
+
+        let blob = PubdataBlob {
+            blob: vec![],
+            commitment: vec![0; 48],
+            proof: vec![0; 48],
+            versioned_hash: H256::repeat_byte(1),
+        };
+        let commit_data =
+            L1BatchCommitData::from_token(l1_batch.l1_commit_data_with_blobs(&[blob])).unwrap();
+        assert!(commit_data.is_pubdata_in_blobs());
+    }
 }
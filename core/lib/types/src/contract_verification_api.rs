@@ -1,13 +1,20 @@
 use std::{collections::HashMap, fmt};
 
+use anyhow::Context as _;
 use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
 use serde::{
     de::{Deserializer, Error, MapAccess, Unexpected, Visitor},
     Deserialize, Serialize,
 };
 
 pub use crate::Execute as ExecuteData;
-use crate::{Address, Bytes};
+use crate::{
+    ethabi::{Contract, Token},
+    Address, Bytes,
+};
+
+static DEPLOYER_CONTRACT: Lazy<Contract> = Lazy::new(zksync_contracts::deployer_contract);
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "codeFormat", content = "sourceCode")]
@@ -215,12 +222,36 @@ pub struct CompilationArtifacts {
     pub abi: serde_json::Value,
 }
 
+/// Type of the match between the deployed bytecode and the bytecode compiled from the provided sources.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VerificationMatch {
+    /// Bytecodes are equal.
+    #[default]
+    Full,
+    /// Bytecodes are equal except for the metadata hash. The executable code is identical, but the sources
+    /// may differ from the original ones in comments, file paths etc.
+    Partial,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct VerificationInfo {
     pub request: VerificationRequest,
     pub artifacts: CompilationArtifacts,
     pub verified_at: DateTime<Utc>,
+    /// Contracts verified before partial matches were introduced always have a full match.
+    #[serde(default)]
+    pub match_type: VerificationMatch,
+    /// For contracts not verified directly, but sharing the bytecode with a verified contract,
+    /// the address of the verified contract.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified_via_address: Option<Address>,
+    /// Set for contracts verified via another contract if constructor arguments cannot be determined
+    /// from the deployment transaction (e.g., for contracts deployed by factories). In this case,
+    /// constructor arguments in `request` are empty.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub constructor_arguments_unknown: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -239,9 +270,113 @@ pub enum DeployContractCalldata {
     Ignore,
 }
 
+/// Constructor arguments of a deployed contract.
+#[derive(Debug, PartialEq)]
+pub enum ConstructorArgs {
+    /// Arguments passed to the constructor that should be checked during verification.
+    Check(Vec<u8>),
+    /// Contract was deployed without calling the constructor.
+    Ignore,
+    /// Arguments cannot be determined from the deployment calldata, e.g., for contracts deployed
+    /// by factories or other contracts.
+    Unknown,
+}
+
+impl DeployContractCalldata {
+    /// Decodes constructor arguments of the contract deployed at the specified address.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the calldata cannot be decoded.
+    pub fn constructor_args(&self, contract_address: Address) -> anyhow::Result<ConstructorArgs> {
+        let Self::Deploy(calldata) = self else {
+            return Ok(ConstructorArgs::Unknown);
+        };
+        if calldata.len() < 4 {
+            anyhow::bail!("calldata is too short: {} bytes", calldata.len());
+        }
+        let (selector, input) = calldata.split_at(4);
+
+        let create = DEPLOYER_CONTRACT.function("create")?;
+        let create2 = DEPLOYER_CONTRACT.function("create2")?;
+        let create_acc = DEPLOYER_CONTRACT.function("createAccount")?;
+        let create2_acc = DEPLOYER_CONTRACT.function("create2Account")?;
+        let force_deploy = DEPLOYER_CONTRACT.function("forceDeployOnAddresses")?;
+
+        // It's assumed that `create` and `create2` methods have the same parameters
+        // and the same for `createAccount` and `create2Account`.
+        let function =
+            if selector == create.short_signature() || selector == create2.short_signature() {
+                create
+            } else if selector == create_acc.short_signature()
+                || selector == create2_acc.short_signature()
+            {
+                create_acc
+            } else if selector == force_deploy.short_signature() {
+                let tokens = force_deploy
+                    .decode_input(input)
+                    .context("failed decoding `forceDeployOnAddresses` input")?;
+                return Self::force_deployment_constructor_args(tokens, contract_address);
+            } else {
+                return Ok(ConstructorArgs::Unknown);
+            };
+
+        let tokens = function
+            .decode_input(input)
+            .with_context(|| format!("failed decoding `{}` input", function.name))?;
+        // Constructor arguments are in the third parameter.
+        let args = tokens
+            .into_iter()
+            .nth(2)
+            .and_then(Token::into_bytes)
+            .with_context(|| {
+                format!(
+                    "the third parameter of `{}` should be of type `bytes`",
+                    function.name
+                )
+            })?;
+        Ok(ConstructorArgs::Check(args))
+    }
+
+    fn force_deployment_constructor_args(
+        tokens: Vec<Token>,
+        contract_address: Address,
+    ) -> anyhow::Result<ConstructorArgs> {
+        let deployments = tokens
+            .into_iter()
+            .next()
+            .and_then(Token::into_array)
+            .context("expected an array of deployments")?;
+        for deployment in deployments {
+            let Token::Tuple(tokens) = deployment else {
+                anyhow::bail!("expected `deployment` to be a tuple");
+            };
+            let [_, address, call_constructor, _, input]: [Token; 5] = tokens
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("unexpected number of `deployment` fields"))?;
+            if address.into_address() != Some(contract_address) {
+                continue;
+            }
+            let call_constructor = call_constructor
+                .into_bool()
+                .context("`callConstructor` should be of type `bool`")?;
+            return Ok(if call_constructor {
+                let input = input
+                    .into_bytes()
+                    .context("`input` should be of type `bytes`")?;
+                ConstructorArgs::Check(input)
+            } else {
+                ConstructorArgs::Ignore
+            });
+        }
+        // The contract wasn't deployed directly by this call.
+        Ok(ConstructorArgs::Unknown)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::SourceCodeData;
+    use super::*;
 
     #[test]
     fn source_code_deserialization() {
@@ -273,4 +408,60 @@ mod tests {
             serde_json::from_str::<SourceCodeData>(type_not_specified_object_str);
         assert!(type_not_specified_object_result.is_err());
     }
+
+    #[test]
+    fn decoding_constructor_args() {
+        let address = Address::repeat_byte(1);
+        let create_input = |function: &str, args: Vec<u8>| {
+            let function = DEPLOYER_CONTRACT.function(function).unwrap();
+            // `createAccount` and `create2Account` have an additional account version param.
+            let tokens = [
+                Token::FixedBytes(vec![0; 32]),
+                Token::FixedBytes(vec![0; 32]),
+                Token::Bytes(args),
+                Token::Uint(0.into()),
+            ];
+            function
+                .encode_input(&tokens[..function.inputs.len()])
+                .unwrap()
+        };
+
+        for function in ["create", "create2", "createAccount", "create2Account"] {
+            let calldata = DeployContractCalldata::Deploy(create_input(function, vec![1; 32]));
+            let args = calldata.constructor_args(address).unwrap();
+            assert_eq!(args, ConstructorArgs::Check(vec![1; 32]), "{function}");
+        }
+
+        let force_deploy_input = DEPLOYER_CONTRACT
+            .function("forceDeployOnAddresses")
+            .unwrap()
+            .encode_input(&[Token::Array(vec![Token::Tuple(vec![
+                Token::FixedBytes(vec![0; 32]),
+                Token::Address(address),
+                Token::Bool(false),
+                Token::Uint(0.into()),
+                Token::Bytes(vec![]),
+            ])])])
+            .unwrap();
+        let calldata = DeployContractCalldata::Deploy(force_deploy_input);
+        let args = calldata.constructor_args(address).unwrap();
+        assert_eq!(args, ConstructorArgs::Ignore);
+        let args = calldata.constructor_args(Address::repeat_byte(2)).unwrap();
+        assert_eq!(args, ConstructorArgs::Unknown);
+
+        let args = DeployContractCalldata::Ignore
+            .constructor_args(address)
+            .unwrap();
+        assert_eq!(args, ConstructorArgs::Unknown);
+        let calldata = DeployContractCalldata::Deploy(vec![0; 36]);
+        let args = calldata.constructor_args(address).unwrap();
+        assert_eq!(args, ConstructorArgs::Unknown);
+
+        let mut malformed_input = create_input("create", vec![1; 32]);
+        malformed_input.truncate(40);
+        let calldata = DeployContractCalldata::Deploy(malformed_input);
+        calldata.constructor_args(address).unwrap_err();
+        let calldata = DeployContractCalldata::Deploy(vec![0; 2]);
+        calldata.constructor_args(address).unwrap_err();
+    }
 }
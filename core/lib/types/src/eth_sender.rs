@@ -1,5 +1,27 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{aggregated_operations::AggregatedActionType, Address, Nonce, H256};
 
+/// Blobs attached to an EIP-4844 (type 3) transaction together with their KZG commitments and proofs.
+/// Blobs are not part of the signed transaction payload, so they are stored separately
+/// and are wrapped into the transaction when it is sent to the L1 node.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct EthTxBlobSidecar {
+    pub blobs: Vec<Vec<u8>>,
+    pub commitments: Vec<Vec<u8>>,
+    pub proofs: Vec<Vec<u8>>,
+    pub versioned_hashes: Vec<H256>,
+}
+
+impl std::fmt::Debug for EthTxBlobSidecar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Do not print blobs
+        f.debug_struct("EthTxBlobSidecar")
+            .field("versioned_hashes", &self.versioned_hashes)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Clone)]
 pub struct EthTx {
     pub id: u32,
@@ -9,6 +31,8 @@ pub struct EthTx {
     pub tx_type: AggregatedActionType,
     pub created_at_timestamp: u64,
     pub predicted_gas_cost: u64,
+    /// Blob sidecar for transactions posting pubdata in EIP-4844 blobs.
+    pub blob_sidecar: Option<EthTxBlobSidecar>,
 }
 
 impl std::fmt::Debug for EthTx {
@@ -21,6 +45,7 @@ impl std::fmt::Debug for EthTx {
             .field("tx_type", &self.tx_type)
             .field("created_at_timestamp", &self.created_at_timestamp)
             .field("predicted_gas_cost", &self.predicted_gas_cost)
+            .field("blob_sidecar", &self.blob_sidecar)
             .finish()
     }
 }
@@ -31,6 +56,7 @@ pub struct TxHistory {
     pub eth_tx_id: u32,
     pub base_fee_per_gas: u64,
     pub priority_fee_per_gas: u64,
+    pub blob_base_fee_per_gas: Option<u64>,
     pub tx_hash: H256,
     pub signed_raw_tx: Vec<u8>,
     pub sent_at_block: Option<u32>,
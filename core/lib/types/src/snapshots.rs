@@ -2,7 +2,7 @@ use std::convert::TryFrom;
 
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
-use zksync_basic_types::{AccountTreeId, L1BatchNumber, MiniblockNumber};
+use zksync_basic_types::{AccountTreeId, L1BatchNumber, MiniblockNumber, H256};
 use zksync_protobuf::{required, ProtoFmt};
 
 use crate::{commitment::L1BatchWithMetadata, Bytes, StorageKey, StorageValue};
@@ -42,6 +42,35 @@ pub struct SnapshotStorageLogsChunkMetadata {
     pub filepath: String,
 }
 
+/// Status of snapshot recovery for a node that was bootstrapped from a snapshot rather than from genesis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotRecoveryStatus {
+    pub l1_batch_number: L1BatchNumber,
+    pub l1_batch_root_hash: H256,
+    pub miniblock_number: MiniblockNumber,
+    pub miniblock_hash: H256,
+    /// Flags indicating whether the storage logs chunk with the corresponding ID was applied.
+    pub storage_logs_chunks_processed: Vec<bool>,
+}
+
+impl SnapshotRecoveryStatus {
+    /// Returns IDs of storage logs chunks that were not applied yet.
+    pub fn storage_logs_chunks_left_to_process(&self) -> Vec<u64> {
+        self.storage_logs_chunks_processed
+            .iter()
+            .enumerate()
+            .filter_map(|(chunk_id, &is_processed)| (!is_processed).then_some(chunk_id as u64))
+            .collect()
+    }
+
+    /// Checks whether all storage logs chunks were applied.
+    pub fn is_finished(&self) -> bool {
+        self.storage_logs_chunks_processed
+            .iter()
+            .all(|&is_processed| is_processed)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct SnapshotStorageLogsStorageKey {
@@ -208,6 +208,28 @@ pub fn compress_with_best_strategy(prev_value: U256, new_value: U256) -> Vec<u8>
         })
 }
 
+/// Inverse of [`compress_with_best_strategy()`]. Reads a single extended compressed value (the metadata byte
+/// followed by the compressed value) from the start of `data` and applies it to `prev_value`.
+/// Returns the new value and the number of bytes consumed, or `None` if `data` is malformed.
+pub fn decompress_value(prev_value: U256, data: &[u8]) -> Option<(U256, usize)> {
+    let (&metadata, data) = data.split_first()?;
+    let operation_id = (metadata & 7) as usize;
+    let size = if operation_id == 0 {
+        32
+    } else {
+        (metadata >> 3) as usize
+    };
+    let value = U256::from_big_endian(data.get(..size)?);
+
+    let new_value = match operation_id {
+        0 | 3 => value,
+        1 => prev_value.overflowing_add(value).0,
+        2 => prev_value.overflowing_sub(value).0,
+        _ => return None,
+    };
+    Some((new_value, size + 1))
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::{Add, BitAnd, Shr, Sub};
@@ -373,6 +395,30 @@ mod tests {
         assert!((((compressed_val.bits() as f64) / 8f64).ceil() as usize) == 1);
     }
 
+    #[test]
+    fn decompressing_values() {
+        let value_pairs = [
+            (U256::from(255438218), U256::from(255438638)),
+            (U256::from(580481589), U256::from(229496100)),
+            (U256::from(580481589), U256::from(1337)),
+            (U256::from(580481589), U256::zero()),
+            (U256::MAX, U256::one()),
+            (U256::zero(), U256::MAX),
+            (U256::zero(), U256::MAX >> 1),
+        ];
+        for (initial_val, final_val) in value_pairs {
+            let mut compressed_value = compress_with_best_strategy(initial_val, final_val);
+            let compressed_len = compressed_value.len();
+            compressed_value.extend_from_slice(&[0xff; 3]); // trailing data must be ignored
+            let decompressed = decompress_value(initial_val, &compressed_value);
+            assert_eq!(decompressed, Some((final_val, compressed_len)));
+        }
+
+        assert_eq!(decompress_value(U256::zero(), &[]), None);
+        assert_eq!(decompress_value(U256::zero(), &[17, 1]), None);
+        assert_eq!(decompress_value(U256::zero(), &[7]), None);
+    }
+
     fn verify_add_is_none(initial_val: U256, final_val: U256) {
         let compression_add_strategy = CompressionByteAdd {
             prev_value: initial_val,
@@ -182,6 +182,98 @@ fn prepend_header(compressed_state_diffs: Vec<u8>) -> Vec<u8> {
     res.to_vec()
 }
 
+/// Key of a state diff decoded from the compressed state diffs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateDiffKey {
+    /// Initial write identified by the derived key (i.e., the hashed storage key).
+    Initial([u8; 32]),
+    /// Repeated write identified by the enumeration index of the key.
+    Repeated(u64),
+}
+
+/// State diff decoded from the compressed state diffs published on L1. Since repeated writes are compressed
+/// relative to the previous value, the final value can only be restored with the knowledge of the previous one
+/// (see [`Self::final_value()`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressedStateDiff {
+    pub key: StateDiffKey,
+    /// Metadata byte followed by the compressed value.
+    pub compressed_value: Vec<u8>,
+}
+
+impl CompressedStateDiff {
+    /// Restores the final value of this state diff given the previous value of the slot.
+    /// For initial writes, the previous value is zero.
+    pub fn final_value(&self, prev_value: U256) -> U256 {
+        // The value is validated during decoding, so unwrapping is safe.
+        compression::decompress_value(prev_value, &self.compressed_value)
+            .expect("malformed compressed value")
+            .0
+    }
+}
+
+/// Inverse of [`compress_state_diffs()`]. Decodes state diffs from the start of `data`, returning them
+/// in the order of publication (initial writes followed by repeated writes) together with the number
+/// of bytes consumed.
+pub fn decompress_state_diffs(data: &[u8]) -> anyhow::Result<(Vec<CompressedStateDiff>, usize)> {
+    anyhow::ensure!(
+        data.len() >= 5,
+        "compressed state diffs header is truncated"
+    );
+    anyhow::ensure!(
+        data[0] == COMPRESSION_VERSION_NUMBER,
+        "unsupported state diffs compression version: {}",
+        data[0]
+    );
+    anyhow::ensure!(
+        data[4] == BYTES_PER_ENUMERATION_INDEX,
+        "unsupported enumeration index size: {}",
+        data[4]
+    );
+    let body_len = u32::from_be_bytes([0, data[1], data[2], data[3]]) as usize;
+    let body = data
+        .get(5..5 + body_len)
+        .ok_or_else(|| anyhow::anyhow!("compressed state diffs are truncated"))?;
+    anyhow::ensure!(body.len() >= 2, "number of initial writes is missing");
+    let initial_writes_count = u16::from_be_bytes([body[0], body[1]]) as usize;
+
+    let mut state_diffs = vec![];
+    let mut offset = 2;
+    while offset < body.len() {
+        let key_size = if state_diffs.len() < initial_writes_count {
+            BYTES_PER_DERIVED_KEY
+        } else {
+            BYTES_PER_ENUMERATION_INDEX
+        } as usize;
+        let key_bytes = body.get(offset..offset + key_size).ok_or_else(|| {
+            anyhow::anyhow!("key of state diff #{} is truncated", state_diffs.len())
+        })?;
+        let key = if key_size == BYTES_PER_DERIVED_KEY as usize {
+            StateDiffKey::Initial(key_bytes.try_into().unwrap())
+        } else {
+            StateDiffKey::Repeated(u32::from_be_bytes(key_bytes.try_into().unwrap()).into())
+        };
+        offset += key_size;
+
+        // The previous value doesn't influence the consumed length, so we can use an arbitrary one.
+        let (_, value_len) = compression::decompress_value(U256::zero(), &body[offset..])
+            .ok_or_else(|| {
+                anyhow::anyhow!("value of state diff #{} is malformed", state_diffs.len())
+            })?;
+        state_diffs.push(CompressedStateDiff {
+            key,
+            compressed_value: body[offset..offset + value_len].to_vec(),
+        });
+        offset += value_len;
+    }
+    anyhow::ensure!(
+        state_diffs.len() >= initial_writes_count,
+        "expected at least {initial_writes_count} state diffs, got {}",
+        state_diffs.len()
+    );
+    Ok((state_diffs, 5 + body_len))
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
@@ -493,6 +585,72 @@ mod tests {
         assert_eq!(encoded_padded_state_diff, expected_padded_encoding);
     }
 
+    #[test]
+    fn decompressing_state_diffs() {
+        let state_diffs = vec![
+            StateDiffRecord {
+                address: Address::repeat_byte(1),
+                key: U256::from(1),
+                derived_key: [1; 32],
+                enumeration_index: 0,
+                initial_value: U256::zero(),
+                final_value: U256::from(123),
+            },
+            StateDiffRecord {
+                address: Address::repeat_byte(2),
+                key: U256::from(1),
+                derived_key: [2; 32],
+                enumeration_index: 5,
+                initial_value: U256::from(1_000),
+                final_value: U256::from(1_001),
+            },
+            StateDiffRecord {
+                address: Address::repeat_byte(1),
+                key: U256::from(2),
+                derived_key: [3; 32],
+                enumeration_index: 0,
+                initial_value: U256::zero(),
+                final_value: U256::MAX >> 1,
+            },
+            StateDiffRecord {
+                address: Address::repeat_byte(3),
+                key: U256::from(1),
+                derived_key: [4; 32],
+                enumeration_index: 8,
+                initial_value: U256::from(1_000),
+                final_value: U256::zero(),
+            },
+        ];
+        let mut compressed = compress_state_diffs(state_diffs.clone());
+        let compressed_len = compressed.len();
+        compressed.extend_from_slice(b"trailing data");
+
+        let (decompressed, consumed_len) = decompress_state_diffs(&compressed).unwrap();
+        assert_eq!(consumed_len, compressed_len);
+        let expected_keys = [
+            StateDiffKey::Initial([1; 32]),
+            StateDiffKey::Initial([3; 32]),
+            StateDiffKey::Repeated(5),
+            StateDiffKey::Repeated(8),
+        ];
+        let keys: Vec<_> = decompressed.iter().map(|diff| diff.key).collect();
+        assert_eq!(keys, expected_keys);
+
+        for diff in &decompressed {
+            let record = state_diffs
+                .iter()
+                .find(|record| match diff.key {
+                    StateDiffKey::Initial(key) => record.derived_key == key,
+                    StateDiffKey::Repeated(index) => record.enumeration_index == index,
+                })
+                .unwrap();
+            assert_eq!(diff.final_value(record.initial_value), record.final_value);
+        }
+
+        let err = decompress_state_diffs(&compressed[..compressed_len - 1]).unwrap_err();
+        assert!(err.to_string().contains("truncated"), "{err}");
+    }
+
     fn verify_value(
         initial_value: U256,
         final_value: U256,
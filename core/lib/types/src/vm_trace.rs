@@ -9,7 +9,7 @@ use zk_evm::zkevm_opcode_defs::FarCallOpcode;
 use zksync_system_constants::BOOTLOADER_ADDRESS;
 use zksync_utils::u256_to_h256;
 
-use crate::{Address, U256};
+use crate::{Address, StorageKey, H256, U256};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum VmTrace {
@@ -201,6 +201,23 @@ impl fmt::Debug for Call {
     }
 }
 
+/// Storage slot touched during VM execution together with its values before and after the execution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchedStorageSlot {
+    /// Fully qualified key of the slot.
+    pub key: StorageKey,
+    /// Value of the slot before the execution.
+    pub pre_value: H256,
+    /// Value of the slot after the execution. Equals `pre_value` if the slot was only read.
+    pub post_value: H256,
+}
+
+impl TouchedStorageSlot {
+    pub fn is_modified(&self) -> bool {
+        self.pre_value != self.post_value
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ViolatedValidationRule {
     TouchedUnallowedStorageSlots(Address, U256),
@@ -209,6 +226,56 @@ pub enum ViolatedValidationRule {
     TookTooManyComputationalGas(u32),
 }
 
+impl ViolatedValidationRule {
+    /// Returns a stable machine-readable name of the rule.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::TouchedUnallowedStorageSlots(..) => "touchedUnallowedStorageSlots",
+            Self::CalledContractWithNoCode(_) => "calledContractWithNoCode",
+            Self::TouchedUnallowedContext => "touchedUnallowedContext",
+            Self::TookTooManyComputationalGas(_) => "tookTooManyComputationalGas",
+        }
+    }
+
+    /// Returns the contract that caused the violation, if it's known from the rule itself.
+    pub fn contract(&self) -> Option<Address> {
+        match self {
+            Self::TouchedUnallowedStorageSlots(contract, _)
+            | Self::CalledContractWithNoCode(contract) => Some(*contract),
+            Self::TouchedUnallowedContext | Self::TookTooManyComputationalGas(_) => None,
+        }
+    }
+
+    /// Returns the storage slot that caused the violation, if any.
+    pub fn slot(&self) -> Option<H256> {
+        match self {
+            Self::TouchedUnallowedStorageSlots(_, key) => Some(u256_to_h256(*key)),
+            _ => None,
+        }
+    }
+}
+
+/// Far call frame on the VM call stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationCallFrame {
+    pub this_address: Address,
+    pub code_address: Address,
+    pub msg_sender: Address,
+}
+
+/// Information about the VM state at the point where a validation rule was violated.
+#[derive(Debug, Clone)]
+pub struct ViolatedValidationRuleContext {
+    pub rule: ViolatedValidationRule,
+    /// Opcode executed when the violation was detected, e.g. `Log(StorageRead)`.
+    pub opcode: String,
+    /// Far call frames from the outermost to the innermost one.
+    pub call_stack: Vec<ValidationCallFrame>,
+    /// Computational gas spent on validation up to (and including) the violating opcode.
+    pub computational_gas_used: u32,
+}
+
 impl Display for ViolatedValidationRule {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
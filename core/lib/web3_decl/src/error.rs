@@ -1,7 +1,7 @@
 //! Definition of errors that can occur in the zkSync Web3 API.
 
 use thiserror::Error;
-use zksync_types::api::SerializationTransactionError;
+use zksync_types::api::{SerializationTransactionError, ValidationDiagnostics};
 
 #[derive(Debug, Error)]
 pub enum Web3Error {
@@ -19,10 +19,17 @@ pub enum Web3Error {
     InvalidTransactionData(#[from] zksync_types::ethabi::Error),
     #[error("{0}")]
     SubmitTransactionError(String, Vec<u8>),
+    /// Transaction was rejected because of failed account validation.
+    #[error("{0}")]
+    ValidationFailed(String, Box<ValidationDiagnostics>),
     #[error("Failed to serialize transaction: {0}")]
     SerializationError(#[from] SerializationTransactionError),
     #[error("Invalid fee parameters: {0}")]
     InvalidFeeParams(String),
+    #[error("Invalid state override: {0}")]
+    InvalidStateOverride(String),
+    #[error("Bundle must contain from 1 to {0} calls")]
+    InvalidBundleSize(usize),
     #[error("More than four topics in filter")]
     TooManyTopics,
     #[error("Your connection time exceeded the limit")]
@@ -1,7 +1,11 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use zksync_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{
+        BlockId, BlockNumber, DebugTrace, L1BatchTrace, ResultDebugTrace, TraceCallConfig,
+        TracerConfig,
+    },
     transaction_request::CallRequest,
+    L1BatchNumber,
 };
 
 use crate::types::H256;
@@ -24,24 +28,30 @@ pub trait DebugNamespace {
         &self,
         block: BlockNumber,
         options: Option<TracerConfig>,
-    ) -> RpcResult<Vec<ResultDebugCall>>;
+    ) -> RpcResult<Vec<ResultDebugTrace>>;
     #[method(name = "traceBlockByHash")]
     async fn trace_block_by_hash(
         &self,
         hash: H256,
         options: Option<TracerConfig>,
-    ) -> RpcResult<Vec<ResultDebugCall>>;
+    ) -> RpcResult<Vec<ResultDebugTrace>>;
     #[method(name = "traceCall")]
     async fn trace_call(
         &self,
         request: CallRequest,
         block: Option<BlockId>,
-        options: Option<TracerConfig>,
-    ) -> RpcResult<DebugCall>;
+        options: Option<TraceCallConfig>,
+    ) -> RpcResult<DebugTrace>;
     #[method(name = "traceTransaction")]
     async fn trace_transaction(
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> RpcResult<Option<DebugCall>>;
+    ) -> RpcResult<Option<DebugTrace>>;
+    #[method(name = "traceL1Batch")]
+    async fn trace_l1_batch(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        options: Option<TracerConfig>,
+    ) -> RpcResult<L1BatchTrace>;
 }
@@ -10,12 +10,12 @@ pub mod zks;
 #[cfg(feature = "client")]
 pub use self::{
     debug::DebugNamespaceClient, en::EnNamespaceClient, eth::EthNamespaceClient,
-    net::NetNamespaceClient, snapshots::SnapshotsNamespaceServer, web3::Web3NamespaceClient,
+    net::NetNamespaceClient, snapshots::SnapshotsNamespaceClient, web3::Web3NamespaceClient,
     zks::ZksNamespaceClient,
 };
 #[cfg(feature = "server")]
 pub use self::{
     debug::DebugNamespaceServer, en::EnNamespaceServer, eth::EthNamespaceServer,
-    net::NetNamespaceServer, snapshots::SnapshotsNamespaceClient, web3::Web3NamespaceServer,
+    net::NetNamespaceServer, snapshots::SnapshotsNamespaceServer, web3::Web3NamespaceServer,
     zks::ZksNamespaceServer,
 };
@@ -4,12 +4,13 @@ use bigdecimal::BigDecimal;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetails,
+        BlockDetails, BlockId, BridgeAddresses, BundleCallResult, BundleSimulationConfig,
+        L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetails,
+        ValidationDiagnostics,
     },
     fee::Fee,
     transaction_request::CallRequest,
-    Address, L1BatchNumber, MiniblockNumber, H256, U256, U64,
+    Address, Bytes, L1BatchNumber, MiniblockNumber, H256, U256, U64,
 };
 
 use crate::types::Token;
@@ -33,6 +34,17 @@ pub trait ZksNamespace {
     #[method(name = "estimateGasL1ToL2")]
     async fn estimate_gas_l1_to_l2(&self, req: CallRequest) -> RpcResult<U256>;
 
+    #[method(name = "simulateBundle")]
+    async fn simulate_bundle(
+        &self,
+        requests: Vec<CallRequest>,
+        block: Option<BlockId>,
+        config: Option<BundleSimulationConfig>,
+    ) -> RpcResult<Vec<BundleCallResult>>;
+
+    #[method(name = "debugValidation")]
+    async fn debug_validation(&self, tx_bytes: Bytes) -> RpcResult<ValidationDiagnostics>;
+
     #[method(name = "getMainContract")]
     async fn get_main_contract(&self) -> RpcResult<Address>;
 
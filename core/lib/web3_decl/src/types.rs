@@ -15,7 +15,7 @@ use itertools::unfold;
 use rlp::Rlp;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 pub use zksync_types::{
-    api::{Block, BlockNumber, Log, TransactionReceipt, TransactionRequest},
+    api::{Block, BlockNumber, L1BatchStatusUpdate, Log, TransactionReceipt, TransactionRequest},
     vm_trace::{ContractSourceDebugInfo, VmDebugTrace, VmExecutionStep},
     web3::{
         ethabi,
@@ -344,7 +344,8 @@ pub enum PubSubResult {
     Header(BlockHeader),
     Log(Log),
     TxHash(H256),
-    Syncing(bool),
+    Syncing(SyncState),
+    L1BatchStatus(L1BatchStatusUpdate),
 }
 
 #[cfg(test)]
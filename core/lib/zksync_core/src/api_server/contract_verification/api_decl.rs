@@ -47,5 +47,7 @@ impl RestApi {
                 "/contract_verification/info/{address}",
                 web::get().to(Self::verification_info),
             )
+            .route("/api", web::get().to(Self::etherscan_get))
+            .route("/api", web::post().to(Self::etherscan_post))
     }
 }
@@ -11,6 +11,22 @@ fn ok_json(data: impl Serialize) -> ActixResult<HttpResponse> {
     Ok(HttpResponse::Ok().json(data))
 }
 
+/// Reason a verification request was not added to the queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum VerificationRejection {
+    NotDeployed,
+    AlreadyVerified,
+}
+
+impl VerificationRejection {
+    pub(super) fn message(self) -> &'static str {
+        match self {
+            Self::NotDeployed => "There is no deployed contract on this address",
+            Self::AlreadyVerified => "This contract is already verified",
+        }
+    }
+}
+
 impl RestApi {
     #[tracing::instrument(skip(query))]
     fn validate_contract_verification_query(
@@ -33,7 +49,22 @@ impl RestApi {
         if let Err(res) = Self::validate_contract_verification_query(&request) {
             return Ok(res);
         }
-        let mut storage = self_
+        let request_id = match self_.add_verification_request(request).await {
+            Ok(request_id) => request_id,
+            Err(rejection) => return Ok(HttpResponse::BadRequest().body(rejection.message())),
+        };
+
+        method_latency.observe();
+        ok_json(request_id)
+    }
+
+    /// Adds a verification request to the queue after checking that the contract is deployed and not verified yet.
+    /// Returns the ID of the added request.
+    pub(super) async fn add_verification_request(
+        &self,
+        request: VerificationIncomingRequest,
+    ) -> Result<usize, VerificationRejection> {
+        let mut storage = self
             .master_connection_pool
             .access_storage_tagged("api")
             .await
@@ -44,9 +75,7 @@ impl RestApi {
             .is_contract_deployed_at_address(request.contract_address)
             .await
         {
-            return Ok(
-                HttpResponse::BadRequest().body("There is no deployed contract on this address")
-            );
+            return Err(VerificationRejection::NotDeployed);
         }
         if storage
             .contract_verification_dal()
@@ -54,17 +83,14 @@ impl RestApi {
             .await
             .unwrap()
         {
-            return Ok(HttpResponse::BadRequest().body("This contract is already verified"));
+            return Err(VerificationRejection::AlreadyVerified);
         }
 
-        let request_id = storage
+        Ok(storage
             .contract_verification_dal()
             .add_contract_verification_request(request)
             .await
-            .unwrap();
-
-        method_latency.observe();
-        ok_json(request_id)
+            .unwrap())
     }
 
     #[tracing::instrument(skip(self_))]
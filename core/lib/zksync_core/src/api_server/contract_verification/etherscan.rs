@@ -0,0 +1,837 @@
+//! Etherscan-compatible contract verification API (`/api?module=contract&action=...`).
+//!
+//! Allows using Etherscan verification plugins (e.g., `hardhat-verify` or `forge verify-contract`)
+//! with the contract verifier. Supported actions are `verifysourcecode`, `checkverifystatus`,
+//! `getsourcecode` and `getabi`. In addition to the standard Etherscan params, `verifysourcecode`
+//! accepts optional `zksolcVersion`, `optimizerMode` and `isSystem` params. If `zksolcVersion` is not
+//! specified (as is the case for unmodified plugins), the latest supported zksolc version is used.
+//!
+//! The `evmversion` param is passed to solc via the standard JSON input settings. zksolc doesn't use
+//! the solc optimizer, so the `runs` param is only accepted if it has the default solc value.
+
+use std::{collections::HashMap, str::FromStr};
+
+use actix_web::{web, HttpResponse, Result as ActixResult};
+use serde::Serialize;
+use zksync_types::{
+    contract_verification_api::{
+        CompilerVersions, SourceCodeData, VerificationIncomingRequest, VerificationInfo,
+    },
+    Address, Bytes,
+};
+
+use super::{api_decl::RestApi, api_impl::VerificationRejection, metrics::METRICS};
+
+/// Request params merged from the query string and (for POST requests) the form body.
+type EtherscanParams = HashMap<String, String>;
+
+const PENDING_STATUS: &str = "Pending in queue";
+const SUCCESS_STATUS: &str = "Pass - Verified";
+const FAILURE_STATUS: &str = "Fail - Unable to verify";
+const ALREADY_VERIFIED: &str = "Contract source code already verified";
+const NOT_VERIFIED: &str = "Contract source code not verified";
+/// Default number of solc optimizer runs. zksolc doesn't use the solc optimizer, so this is the only
+/// value accepted in the `runs` param.
+const DEFAULT_OPTIMIZER_RUNS: u32 = 200;
+/// Value of the `evmversion` param meaning the default EVM version for the compiler.
+const DEFAULT_EVM_VERSION: &str = "default";
+
+#[derive(Debug, thiserror::Error)]
+enum EtherscanError {
+    #[error("Missing or empty parameter `{0}`")]
+    MissingParam(&'static str),
+    #[error("Invalid parameter `{0}`: {1}")]
+    InvalidParam(&'static str, String),
+    #[error("Unsupported code format `{0}`")]
+    UnsupportedCodeFormat(String),
+    #[error("Unknown module / action: {0}")]
+    UnknownAction(String),
+    #[error("{0}")]
+    Rejected(&'static str),
+}
+
+/// Response envelope used by Etherscan: `status` is "1" for successful requests and "0" otherwise.
+#[derive(Debug, Serialize)]
+struct EtherscanResponse {
+    status: &'static str,
+    message: &'static str,
+    result: serde_json::Value,
+}
+
+impl EtherscanResponse {
+    fn ok(result: impl Into<serde_json::Value>) -> Self {
+        Self {
+            status: "1",
+            message: "OK",
+            result: result.into(),
+        }
+    }
+
+    fn not_ok(result: impl Into<serde_json::Value>) -> Self {
+        Self {
+            status: "0",
+            message: "NOTOK",
+            result: result.into(),
+        }
+    }
+}
+
+/// Contract information in the `getsourcecode` format.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct EtherscanSourceCode {
+    source_code: String,
+    #[serde(rename = "ABI")]
+    abi: String,
+    contract_name: String,
+    compiler_version: String,
+    zk_compiler_version: String,
+    optimization_used: String,
+    runs: String,
+    constructor_arguments: String,
+    #[serde(rename = "EVMVersion")]
+    evm_version: String,
+    library: String,
+    license_type: String,
+    proxy: String,
+    implementation: String,
+    swarm_source: String,
+}
+
+impl EtherscanSourceCode {
+    fn not_verified() -> Self {
+        Self {
+            abi: NOT_VERIFIED.to_owned(),
+            proxy: "0".to_owned(),
+            ..Self::default()
+        }
+    }
+
+    fn new(info: &VerificationInfo) -> Self {
+        let request = &info.request.req;
+        let mut evm_version = None;
+        let source_code = match &request.source_code_data {
+            SourceCodeData::SolSingleFile(source) | SourceCodeData::YulSingleFile(source) => {
+                source.clone()
+            }
+            // Etherscan wraps standard JSON input into an additional pair of braces.
+            SourceCodeData::StandardJsonInput(input) => {
+                evm_version = input
+                    .get("settings")
+                    .and_then(|settings| settings.get("evmVersion"))
+                    .and_then(serde_json::Value::as_str);
+                format!("{{{}}}", serde_json::Value::Object(input.clone()))
+            }
+            SourceCodeData::VyperMultiFile(sources) => serde_json::to_string(sources).unwrap(),
+        };
+        Self {
+            source_code,
+            abi: info.artifacts.abi.to_string(),
+            contract_name: request.contract_name.clone(),
+            compiler_version: request.compiler_versions.compiler_version(),
+            zk_compiler_version: request.compiler_versions.zk_compiler_version(),
+            optimization_used: if request.optimization_used { "1" } else { "0" }.to_owned(),
+            runs: DEFAULT_OPTIMIZER_RUNS.to_string(),
+            // Etherscan clients expect hex-encoded args here, so unknown args are returned as an empty string.
+            constructor_arguments: if info.constructor_arguments_unknown {
+                String::new()
+            } else {
+                hex::encode(&request.constructor_arguments.0)
+            },
+            evm_version: evm_version.unwrap_or("Default").to_owned(),
+            proxy: "0".to_owned(),
+            ..Self::default()
+        }
+    }
+}
+
+fn required_param<'a>(
+    params: &'a EtherscanParams,
+    name: &'static str,
+) -> Result<&'a str, EtherscanError> {
+    params
+        .get(name)
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .ok_or(EtherscanError::MissingParam(name))
+}
+
+fn parse_address(params: &EtherscanParams, name: &'static str) -> Result<Address, EtherscanError> {
+    let address = required_param(params, name)?;
+    Address::from_str(address).map_err(|err| EtherscanError::InvalidParam(name, err.to_string()))
+}
+
+fn parse_flag(params: &EtherscanParams, name: &'static str) -> Result<bool, EtherscanError> {
+    match params.get(name).map(|value| value.trim()) {
+        None | Some("" | "0" | "false") => Ok(false),
+        Some("1" | "true") => Ok(true),
+        Some(value) => Err(EtherscanError::InvalidParam(
+            name,
+            format!("expected 0 or 1, got `{value}`"),
+        )),
+    }
+}
+
+/// Checks that the `runs` param, if specified, has the default value; zksolc doesn't support
+/// configuring optimizer runs.
+fn check_optimizer_runs(params: &EtherscanParams) -> Result<(), EtherscanError> {
+    let Some(runs) = params.get("runs").map(|runs| runs.trim()) else {
+        return Ok(());
+    };
+    if runs.is_empty() {
+        return Ok(());
+    }
+    let runs: u32 = runs
+        .parse()
+        .map_err(|err| EtherscanError::InvalidParam("runs", format!("{err}")))?;
+    if runs != DEFAULT_OPTIMIZER_RUNS {
+        return Err(EtherscanError::InvalidParam(
+            "runs",
+            format!(
+                "zksolc doesn't support optimizer runs; omit the param or set it to {DEFAULT_OPTIMIZER_RUNS}, \
+                 and use `optimizerMode` to configure optimizations"
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Parses the `evmversion` param. Returns `None` if the default EVM version should be used.
+fn parse_evm_version(params: &EtherscanParams) -> Result<Option<String>, EtherscanError> {
+    let Some(version) = params.get("evmversion").map(|version| version.trim()) else {
+        return Ok(None);
+    };
+    if version.is_empty() || version.eq_ignore_ascii_case(DEFAULT_EVM_VERSION) {
+        return Ok(None);
+    }
+    if !version.chars().all(|ch| ch.is_ascii_alphanumeric()) {
+        return Err(EtherscanError::InvalidParam(
+            "evmversion",
+            format!("invalid EVM version `{version}`"),
+        ));
+    }
+    Ok(Some(version.to_owned()))
+}
+
+/// Sets the EVM version in the standard JSON input settings.
+fn set_evm_version(
+    input: &mut serde_json::Map<String, serde_json::Value>,
+    evm_version: String,
+) -> Result<(), EtherscanError> {
+    let settings = input
+        .entry("settings")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or_else(|| {
+            EtherscanError::InvalidParam("sourceCode", "`settings` is not an object".to_owned())
+        })?;
+    match settings.get("evmVersion") {
+        Some(existing) if existing.as_str() != Some(evm_version.as_str()) => {
+            Err(EtherscanError::InvalidParam(
+                "evmversion",
+                format!("conflicts with EVM version {existing} in the standard JSON input"),
+            ))
+        }
+        _ => {
+            settings.insert("evmVersion".to_owned(), evm_version.into());
+            Ok(())
+        }
+    }
+}
+
+/// Converts a single-file Solidity source into the standard JSON input, so that compiler settings
+/// not supported for single-file sources (e.g., the EVM version) can be specified.
+fn single_file_standard_json(
+    contract_name: &str,
+    source_code: &str,
+    optimization_used: bool,
+    is_system: bool,
+) -> serde_json::Map<String, serde_json::Value> {
+    // Mirrors the file name used by the contract verifier for single-file sources.
+    let file_name = match contract_name.rsplit_once(':') {
+        Some((file_name, _)) => file_name.to_owned(),
+        None => format!("{contract_name}.sol"),
+    };
+    let input = serde_json::json!({
+        "language": "Solidity",
+        "sources": {
+            file_name: { "content": source_code },
+        },
+        "settings": {
+            "optimizer": { "enabled": optimization_used },
+            "isSystem": is_system,
+        },
+    });
+    match input {
+        serde_json::Value::Object(input) => input,
+        _ => unreachable!(),
+    }
+}
+
+/// Converts a solc version in the Etherscan format (e.g., `v0.8.17+commit.8df45f5f`)
+/// to the format used by the contract verifier (e.g., `0.8.17`).
+fn normalize_solc_version(version: &str) -> &str {
+    let version = version.strip_prefix('v').unwrap_or(version);
+    version
+        .split_once('+')
+        .map_or(version, |(version, _)| version)
+}
+
+/// Returns the latest of the specified compiler versions (e.g., `v1.3.14`), comparing them numerically.
+fn latest_compiler_version(versions: &[String]) -> Option<&str> {
+    let version_key = |version: &str| -> Vec<u64> {
+        let version = version.strip_prefix('v').unwrap_or(version);
+        version
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    versions
+        .iter()
+        .max_by_key(|version| version_key(version))
+        .map(String::as_str)
+}
+
+fn parse_verification_request(
+    params: &EtherscanParams,
+    default_zksolc_version: Option<&str>,
+) -> Result<VerificationIncomingRequest, EtherscanError> {
+    let contract_address = parse_address(params, "contractaddress")?;
+    let contract_name = required_param(params, "contractname")?;
+    let optimization_used = parse_flag(params, "optimizationUsed")?;
+    let is_system = parse_flag(params, "isSystem")?;
+    check_optimizer_runs(params)?;
+    let evm_version = parse_evm_version(params)?;
+
+    let source_code = required_param(params, "sourceCode")?;
+    let code_format = params
+        .get("codeformat")
+        .map_or("solidity-single-file", |format| format.trim());
+    let source_code_data = match code_format {
+        "solidity-single-file" => match evm_version {
+            None => SourceCodeData::SolSingleFile(source_code.to_owned()),
+            Some(evm_version) => {
+                let mut input = single_file_standard_json(
+                    contract_name,
+                    source_code,
+                    optimization_used,
+                    is_system,
+                );
+                set_evm_version(&mut input, evm_version)?;
+                SourceCodeData::StandardJsonInput(input)
+            }
+        },
+        "solidity-standard-json-input" => {
+            let mut input = serde_json::from_str(source_code)
+                .map_err(|err| EtherscanError::InvalidParam("sourceCode", err.to_string()))?;
+            if let Some(evm_version) = evm_version {
+                set_evm_version(&mut input, evm_version)?;
+            }
+            SourceCodeData::StandardJsonInput(input)
+        }
+        _ => {
+            return Err(EtherscanError::UnsupportedCodeFormat(
+                code_format.to_owned(),
+            ))
+        }
+    };
+
+    let zksolc_version = match required_param(params, "zksolcVersion") {
+        Ok(version) => version,
+        Err(err) => default_zksolc_version.ok_or(err)?,
+    };
+    let compiler_versions = CompilerVersions::Solc {
+        compiler_zksolc_version: zksolc_version.to_owned(),
+        compiler_solc_version: normalize_solc_version(required_param(params, "compilerversion")?)
+            .to_owned(),
+    };
+
+    // Etherscan API uses the misspelled param name; the correct spelling is accepted as well.
+    let constructor_arguments = params
+        .get("constructorArguements")
+        .or_else(|| params.get("constructorArguments"))
+        .map(|args| args.trim());
+    let constructor_arguments = match constructor_arguments {
+        Some(args) => {
+            let args = args.strip_prefix("0x").unwrap_or(args);
+            hex::decode(args).map_err(|err| {
+                EtherscanError::InvalidParam("constructorArguements", err.to_string())
+            })?
+        }
+        None => vec![],
+    };
+
+    Ok(VerificationIncomingRequest {
+        contract_address,
+        source_code_data,
+        contract_name: contract_name.to_owned(),
+        compiler_versions,
+        optimization_used,
+        optimizer_mode: params
+            .get("optimizerMode")
+            .filter(|mode| !mode.is_empty())
+            .cloned(),
+        constructor_arguments: Bytes(constructor_arguments),
+        is_system,
+    })
+}
+
+impl RestApi {
+    #[tracing::instrument(skip(self_, query))]
+    pub async fn etherscan_get(
+        self_: web::Data<Self>,
+        query: web::Query<EtherscanParams>,
+    ) -> ActixResult<HttpResponse> {
+        Ok(self_.etherscan_api(query.into_inner()).await)
+    }
+
+    /// Etherscan plugins send `verifysourcecode` requests as POST requests with form-encoded params.
+    #[tracing::instrument(skip(self_, form))]
+    pub async fn etherscan_post(
+        self_: web::Data<Self>,
+        query: web::Query<EtherscanParams>,
+        form: web::Form<EtherscanParams>,
+    ) -> ActixResult<HttpResponse> {
+        let mut params = query.into_inner();
+        params.extend(form.into_inner());
+        Ok(self_.etherscan_api(params).await)
+    }
+
+    async fn etherscan_api(&self, params: EtherscanParams) -> HttpResponse {
+        let module = params.get("module").map_or("", String::as_str);
+        let action = params.get("action").map_or("", String::as_str);
+        let response = match (module, action) {
+            ("contract", "verifysourcecode") => {
+                let latency = METRICS.call[&"etherscan_verifysourcecode"].start();
+                let response = self.etherscan_verify_source_code(&params).await;
+                latency.observe();
+                response
+            }
+            ("contract", "checkverifystatus") => {
+                let latency = METRICS.call[&"etherscan_checkverifystatus"].start();
+                let response = self.etherscan_check_verify_status(&params).await;
+                latency.observe();
+                response
+            }
+            ("contract", "getsourcecode") => {
+                let latency = METRICS.call[&"etherscan_getsourcecode"].start();
+                let response = self.etherscan_get_source_code(&params).await;
+                latency.observe();
+                response
+            }
+            ("contract", "getabi") => {
+                let latency = METRICS.call[&"etherscan_getabi"].start();
+                let response = self.etherscan_get_abi(&params).await;
+                latency.observe();
+                response
+            }
+            _ => Err(EtherscanError::UnknownAction(format!("{module}/{action}"))),
+        };
+
+        let response = response.unwrap_or_else(|err| EtherscanResponse::not_ok(err.to_string()));
+        // Etherscan returns errors with the 200 status code, and plugins rely on this.
+        HttpResponse::Ok().json(response)
+    }
+
+    async fn etherscan_verify_source_code(
+        &self,
+        params: &EtherscanParams,
+    ) -> Result<EtherscanResponse, EtherscanError> {
+        let default_zksolc_version = if required_param(params, "zksolcVersion").is_ok() {
+            None
+        } else {
+            self.latest_zksolc_version().await
+        };
+        let request = parse_verification_request(params, default_zksolc_version.as_deref())?;
+        match self.add_verification_request(request).await {
+            // Etherscan uses GUIDs to identify verification requests; we use request IDs instead.
+            Ok(request_id) => Ok(EtherscanResponse::ok(request_id.to_string())),
+            Err(VerificationRejection::AlreadyVerified) => {
+                Ok(EtherscanResponse::not_ok(ALREADY_VERIFIED))
+            }
+            Err(rejection) => Err(EtherscanError::Rejected(rejection.message())),
+        }
+    }
+
+    async fn latest_zksolc_version(&self) -> Option<String> {
+        let versions = self
+            .replica_connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap()
+            .contract_verification_dal()
+            .get_zksolc_versions()
+            .await
+            .unwrap();
+        latest_compiler_version(&versions).map(str::to_owned)
+    }
+
+    async fn etherscan_check_verify_status(
+        &self,
+        params: &EtherscanParams,
+    ) -> Result<EtherscanResponse, EtherscanError> {
+        let guid = required_param(params, "guid")?;
+        let request_id: usize = guid
+            .parse()
+            .map_err(|_| EtherscanError::InvalidParam("guid", format!("unknown GUID `{guid}`")))?;
+        let status = self
+            .replica_connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap()
+            .contract_verification_dal()
+            .get_verification_request_status(request_id)
+            .await
+            .unwrap()
+            .ok_or_else(|| {
+                EtherscanError::InvalidParam("guid", format!("unknown GUID `{guid}`"))
+            })?;
+
+        Ok(match status.status.as_str() {
+            "successful" => EtherscanResponse::ok(SUCCESS_STATUS),
+            "failed" => EtherscanResponse::not_ok(FAILURE_STATUS),
+            _ => EtherscanResponse::not_ok(PENDING_STATUS),
+        })
+    }
+
+    async fn get_verification_info(
+        &self,
+        params: &EtherscanParams,
+    ) -> Result<Option<VerificationInfo>, EtherscanError> {
+        let address = parse_address(params, "address")?;
+        Ok(self
+            .replica_connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap()
+            .contract_verification_dal()
+            .get_contract_verification_info(address)
+            .await
+            .unwrap())
+    }
+
+    async fn etherscan_get_source_code(
+        &self,
+        params: &EtherscanParams,
+    ) -> Result<EtherscanResponse, EtherscanError> {
+        let source_code = match self.get_verification_info(params).await? {
+            Some(info) => EtherscanSourceCode::new(&info),
+            None => EtherscanSourceCode::not_verified(),
+        };
+        let source_code = serde_json::to_value([source_code]).unwrap();
+        Ok(EtherscanResponse::ok(source_code))
+    }
+
+    async fn etherscan_get_abi(
+        &self,
+        params: &EtherscanParams,
+    ) -> Result<EtherscanResponse, EtherscanError> {
+        Ok(match self.get_verification_info(params).await? {
+            Some(info) => EtherscanResponse::ok(info.artifacts.abi.to_string()),
+            None => EtherscanResponse::not_ok(NOT_VERIFIED),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use actix_web::{
+        test::{call_service, init_service, read_body_json, TestRequest},
+        App,
+    };
+    use assert_matches::assert_matches;
+    use zksync_dal::ConnectionPool;
+    use zksync_types::{L2ChainId, L2_ETH_TOKEN_ADDRESS};
+
+    use super::*;
+    use crate::genesis::{ensure_genesis_state, GenesisParams};
+
+    fn verification_params() -> EtherscanParams {
+        let params = [
+            ("module", "contract"),
+            ("action", "verifysourcecode"),
+            (
+                "contractaddress",
+                "0x1111111111111111111111111111111111111111",
+            ),
+            ("sourceCode", "contract Counter {}"),
+            ("codeformat", "solidity-single-file"),
+            ("contractname", "Counter.sol:Counter"),
+            ("compilerversion", "v0.8.17+commit.8df45f5f"),
+            ("zksolcVersion", "v1.3.14"),
+            ("optimizationUsed", "1"),
+            ("runs", "200"),
+            (
+                "constructorArguements",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+            ),
+        ];
+        params
+            .into_iter()
+            .map(|(name, value)| (name.to_owned(), value.to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn selecting_latest_compiler_version() {
+        let versions = ["v1.3.9", "v1.3.16", "v1.3.14"].map(str::to_owned);
+        assert_eq!(latest_compiler_version(&versions), Some("v1.3.16"));
+        assert_eq!(latest_compiler_version(&[]), None);
+    }
+
+    #[test]
+    fn normalizing_solc_version() {
+        assert_eq!(normalize_solc_version("v0.8.17+commit.8df45f5f"), "0.8.17");
+        assert_eq!(normalize_solc_version("0.8.17"), "0.8.17");
+        assert_eq!(normalize_solc_version("v0.8.17"), "0.8.17");
+    }
+
+    #[test]
+    fn parsing_verification_request() {
+        let request = parse_verification_request(&verification_params(), None).unwrap();
+        assert_eq!(request.contract_address, Address::repeat_byte(0x11));
+        assert_matches!(
+            &request.source_code_data,
+            SourceCodeData::SolSingleFile(source) if source == "contract Counter {}"
+        );
+        assert_eq!(request.contract_name, "Counter.sol:Counter");
+        assert_matches!(
+            &request.compiler_versions,
+            CompilerVersions::Solc { compiler_zksolc_version, compiler_solc_version }
+                if compiler_zksolc_version == "v1.3.14" && compiler_solc_version == "0.8.17"
+        );
+        assert!(request.optimization_used);
+        assert_eq!(request.optimizer_mode, None);
+        assert_eq!(request.constructor_arguments.0.len(), 32);
+        assert_eq!(request.constructor_arguments.0[31], 1);
+        assert!(!request.is_system);
+    }
+
+    #[test]
+    fn parsing_standard_json_verification_request() {
+        let mut params = verification_params();
+        params.insert("codeformat".into(), "solidity-standard-json-input".into());
+        params.insert("sourceCode".into(), r#"{"language":"Solidity"}"#.into());
+        params.remove("optimizationUsed");
+        params.remove("constructorArguements");
+
+        let request = parse_verification_request(&params, None).unwrap();
+        assert_matches!(
+            &request.source_code_data,
+            SourceCodeData::StandardJsonInput(input) if input["language"] == "Solidity"
+        );
+        assert!(!request.optimization_used);
+        assert!(request.constructor_arguments.0.is_empty());
+    }
+
+    #[test]
+    fn parsing_invalid_verification_request() {
+        let mut params = verification_params();
+        params.remove("zksolcVersion");
+        let err = parse_verification_request(&params, None).unwrap_err();
+        assert_matches!(err, EtherscanError::MissingParam("zksolcVersion"));
+        let request = parse_verification_request(&params, Some("v1.3.16")).unwrap();
+        assert_matches!(
+            &request.compiler_versions,
+            CompilerVersions::Solc { compiler_zksolc_version, .. } if compiler_zksolc_version == "v1.3.16"
+        );
+
+        let mut params = verification_params();
+        params.insert("codeformat".into(), "vyper-json".into());
+        let err = parse_verification_request(&params, None).unwrap_err();
+        assert_matches!(err, EtherscanError::UnsupportedCodeFormat(_));
+
+        let mut params = verification_params();
+        params.insert("optimizationUsed".into(), "yes".into());
+        let err = parse_verification_request(&params, None).unwrap_err();
+        assert_matches!(err, EtherscanError::InvalidParam("optimizationUsed", _));
+
+        let mut params = verification_params();
+        params.insert("runs".into(), "1000".into());
+        let err = parse_verification_request(&params, None).unwrap_err();
+        assert_matches!(err, EtherscanError::InvalidParam("runs", _));
+
+        let mut params = verification_params();
+        params.insert("evmversion".into(), "paris; rm".into());
+        let err = parse_verification_request(&params, None).unwrap_err();
+        assert_matches!(err, EtherscanError::InvalidParam("evmversion", _));
+    }
+
+    #[test]
+    fn parsing_verification_request_with_evm_version() {
+        let mut params = verification_params();
+        params.insert("evmversion".into(), "default".into());
+        let request = parse_verification_request(&params, None).unwrap();
+        assert_matches!(request.source_code_data, SourceCodeData::SolSingleFile(_));
+
+        // Single-file source is converted to the standard JSON input.
+        params.insert("evmversion".into(), "paris".into());
+        params.insert("isSystem".into(), "1".into());
+        let request = parse_verification_request(&params, None).unwrap();
+        let SourceCodeData::StandardJsonInput(input) = &request.source_code_data else {
+            panic!("unexpected source code: {:?}", request.source_code_data);
+        };
+        assert_eq!(
+            input["sources"]["Counter.sol"]["content"],
+            "contract Counter {}"
+        );
+        assert_eq!(input["settings"]["evmVersion"], "paris");
+        assert_eq!(input["settings"]["optimizer"]["enabled"], true);
+        assert_eq!(input["settings"]["isSystem"], true);
+
+        params.insert("codeformat".into(), "solidity-standard-json-input".into());
+        params.insert(
+            "sourceCode".into(),
+            r#"{"language":"Solidity","settings":{"optimizer":{"enabled":true}}}"#.into(),
+        );
+        let request = parse_verification_request(&params, None).unwrap();
+        let SourceCodeData::StandardJsonInput(input) = &request.source_code_data else {
+            panic!("unexpected source code: {:?}", request.source_code_data);
+        };
+        assert_eq!(input["settings"]["evmVersion"], "paris");
+
+        params.insert(
+            "sourceCode".into(),
+            r#"{"language":"Solidity","settings":{"evmVersion":"london"}}"#.into(),
+        );
+        let err = parse_verification_request(&params, None).unwrap_err();
+        assert_matches!(err, EtherscanError::InvalidParam("evmversion", _));
+    }
+
+    /// Prepares storage with a deployed contract at [`L2_ETH_TOKEN_ADDRESS`] and supported zksolc versions.
+    async fn prepare_api() -> (ConnectionPool, RestApi) {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+            .await
+            .unwrap();
+        let versions = ["v1.3.14", "v1.3.16", "v1.3.9"].map(str::to_owned).to_vec();
+        storage
+            .contract_verification_dal()
+            .set_zksolc_versions(versions)
+            .await
+            .unwrap();
+        drop(storage);
+
+        let api = RestApi::new(pool.clone(), pool.clone());
+        (pool, api)
+    }
+
+    async fn call_api(
+        api: &RestApi,
+        request: TestRequest,
+    ) -> serde_json::Map<String, serde_json::Value> {
+        let app = init_service(App::new().service(api.clone().into_scope())).await;
+        let response = call_service(&app, request.to_request()).await;
+        assert!(response.status().is_success());
+        let response: serde_json::Value = read_body_json(response).await;
+        response.as_object().unwrap().clone()
+    }
+
+    async fn assert_queued_request(pool: &ConnectionPool, expected_contract_name: &str) {
+        let request = pool
+            .access_storage()
+            .await
+            .unwrap()
+            .contract_verification_dal()
+            .get_next_queued_verification_request(Duration::from_secs(60))
+            .await
+            .unwrap()
+            .expect("no queued request");
+        assert_eq!(request.req.contract_address, L2_ETH_TOKEN_ADDRESS);
+        assert_eq!(request.req.contract_name, expected_contract_name);
+        assert_matches!(
+            &request.req.compiler_versions,
+            CompilerVersions::Solc { compiler_zksolc_version, compiler_solc_version }
+                if compiler_zksolc_version == "v1.3.16" && compiler_solc_version == "0.8.17"
+        );
+    }
+
+    #[actix_rt::test]
+    async fn verifying_contract_with_hardhat_plugin_request() {
+        let (pool, api) = prepare_api().await;
+        // `hardhat-verify` sends all params in the form body and doesn't specify the zksolc version.
+        let standard_json = r#"{"language":"Solidity","sources":{"contracts/Counter.sol":{"content":"contract Counter {}"}},"settings":{"optimizer":{"enabled":true}}}"#;
+        let contract_address = format!("{L2_ETH_TOKEN_ADDRESS:?}");
+        let form = [
+            ("apikey", "test"),
+            ("module", "contract"),
+            ("action", "verifysourcecode"),
+            ("contractaddress", contract_address.as_str()),
+            ("sourceCode", standard_json),
+            ("codeformat", "solidity-standard-json-input"),
+            ("contractname", "contracts/Counter.sol:Counter"),
+            ("compilerversion", "v0.8.17+commit.8df45f5f"),
+            ("constructorArguements", ""),
+        ];
+        let request = TestRequest::post().uri("/api").set_form(&form[..]);
+        let response = call_api(&api, request).await;
+        assert_eq!(response["status"], "1", "{response:?}");
+        let guid = response["result"].as_str().unwrap().to_owned();
+
+        let request = TestRequest::get().uri(&format!(
+            "/api?apikey=test&module=contract&action=checkverifystatus&guid={guid}"
+        ));
+        let response = call_api(&api, request).await;
+        assert_eq!(response["status"], "0", "{response:?}");
+        assert_eq!(response["result"], PENDING_STATUS);
+
+        assert_queued_request(&pool, "contracts/Counter.sol:Counter").await;
+    }
+
+    #[actix_rt::test]
+    async fn verifying_contract_with_foundry_request() {
+        let (pool, api) = prepare_api().await;
+        // `forge verify-contract` passes `module` and `action` in the query string, and the remaining params
+        // in the form body.
+        let contract_address = format!("{L2_ETH_TOKEN_ADDRESS:?}");
+        let form = [
+            ("apikey", "test"),
+            ("contractaddress", contract_address.as_str()),
+            ("sourceCode", "contract Counter {}"),
+            ("codeformat", "solidity-single-file"),
+            ("contractname", "src/Counter.sol:Counter"),
+            ("compilerversion", "v0.8.17+commit.8df45f5f"),
+            ("optimizationUsed", "1"),
+            ("runs", "200"),
+            ("evmversion", "paris"),
+            ("licenseType", "3"),
+            (
+                "constructorArguements",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+            ),
+        ];
+        let request = TestRequest::post()
+            .uri("/api?module=contract&action=verifysourcecode")
+            .set_form(&form[..]);
+        let response = call_api(&api, request).await;
+        assert_eq!(response["status"], "1", "{response:?}");
+
+        assert_queued_request(&pool, "src/Counter.sol:Counter").await;
+    }
+
+    #[actix_rt::test]
+    async fn rejecting_verification_request_for_missing_contract() {
+        let (_, api) = prepare_api().await;
+        let form = [
+            ("module", "contract"),
+            ("action", "verifysourcecode"),
+            (
+                "contractaddress",
+                "0x1111111111111111111111111111111111111111",
+            ),
+            ("sourceCode", "contract Counter {}"),
+            ("contractname", "Counter.sol:Counter"),
+            ("compilerversion", "v0.8.17+commit.8df45f5f"),
+        ];
+        let request = TestRequest::post().uri("/api").set_form(&form[..]);
+        let response = call_api(&api, request).await;
+        assert_eq!(response["status"], "0", "{response:?}");
+        assert_eq!(
+            response["result"],
+            VerificationRejection::NotDeployed.message()
+        );
+    }
+}
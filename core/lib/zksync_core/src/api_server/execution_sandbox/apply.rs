@@ -30,6 +30,7 @@ use zksync_types::{
 use zksync_utils::{h256_to_u256, time::seconds_since_epoch, u256_to_h256};
 
 use super::{
+    storage::StorageWithOverrides,
     vm_metrics::{self, SandboxStage, SANDBOX_METRICS},
     BlockArgs, TxExecutionArgs, TxSharedArgs, VmPermit,
 };
@@ -43,7 +44,7 @@ pub(super) fn apply_vm_in_sandbox<T>(
     tx: Transaction,
     block_args: BlockArgs,
     apply: impl FnOnce(
-        &mut VmInstance<StorageView<PostgresStorage<'_>>, HistoryDisabled>,
+        &mut VmInstance<StorageView<StorageWithOverrides<PostgresStorage<'_>>>, HistoryDisabled>,
         Transaction,
     ) -> T,
 ) -> T {
@@ -128,6 +129,7 @@ pub(super) fn apply_vm_in_sandbox<T>(
 
     let storage = PostgresStorage::new(rt_handle.clone(), connection, state_l2_block_number, false)
         .with_caches(shared_args.caches);
+    let storage = StorageWithOverrides::new(storage, execution_args.state_override.as_ref());
     let mut storage_view = StorageView::new(storage);
 
     let storage_view_setup_started_at = Instant::now();
@@ -141,8 +143,9 @@ pub(super) fn apply_vm_in_sandbox<T>(
 
     let payer = tx.payer();
     let balance_key = storage_key_for_eth_balance(&payer);
-    let mut current_balance = h256_to_u256(storage_view.read_value(&balance_key));
-    current_balance += execution_args.added_balance;
+    let current_balance = h256_to_u256(storage_view.read_value(&balance_key));
+    // The balance may be overridden to an arbitrary value, so the addition may overflow.
+    let current_balance = current_balance.saturating_add(execution_args.added_balance);
     storage_view.set_value(balance_key, u256_to_h256(current_balance));
 
     // Reset L2 block info.
@@ -1,7 +1,11 @@
 //! Implementation of "executing" methods, e.g. `eth_call`.
 
+use std::iter;
+
 use multivm::{
-    interface::{TxExecutionMode, VmExecutionMode, VmExecutionResultAndLogs, VmInterface},
+    interface::{
+        ExecutionResult, TxExecutionMode, VmExecutionMode, VmExecutionResultAndLogs, VmInterface,
+    },
     tracers::StorageInvocations,
     vm_latest::constants::ETH_CALL_GAS_LIMIT,
     MultiVMTracer,
@@ -9,8 +13,8 @@ use multivm::{
 use tracing::{span, Level};
 use zksync_dal::ConnectionPool;
 use zksync_types::{
-    fee::TransactionExecutionMetrics, l2::L2Tx, ExecuteTransactionCommon, Nonce,
-    PackedEthSignature, Transaction, U256,
+    api::StateOverride, fee::TransactionExecutionMetrics, l2::L2Tx, ExecuteTransactionCommon,
+    Nonce, PackedEthSignature, Transaction, U256,
 };
 
 use super::{apply, vm_metrics, ApiTracer, BlockArgs, TxSharedArgs, VmPermit};
@@ -22,6 +26,8 @@ pub(crate) struct TxExecutionArgs {
     pub added_balance: U256,
     pub enforced_base_fee: Option<u64>,
     pub missed_storage_invocation_limit: usize,
+    /// State overrides applied on top of the Postgres state; must be validated beforehand.
+    pub state_override: Option<StateOverride>,
 }
 
 impl TxExecutionArgs {
@@ -32,12 +38,14 @@ impl TxExecutionArgs {
             added_balance: U256::zero(),
             enforced_base_fee: Some(tx.common_data.fee.max_fee_per_gas.as_u64()),
             missed_storage_invocation_limit: usize::MAX,
+            state_override: None,
         }
     }
 
     fn for_eth_call(
         enforced_base_fee: u64,
         vm_execution_cache_misses_limit: Option<usize>,
+        state_override: Option<StateOverride>,
     ) -> Self {
         let missed_storage_invocation_limit = vm_execution_cache_misses_limit.unwrap_or(usize::MAX);
         Self {
@@ -46,6 +54,7 @@ impl TxExecutionArgs {
             added_balance: U256::zero(),
             enforced_base_fee: Some(enforced_base_fee),
             missed_storage_invocation_limit,
+            state_override,
         }
     }
 
@@ -53,6 +62,7 @@ impl TxExecutionArgs {
         vm_execution_cache_misses_limit: Option<usize>,
         tx: &Transaction,
         base_fee: u64,
+        state_override: Option<StateOverride>,
     ) -> Self {
         let missed_storage_invocation_limit = vm_execution_cache_misses_limit.unwrap_or(usize::MAX);
         // For L2 transactions we need to explicitly put enough balance into the account of the users
@@ -63,16 +73,25 @@ impl TxExecutionArgs {
             ExecuteTransactionCommon::ProtocolUpgrade(_) => U256::zero(),
         };
 
+        // If the initiator nonce is overridden, it must not be replaced with the transaction nonce. The consistency
+        // of the two nonces is checked when processing the request.
+        let has_nonce_override = state_override
+            .as_ref()
+            .and_then(|overrides| overrides.get(&tx.initiator_account()))
+            .map_or(false, |account| account.nonce.is_some());
+        let enforced_nonce = tx.nonce().filter(|_| !has_nonce_override);
         Self {
             execution_mode: TxExecutionMode::EstimateFee,
             missed_storage_invocation_limit,
-            enforced_nonce: tx.nonce(),
+            enforced_nonce,
             added_balance,
             enforced_base_fee: Some(base_fee),
+            state_override,
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn execute_tx_eth_call(
     vm_permit: VmPermit,
     shared_args: TxSharedArgs,
@@ -81,19 +100,15 @@ pub(crate) async fn execute_tx_eth_call(
     block_args: BlockArgs,
     vm_execution_cache_misses_limit: Option<usize>,
     custom_tracers: Vec<ApiTracer>,
+    state_override: Option<StateOverride>,
 ) -> VmExecutionResultAndLogs {
     let enforced_base_fee = tx.common_data.fee.max_fee_per_gas.as_u64();
-    let execution_args =
-        TxExecutionArgs::for_eth_call(enforced_base_fee, vm_execution_cache_misses_limit);
-
-    if tx.common_data.signature.is_empty() {
-        tx.common_data.signature = PackedEthSignature::default().serialize_packed().into();
-    }
-
-    // Protection against infinite-loop eth_calls and alike:
-    // limiting the amount of gas the call can use.
-    // We can't use BLOCK_ERGS_LIMIT here since the VM itself has some overhead.
-    tx.common_data.fee.gas_limit = ETH_CALL_GAS_LIMIT.into();
+    let execution_args = TxExecutionArgs::for_eth_call(
+        enforced_base_fee,
+        vm_execution_cache_misses_limit,
+        state_override,
+    );
+    prepare_tx_for_eth_call(&mut tx);
     let (vm_result, _) = execute_tx_in_sandbox(
         vm_permit,
         shared_args,
@@ -108,6 +123,100 @@ pub(crate) async fn execute_tx_eth_call(
     vm_result
 }
 
+fn prepare_tx_for_eth_call(tx: &mut L2Tx) {
+    if tx.common_data.signature.is_empty() {
+        tx.common_data.signature = PackedEthSignature::default().serialize_packed().into();
+    }
+
+    // Protection against infinite-loop eth_calls and alike:
+    // limiting the amount of gas the call can use.
+    // We can't use BLOCK_ERGS_LIMIT here since the VM itself has some overhead.
+    tx.common_data.fee.gas_limit = ETH_CALL_GAS_LIMIT.into();
+}
+
+/// Executes an ordered bundle of `eth_call`-like transactions in a single VM instance, so that each transaction
+/// observes state changes made by the previous ones. Each transaction is traced by its own set of `custom_tracers`.
+///
+/// Returns execution results in the order of the provided transactions. Execution stops after the first halted
+/// transaction, so the returned results may be fewer than the provided transactions.
+pub(crate) async fn execute_bundle_eth_call(
+    vm_permit: VmPermit,
+    shared_args: TxSharedArgs,
+    connection_pool: ConnectionPool,
+    txs: Vec<(L2Tx, Vec<ApiTracer>)>,
+    block_args: BlockArgs,
+    vm_execution_cache_misses_limit: Option<usize>,
+    state_override: Option<StateOverride>,
+) -> Vec<VmExecutionResultAndLogs> {
+    assert!(!txs.is_empty(), "Cannot execute an empty bundle");
+
+    // All transactions are executed in the same block, so the base fee must be acceptable for each of them.
+    let enforced_base_fee = txs
+        .iter()
+        .map(|(tx, _)| tx.common_data.fee.max_fee_per_gas.as_u64())
+        .min()
+        .unwrap();
+    let execution_args = TxExecutionArgs::for_eth_call(
+        enforced_base_fee,
+        vm_execution_cache_misses_limit,
+        state_override,
+    );
+    let mut txs = txs.into_iter().map(|(mut tx, tracers)| {
+        prepare_tx_for_eth_call(&mut tx);
+        (Transaction::from(tx), tracers)
+    });
+    let (first_tx, first_tx_tracers) = txs.next().unwrap();
+
+    tokio::task::spawn_blocking(move || {
+        let span = span!(Level::DEBUG, "execute_bundle_in_sandbox").entered();
+        let results = apply::apply_vm_in_sandbox(
+            vm_permit,
+            shared_args,
+            &execution_args,
+            &connection_pool,
+            first_tx,
+            block_args,
+            |vm, first_tx| {
+                let txs = iter::once((first_tx, first_tx_tracers)).chain(txs);
+                execute_until_halt(txs, |(tx, custom_tracers)| {
+                    vm.push_transaction(tx);
+                    // The limit is applied to each transaction separately, like for `eth_call`.
+                    let storage_invocation_tracer =
+                        StorageInvocations::new(execution_args.missed_storage_invocation_limit);
+                    let custom_tracers: Vec<_> = custom_tracers
+                        .into_iter()
+                        .map(|tracer| tracer.into_boxed())
+                        .chain([storage_invocation_tracer.into_tracer_pointer()])
+                        .collect();
+                    vm.inspect(custom_tracers.into(), VmExecutionMode::OneTx)
+                })
+            },
+        );
+        span.exit();
+        results
+    })
+    .await
+    .unwrap()
+}
+
+/// Executes transactions one by one until the first halted one. A halted transaction is not rolled back
+/// by the sandbox VM, so the VM state is unreliable for executing subsequent transactions.
+fn execute_until_halt<T>(
+    txs: impl IntoIterator<Item = T>,
+    mut execute_tx: impl FnMut(T) -> VmExecutionResultAndLogs,
+) -> Vec<VmExecutionResultAndLogs> {
+    let mut results = vec![];
+    for tx in txs {
+        let result = execute_tx(tx);
+        let is_halted = matches!(result.result, ExecutionResult::Halt { .. });
+        results.push(result);
+        if is_halted {
+            break;
+        }
+    }
+    results
+}
+
 #[tracing::instrument(skip_all)]
 pub(crate) async fn execute_tx_with_pending_state(
     vm_permit: VmPermit,
@@ -185,3 +294,47 @@ async fn execute_tx_in_sandbox(
         vm_metrics::collect_tx_execution_metrics(total_factory_deps, &execution_result);
     (execution_result, tx_execution_metrics)
 }
+
+#[cfg(test)]
+mod tests {
+    use multivm::{
+        interface::{Halt, Refunds, VmExecutionStatistics},
+        vm_latest::VmExecutionLogs,
+    };
+
+    use super::*;
+
+    fn mock_result(result: ExecutionResult) -> VmExecutionResultAndLogs {
+        VmExecutionResultAndLogs {
+            result,
+            logs: VmExecutionLogs::default(),
+            statistics: VmExecutionStatistics::default(),
+            refunds: Refunds::default(),
+        }
+    }
+
+    #[test]
+    fn bundle_execution_stops_at_first_halt() {
+        let outcomes = [
+            ExecutionResult::Success { output: vec![1] },
+            ExecutionResult::Halt {
+                reason: Halt::InnerTxError,
+            },
+            ExecutionResult::Success { output: vec![2] },
+        ];
+        let mut executed_txs = vec![];
+        let results = execute_until_halt(0..outcomes.len(), |i| {
+            executed_txs.push(i);
+            mock_result(outcomes[i].clone())
+        });
+
+        assert_eq!(executed_txs, [0, 1]);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].result, outcomes[0]);
+        assert_eq!(results[1].result, outcomes[1]);
+
+        // Without halts, all transactions are executed.
+        let results = execute_until_halt([0, 2], |i| mock_result(outcomes[i].clone()));
+        assert_eq!(results.len(), 2);
+    }
+}
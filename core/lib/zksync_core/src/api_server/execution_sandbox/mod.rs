@@ -11,7 +11,12 @@ use zksync_utils::bytecode::{compress_bytecode, hash_bytecode};
 use self::vm_metrics::SandboxStage;
 pub(super) use self::{
     error::SandboxExecutionError,
-    execute::{execute_tx_eth_call, execute_tx_with_pending_state, TxExecutionArgs},
+    execute::{
+        execute_bundle_eth_call, execute_tx_eth_call, execute_tx_with_pending_state,
+        TxExecutionArgs,
+    },
+    replay::{replay_l1_batch, ReplayRange, ReplayTracer},
+    storage::validate_state_override,
     tracers::ApiTracer,
     vm_metrics::{SubmitTxStage, SANDBOX_METRICS},
 };
@@ -21,6 +26,8 @@ use super::tx_sender::MultiVMBaseSystemContracts;
 mod apply;
 mod error;
 mod execute;
+mod replay;
+mod storage;
 mod tracers;
 mod validate;
 mod vm_metrics;
@@ -0,0 +1,157 @@
+//! Re-execution of sealed L1 batches, used to trace historical transactions and batches.
+
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use multivm::{
+    interface::{FinishedL1Batch, L2BlockEnv, VmExecutionResultAndLogs, VmInterface},
+    tracers::{CallTracer, PrestateTracer},
+    vm_latest::HistoryEnabled,
+    MultiVMTracer, VmInstance,
+};
+use once_cell::sync::OnceCell;
+use zksync_dal::ConnectionPool;
+use zksync_state::WriteStorage;
+use zksync_types::{
+    vm_trace::{Call, TouchedStorageSlot},
+    L1BatchNumber, L2ChainId, MiniblockNumber, Transaction, H256,
+};
+
+use super::VmPermit;
+use crate::basic_witness_input_producer::vm_interactions::{self, create_vm};
+
+/// Tracer collecting data for each re-executed transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReplayTracer {
+    /// Only execution results are collected.
+    None,
+    /// Collects call traces.
+    Call,
+    /// Collects storage slots touched by each transaction, together with their values before and after it.
+    Prestate,
+}
+
+/// Part of an L1 batch to re-execute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReplayRange {
+    /// All transactions in the batch, followed by the batch tip.
+    L1Batch,
+    /// Transactions up to and including the last transaction in the specified miniblock.
+    UpToMiniblock(MiniblockNumber),
+    /// Transactions up to and including the specified transaction.
+    UpToTransaction(H256),
+}
+
+/// Transaction re-executed as a part of an L1 batch.
+#[derive(Debug)]
+pub(crate) struct ReplayedTransaction {
+    pub tx: Transaction,
+    pub miniblock_number: MiniblockNumber,
+    pub result: VmExecutionResultAndLogs,
+    /// Empty unless call traces were requested.
+    pub call_trace: Vec<Call>,
+    /// Empty unless touched storage slots were requested.
+    pub touched_slots: Vec<TouchedStorageSlot>,
+}
+
+/// Outcome of re-executing an L1 batch.
+#[derive(Debug)]
+pub(crate) struct ReplayedL1Batch {
+    pub transactions: Vec<ReplayedTransaction>,
+    /// Set only if the entire batch was re-executed.
+    pub finished_batch: Option<FinishedL1Batch>,
+}
+
+/// Re-executes transactions in a sealed L1 batch the same way the state keeper has executed them,
+/// starting from the state at the end of the previous batch.
+///
+/// The batch must be sealed and must not be the genesis batch.
+pub(crate) async fn replay_l1_batch(
+    vm_permit: VmPermit,
+    connection_pool: ConnectionPool,
+    chain_id: L2ChainId,
+    l1_batch_number: L1BatchNumber,
+    range: ReplayRange,
+    tracer: ReplayTracer,
+) -> anyhow::Result<ReplayedL1Batch> {
+    tokio::task::spawn_blocking(move || {
+        let rt_handle = vm_permit.rt_handle().clone();
+        let mut connection = rt_handle.block_on(connection_pool.access_storage_tagged("api"))?;
+        let miniblocks = rt_handle.block_on(
+            connection
+                .transactions_dal()
+                .get_miniblocks_to_execute_for_l1_batch(l1_batch_number),
+        )?;
+        let (mut vm, _) = create_vm(rt_handle, l1_batch_number, connection, chain_id)?;
+
+        let mut transactions = vec![];
+        let next_miniblocks = miniblocks.iter().skip(1).map(Some).chain([None]);
+        'miniblocks: for (miniblock, next_miniblock) in miniblocks.iter().zip(next_miniblocks) {
+            for tx in &miniblock.txs {
+                let tx_hash = tx.hash();
+                let replayed_tx = execute_tx(&mut vm, tx, miniblock.number, tracer)
+                    .with_context(|| format!("failed re-executing transaction {tx_hash:?}"))?;
+                transactions.push(replayed_tx);
+                if range == ReplayRange::UpToTransaction(tx_hash) {
+                    break 'miniblocks;
+                }
+            }
+            if range == ReplayRange::UpToMiniblock(miniblock.number) {
+                break;
+            }
+            if let Some(next_miniblock) = next_miniblock {
+                vm.start_new_l2_block(L2BlockEnv::from_miniblock_data(next_miniblock));
+            }
+        }
+        let finished_batch = (range == ReplayRange::L1Batch).then(|| vm.finish_batch());
+
+        drop(vm_permit); // Ensure that the permit lives until this point.
+        Ok(ReplayedL1Batch {
+            transactions,
+            finished_batch,
+        })
+    })
+    .await
+    .context("L1 batch replay panicked")?
+}
+
+fn execute_tx<S: WriteStorage>(
+    vm: &mut VmInstance<S, HistoryEnabled>,
+    tx: &Transaction,
+    miniblock_number: MiniblockNumber,
+    tracer: ReplayTracer,
+) -> anyhow::Result<ReplayedTransaction> {
+    let mut call_tracer_result = Arc::default();
+    let mut prestate_tracer_result = Arc::default();
+    let create_tracers = || {
+        call_tracer_result = Arc::new(OnceCell::default());
+        prestate_tracer_result = Arc::new(OnceCell::default());
+        match tracer {
+            ReplayTracer::None => vec![],
+            ReplayTracer::Call => {
+                vec![CallTracer::new(call_tracer_result.clone()).into_tracer_pointer()]
+            }
+            ReplayTracer::Prestate => {
+                vec![PrestateTracer::new(prestate_tracer_result.clone()).into_tracer_pointer()]
+            }
+        }
+    };
+    let result = vm_interactions::execute_tx(tx, vm, create_tracers)?;
+
+    // Tracers are dropped after execution, so these are the only copies of the `Arc`s.
+    let call_trace = Arc::try_unwrap(call_tracer_result)
+        .unwrap()
+        .take()
+        .unwrap_or_default();
+    let touched_slots = Arc::try_unwrap(prestate_tracer_result)
+        .unwrap()
+        .take()
+        .unwrap_or_default();
+    Ok(ReplayedTransaction {
+        tx: tx.clone(),
+        miniblock_number,
+        result,
+        call_trace,
+        touched_slots,
+    })
+}
@@ -0,0 +1,270 @@
+//! VM storage functionality specifically used in the VM sandbox.
+
+use std::collections::{HashMap, HashSet};
+
+use zksync_state::ReadStorage;
+use zksync_types::{
+    api::StateOverride,
+    get_code_key, get_known_code_key, get_nonce_key,
+    utils::{decompose_full_nonce, nonces_to_full_nonce, storage_key_for_eth_balance},
+    AccountTreeId, Address, StorageKey, StorageValue, H256, U256,
+};
+use zksync_utils::{
+    bytecode::{hash_bytecode, validate_bytecode},
+    h256_to_u256, u256_to_h256,
+};
+
+/// Checks that state overrides can be applied by [`StorageWithOverrides`].
+pub(crate) fn validate_state_override(state_override: &StateOverride) -> Result<(), String> {
+    for (address, account) in state_override {
+        if account.state.is_some() && account.state_diff.is_some() {
+            return Err(format!(
+                "account {address:?} has both `state` and `stateDiff` overrides"
+            ));
+        }
+        if let Some(nonce) = account.nonce {
+            if nonce > U256::from(u32::MAX) {
+                return Err(format!(
+                    "nonce override for account {address:?} is too large"
+                ));
+            }
+        }
+        if let Some(code) = &account.code {
+            if !code.0.is_empty() {
+                validate_bytecode(&code.0).map_err(|err| {
+                    format!("invalid bytecode override for account {address:?}: {err}")
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Storage applying [`StateOverride`]s on top of the wrapped storage.
+///
+/// Overrides must be validated with [`validate_state_override()`] beforehand.
+#[derive(Debug)]
+pub(super) struct StorageWithOverrides<S> {
+    storage_handle: S,
+    overridden_slots: HashMap<StorageKey, StorageValue>,
+    overridden_factory_deps: HashMap<H256, Vec<u8>>,
+    /// Accounts with the fully replaced storage.
+    overridden_accounts: HashSet<Address>,
+}
+
+impl<S: ReadStorage> StorageWithOverrides<S> {
+    pub fn new(storage_handle: S, state_override: Option<&StateOverride>) -> Self {
+        let mut this = Self {
+            storage_handle,
+            overridden_slots: HashMap::new(),
+            overridden_factory_deps: HashMap::new(),
+            overridden_accounts: HashSet::new(),
+        };
+        let Some(state_override) = state_override else {
+            return this;
+        };
+
+        for (&address, account) in state_override {
+            if let Some(balance) = account.balance {
+                let balance_key = storage_key_for_eth_balance(&address);
+                this.overridden_slots
+                    .insert(balance_key, u256_to_h256(balance));
+            }
+            if let Some(nonce) = account.nonce {
+                // Only the transaction nonce is overridden; the deployment nonce is retained.
+                let nonce_key = get_nonce_key(&address);
+                let full_nonce = this.storage_handle.read_value(&nonce_key);
+                let (_, deployment_nonce) = decompose_full_nonce(h256_to_u256(full_nonce));
+                let full_nonce = nonces_to_full_nonce(nonce, deployment_nonce);
+                this.overridden_slots
+                    .insert(nonce_key, u256_to_h256(full_nonce));
+            }
+            if let Some(code) = &account.code {
+                let code_key = get_code_key(&address);
+                if code.0.is_empty() {
+                    this.overridden_slots.insert(code_key, H256::zero());
+                } else {
+                    let code_hash = hash_bytecode(&code.0);
+                    this.overridden_slots.insert(code_key, code_hash);
+                    this.overridden_slots
+                        .insert(get_known_code_key(&code_hash), H256::from_low_u64_be(1));
+                    this.overridden_factory_deps
+                        .insert(code_hash, code.0.clone());
+                }
+            }
+
+            let account_id = AccountTreeId::new(address);
+            if let Some(state) = &account.state {
+                this.overridden_accounts.insert(address);
+                for (&key, &value) in state {
+                    let key = StorageKey::new(account_id, key);
+                    this.overridden_slots.insert(key, value);
+                }
+            }
+            if let Some(state_diff) = &account.state_diff {
+                for (&key, &value) in state_diff {
+                    let key = StorageKey::new(account_id, key);
+                    this.overridden_slots.insert(key, value);
+                }
+            }
+        }
+        this
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for StorageWithOverrides<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        if let Some(&value) = self.overridden_slots.get(key) {
+            return value;
+        }
+        if self.overridden_accounts.contains(key.address()) {
+            return H256::zero();
+        }
+        self.storage_handle.read_value(key)
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        // Slots of accounts with the fully replaced storage are considered never written to,
+        // unless they are included into the override.
+        if self.overridden_accounts.contains(key.address())
+            && !self.overridden_slots.contains_key(key)
+        {
+            return true;
+        }
+        self.storage_handle.is_write_initial(key)
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        if let Some(dep) = self.overridden_factory_deps.get(&hash) {
+            return Some(dep.clone());
+        }
+        self.storage_handle.load_factory_dep(hash)
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        if self.overridden_accounts.contains(key.address())
+            && !self.overridden_slots.contains_key(key)
+        {
+            return None;
+        }
+        self.storage_handle.get_enumeration_index(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_state::InMemoryStorage;
+    use zksync_types::{api::OverrideAccount, Bytes};
+
+    use super::*;
+
+    #[test]
+    fn applying_state_overrides() {
+        let account = Address::repeat_byte(1);
+        let other_account = Address::repeat_byte(2);
+        let slot = |address, key: u64| {
+            StorageKey::new(AccountTreeId::new(address), H256::from_low_u64_be(key))
+        };
+
+        let mut storage = InMemoryStorage::with_system_contracts(hash_bytecode);
+        storage.set_value(slot(account, 1), H256::repeat_byte(1));
+        storage.set_value(slot(account, 2), H256::repeat_byte(2));
+        storage.set_value(slot(other_account, 1), H256::repeat_byte(1));
+        storage.set_value(slot(other_account, 2), H256::repeat_byte(2));
+        let full_nonce = nonces_to_full_nonce(5.into(), 3.into());
+        storage.set_value(get_nonce_key(&account), u256_to_h256(full_nonce));
+
+        let code = vec![0_u8; 32];
+        let state_override = StateOverride::from([
+            (
+                account,
+                OverrideAccount {
+                    balance: Some(100.into()),
+                    nonce: Some(10.into()),
+                    code: Some(Bytes(code.clone())),
+                    state: Some(HashMap::from([(
+                        H256::from_low_u64_be(1),
+                        H256::repeat_byte(0xff),
+                    )])),
+                    state_diff: None,
+                },
+            ),
+            (
+                other_account,
+                OverrideAccount {
+                    state_diff: Some(HashMap::from([(
+                        H256::from_low_u64_be(1),
+                        H256::repeat_byte(0xff),
+                    )])),
+                    ..OverrideAccount::default()
+                },
+            ),
+        ]);
+        validate_state_override(&state_override).unwrap();
+        let mut storage = StorageWithOverrides::new(storage, Some(&state_override));
+
+        let balance = storage.read_value(&storage_key_for_eth_balance(&account));
+        assert_eq!(h256_to_u256(balance), 100.into());
+        let full_nonce = storage.read_value(&get_nonce_key(&account));
+        let (nonce, deployment_nonce) = decompose_full_nonce(h256_to_u256(full_nonce));
+        assert_eq!((nonce, deployment_nonce), (10.into(), 3.into()));
+
+        let code_hash = storage.read_value(&get_code_key(&account));
+        assert_eq!(code_hash, hash_bytecode(&code));
+        assert!(storage.is_bytecode_known(&code_hash));
+        assert_eq!(storage.load_factory_dep(code_hash), Some(code));
+
+        assert_eq!(
+            storage.read_value(&slot(account, 1)),
+            H256::repeat_byte(0xff)
+        );
+        assert_eq!(storage.read_value(&slot(account, 2)), H256::zero());
+        assert_eq!(
+            storage.read_value(&slot(other_account, 1)),
+            H256::repeat_byte(0xff)
+        );
+        assert_eq!(
+            storage.read_value(&slot(other_account, 2)),
+            H256::repeat_byte(2)
+        );
+
+        // Storage of `account` is fully replaced, so its slots not mentioned in the override are considered new.
+        assert!(storage.is_write_initial(&slot(account, 2)));
+        assert_eq!(storage.get_enumeration_index(&slot(account, 2)), None);
+        assert!(!storage.is_write_initial(&slot(other_account, 2)));
+    }
+
+    #[test]
+    fn validating_state_overrides() {
+        let account = Address::repeat_byte(1);
+        let state_override = StateOverride::from([(
+            account,
+            OverrideAccount {
+                state: Some(HashMap::new()),
+                state_diff: Some(HashMap::new()),
+                ..OverrideAccount::default()
+            },
+        )]);
+        let err = validate_state_override(&state_override).unwrap_err();
+        assert!(err.contains("stateDiff"), "{err}");
+
+        let state_override = StateOverride::from([(
+            account,
+            OverrideAccount {
+                code: Some(Bytes(vec![0; 64])),
+                ..OverrideAccount::default()
+            },
+        )]);
+        let err = validate_state_override(&state_override).unwrap_err();
+        assert!(err.contains("invalid bytecode"), "{err}");
+
+        let state_override = StateOverride::from([(
+            account,
+            OverrideAccount {
+                code: Some(Bytes(vec![])),
+                ..OverrideAccount::default()
+            },
+        )]);
+        validate_state_override(&state_override).unwrap();
+    }
+}
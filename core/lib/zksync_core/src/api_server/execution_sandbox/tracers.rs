@@ -1,14 +1,19 @@
 use std::sync::Arc;
 
-use multivm::{tracers::CallTracer, vm_latest::HistoryMode, MultiVMTracer, MultiVmTracerPointer};
+use multivm::{
+    tracers::{CallTracer, PrestateTracer},
+    vm_latest::HistoryMode,
+    MultiVMTracer, MultiVmTracerPointer,
+};
 use once_cell::sync::OnceCell;
 use zksync_state::WriteStorage;
-use zksync_types::vm_trace::Call;
+use zksync_types::vm_trace::{Call, TouchedStorageSlot};
 
 /// Custom tracers supported by our API
 #[derive(Debug)]
 pub(crate) enum ApiTracer {
     CallTracer(Arc<OnceCell<Vec<Call>>>),
+    PrestateTracer(Arc<OnceCell<Vec<TouchedStorageSlot>>>),
 }
 
 impl ApiTracer {
@@ -20,6 +25,9 @@ impl ApiTracer {
     ) -> MultiVmTracerPointer<S, H> {
         match self {
             ApiTracer::CallTracer(tracer) => CallTracer::new(tracer.clone()).into_tracer_pointer(),
+            ApiTracer::PrestateTracer(tracer) => {
+                PrestateTracer::new(tracer.clone()).into_tracer_pointer()
+            }
         }
     }
 }
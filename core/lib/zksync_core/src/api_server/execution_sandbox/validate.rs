@@ -10,7 +10,10 @@ use multivm::{
     MultiVMTracer,
 };
 use zksync_dal::{ConnectionPool, StorageProcessor};
-use zksync_types::{l2::L2Tx, Transaction, TRUSTED_ADDRESS_SLOTS, TRUSTED_TOKEN_SLOTS, U256};
+use zksync_types::{
+    api::ValidationDiagnostics, l2::L2Tx, Transaction, TRUSTED_ADDRESS_SLOTS, TRUSTED_TOKEN_SLOTS,
+    U256,
+};
 
 use super::{
     adjust_l1_gas_price_for_tx, apply,
@@ -25,7 +28,7 @@ impl TxSharedArgs {
         connection_pool: ConnectionPool,
         tx: L2Tx,
         computational_gas_limit: u32,
-    ) -> Result<(), ValidationError> {
+    ) -> ValidationDiagnostics {
         let mut connection = connection_pool.access_storage_tagged("api").await.unwrap();
         let block_args = BlockArgs::pending(&mut connection).await;
         drop(connection);
@@ -57,7 +60,7 @@ impl TxSharedArgs {
         tx: L2Tx,
         block_args: BlockArgs,
         computational_gas_limit: u32,
-    ) -> Result<(), ValidationError> {
+    ) -> ValidationDiagnostics {
         let stage_latency = SANDBOX_METRICS.sandbox[&SandboxStage::ValidateInSandbox].start();
         let mut connection = connection_pool.access_storage_tagged("api").await.unwrap();
         let validation_params =
@@ -83,6 +86,7 @@ impl TxSharedArgs {
 
                     let (tracer, validation_result) =
                         ValidationTracer::<HistoryDisabled>::new(validation_params);
+                    let diagnostics = tracer.diagnostics();
 
                     let result = vm.inspect(
                         vec![
@@ -94,12 +98,18 @@ impl TxSharedArgs {
                         VmExecutionMode::OneTx,
                     );
 
-                    let result = match (result.result, validation_result.get()) {
-                        (_, Some(err)) => Err(ValidationError::ViolatedRule(err.clone())),
+                    let error = match (result.result, validation_result.get()) {
+                        (_, Some(err)) => Some(ValidationError::ViolatedRule(err.clone())),
                         (ExecutionResult::Halt { reason }, _) => {
-                            Err(ValidationError::FailedTx(reason))
+                            Some(ValidationError::FailedTx(reason))
                         }
-                        (_, None) => Ok(()),
+                        (_, None) => None,
+                    };
+                    let result = ValidationDiagnostics {
+                        error: error.map(|err| err.to_string()),
+                        violated_rule: diagnostics.violation().cloned().map(Into::into),
+                        computational_gas_used: diagnostics.computational_gas_used(),
+                        computational_gas_limit,
                     };
 
                     stage_latency.observe();
@@ -20,9 +20,13 @@ use multivm::{
 };
 use zksync_config::configs::{api::Web3JsonRpcConfig, chain::StateKeeperConfig};
 use zksync_contracts::BaseSystemContracts;
-use zksync_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool};
+use zksync_dal::{
+    transactions_dal::{L2TxInsertionLimits, L2TxSubmissionResult},
+    ConnectionPool,
+};
 use zksync_state::PostgresStorageCaches;
 use zksync_types::{
+    api::{StateOverride, ValidationDiagnostics},
     fee::{Fee, TransactionExecutionMetrics},
     get_code_key, get_intrinsic_constants,
     l2::{error::TxCheckError::TxDuplication, L2Tx},
@@ -31,21 +35,23 @@ use zksync_types::{
     ProtocolVersionId, Transaction, H160, H256, MAX_GAS_PER_PUBDATA_BYTE, MAX_L2_TX_GAS_LIMIT,
     MAX_NEW_FACTORY_DEPS, U256,
 };
-use zksync_utils::h256_to_u256;
+use zksync_utils::{bytecode::hash_bytecode, h256_to_u256};
 
 pub(super) use self::{proxy::TxProxy, result::SubmitTxError};
 use crate::{
     api_server::{
         execution_sandbox::{
-            adjust_l1_gas_price_for_tx, execute_tx_eth_call, execute_tx_with_pending_state,
-            get_pubdata_for_factory_deps, BlockArgs, SubmitTxStage, TxExecutionArgs, TxSharedArgs,
-            VmConcurrencyLimiter, VmPermit, SANDBOX_METRICS,
+            adjust_l1_gas_price_for_tx, execute_bundle_eth_call, execute_tx_eth_call,
+            execute_tx_with_pending_state, get_pubdata_for_factory_deps, ApiTracer, BlockArgs,
+            SubmitTxStage, TxExecutionArgs, TxSharedArgs, VmConcurrencyLimiter, VmPermit,
+            SANDBOX_METRICS,
         },
         tx_sender::result::ApiCallResult,
     },
     l1_gas_price::L1GasPriceProvider,
     metrics::{TxStage, APP_METRICS},
     state_keeper::seal_criteria::{ConditionalSealer, SealData},
+    sync_layer::FailoverMainNodeClient,
 };
 
 mod proxy;
@@ -172,8 +178,8 @@ impl TxSenderBuilder {
         }
     }
 
-    pub fn with_tx_proxy(mut self, main_node_url: &str) -> Self {
-        self.proxy = Some(TxProxy::new(main_node_url));
+    pub fn with_tx_proxy(mut self, main_node_client: FailoverMainNodeClient) -> Self {
+        self.proxy = Some(TxProxy::new(main_node_client));
         self
     }
 
@@ -228,6 +234,9 @@ pub struct TxSenderConfig {
     pub vm_execution_cache_misses_limit: Option<usize>,
     pub validation_computational_gas_limit: u32,
     pub chain_id: L2ChainId,
+    /// Replacement and per-account limits enforced on submitted transactions. Should be consistent
+    /// with the mempool configuration.
+    pub mempool_limits: L2TxInsertionLimits,
 }
 
 impl TxSenderConfig {
@@ -246,6 +255,7 @@ impl TxSenderConfig {
             validation_computational_gas_limit: state_keeper_config
                 .validation_computational_gas_limit,
             chain_id,
+            mempool_limits: L2TxInsertionLimits::default(),
         }
     }
 }
@@ -332,7 +342,7 @@ impl<G: L1GasPriceProvider> TxSender<G> {
 
         let stage_latency = SANDBOX_METRICS.submit_tx[&SubmitTxStage::VerifyExecute].start();
         let computational_gas_limit = self.0.sender_config.validation_computational_gas_limit;
-        let validation_result = shared_args
+        let validation_diagnostics = shared_args
             .validate_tx_with_pending_state(
                 vm_permit,
                 self.0.replica_connection_pool.clone(),
@@ -342,8 +352,10 @@ impl<G: L1GasPriceProvider> TxSender<G> {
             .await;
         stage_latency.observe();
 
-        if let Err(err) = validation_result {
-            return Err(err.into());
+        if !validation_diagnostics.is_success() {
+            return Err(SubmitTxError::ValidationFailedWithDiagnostics(Box::new(
+                validation_diagnostics,
+            )));
         }
 
         let stage_started_at = Instant::now();
@@ -381,7 +393,7 @@ impl<G: L1GasPriceProvider> TxSender<G> {
             .await
             .unwrap()
             .transactions_dal()
-            .insert_transaction_l2(tx, tx_metrics)
+            .insert_transaction_l2_with_limits(tx, tx_metrics, &self.0.sender_config.mempool_limits)
             .await;
 
         APP_METRICS.processed_txs[&TxStage::Mempool(submission_res_handle)].inc();
@@ -393,6 +405,17 @@ impl<G: L1GasPriceProvider> TxSender<G> {
                 nonce,
             )),
             L2TxSubmissionResult::Duplicate => Err(SubmitTxError::IncorrectTx(TxDuplication(hash))),
+            L2TxSubmissionResult::Underpriced => Err(SubmitTxError::ReplacementUnderpriced),
+            L2TxSubmissionResult::AccountLimitReached => {
+                let max_pending_txs = self
+                    .0
+                    .sender_config
+                    .mempool_limits
+                    .max_pending_txs_per_account;
+                Err(SubmitTxError::TooManyPendingTransactions(
+                    max_pending_txs.unwrap_or_default(),
+                ))
+            }
             _ => {
                 SANDBOX_METRICS.submit_tx[&SubmitTxStage::DbInsert]
                     .observe(stage_started_at.elapsed());
@@ -586,6 +609,7 @@ impl<G: L1GasPriceProvider> TxSender<G> {
         tx_gas_limit: u32,
         l1_gas_price: u64,
         base_fee: u64,
+        state_override: Option<&StateOverride>,
     ) -> (VmExecutionResultAndLogs, TransactionExecutionMetrics) {
         let gas_limit_with_overhead = tx_gas_limit
             + derive_overhead(
@@ -617,8 +641,12 @@ impl<G: L1GasPriceProvider> TxSender<G> {
 
         let shared_args = self.shared_args_for_gas_estimate(l1_gas_price);
         let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
-        let execution_args =
-            TxExecutionArgs::for_gas_estimate(vm_execution_cache_misses_limit, &tx, base_fee);
+        let execution_args = TxExecutionArgs::for_gas_estimate(
+            vm_execution_cache_misses_limit,
+            &tx,
+            base_fee,
+            state_override.cloned(),
+        );
         let (exec_result, tx_metrics) = execute_tx_with_pending_state(
             vm_permit,
             shared_args,
@@ -650,6 +678,7 @@ impl<G: L1GasPriceProvider> TxSender<G> {
         mut tx: Transaction,
         estimated_fee_scale_factor: f64,
         acceptable_overestimation: u32,
+        state_override: Option<StateOverride>,
     ) -> Result<Fee, SubmitTxError> {
         let estimation_started_at = Instant::now();
         let l1_gas_price = {
@@ -684,24 +713,32 @@ impl<G: L1GasPriceProvider> TxSender<G> {
         }
 
         let hashed_key = get_code_key(&tx.initiator_account());
+        let initiator_override = state_override
+            .as_ref()
+            .and_then(|overrides| overrides.get(&tx.initiator_account()));
         // if the default account does not have enough funds
         // for transferring tx.value, without taking into account the fee,
         // there is no sense to estimate the fee
-        let account_code_hash = self
-            .0
-            .replica_connection_pool
-            .access_storage_tagged("api")
-            .await
-            .unwrap()
-            .storage_dal()
-            .get_by_key(&hashed_key)
-            .await
-            .unwrap_or_default();
+        let account_code_hash = match initiator_override.and_then(|account| account.code.as_ref()) {
+            Some(code) if code.0.is_empty() => H256::zero(),
+            Some(code) => hash_bytecode(&code.0),
+            None => self
+                .0
+                .replica_connection_pool
+                .access_storage_tagged("api")
+                .await
+                .unwrap()
+                .storage_dal()
+                .get_by_key(&hashed_key)
+                .await
+                .unwrap_or_default(),
+        };
+        let balance = match initiator_override.and_then(|account| account.balance) {
+            Some(balance) => balance,
+            None => self.get_balance(&tx.initiator_account()).await,
+        };
 
-        if !tx.is_l1()
-            && account_code_hash == H256::zero()
-            && tx.execute.value > self.get_balance(&tx.initiator_account()).await
-        {
+        if !tx.is_l1() && account_code_hash == H256::zero() && tx.execute.value > balance {
             tracing::info!(
                 "fee estimation failed on validation step.
                 account: {} does not have enough funds for for transferring tx.value: {}.",
@@ -777,6 +814,7 @@ impl<G: L1GasPriceProvider> TxSender<G> {
                     try_gas_limit,
                     l1_gas_price,
                     base_fee,
+                    state_override.as_ref(),
                 )
                 .await;
 
@@ -814,6 +852,7 @@ impl<G: L1GasPriceProvider> TxSender<G> {
                 suggested_gas_limit,
                 l1_gas_price,
                 base_fee,
+                state_override.as_ref(),
             )
             .await;
 
@@ -850,6 +889,7 @@ impl<G: L1GasPriceProvider> TxSender<G> {
         &self,
         block_args: BlockArgs,
         tx: L2Tx,
+        state_override: Option<StateOverride>,
     ) -> Result<Vec<u8>, SubmitTxError> {
         let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
         let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
@@ -863,11 +903,54 @@ impl<G: L1GasPriceProvider> TxSender<G> {
             block_args,
             vm_execution_cache_misses_limit,
             vec![],
+            state_override,
         )
         .await
         .into_api_call_result()
     }
 
+    /// Runs only the account validation phase of the transaction on top of the pending state.
+    pub(super) async fn debug_validation(
+        &self,
+        tx: L2Tx,
+    ) -> Result<ValidationDiagnostics, SubmitTxError> {
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+
+        let computational_gas_limit = self.0.sender_config.validation_computational_gas_limit;
+        Ok(self
+            .shared_args()
+            .validate_tx_with_pending_state(
+                vm_permit,
+                self.0.replica_connection_pool.clone(),
+                tx,
+                computational_gas_limit,
+            )
+            .await)
+    }
+
+    pub(super) async fn simulate_bundle(
+        &self,
+        block_args: BlockArgs,
+        txs: Vec<(L2Tx, Vec<ApiTracer>)>,
+        state_override: Option<StateOverride>,
+    ) -> Result<Vec<VmExecutionResultAndLogs>, SubmitTxError> {
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+
+        let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
+        Ok(execute_bundle_eth_call(
+            vm_permit,
+            self.shared_args(),
+            self.0.replica_connection_pool.clone(),
+            txs,
+            block_args,
+            vm_execution_cache_misses_limit,
+            state_override,
+        )
+        .await)
+    }
+
     pub fn gas_price(&self) -> u64 {
         let gas_price = self.0.l1_gas_price_source.estimate_effective_gas_price();
         let l1_gas_price = (gas_price as f64 * self.0.sender_config.gas_price_scale_factor).round();
@@ -3,7 +3,7 @@ use multivm::{
     tracers::validator::ValidationError,
 };
 use thiserror::Error;
-use zksync_types::{l2::error::TxCheckError, U256};
+use zksync_types::{api::ValidationDiagnostics, l2::error::TxCheckError, U256};
 
 use crate::api_server::execution_sandbox::SandboxExecutionError;
 
@@ -31,6 +31,12 @@ pub enum SubmitTxError {
     BootloaderFailure(String),
     #[error("failed to validate the transaction. reason: {0}")]
     ValidationFailed(String),
+    /// Same as `ValidationFailed`, but with structured diagnostics from the validation tracer.
+    #[error(
+        "failed to validate the transaction. reason: {}",
+        .0.error.as_deref().unwrap_or_default()
+    )]
+    ValidationFailedWithDiagnostics(Box<ValidationDiagnostics>),
     #[error("not enough balance to cover the fee. error message: {0}")]
     FailedToChargeFee(String),
     #[error("failed paymaster validation. error message: {0}")]
@@ -66,6 +72,10 @@ pub enum SubmitTxError {
     /// than required to start the invocation.
     #[error("intrinsic gas too low")]
     IntrinsicGas,
+    #[error("replacement transaction underpriced")]
+    ReplacementUnderpriced,
+    #[error("too many pending transactions from the account; at most {0} are allowed")]
+    TooManyPendingTransactions(usize),
     /// Error returned from main node
     #[error("{0}")]
     ProxyError(#[from] zksync_web3_decl::jsonrpsee::core::Error),
@@ -84,7 +94,9 @@ impl SubmitTxError {
             Self::RateLimitExceeded => "rate-limit-exceeded",
             Self::ServerShuttingDown => "shutting-down",
             Self::BootloaderFailure(_) => "bootloader-failure",
-            Self::ValidationFailed(_) => "validation-failed",
+            Self::ValidationFailed(_) | Self::ValidationFailedWithDiagnostics(_) => {
+                "validation-failed"
+            }
             Self::FailedToChargeFee(_) => "failed-too-charge-fee",
             Self::PaymasterValidationFailed(_) => "failed-paymaster-validation",
             Self::PrePaymasterPreparationFailed(_) => "failed-prepaymaster-preparation",
@@ -98,10 +110,20 @@ impl SubmitTxError {
             Self::FeePerPubdataByteTooHigh => "pubdata-price-limit-too-high",
             Self::InsufficientFundsForTransfer => "insufficient-funds-for-transfer",
             Self::IntrinsicGas => "intrinsic-gas",
+            Self::ReplacementUnderpriced => "replacement-underpriced",
+            Self::TooManyPendingTransactions(_) => "too-many-pending-transactions",
             Self::ProxyError(_) => "proxy-error",
         }
     }
 
+    pub fn validation_diagnostics(&self) -> Option<&ValidationDiagnostics> {
+        if let Self::ValidationFailedWithDiagnostics(diagnostics) = self {
+            Some(diagnostics)
+        } else {
+            None
+        }
+    }
+
     pub fn data(&self) -> Vec<u8> {
         if let Self::ExecutionReverted(_, data) = self {
             data.clone()
@@ -16,9 +16,13 @@ pub fn into_jsrpc_error(err: Web3Error) -> Error {
             | Web3Error::TooManyTopics
             | Web3Error::FilterNotFound
             | Web3Error::InvalidFeeParams(_)
+            | Web3Error::InvalidStateOverride(_)
+            | Web3Error::InvalidBundleSize(_)
             | Web3Error::LogsLimitExceeded(_, _, _)
             | Web3Error::InvalidFilterBlockHash => ErrorCode::InvalidParams,
-            Web3Error::SubmitTransactionError(_, _) | Web3Error::SerializationError(_) => 3.into(),
+            Web3Error::SubmitTransactionError(_, _)
+            | Web3Error::ValidationFailed(_, _)
+            | Web3Error::SerializationError(_) => 3.into(),
             Web3Error::PubSubTimeout => 4.into(),
             Web3Error::RequestTimeout => 5.into(),
             Web3Error::TreeApiUnavailable => 6.into(),
@@ -31,6 +35,7 @@ pub fn into_jsrpc_error(err: Web3Error) -> Error {
             Web3Error::SubmitTransactionError(_, data) => {
                 Some(format!("0x{}", hex::encode(data)).into())
             }
+            Web3Error::ValidationFailed(_, diagnostics) => serde_json::to_value(diagnostics).ok(),
             _ => None,
         },
     }
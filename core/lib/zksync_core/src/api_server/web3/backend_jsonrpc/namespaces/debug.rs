@@ -1,9 +1,12 @@
 use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_derive::rpc;
 use zksync_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{
+        BlockId, BlockNumber, DebugTrace, L1BatchTrace, ResultDebugTrace, TraceCallConfig,
+        TracerConfig,
+    },
     transaction_request::CallRequest,
-    H256,
+    L1BatchNumber, H256,
 };
 
 use crate::api_server::web3::{
@@ -17,29 +20,36 @@ pub trait DebugNamespaceT {
         &self,
         block: BlockNumber,
         options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<Vec<ResultDebugCall>>>;
+    ) -> BoxFuture<Result<Vec<ResultDebugTrace>>>;
 
     #[rpc(name = "debug_traceBlockByHash")]
     fn trace_block_by_hash(
         &self,
         hash: H256,
         options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<Vec<ResultDebugCall>>>;
+    ) -> BoxFuture<Result<Vec<ResultDebugTrace>>>;
 
     #[rpc(name = "debug_traceCall")]
     fn trace_call(
         &self,
         request: CallRequest,
         block: Option<BlockId>,
-        options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<DebugCall>>;
+        options: Option<TraceCallConfig>,
+    ) -> BoxFuture<Result<DebugTrace>>;
 
     #[rpc(name = "debug_traceTransaction")]
     fn trace_transaction(
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<Option<DebugCall>>>;
+    ) -> BoxFuture<Result<Option<DebugTrace>>>;
+
+    #[rpc(name = "debug_traceL1Batch")]
+    fn trace_l1_batch(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        options: Option<TracerConfig>,
+    ) -> BoxFuture<Result<L1BatchTrace>>;
 }
 
 impl DebugNamespaceT for DebugNamespace {
@@ -47,7 +57,7 @@ impl DebugNamespaceT for DebugNamespace {
         &self,
         block: BlockNumber,
         options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<Vec<ResultDebugCall>>> {
+    ) -> BoxFuture<Result<Vec<ResultDebugTrace>>> {
         let self_ = self.clone();
         Box::pin(async move {
             self_
@@ -61,7 +71,7 @@ impl DebugNamespaceT for DebugNamespace {
         &self,
         hash: H256,
         options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<Vec<ResultDebugCall>>> {
+    ) -> BoxFuture<Result<Vec<ResultDebugTrace>>> {
         let self_ = self.clone();
         Box::pin(async move {
             self_
@@ -75,8 +85,8 @@ impl DebugNamespaceT for DebugNamespace {
         &self,
         request: CallRequest,
         block: Option<BlockId>,
-        options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<DebugCall>> {
+        options: Option<TraceCallConfig>,
+    ) -> BoxFuture<Result<DebugTrace>> {
         let self_ = self.clone();
         Box::pin(async move {
             self_
@@ -90,8 +100,27 @@ impl DebugNamespaceT for DebugNamespace {
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<Option<DebugCall>>> {
+    ) -> BoxFuture<Result<Option<DebugTrace>>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .debug_trace_transaction_impl(tx_hash, options)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
+    fn trace_l1_batch(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        options: Option<TracerConfig>,
+    ) -> BoxFuture<Result<L1BatchTrace>> {
         let self_ = self.clone();
-        Box::pin(async move { Ok(self_.debug_trace_transaction_impl(tx_hash, options).await) })
+        Box::pin(async move {
+            self_
+                .debug_trace_l1_batch_impl(l1_batch_number, options)
+                .await
+                .map_err(into_jsrpc_error)
+        })
     }
 }
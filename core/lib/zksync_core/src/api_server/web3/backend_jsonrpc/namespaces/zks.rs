@@ -5,12 +5,13 @@ use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_derive::rpc;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetails,
+        BlockDetails, BlockId, BridgeAddresses, BundleCallResult, BundleSimulationConfig,
+        L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetails,
+        ValidationDiagnostics,
     },
     fee::Fee,
     transaction_request::CallRequest,
-    Address, L1BatchNumber, MiniblockNumber, H256, U256, U64,
+    Address, Bytes, L1BatchNumber, MiniblockNumber, H256, U256, U64,
 };
 use zksync_web3_decl::types::Token;
 
@@ -27,6 +28,17 @@ pub trait ZksNamespaceT {
     #[rpc(name = "zks_estimateGasL1ToL2")]
     fn estimate_gas_l1_to_l2(&self, req: CallRequest) -> BoxFuture<Result<U256>>;
 
+    #[rpc(name = "zks_simulateBundle")]
+    fn simulate_bundle(
+        &self,
+        requests: Vec<CallRequest>,
+        block: Option<BlockId>,
+        config: Option<BundleSimulationConfig>,
+    ) -> BoxFuture<Result<Vec<BundleCallResult>>>;
+
+    #[rpc(name = "zks_debugValidation")]
+    fn debug_validation(&self, tx_bytes: Bytes) -> BoxFuture<Result<ValidationDiagnostics>>;
+
     #[rpc(name = "zks_getMainContract")]
     fn get_main_contract(&self) -> BoxFuture<Result<Address>>;
 
@@ -131,6 +143,31 @@ impl<G: L1GasPriceProvider + Send + Sync + 'static> ZksNamespaceT for ZksNamespa
         })
     }
 
+    fn simulate_bundle(
+        &self,
+        requests: Vec<CallRequest>,
+        block: Option<BlockId>,
+        config: Option<BundleSimulationConfig>,
+    ) -> BoxFuture<Result<Vec<BundleCallResult>>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .simulate_bundle_impl(requests, block, config)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
+    fn debug_validation(&self, tx_bytes: Bytes) -> BoxFuture<Result<ValidationDiagnostics>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .debug_validation_impl(tx_bytes)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
     fn get_main_contract(&self) -> BoxFuture<Result<Address>> {
         let self_ = self.clone();
         Box::pin(async move { Ok(self_.get_main_contract_impl()) })
@@ -26,19 +26,27 @@ pub fn into_jsrpc_error(err: Web3Error) -> ErrorObjectOwned {
             | Web3Error::TooManyTopics
             | Web3Error::FilterNotFound
             | Web3Error::InvalidFeeParams(_)
+            | Web3Error::InvalidStateOverride(_)
+            | Web3Error::InvalidBundleSize(_)
             | Web3Error::InvalidFilterBlockHash
             | Web3Error::LogsLimitExceeded(_, _, _) => ErrorCode::InvalidParams.code(),
-            Web3Error::SubmitTransactionError(_, _) | Web3Error::SerializationError(_) => 3,
+            Web3Error::SubmitTransactionError(_, _)
+            | Web3Error::ValidationFailed(_, _)
+            | Web3Error::SerializationError(_) => 3,
             Web3Error::PubSubTimeout => 4,
             Web3Error::RequestTimeout => 5,
             Web3Error::TreeApiUnavailable => 6,
         },
         match err {
-            Web3Error::SubmitTransactionError(ref message, _) => message.clone(),
+            Web3Error::SubmitTransactionError(ref message, _)
+            | Web3Error::ValidationFailed(ref message, _) => message.clone(),
             _ => err.to_string(),
         },
         match err {
-            Web3Error::SubmitTransactionError(_, data) => Some(format!("0x{}", hex::encode(data))),
+            Web3Error::SubmitTransactionError(_, data) => {
+                Some(format!("0x{}", hex::encode(data)).into())
+            }
+            Web3Error::ValidationFailed(_, diagnostics) => serde_json::to_value(diagnostics).ok(),
             _ => None,
         },
     )
@@ -1,7 +1,10 @@
 use zksync_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{
+        BlockId, BlockNumber, DebugTrace, L1BatchTrace, ResultDebugTrace, TraceCallConfig,
+        TracerConfig,
+    },
     transaction_request::CallRequest,
-    H256,
+    L1BatchNumber, H256,
 };
 use zksync_web3_decl::{
     jsonrpsee::core::{async_trait, RpcResult},
@@ -16,7 +19,7 @@ impl DebugNamespaceServer for DebugNamespace {
         &self,
         block: BlockNumber,
         options: Option<TracerConfig>,
-    ) -> RpcResult<Vec<ResultDebugCall>> {
+    ) -> RpcResult<Vec<ResultDebugTrace>> {
         self.debug_trace_block_impl(BlockId::Number(block), options)
             .await
             .map_err(into_jsrpc_error)
@@ -25,7 +28,7 @@ impl DebugNamespaceServer for DebugNamespace {
         &self,
         hash: H256,
         options: Option<TracerConfig>,
-    ) -> RpcResult<Vec<ResultDebugCall>> {
+    ) -> RpcResult<Vec<ResultDebugTrace>> {
         self.debug_trace_block_impl(BlockId::Hash(hash), options)
             .await
             .map_err(into_jsrpc_error)
@@ -34,8 +37,8 @@ impl DebugNamespaceServer for DebugNamespace {
         &self,
         request: CallRequest,
         block: Option<BlockId>,
-        options: Option<TracerConfig>,
-    ) -> RpcResult<DebugCall> {
+        options: Option<TraceCallConfig>,
+    ) -> RpcResult<DebugTrace> {
         self.debug_trace_call_impl(request, block, options)
             .await
             .map_err(into_jsrpc_error)
@@ -44,7 +47,18 @@ impl DebugNamespaceServer for DebugNamespace {
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> RpcResult<Option<DebugCall>> {
-        Ok(self.debug_trace_transaction_impl(tx_hash, options).await)
+    ) -> RpcResult<Option<DebugTrace>> {
+        self.debug_trace_transaction_impl(tx_hash, options)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+    async fn trace_l1_batch(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        options: Option<TracerConfig>,
+    ) -> RpcResult<L1BatchTrace> {
+        self.debug_trace_l1_batch_impl(l1_batch_number, options)
+            .await
+            .map_err(into_jsrpc_error)
     }
 }
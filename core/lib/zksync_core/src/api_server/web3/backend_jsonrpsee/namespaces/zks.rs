@@ -3,12 +3,13 @@ use std::collections::HashMap;
 use bigdecimal::BigDecimal;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetails,
+        BlockDetails, BlockId, BridgeAddresses, BundleCallResult, BundleSimulationConfig,
+        L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetails,
+        ValidationDiagnostics,
     },
     fee::Fee,
     transaction_request::CallRequest,
-    Address, L1BatchNumber, MiniblockNumber, H256, U256, U64,
+    Address, Bytes, L1BatchNumber, MiniblockNumber, H256, U256, U64,
 };
 use zksync_web3_decl::{
     jsonrpsee::core::{async_trait, RpcResult},
@@ -33,6 +34,23 @@ impl<G: L1GasPriceProvider + Send + Sync + 'static> ZksNamespaceServer for ZksNa
             .map_err(into_jsrpc_error)
     }
 
+    async fn simulate_bundle(
+        &self,
+        requests: Vec<CallRequest>,
+        block: Option<BlockId>,
+        config: Option<BundleSimulationConfig>,
+    ) -> RpcResult<Vec<BundleCallResult>> {
+        self.simulate_bundle_impl(requests, block, config)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
+    async fn debug_validation(&self, tx_bytes: Bytes) -> RpcResult<ValidationDiagnostics> {
+        self.debug_validation_impl(tx_bytes)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
     async fn get_main_contract(&self) -> RpcResult<Address> {
         Ok(self.get_main_contract_impl())
     }
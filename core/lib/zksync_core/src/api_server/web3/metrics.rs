@@ -179,6 +179,8 @@ pub(super) enum SubscriptionType {
     Blocks,
     Txs,
     Logs,
+    Syncing,
+    L1BatchStatuses,
 }
 
 #[derive(Debug, Metrics)]
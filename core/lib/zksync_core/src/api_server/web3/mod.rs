@@ -541,6 +541,9 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             if let Some(sender) = self.pub_sub_events_sender.take() {
                 pub_sub.set_events_sender(sender);
             }
+            if let Some(sync_state) = self.sync_state.clone() {
+                pub_sub.set_sync_state(sync_state);
+            }
             let polling_interval = self
                 .polling_interval
                 .context("Polling interval is not set")?;
@@ -5,18 +5,23 @@ use once_cell::sync::OnceCell;
 use zksync_dal::ConnectionPool;
 use zksync_state::PostgresStorageCaches;
 use zksync_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{
+        BlockId, BlockNumber, DebugCall, DebugTrace, L1BatchTrace, L1BatchTransactionTrace,
+        PrestateTrace, ResultDebugTrace, SupportedTracers, TraceCallConfig, TracerConfig,
+        TransactionId,
+    },
     l2::L2Tx,
     transaction_request::CallRequest,
     vm_trace::Call,
-    AccountTreeId, L2ChainId, H256, USED_BOOTLOADER_MEMORY_BYTES,
+    AccountTreeId, L1BatchNumber, L2ChainId, MiniblockNumber, H256, USED_BOOTLOADER_MEMORY_BYTES,
 };
 use zksync_web3_decl::error::Web3Error;
 
 use crate::{
     api_server::{
         execution_sandbox::{
-            execute_tx_eth_call, ApiTracer, BlockArgs, TxSharedArgs, VmConcurrencyLimiter,
+            execute_tx_eth_call, replay_l1_batch, validate_state_override, ApiTracer, BlockArgs,
+            ReplayRange, ReplayTracer, TxSharedArgs, VmConcurrencyLimiter,
         },
         tx_sender::ApiContracts,
         web3::{
@@ -63,19 +68,50 @@ impl DebugNamespace {
         &self,
         block_id: BlockId,
         options: Option<TracerConfig>,
-    ) -> Result<Vec<ResultDebugCall>, Web3Error> {
+    ) -> Result<Vec<ResultDebugTrace>, Web3Error> {
         const METHOD_NAME: &str = "debug_trace_block";
 
         let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+        let (tracer, tracer_config) = options
+            .map(|options| (options.tracer, options.tracer_config))
+            .unwrap_or((SupportedTracers::CallTracer, Default::default()));
         let mut connection = self
             .connection_pool
             .access_storage_tagged("api")
             .await
             .unwrap();
         let block_number = resolve_block(&mut connection, block_id, METHOD_NAME).await?;
+
+        if tracer == SupportedTracers::PrestateTracer {
+            let l1_batch_number = connection
+                .blocks_web3_dal()
+                .get_l1_batch_number_of_miniblock(block_number)
+                .await
+                .map_err(|err| internal_error(METHOD_NAME, err))?;
+            drop(connection);
+            // Miniblocks in the genesis L1 batch contain no transactions.
+            let traces = match l1_batch_number {
+                None => return Err(Web3Error::NoBlock),
+                Some(L1BatchNumber(0)) => vec![],
+                Some(l1_batch_number) => {
+                    let range = ReplayRange::UpToMiniblock(block_number);
+                    let diff_mode = tracer_config.diff_mode;
+                    self.replay_prestate(l1_batch_number, range, diff_mode, METHOD_NAME)
+                        .await?
+                        .into_iter()
+                        .filter(|(_, miniblock_number, _)| *miniblock_number == block_number)
+                        .map(|(_, _, trace)| ResultDebugTrace {
+                            result: DebugTrace::Prestate(trace),
+                        })
+                        .collect()
+                }
+            };
+
+            let block_diff = self.last_sealed_miniblock.diff(block_number);
+            method_latency.observe(block_diff);
+            return Ok(traces);
+        }
+
         let call_trace = connection
             .blocks_web3_dal()
             .get_trace_for_miniblock(block_number)
@@ -85,10 +121,12 @@ impl DebugNamespace {
             .into_iter()
             .map(|call_trace| {
                 let mut result: DebugCall = call_trace.into();
-                if only_top_call {
+                if tracer_config.only_top_call {
                     result.calls = vec![];
                 }
-                ResultDebugCall { result }
+                ResultDebugTrace {
+                    result: result.into(),
+                }
             })
             .collect();
 
@@ -102,25 +140,56 @@ impl DebugNamespace {
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> Option<DebugCall> {
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
-        let call_trace = self
+    ) -> Result<Option<DebugTrace>, Web3Error> {
+        const METHOD_NAME: &str = "debug_trace_transaction";
+
+        let (tracer, tracer_config) = options
+            .map(|options| (options.tracer, options.tracer_config))
+            .unwrap_or((SupportedTracers::CallTracer, Default::default()));
+        let mut connection = self
             .connection_pool
             .access_storage_tagged("api")
             .await
-            .unwrap()
-            .transactions_dal()
-            .get_call_trace(tx_hash)
-            .await;
-        call_trace.map(|call_trace| {
+            .unwrap();
+
+        if tracer == SupportedTracers::PrestateTracer {
+            // Storage reads are not persisted, so the transaction is re-executed to collect all touched slots.
+            let tx = connection
+                .transactions_web3_dal()
+                .get_transaction(TransactionId::Hash(tx_hash), self.chain_id)
+                .await
+                .map_err(|err| internal_error(METHOD_NAME, err))?;
+            let Some(block_number) = tx.and_then(|tx| tx.block_number) else {
+                return Ok(None);
+            };
+            let l1_batch_number = connection
+                .blocks_web3_dal()
+                .get_l1_batch_number_of_miniblock(MiniblockNumber(block_number.as_u32()))
+                .await
+                .map_err(|err| internal_error(METHOD_NAME, err))?;
+            drop(connection);
+            // Transactions can only be re-executed once their L1 batch is sealed.
+            let l1_batch_number = l1_batch_number.ok_or(Web3Error::NoBlock)?;
+
+            let range = ReplayRange::UpToTransaction(tx_hash);
+            let diff_mode = tracer_config.diff_mode;
+            let trace = self
+                .replay_prestate(l1_batch_number, range, diff_mode, METHOD_NAME)
+                .await?
+                .into_iter()
+                .find(|(hash, ..)| *hash == tx_hash)
+                .map(|(.., trace)| DebugTrace::Prestate(trace));
+            return Ok(trace);
+        }
+
+        let call_trace = connection.transactions_dal().get_call_trace(tx_hash).await;
+        Ok(call_trace.map(|call_trace| {
             let mut result: DebugCall = call_trace.into();
-            if only_top_call {
+            if tracer_config.only_top_call {
                 result.calls = vec![];
             }
-            result
-        })
+            result.into()
+        }))
     }
 
     #[tracing::instrument(skip(self, request, block_id))]
@@ -128,15 +197,24 @@ impl DebugNamespace {
         &self,
         request: CallRequest,
         block_id: Option<BlockId>,
-        options: Option<TracerConfig>,
-    ) -> Result<DebugCall, Web3Error> {
+        options: Option<TraceCallConfig>,
+    ) -> Result<DebugTrace, Web3Error> {
         const METHOD_NAME: &str = "debug_trace_call";
 
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+        let (tracer, tracer_config, state_override) = options
+            .map(|config| {
+                let TracerConfig {
+                    tracer,
+                    tracer_config,
+                } = config.options;
+                (tracer, tracer_config, config.state_overrides)
+            })
+            .unwrap_or((SupportedTracers::CallTracer, Default::default(), None));
+        if let Some(state_override) = &state_override {
+            validate_state_override(state_override).map_err(Web3Error::InvalidStateOverride)?;
+        }
 
         let mut connection = self
             .connection_pool
@@ -155,12 +233,17 @@ impl DebugNamespace {
         let vm_permit = self.vm_concurrency_limiter.acquire().await;
         let vm_permit = vm_permit.ok_or(Web3Error::InternalError)?;
 
-        // We don't need properly trace if we only need top call
         let call_tracer_result = Arc::new(OnceCell::default());
-        let custom_tracers = if only_top_call {
-            vec![]
-        } else {
-            vec![ApiTracer::CallTracer(call_tracer_result.clone())]
+        let prestate_tracer_result = Arc::new(OnceCell::default());
+        let custom_tracers = match tracer {
+            // We don't need properly trace if we only need top call
+            SupportedTracers::CallTracer if tracer_config.only_top_call => vec![],
+            SupportedTracers::CallTracer => {
+                vec![ApiTracer::CallTracer(call_tracer_result.clone())]
+            }
+            SupportedTracers::PrestateTracer => {
+                vec![ApiTracer::PrestateTracer(prestate_tracer_result.clone())]
+            }
         };
 
         let result = execute_tx_eth_call(
@@ -171,9 +254,25 @@ impl DebugNamespace {
             block_args,
             self.vm_execution_cache_misses_limit,
             custom_tracers,
+            state_override,
         )
         .await;
 
+        if tracer == SupportedTracers::PrestateTracer {
+            // Like in Geth, the state is reported regardless of the execution outcome.
+            // We had only one copy of Arc this arc is already dropped it's safe to unwrap
+            let slots = Arc::try_unwrap(prestate_tracer_result)
+                .unwrap()
+                .take()
+                .unwrap_or_default();
+            let accounts = [tx.initiator_account(), tx.execute.contract_address];
+            let trace = PrestateTrace::new(&slots, accounts, tracer_config.diff_mode);
+
+            let block_diff = self.last_sealed_miniblock.diff_with_block_args(&block_args);
+            method_latency.observe(block_diff);
+            return Ok(DebugTrace::Prestate(trace));
+        }
+
         let (output, revert_reason) = match result.result {
             ExecutionResult::Success { output, .. } => (output, None),
             ExecutionResult::Revert { output } => (vec![], Some(output.to_string())),
@@ -202,7 +301,136 @@ impl DebugNamespace {
 
         let block_diff = self.last_sealed_miniblock.diff_with_block_args(&block_args);
         method_latency.observe(block_diff);
-        Ok(call.into())
+        Ok(DebugCall::from(call).into())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn debug_trace_l1_batch_impl(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        options: Option<TracerConfig>,
+    ) -> Result<L1BatchTrace, Web3Error> {
+        const METHOD_NAME: &str = "debug_trace_l1_batch";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        if matches!(&options, Some(options) if options.tracer != SupportedTracers::CallTracer) {
+            return Err(Web3Error::NotImplemented);
+        }
+        let only_top_call = options
+            .map(|options| options.tracer_config.only_top_call)
+            .unwrap_or(false);
+
+        let mut connection = self
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap();
+        let sealed_l1_batch_number = connection
+            .blocks_web3_dal()
+            .get_sealed_l1_batch_number()
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        drop(connection);
+        // The genesis batch is not executed in the VM, so there's nothing to replay.
+        if l1_batch_number == L1BatchNumber(0) || l1_batch_number > sealed_l1_batch_number {
+            return Err(Web3Error::NoBlock);
+        }
+
+        let vm_permit = self.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(Web3Error::InternalError)?;
+        let tracer = if only_top_call {
+            ReplayTracer::None
+        } else {
+            ReplayTracer::Call
+        };
+        let replayed_batch = replay_l1_batch(
+            vm_permit,
+            self.connection_pool.clone(),
+            self.chain_id,
+            l1_batch_number,
+            ReplayRange::L1Batch,
+            tracer,
+        )
+        .await
+        .map_err(|err| internal_error(METHOD_NAME, err))?;
+
+        let transactions = replayed_batch
+            .transactions
+            .into_iter()
+            .map(|replayed| {
+                let (tx, result) = (replayed.tx, replayed.result);
+                let tx_hash = tx.hash();
+                let (output, revert_reason) = match result.result {
+                    ExecutionResult::Success { output } => (output, None),
+                    ExecutionResult::Revert { output } => (vec![], Some(output.to_string())),
+                    // Halted transactions can still be included into a batch (e.g., L1 transactions).
+                    ExecutionResult::Halt { reason } => (vec![], Some(reason.to_string())),
+                };
+                let call = Call::new_high_level(
+                    tx.gas_limit().as_u32(),
+                    result.statistics.gas_used,
+                    tx.execute.value,
+                    tx.execute.calldata,
+                    output,
+                    revert_reason,
+                    replayed.call_trace,
+                );
+                L1BatchTransactionTrace {
+                    tx_hash,
+                    miniblock_number: replayed.miniblock_number,
+                    result: call.into(),
+                    gas_refunded: result.refunds.gas_refunded,
+                    operator_suggested_refund: result.refunds.operator_suggested_refund,
+                    pubdata_published: result.statistics.pubdata_published,
+                }
+            })
+            .collect();
+
+        let finished_batch = replayed_batch
+            .finished_batch
+            .expect("entire L1 batch is replayed");
+        let block_tip_result = finished_batch.block_tip_execution_result;
+        method_latency.observe();
+        Ok(L1BatchTrace {
+            l1_batch_number,
+            transactions,
+            block_tip_gas_used: block_tip_result.statistics.gas_used,
+            block_tip_pubdata_published: block_tip_result.statistics.pubdata_published,
+        })
+    }
+
+    /// Re-executes transactions in a sealed L1 batch and returns prestate traces for the replayed transactions
+    /// together with their hashes and miniblocks.
+    async fn replay_prestate(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        range: ReplayRange,
+        diff_mode: bool,
+        method_name: &'static str,
+    ) -> Result<Vec<(H256, MiniblockNumber, PrestateTrace)>, Web3Error> {
+        let vm_permit = self.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(Web3Error::InternalError)?;
+        let replayed_batch = replay_l1_batch(
+            vm_permit,
+            self.connection_pool.clone(),
+            self.chain_id,
+            l1_batch_number,
+            range,
+            ReplayTracer::Prestate,
+        )
+        .await
+        .map_err(|err| internal_error(method_name, err))?;
+
+        let traces = replayed_batch.transactions.into_iter().map(|replayed| {
+            let accounts = [
+                replayed.tx.initiator_account(),
+                replayed.tx.execute.contract_address,
+            ];
+            // Like in Geth, the state is reported regardless of the execution outcome.
+            let trace = PrestateTrace::new(&replayed.touched_slots, accounts, diff_mode);
+            (replayed.tx.hash(), replayed.miniblock_number, trace)
+        });
+        Ok(traces.collect())
     }
 
     fn shared_args(&self) -> TxSharedArgs {
@@ -1,17 +1,18 @@
 use zksync_types::{
     api::{
-        BlockId, BlockNumber, GetLogsFilter, Transaction, TransactionId, TransactionReceipt,
-        TransactionVariant,
+        BlockId, BlockNumber, EthProof, EthStorageProof, GetLogsFilter, StateOverride, Transaction,
+        TransactionId, TransactionReceipt, TransactionVariant,
     },
+    get_code_key, get_nonce_key,
     l2::{L2Tx, TransactionType},
     transaction_request::CallRequest,
-    utils::decompose_full_nonce,
+    utils::{decompose_full_nonce, storage_key_for_eth_balance},
     web3,
-    web3::types::{FeeHistory, SyncInfo, SyncState},
-    AccountTreeId, Bytes, MiniblockNumber, StorageKey, H256, L2_ETH_TOKEN_ADDRESS,
+    web3::types::{FeeHistory, SyncState},
+    AccountTreeId, Bytes, L1BatchNumber, MiniblockNumber, StorageKey, H256, L2_ETH_TOKEN_ADDRESS,
     MAX_GAS_PER_PUBDATA_BYTE, U256,
 };
-use zksync_utils::u256_to_h256;
+use zksync_utils::{h256_to_u256, u256_to_h256};
 use zksync_web3_decl::{
     error::Web3Error,
     types::{Address, Block, Filter, FilterChanges, Log, U64},
@@ -19,7 +20,8 @@ use zksync_web3_decl::{
 
 use crate::{
     api_server::{
-        execution_sandbox::BlockArgs,
+        execution_sandbox::{validate_state_override, BlockArgs},
+        tree::TreeApiClient,
         web3::{
             backend_jsonrpc::error::internal_error,
             metrics::{BlockCallObserver, API_METRICS},
@@ -73,14 +75,19 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
         block_number
     }
 
-    #[tracing::instrument(skip(self, request, block_id))]
+    #[tracing::instrument(skip(self, request, block_id, state_override))]
     pub async fn call_impl(
         &self,
         request: CallRequest,
         block_id: Option<BlockId>,
+        state_override: Option<StateOverride>,
     ) -> Result<Bytes, Web3Error> {
         const METHOD_NAME: &str = "call";
 
+        if let Some(state_override) = &state_override {
+            validate_state_override(state_override).map_err(Web3Error::InvalidStateOverride)?;
+        }
+
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
         let mut connection = self
@@ -97,7 +104,11 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
 
         let tx = L2Tx::from_request(request.into(), self.state.api_config.max_tx_size)?;
 
-        let call_result = self.state.tx_sender.eth_call(block_args, tx).await;
+        let call_result = self
+            .state
+            .tx_sender
+            .eth_call(block_args, tx, state_override)
+            .await;
         let res_bytes = call_result
             .map_err(|err| Web3Error::SubmitTransactionError(err.to_string(), err.data()))?;
 
@@ -109,16 +120,38 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
         Ok(res_bytes.into())
     }
 
-    #[tracing::instrument(skip(self, request, _block))]
+    #[tracing::instrument(skip(self, request, _block, state_override))]
     pub async fn estimate_gas_impl(
         &self,
         request: CallRequest,
         _block: Option<BlockNumber>,
+        state_override: Option<StateOverride>,
     ) -> Result<U256, Web3Error> {
         const METHOD_NAME: &str = "estimate_gas";
 
+        if let Some(state_override) = &state_override {
+            validate_state_override(state_override).map_err(Web3Error::InvalidStateOverride)?;
+        }
+
         let method_latency = API_METRICS.start_call(METHOD_NAME);
         let mut request_with_gas_per_pubdata_overridden = request;
+        let from = request_with_gas_per_pubdata_overridden
+            .from
+            .unwrap_or_default();
+        let nonce_override = state_override
+            .as_ref()
+            .and_then(|overrides| overrides.get(&from)?.nonce);
+        if let Some(nonce_override) = nonce_override {
+            let nonce = request_with_gas_per_pubdata_overridden
+                .nonce
+                .get_or_insert(nonce_override);
+            if *nonce != nonce_override {
+                let message = format!(
+                    "nonce override for account {from:?} differs from the transaction nonce"
+                );
+                return Err(Web3Error::InvalidStateOverride(message));
+            }
+        }
         self.state
             .set_nonce_for_call_request(&mut request_with_gas_per_pubdata_overridden)
             .await?;
@@ -158,7 +191,12 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
         let fee = self
             .state
             .tx_sender
-            .get_txs_fee_in_wei(tx.into(), scale_factor, acceptable_overestimation)
+            .get_txs_fee_in_wei(
+                tx.into(),
+                scale_factor,
+                acceptable_overestimation,
+                state_override,
+            )
             .await
             .map_err(|err| Web3Error::SubmitTransactionError(err.to_string(), err.data()))?;
 
@@ -391,6 +429,126 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
         Ok(value)
     }
 
+    /// Returns Merkle proofs for the account and the specified storage `keys` in the `eth_getProof` format.
+    ///
+    /// The proofs are generated for the state at the end of the L1 batch covering the requested block.
+    /// For `latest` and `pending` tags, the latest L1 batch processed by the Merkle tree is used.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_proof_impl(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block_id: Option<BlockId>,
+    ) -> Result<EthProof, Web3Error> {
+        const METHOD_NAME: &str = "get_proof";
+
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Latest));
+        let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
+        let tree_api = self
+            .state
+            .tree_api
+            .as_ref()
+            .ok_or(Web3Error::TreeApiUnavailable)?;
+
+        let l1_batch_number = match block_id {
+            // The latest miniblocks usually belong to an unsealed L1 batch, which isn't present in the tree.
+            BlockId::Number(BlockNumber::Latest | BlockNumber::Pending) => {
+                let tree_info = tree_api
+                    .get_info()
+                    .await
+                    .map_err(|err| internal_error(METHOD_NAME, err))?;
+                let last_l1_batch_number = tree_info.next_l1_batch_number.0.checked_sub(1);
+                L1BatchNumber(last_l1_batch_number.ok_or(Web3Error::NoBlock)?)
+            }
+            _ => {
+                let mut connection = self
+                    .state
+                    .connection_pool
+                    .access_storage_tagged("api")
+                    .await
+                    .unwrap();
+                let block_number = resolve_block(&mut connection, block_id, METHOD_NAME).await?;
+                let resolved = connection
+                    .storage_web3_dal()
+                    .resolve_l1_batch_number_of_miniblock(block_number)
+                    .await
+                    .map_err(|err| internal_error(METHOD_NAME, err))?;
+                resolved.miniblock_l1_batch.ok_or(Web3Error::NoBlock)?
+            }
+        };
+
+        let mut connection = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap();
+        // The root hash is absent if the tree hasn't processed the L1 batch yet.
+        let storage_hash = connection
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?
+            .ok_or(Web3Error::NoBlock)?;
+        drop(connection);
+
+        let account_keys = [
+            get_code_key(&address),
+            storage_key_for_eth_balance(&address),
+            get_nonce_key(&address),
+        ];
+        let storage_keys = keys
+            .iter()
+            .map(|key| StorageKey::new(AccountTreeId::new(address), *key));
+        let expected_len = account_keys.len() + keys.len();
+        let hashed_keys = account_keys
+            .into_iter()
+            .chain(storage_keys)
+            .map(|key| key.hashed_key_u256())
+            .collect();
+        let entries = tree_api
+            .get_proofs(l1_batch_number, hashed_keys)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        if entries.len() != expected_len {
+            let err = format!(
+                "Merkle tree API returned {} entries, while {expected_len} were requested",
+                entries.len()
+            );
+            return Err(internal_error(METHOD_NAME, err));
+        }
+        let mut entries = entries.into_iter();
+        let (Some(code_entry), Some(balance_entry), Some(nonce_entry)) =
+            (entries.next(), entries.next(), entries.next())
+        else {
+            unreachable!("number of entries is checked above");
+        };
+
+        let storage_proof = entries
+            .zip(keys)
+            .map(|(entry, key)| EthStorageProof {
+                key,
+                value: h256_to_u256(entry.value),
+                proof: entry.merkle_path,
+            })
+            .collect();
+        let (nonce, _) = decompose_full_nonce(h256_to_u256(nonce_entry.value));
+
+        method_latency.observe_without_diff();
+        Ok(EthProof {
+            address,
+            account_proof: code_entry.merkle_path,
+            balance: h256_to_u256(balance_entry.value),
+            balance_proof: balance_entry.merkle_path,
+            nonce,
+            nonce_proof: nonce_entry.merkle_path,
+            code_hash: code_entry.value,
+            storage_hash,
+            storage_proof,
+            l1_batch_number,
+        })
+    }
+
     /// Account nonce.
     #[tracing::instrument(skip(self))]
     pub async fn get_transaction_count_impl(
@@ -666,7 +824,14 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
         let submit_result = submit_result.map(|_| hash).map_err(|err| {
             tracing::debug!("Send raw transaction error: {err}");
             API_METRICS.submit_tx_error[&err.prom_error_code()].inc();
-            Web3Error::SubmitTransactionError(err.to_string(), err.data())
+            let diagnostics = err
+                .validation_diagnostics()
+                .filter(|_| self.state.api_config.validation_diagnostics_in_errors);
+            if let Some(diagnostics) = diagnostics {
+                Web3Error::ValidationFailed(err.to_string(), Box::new(diagnostics.clone()))
+            } else {
+                Web3Error::SubmitTransactionError(err.to_string(), err.data())
+            }
         });
 
         method_latency.observe();
@@ -680,21 +845,11 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
 
     #[tracing::instrument(skip(self))]
     pub fn syncing_impl(&self) -> SyncState {
-        if let Some(state) = &self.state.sync_state {
-            // Node supports syncing process (i.e. not the main node).
-            if state.is_synced() {
-                SyncState::NotSyncing
-            } else {
-                SyncState::Syncing(SyncInfo {
-                    starting_block: 0u64.into(), // We always start syncing from genesis right now.
-                    current_block: state.get_local_block().0.into(),
-                    highest_block: state.get_main_node_block().0.into(),
-                })
-            }
-        } else {
-            // If there is no sync state, then the node is the main node and it's always synced.
-            SyncState::NotSyncing
-        }
+        // If there is no sync state, then the node is the main node and it's always synced.
+        self.state
+            .sync_state
+            .as_ref()
+            .map_or(SyncState::NotSyncing, |state| state.web3_status())
     }
 
     #[tracing::instrument(skip(self))]
@@ -1,12 +1,15 @@
-use std::{collections::HashMap, convert::TryInto};
+use std::{collections::HashMap, convert::TryInto, sync::Arc};
 
 use bigdecimal::{BigDecimal, Zero};
+use multivm::interface::ExecutionResult;
+use once_cell::sync::OnceCell;
 use zksync_dal::StorageProcessor;
 use zksync_mini_merkle_tree::MiniMerkleTree;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, Proof,
-        ProtocolVersion, StorageProof, TransactionDetails,
+        BlockDetails, BlockId, BlockNumber, BridgeAddresses, BundleCallResult,
+        BundleSimulationConfig, DebugCall, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, Log,
+        Proof, ProtocolVersion, StorageProof, TransactionDetails, ValidationDiagnostics,
     },
     fee::Fee,
     l1::L1Tx,
@@ -14,9 +17,10 @@ use zksync_types::{
     l2_to_l1_log::L2ToL1Log,
     tokens::ETHEREUM_ADDRESS,
     transaction_request::CallRequest,
-    AccountTreeId, L1BatchNumber, MiniblockNumber, StorageKey, Transaction, L1_MESSENGER_ADDRESS,
-    L2_ETH_TOKEN_ADDRESS, MAX_GAS_PER_PUBDATA_BYTE, REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE, U256,
-    U64,
+    vm_trace::Call,
+    AccountTreeId, Bytes, L1BatchNumber, MiniblockNumber, StorageKey, Transaction,
+    L1_MESSENGER_ADDRESS, L2_ETH_TOKEN_ADDRESS, MAX_GAS_PER_PUBDATA_BYTE,
+    REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE, U256, U64,
 };
 use zksync_utils::{address_to_h256, ratio_to_big_decimal_normalized};
 use zksync_web3_decl::{
@@ -26,12 +30,16 @@ use zksync_web3_decl::{
 
 use crate::{
     api_server::{
+        execution_sandbox::{validate_state_override, ApiTracer, BlockArgs},
         tree::TreeApiClient,
         web3::{backend_jsonrpc::error::internal_error, metrics::API_METRICS, RpcState},
     },
     l1_gas_price::L1GasPriceProvider,
 };
 
+/// Maximum number of calls in a bundle accepted by `zks_simulateBundle`.
+const MAX_BUNDLE_SIZE: usize = 32;
+
 #[derive(Debug)]
 pub struct ZksNamespace<G> {
     pub state: RpcState<G>,
@@ -114,13 +122,177 @@ impl<G: L1GasPriceProvider> ZksNamespace<G> {
         let fee = self
             .state
             .tx_sender
-            .get_txs_fee_in_wei(tx, scale_factor, acceptable_overestimation)
+            .get_txs_fee_in_wei(tx, scale_factor, acceptable_overestimation, None)
             .await
             .map_err(|err| Web3Error::SubmitTransactionError(err.to_string(), err.data()))?;
 
         Ok(fee)
     }
 
+    #[tracing::instrument(skip(self, requests, block_id, config))]
+    pub async fn simulate_bundle_impl(
+        &self,
+        requests: Vec<CallRequest>,
+        block_id: Option<BlockId>,
+        config: Option<BundleSimulationConfig>,
+    ) -> Result<Vec<BundleCallResult>, Web3Error> {
+        const METHOD_NAME: &str = "simulate_bundle";
+
+        if requests.is_empty() || requests.len() > MAX_BUNDLE_SIZE {
+            return Err(Web3Error::InvalidBundleSize(MAX_BUNDLE_SIZE));
+        }
+        let config = config.unwrap_or_default();
+        if let Some(state_override) = &config.state_overrides {
+            validate_state_override(state_override).map_err(Web3Error::InvalidStateOverride)?;
+        }
+
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
+        let mut connection = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap();
+        let block_args = BlockArgs::new(&mut connection, block_id)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?
+            .ok_or(Web3Error::NoBlock)?;
+        drop(connection);
+
+        let mut txs = Vec::with_capacity(requests.len());
+        let mut call_traces = Vec::with_capacity(requests.len());
+        for request in requests {
+            let tx = L2Tx::from_request(request.into(), self.state.api_config.max_tx_size)?;
+            let call_trace = Arc::new(OnceCell::default());
+            let tracers = if config.with_call_traces {
+                vec![ApiTracer::CallTracer(call_trace.clone())]
+            } else {
+                vec![]
+            };
+            txs.push((tx.clone(), tracers));
+            call_traces.push((tx, call_trace));
+        }
+
+        let results = self
+            .state
+            .tx_sender
+            .simulate_bundle(block_args, txs, config.state_overrides)
+            .await
+            .map_err(|err| Web3Error::SubmitTransactionError(err.to_string(), err.data()))?;
+
+        let call_count = call_traces.len();
+        let mut log_index = 0_u64;
+        let mut results: Vec<_> = results
+            .into_iter()
+            .zip(call_traces)
+            .enumerate()
+            .map(|(tx_index, (result, (tx, call_trace)))| {
+                let logs = result
+                    .logs
+                    .events
+                    .into_iter()
+                    .enumerate()
+                    .map(|(tx_log_index, event)| {
+                        let log = Log {
+                            address: event.address,
+                            topics: event.indexed_topics,
+                            data: event.value.into(),
+                            block_hash: None,
+                            block_number: None,
+                            l1_batch_number: None,
+                            transaction_hash: None,
+                            transaction_index: Some(tx_index.into()),
+                            log_index: Some(log_index.into()),
+                            transaction_log_index: Some(tx_log_index.into()),
+                            log_type: None,
+                            removed: Some(false),
+                        };
+                        log_index += 1;
+                        log
+                    })
+                    .collect();
+
+                let (output, error, revert_reason) = match result.result {
+                    ExecutionResult::Success { output } => (output, None, None),
+                    ExecutionResult::Revert { output } => (
+                        output.encoded_data(),
+                        None,
+                        Some(output.to_user_friendly_string()),
+                    ),
+                    ExecutionResult::Halt { reason } => (vec![], Some(reason.to_string()), None),
+                };
+                let gas_used = result.statistics.gas_used;
+                let trace = config.with_call_traces.then(|| {
+                    // The tracer is dropped after execution, so we hold the only copy of the `Arc`.
+                    let calls = Arc::try_unwrap(call_trace)
+                        .unwrap()
+                        .take()
+                        .unwrap_or_default();
+                    let call = Call::new_high_level(
+                        tx.common_data.fee.gas_limit.as_u32(),
+                        gas_used,
+                        tx.execute.value,
+                        tx.execute.calldata,
+                        output.clone(),
+                        revert_reason.clone(),
+                        calls,
+                    );
+                    DebugCall::from(call)
+                });
+
+                BundleCallResult {
+                    output: output.into(),
+                    error,
+                    revert_reason,
+                    gas_used: gas_used.into(),
+                    logs,
+                    trace,
+                }
+            })
+            .collect();
+        // Bundle execution stops after the first halted call; the remaining calls are not executed.
+        let halted_call_index = results.len() - 1;
+        results.resize_with(call_count, || BundleCallResult {
+            output: Bytes::default(),
+            error: Some(format!(
+                "not executed since call #{halted_call_index} in the bundle was halted"
+            )),
+            revert_reason: None,
+            gas_used: U256::zero(),
+            logs: vec![],
+            trace: None,
+        });
+
+        let block_diff = self
+            .state
+            .last_sealed_miniblock
+            .diff_with_block_args(&block_args);
+        method_latency.observe(block_diff);
+        Ok(results)
+    }
+
+    #[tracing::instrument(skip(self, tx_bytes))]
+    pub async fn debug_validation_impl(
+        &self,
+        tx_bytes: Bytes,
+    ) -> Result<ValidationDiagnostics, Web3Error> {
+        const METHOD_NAME: &str = "debug_validation";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let (mut tx, hash) = self.state.parse_transaction_bytes(&tx_bytes.0)?;
+        tx.set_input(tx_bytes.0, hash);
+
+        let diagnostics = self
+            .state
+            .tx_sender
+            .debug_validation(tx)
+            .await
+            .map_err(|err| Web3Error::SubmitTransactionError(err.to_string(), err.data()))?;
+        method_latency.observe();
+        Ok(diagnostics)
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn get_main_contract_impl(&self) -> Address {
         self.state.api_config.diamond_proxy_addr
@@ -1,6 +1,10 @@
 //! (Largely) backend-agnostic logic for dealing with Web3 subscriptions.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeSet, HashMap},
+    ops,
+    sync::Arc,
+};
 
 use anyhow::Context as _;
 use jsonrpc_core::error::{Error, ErrorCode};
@@ -11,16 +15,68 @@ use tokio::{
     time::{interval, Duration},
 };
 use zksync_dal::ConnectionPool;
-use zksync_types::{MiniblockNumber, H128, H256};
-use zksync_web3_decl::types::{BlockHeader, Log, PubSubFilter, PubSubResult};
+use zksync_types::{
+    api::{L1BatchStatus, L1BatchStatusUpdate},
+    L1BatchNumber, MiniblockNumber, H128, H256,
+};
+use zksync_web3_decl::types::{BlockHeader, Log, PubSubFilter, PubSubResult, SyncState};
 
 use super::{
     metrics::{SubscriptionType, PUB_SUB_METRICS},
     namespaces::eth::EVENT_TOPIC_NUMBER_LIMIT,
 };
+use crate::sync_layer;
 
 pub(super) type SubscriptionMap<T> = Arc<RwLock<HashMap<SubscriptionId, T>>>;
 
+/// Maximum number of L1 batches behind the last batch with a certain status that are re-scanned
+/// for updates. Batches can reach a status out of order (e.g., if L1 transactions are confirmed out of order),
+/// so a batch below the last one with the status may still get an update.
+const L1_BATCH_STATUS_RESCAN_WINDOW: u32 = 32;
+
+/// Cursor over updates for a single [`L1BatchStatus`].
+#[derive(Debug)]
+struct L1BatchStatusCursor {
+    /// All batches below this number are either reported or are too old to be re-scanned.
+    next_number: L1BatchNumber,
+    /// Reported batches starting from `next_number`.
+    reported: BTreeSet<L1BatchNumber>,
+}
+
+impl L1BatchStatusCursor {
+    fn new(last_number: Option<L1BatchNumber>) -> Self {
+        Self {
+            next_number: last_number.map_or(L1BatchNumber(0), |number| number + 1),
+            reported: BTreeSet::new(),
+        }
+    }
+
+    /// Returns the range of batches to scan given the last batch number with the status.
+    fn scan_range(&mut self, last_number: L1BatchNumber) -> ops::RangeInclusive<L1BatchNumber> {
+        // If the number has decreased (e.g., because of a block revert), forget about reverted batches.
+        if last_number < self.next_number {
+            self.next_number = last_number + 1;
+        }
+        self.reported.retain(|&number| number <= last_number);
+
+        let window_start =
+            L1BatchNumber((last_number.0 + 1).saturating_sub(L1_BATCH_STATUS_RESCAN_WINDOW));
+        if self.next_number < window_start {
+            self.next_number = window_start;
+            self.reported.retain(|&number| number >= window_start);
+        }
+        self.next_number..=last_number
+    }
+
+    /// Filters out updates that were already reported and records the remaining ones.
+    fn filter_updates(&mut self, updates: &mut Vec<L1BatchStatusUpdate>) {
+        updates.retain(|update| self.reported.insert(update.l1_batch_number));
+        while self.reported.remove(&self.next_number) {
+            self.next_number += 1;
+        }
+    }
+}
+
 /// Events emitted by the subscription logic. Only used in WebSocket server tests so far.
 #[derive(Debug)]
 pub(super) enum PubSubEvent {
@@ -160,6 +216,154 @@ impl PubSubNotifier<typed::Sink<PubSubResult>> {
             .await
             .context("get_pending_txs_hashes_after()")
     }
+
+    async fn notify_syncing(
+        self,
+        sync_state: sync_layer::SyncState,
+        stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let mut is_synced = sync_state.is_synced();
+        let mut timer = interval(self.polling_interval);
+        loop {
+            if *stop_receiver.borrow() {
+                tracing::info!("Stop signal received, pubsub_syncing_notifier is shutting down");
+                break;
+            }
+            timer.tick().await;
+
+            let status = sync_state.web3_status();
+            let new_is_synced = matches!(status, SyncState::NotSyncing);
+            // Only changes of the sync status are reported, similar to Geth.
+            if new_is_synced != is_synced {
+                is_synced = new_is_synced;
+                let notify_latency =
+                    PUB_SUB_METRICS.notify_subscribers_latency[&SubscriptionType::Syncing].start();
+                for sink in self.current_subscribers().await {
+                    if sink
+                        .notify(Ok(PubSubResult::Syncing(status.clone())))
+                        .is_ok()
+                    {
+                        PUB_SUB_METRICS.notify[&SubscriptionType::Syncing].inc();
+                    }
+                }
+                notify_latency.observe();
+            }
+            self.emit_event(PubSubEvent::NotifyIterationFinished(
+                SubscriptionType::Syncing,
+            ));
+        }
+        Ok(())
+    }
+
+    async fn notify_l1_batch_statuses(
+        self,
+        stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let last_numbers = self.last_l1_batch_numbers().await?;
+        let mut cursors = last_numbers.map(L1BatchStatusCursor::new);
+        let mut timer = interval(self.polling_interval);
+        loop {
+            if *stop_receiver.borrow() {
+                tracing::info!(
+                    "Stop signal received, pubsub_l1_batch_status_notifier is shutting down"
+                );
+                break;
+            }
+            timer.tick().await;
+
+            let db_latency =
+                PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::L1BatchStatuses].start();
+            let new_numbers = self.last_l1_batch_numbers().await?;
+            let updates = self
+                .new_l1_batch_status_updates(&mut cursors, &new_numbers)
+                .await?;
+            db_latency.observe();
+
+            if !updates.is_empty() {
+                let notify_latency = PUB_SUB_METRICS.notify_subscribers_latency
+                    [&SubscriptionType::L1BatchStatuses]
+                    .start();
+                for sink in self.current_subscribers().await {
+                    for update in updates.iter().cloned() {
+                        if sink
+                            .notify(Ok(PubSubResult::L1BatchStatus(update)))
+                            .is_err()
+                        {
+                            // Subscriber disconnected.
+                            break;
+                        }
+                        PUB_SUB_METRICS.notify[&SubscriptionType::L1BatchStatuses].inc();
+                    }
+                }
+                notify_latency.observe();
+            }
+            self.emit_event(PubSubEvent::NotifyIterationFinished(
+                SubscriptionType::L1BatchStatuses,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the last L1 batch number for each of [`L1BatchStatus::ALL`].
+    async fn last_l1_batch_numbers(&self) -> anyhow::Result<[Option<L1BatchNumber>; 4]> {
+        let mut storage = self
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .context("access_storage_tagged")?;
+        let sealed = storage
+            .blocks_web3_dal()
+            .get_sealed_l1_batch_number()
+            .await
+            .context("get_sealed_l1_batch_number()")?;
+        let committed = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_committed_on_eth()
+            .await
+            .context("get_number_of_last_l1_batch_committed_on_eth()")?;
+        let proven = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_proven_on_eth()
+            .await
+            .context("get_number_of_last_l1_batch_proven_on_eth()")?;
+        let executed = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await
+            .context("get_number_of_last_l1_batch_executed_on_eth()")?;
+        Ok([Some(sealed), committed, proven, executed])
+    }
+
+    async fn new_l1_batch_status_updates(
+        &self,
+        cursors: &mut [L1BatchStatusCursor; 4],
+        new_numbers: &[Option<L1BatchNumber>; 4],
+    ) -> anyhow::Result<Vec<L1BatchStatusUpdate>> {
+        let mut storage = self
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .context("access_storage_tagged")?;
+        let mut updates = vec![];
+        let cursors = cursors.iter_mut().zip(new_numbers);
+        for (status, (cursor, &new_number)) in L1BatchStatus::ALL.into_iter().zip(cursors) {
+            let Some(new_number) = new_number else {
+                continue;
+            };
+            let range = cursor.scan_range(new_number);
+            if range.is_empty() {
+                continue;
+            }
+            let mut status_updates = storage
+                .blocks_web3_dal()
+                .get_l1_batch_status_updates(status, range.clone())
+                .await
+                .with_context(|| format!("get_l1_batch_status_updates({status:?}, {range:?})"))?;
+            cursor.filter_updates(&mut status_updates);
+            updates.extend(status_updates);
+        }
+        Ok(updates)
+    }
 }
 
 impl PubSubNotifier<(typed::Sink<PubSubResult>, PubSubFilter)> {
@@ -220,6 +424,10 @@ pub(super) struct EthSubscribe {
     active_block_subs: SubscriptionMap<typed::Sink<PubSubResult>>,
     active_tx_subs: SubscriptionMap<typed::Sink<PubSubResult>>,
     active_log_subs: SubscriptionMap<(typed::Sink<PubSubResult>, PubSubFilter)>,
+    active_sync_subs: SubscriptionMap<typed::Sink<PubSubResult>>,
+    active_l1_batch_status_subs: SubscriptionMap<typed::Sink<PubSubResult>>,
+    /// Sync state of the node; `None` for the main node, which is always synced.
+    sync_state: Option<sync_layer::SyncState>,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
@@ -230,6 +438,9 @@ impl EthSubscribe {
             active_block_subs: SubscriptionMap::default(),
             active_tx_subs: SubscriptionMap::default(),
             active_log_subs: SubscriptionMap::default(),
+            active_sync_subs: SubscriptionMap::default(),
+            active_l1_batch_status_subs: SubscriptionMap::default(),
+            sync_state: None,
             events_sender: None,
         }
     }
@@ -238,6 +449,10 @@ impl EthSubscribe {
         self.events_sender = Some(sender);
     }
 
+    pub fn set_sync_state(&mut self, sync_state: sync_layer::SyncState) {
+        self.sync_state = Some(sync_state);
+    }
+
     /// Assigns ID for the subscriber if the connection is open, returns error otherwise.
     fn assign_id(
         subscriber: typed::Subscriber<PubSubResult>,
@@ -307,11 +522,26 @@ impl EthSubscribe {
                 }
             }
             "syncing" => {
-                let Ok((sink, _id)) = Self::assign_id(subscriber) else {
+                let mut sync_subs = self.active_sync_subs.write().await;
+                let Ok((sink, id)) = Self::assign_id(subscriber) else {
                     return;
                 };
-                let _ = sink.notify(Ok(PubSubResult::Syncing(false)));
-                None
+                // Report the current status right away; subsequent notifications are only sent on changes.
+                let status = self
+                    .sync_state
+                    .as_ref()
+                    .map_or(SyncState::NotSyncing, |state| state.web3_status());
+                sink.notify(Ok(PubSubResult::Syncing(status))).ok();
+                sync_subs.insert(id, sink);
+                Some(SubscriptionType::Syncing)
+            }
+            "zks_l1BatchStatus" => {
+                let mut l1_batch_status_subs = self.active_l1_batch_status_subs.write().await;
+                let Ok((sink, id)) = Self::assign_id(subscriber) else {
+                    return;
+                };
+                l1_batch_status_subs.insert(id, sink);
+                Some(SubscriptionType::L1BatchStatuses)
             }
             _ => {
                 Self::reject(subscriber);
@@ -335,6 +565,16 @@ impl EthSubscribe {
             Some(SubscriptionType::Txs)
         } else if self.active_log_subs.write().await.remove(&id).is_some() {
             Some(SubscriptionType::Logs)
+        } else if self.active_sync_subs.write().await.remove(&id).is_some() {
+            Some(SubscriptionType::Syncing)
+        } else if self
+            .active_l1_batch_status_subs
+            .write()
+            .await
+            .remove(&id)
+            .is_some()
+        {
+            Some(SubscriptionType::L1BatchStatuses)
         } else {
             None
         };
@@ -357,7 +597,7 @@ impl EthSubscribe {
         polling_interval: Duration,
         stop_receiver: watch::Receiver<bool>,
     ) -> Vec<JoinHandle<anyhow::Result<()>>> {
-        let mut notifier_tasks = Vec::with_capacity(3);
+        let mut notifier_tasks = Vec::with_capacity(5);
         let notifier = PubSubNotifier {
             subscribers: self.active_block_subs.clone(),
             connection_pool: connection_pool.clone(),
@@ -378,13 +618,112 @@ impl EthSubscribe {
 
         let notifier = PubSubNotifier {
             subscribers: self.active_log_subs.clone(),
+            connection_pool: connection_pool.clone(),
+            polling_interval,
+            events_sender: self.events_sender.clone(),
+        };
+        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver.clone()));
+        notifier_tasks.push(notifier_task);
+
+        // The main node is always synced, so there's nothing to notify about.
+        if let Some(sync_state) = self.sync_state.clone() {
+            let notifier = PubSubNotifier {
+                subscribers: self.active_sync_subs.clone(),
+                connection_pool: connection_pool.clone(),
+                polling_interval,
+                events_sender: self.events_sender.clone(),
+            };
+            let notifier_task =
+                tokio::spawn(notifier.notify_syncing(sync_state, stop_receiver.clone()));
+            notifier_tasks.push(notifier_task);
+        }
+
+        let notifier = PubSubNotifier {
+            subscribers: self.active_l1_batch_status_subs.clone(),
             connection_pool,
             polling_interval,
             events_sender: self.events_sender.clone(),
         };
-        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver));
+        let notifier_task = tokio::spawn(notifier.notify_l1_batch_statuses(stop_receiver));
 
         notifier_tasks.push(notifier_task);
         notifier_tasks
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn committed_updates(numbers: impl IntoIterator<Item = u32>) -> Vec<L1BatchStatusUpdate> {
+        numbers
+            .into_iter()
+            .map(|number| L1BatchStatusUpdate {
+                l1_batch_number: L1BatchNumber(number),
+                status: L1BatchStatus::Committed,
+                l1_tx_hash: Some(H256::repeat_byte(number as u8)),
+            })
+            .collect()
+    }
+
+    fn update_numbers(updates: &[L1BatchStatusUpdate]) -> Vec<u32> {
+        updates
+            .iter()
+            .map(|update| update.l1_batch_number.0)
+            .collect()
+    }
+
+    #[test]
+    fn l1_batch_status_cursor_rescans_out_of_order_updates() {
+        let mut cursor = L1BatchStatusCursor::new(Some(L1BatchNumber(2)));
+        assert_eq!(
+            cursor.scan_range(L1BatchNumber(5)),
+            L1BatchNumber(3)..=L1BatchNumber(5)
+        );
+        // Batch #4 hasn't reached the status yet.
+        let mut updates = committed_updates([3, 5]);
+        cursor.filter_updates(&mut updates);
+        assert_eq!(update_numbers(&updates), [3, 5]);
+        assert_eq!(cursor.next_number, L1BatchNumber(4));
+
+        assert_eq!(
+            cursor.scan_range(L1BatchNumber(6)),
+            L1BatchNumber(4)..=L1BatchNumber(6)
+        );
+        let mut updates = committed_updates([4, 5, 6]);
+        cursor.filter_updates(&mut updates);
+        assert_eq!(update_numbers(&updates), [4, 6]);
+        assert_eq!(cursor.next_number, L1BatchNumber(7));
+        assert!(cursor.reported.is_empty());
+    }
+
+    #[test]
+    fn l1_batch_status_cursor_limits_rescan_window() {
+        let mut cursor = L1BatchStatusCursor::new(Some(L1BatchNumber(0)));
+        let last_number = L1BatchNumber(100);
+        let range = cursor.scan_range(last_number);
+        assert_eq!(
+            *range.start(),
+            last_number + 1 - L1_BATCH_STATUS_RESCAN_WINDOW
+        );
+        assert_eq!(*range.end(), last_number);
+    }
+
+    #[test]
+    fn l1_batch_status_cursor_handles_reverts() {
+        let mut cursor = L1BatchStatusCursor::new(Some(L1BatchNumber(2)));
+        cursor.scan_range(L1BatchNumber(5));
+        let mut updates = committed_updates([3, 5]);
+        cursor.filter_updates(&mut updates);
+
+        // Batches #4 and #5 are reverted, and then batch #5 is re-created.
+        assert!(cursor.scan_range(L1BatchNumber(3)).is_empty());
+        assert_eq!(
+            cursor.scan_range(L1BatchNumber(5)),
+            L1BatchNumber(4)..=L1BatchNumber(5)
+        );
+        let mut updates = committed_updates([4, 5]);
+        cursor.filter_updates(&mut updates);
+        assert_eq!(update_numbers(&updates), [4, 5]);
+    }
+}
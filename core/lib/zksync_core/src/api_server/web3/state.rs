@@ -45,6 +45,7 @@ pub struct InternalApiConfig {
     pub l2_testnet_paymaster_addr: Option<Address>,
     pub req_entities_limit: usize,
     pub fee_history_limit: u64,
+    pub validation_diagnostics_in_errors: bool,
 }
 
 impl InternalApiConfig {
@@ -70,6 +71,7 @@ impl InternalApiConfig {
             l2_testnet_paymaster_addr: contracts_config.l2_testnet_paymaster_addr,
             req_entities_limit: web3_config.req_entities_limit(),
             fee_history_limit: web3_config.fee_history_limit(),
+            validation_diagnostics_in_errors: web3_config.validation_diagnostics_in_errors(),
         }
     }
 }
@@ -1,7 +1,8 @@
-use std::{sync::Arc, time::Instant};
+use std::{collections::HashMap, net::Ipv4Addr, sync::Arc, time::Instant};
 
 use assert_matches::assert_matches;
 use async_trait::async_trait;
+use tempfile::TempDir;
 use tokio::sync::watch;
 use zksync_config::configs::{
     api::Web3JsonRpcConfig,
@@ -9,16 +10,31 @@ use zksync_config::configs::{
     ContractsConfig,
 };
 use zksync_contracts::BaseSystemContractsHashes;
+use zksync_crypto::hasher::blake2::Blake2Hasher;
 use zksync_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool};
 use zksync_health_check::CheckHealth;
+use zksync_merkle_tree::TreeEntryWithProof;
 use zksync_state::PostgresStorageCaches;
+use zksync_test_account::Account;
 use zksync_types::{
-    block::MiniblockHeader, fee::TransactionExecutionMetrics, tx::IncludedTxLocation, Address,
-    L1BatchNumber, ProtocolVersionId, VmEvent, H256, U64,
+    api::{
+        self, BundleSimulationConfig, CallTracerConfig, DebugTrace, OverrideAccount, StateOverride,
+        SupportedTracers, TraceCallConfig, TracerConfig,
+    },
+    block::MiniblockHeader,
+    fee::{Fee, TransactionExecutionMetrics},
+    get_code_key, get_nonce_key,
+    l2::L2Tx,
+    transaction_request::{CallRequest, PaymasterParams},
+    tx::IncludedTxLocation,
+    utils::storage_key_for_eth_balance,
+    AccountTreeId, Address, Bytes, Execute, L1BatchNumber, L2ChainId, MiniblockNumber, Nonce,
+    PackedEthSignature, ProtocolVersionId, StorageKey, Transaction, VmEvent, H256, U256, U64,
 };
+use zksync_utils::{h256_to_u256, u256_to_h256};
 use zksync_web3_decl::{
     jsonrpsee::{core::Error as RpcError, http_client::HttpClient, types::error::ErrorCode},
-    namespaces::{EthNamespaceClient, ZksNamespaceClient},
+    namespaces::{DebugNamespaceClient, EthNamespaceClient, ZksNamespaceClient},
     types::FilterChanges,
 };
 
@@ -26,7 +42,10 @@ use super::{metrics::ApiTransportLabel, *};
 use crate::{
     api_server::tx_sender::TxSenderConfig,
     genesis::{ensure_genesis_state, GenesisParams},
-    state_keeper::tests::create_l2_transaction,
+    metadata_calculator::tests::{
+        gen_storage_logs, reset_db_state, run_calculator, setup_calculator,
+    },
+    state_keeper::tests::{create_l2_transaction, execute_and_seal_l1_batch},
 };
 
 mod ws;
@@ -81,9 +100,34 @@ pub(crate) async fn spawn_http_server(
     pool: ConnectionPool,
     stop_receiver: watch::Receiver<bool>,
 ) -> ApiServerHandles {
-    spawn_server(ApiTransportLabel::Http, network_config, pool, stop_receiver)
-        .await
-        .0
+    spawn_server(
+        ApiTransportLabel::Http,
+        network_config,
+        &Web3JsonRpcConfig::for_tests(),
+        pool,
+        None,
+        stop_receiver,
+    )
+    .await
+    .0
+}
+
+async fn spawn_http_server_with_tree_api(
+    network_config: &NetworkConfig,
+    pool: ConnectionPool,
+    tree_api_url: String,
+    stop_receiver: watch::Receiver<bool>,
+) -> ApiServerHandles {
+    spawn_server(
+        ApiTransportLabel::Http,
+        network_config,
+        &Web3JsonRpcConfig::for_tests(),
+        pool,
+        Some(tree_api_url),
+        stop_receiver,
+    )
+    .await
+    .0
 }
 
 async fn spawn_ws_server(
@@ -91,27 +135,36 @@ async fn spawn_ws_server(
     pool: ConnectionPool,
     stop_receiver: watch::Receiver<bool>,
 ) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
-    spawn_server(ApiTransportLabel::Ws, network_config, pool, stop_receiver).await
+    spawn_server(
+        ApiTransportLabel::Ws,
+        network_config,
+        &Web3JsonRpcConfig::for_tests(),
+        pool,
+        None,
+        stop_receiver,
+    )
+    .await
 }
 
 async fn spawn_server(
     transport: ApiTransportLabel,
     network_config: &NetworkConfig,
+    web3_config: &Web3JsonRpcConfig,
     pool: ConnectionPool,
+    tree_api_url: Option<String>,
     stop_receiver: watch::Receiver<bool>,
 ) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
     let contracts_config = ContractsConfig::for_tests();
-    let web3_config = Web3JsonRpcConfig::for_tests();
     let state_keeper_config = StateKeeperConfig::for_tests();
-    let api_config = InternalApiConfig::new(network_config, &web3_config, &contracts_config);
+    let api_config = InternalApiConfig::new(network_config, web3_config, &contracts_config);
     let tx_sender_config =
-        TxSenderConfig::new(&state_keeper_config, &web3_config, api_config.l2_chain_id);
+        TxSenderConfig::new(&state_keeper_config, web3_config, api_config.l2_chain_id);
 
     let storage_caches = PostgresStorageCaches::new(1, 1);
     let gas_adjuster = Arc::new(MockL1GasPriceProvider(1));
     let (tx_sender, vm_barrier) = crate::build_tx_sender(
         &tx_sender_config,
-        &web3_config,
+        web3_config,
         &state_keeper_config,
         pool.clone(),
         pool.clone(),
@@ -128,11 +181,14 @@ async fn spawn_server(
             .with_polling_interval(POLL_INTERVAL)
             .with_subscriptions_limit(100),
     };
+    let mut namespaces = Namespace::DEFAULT.to_vec();
+    namespaces.push(Namespace::Debug);
     let server_handles = server_builder
         .with_threads(1)
         .with_tx_sender(tx_sender, vm_barrier)
         .with_pub_sub_events(pub_sub_events_sender)
-        .enable_api_namespaces(Namespace::DEFAULT.to_vec())
+        .with_tree_api(tree_api_url)
+        .enable_api_namespaces(namespaces)
         .build(stop_receiver)
         .await
         .expect("Failed spawning JSON-RPC server");
@@ -141,6 +197,10 @@ async fn spawn_server(
 
 #[async_trait]
 trait HttpTest {
+    fn web3_config(&self) -> Web3JsonRpcConfig {
+        Web3JsonRpcConfig::for_tests()
+    }
+
     async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()>;
 }
 
@@ -160,7 +220,15 @@ async fn test_http_server(test: impl HttpTest) {
     drop(storage);
 
     let (stop_sender, stop_receiver) = watch::channel(false);
-    let server_handles = spawn_http_server(&network_config, pool.clone(), stop_receiver).await;
+    let (server_handles, _) = spawn_server(
+        ApiTransportLabel::Http,
+        &network_config,
+        &test.web3_config(),
+        pool.clone(),
+        None,
+        stop_receiver,
+    )
+    .await;
     server_handles.wait_until_ready().await;
 
     let client = <HttpClient>::builder()
@@ -494,3 +562,621 @@ impl HttpTest for LogFilterChangesWithBlockBoundaries {
 async fn log_filter_changes_with_block_boundaries() {
     test_http_server(LogFilterChangesWithBlockBoundaries).await;
 }
+
+#[derive(Debug)]
+struct PrestateTracingForSealedTransactions;
+
+#[async_trait]
+impl HttpTest for PrestateTracingForSealedTransactions {
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let account = Account::random();
+        let contract_address = Address::repeat_byte(0x23);
+        let txs: Vec<_> = (0..2)
+            .map(|serial_id| {
+                let execute = Execute {
+                    contract_address,
+                    calldata: vec![],
+                    value: 0.into(),
+                    factory_deps: None,
+                };
+                account.get_l1_tx(execute, serial_id)
+            })
+            .collect();
+        let tx_hashes: Vec<_> = txs.iter().map(Transaction::hash).collect();
+        let chain_id = NetworkConfig::for_tests().zksync_network_id;
+        execute_and_seal_l1_batch(pool, chain_id, vec![txs]).await;
+
+        let prestate_tracer = TracerConfig {
+            tracer: SupportedTracers::PrestateTracer,
+            tracer_config: CallTracerConfig::default(),
+        };
+        let mut tx_traces = vec![];
+        for &tx_hash in &tx_hashes {
+            let trace = client
+                .trace_transaction(tx_hash, Some(prestate_tracer.clone()))
+                .await?;
+            let Some(DebugTrace::Prestate(api::PrestateTrace::Prestate(accounts))) = &trace else {
+                anyhow::bail!("Unexpected trace for transaction {tx_hash:?}: {trace:?}");
+            };
+            // The code of the called contract is only read, so it's not present in the persisted storage logs.
+            let contract = accounts.get(&contract_address);
+            assert_eq!(
+                contract.and_then(|contract| contract.code_hash),
+                Some(H256::zero())
+            );
+            tx_traces.push(serde_json::to_value(trace)?);
+        }
+
+        let block_number = api::BlockNumber::Number(1.into());
+        let block_traces = client
+            .trace_block_by_number(block_number, Some(prestate_tracer.clone()))
+            .await?;
+        let block_traces: Vec<_> = block_traces
+            .into_iter()
+            .map(|trace| serde_json::to_value(trace.result))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(block_traces, tx_traces);
+
+        let unknown_tx_trace = client
+            .trace_transaction(H256::repeat_byte(0xff), Some(prestate_tracer))
+            .await?;
+        assert!(unknown_tx_trace.is_none());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn prestate_tracing_for_sealed_transactions() {
+    test_http_server(PrestateTracingForSealedTransactions).await;
+}
+
+#[derive(Debug)]
+struct L1BatchTracing;
+
+#[async_trait]
+impl HttpTest for L1BatchTracing {
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let account = Account::random();
+        let txs: Vec<_> = (0..3)
+            .map(|serial_id| {
+                let execute = Execute {
+                    contract_address: Address::random(),
+                    calldata: vec![],
+                    value: 0.into(),
+                    factory_deps: None,
+                };
+                account.get_l1_tx(execute, serial_id)
+            })
+            .collect();
+        let tx_hashes: Vec<_> = txs.iter().map(Transaction::hash).collect();
+        let chain_id = NetworkConfig::for_tests().zksync_network_id;
+        let miniblocks = vec![txs[..2].to_vec(), txs[2..].to_vec()];
+        let l1_batch_number = execute_and_seal_l1_batch(pool, chain_id, miniblocks).await;
+        assert_eq!(l1_batch_number, L1BatchNumber(1));
+
+        let trace = client.trace_l1_batch(l1_batch_number, None).await?;
+        assert_eq!(trace.l1_batch_number, l1_batch_number);
+        let traced_txs: Vec<_> = trace
+            .transactions
+            .iter()
+            .map(|tx| (tx.tx_hash, tx.miniblock_number))
+            .collect();
+        let expected_miniblocks = [1, 1, 2].map(MiniblockNumber);
+        let expected_txs: Vec<_> = tx_hashes.into_iter().zip(expected_miniblocks).collect();
+        assert_eq!(traced_txs, expected_txs);
+
+        for tx_trace in &trace.transactions {
+            assert!(!tx_trace.result.calls.is_empty());
+            // Compare with the call trace persisted by the state keeper during the original execution.
+            let persisted_trace = client.trace_transaction(tx_trace.tx_hash, None).await?;
+            let Some(DebugTrace::Call(persisted_call)) = persisted_trace else {
+                anyhow::bail!(
+                    "No call trace persisted for transaction {:?}",
+                    tx_trace.tx_hash
+                );
+            };
+            assert_eq!(tx_trace.result.from, persisted_call.from);
+            assert_eq!(tx_trace.result.to, persisted_call.to);
+            assert_eq!(
+                serde_json::to_value(&tx_trace.result.calls)?,
+                serde_json::to_value(&persisted_call.calls)?
+            );
+        }
+
+        let only_top_call = TracerConfig {
+            tracer: SupportedTracers::CallTracer,
+            tracer_config: CallTracerConfig {
+                only_top_call: true,
+                ..CallTracerConfig::default()
+            },
+        };
+        let trace = client
+            .trace_l1_batch(l1_batch_number, Some(only_top_call))
+            .await?;
+        assert_eq!(trace.transactions.len(), 3);
+        assert!(trace
+            .transactions
+            .iter()
+            .all(|tx| tx.result.calls.is_empty()));
+
+        // The genesis batch is not executed in the VM, and the next batch is not sealed yet.
+        for l1_batch_number in [0, 2].map(L1BatchNumber) {
+            let err = client
+                .trace_l1_batch(l1_batch_number, None)
+                .await
+                .unwrap_err();
+            assert_matches!(err, RpcError::Call(_));
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn l1_batch_tracing() {
+    test_http_server(L1BatchTracing).await;
+}
+
+#[derive(Debug)]
+struct ProofWithoutTreeApi;
+
+#[async_trait]
+impl HttpTest for ProofWithoutTreeApi {
+    async fn test(&self, client: &HttpClient, _pool: &ConnectionPool) -> anyhow::Result<()> {
+        // `zks_getProof` has the same Rust method name, hence the qualified call.
+        let proof_future = EthNamespaceClient::get_proof(
+            client,
+            Address::repeat_byte(1),
+            vec![H256::zero()],
+            None,
+        );
+        let err = proof_future.await.unwrap_err();
+        // The error code corresponds to `Web3Error::TreeApiUnavailable`.
+        assert_matches!(err, RpcError::Call(err) if err.code() == 6);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn proof_without_tree_api() {
+    test_http_server(ProofWithoutTreeApi).await;
+}
+
+#[tokio::test]
+async fn getting_proofs() {
+    let pool = ConnectionPool::test_pool().await;
+    let temp_dir = TempDir::new().unwrap();
+    let (calculator, _) = setup_calculator(temp_dir.path(), &pool).await;
+    reset_db_state(&pool, 1).await;
+    let tree_reader = calculator.tree_reader();
+    let calculator_task = tokio::spawn(run_calculator(calculator, pool.clone()));
+    let tree_reader = tree_reader.await;
+    let root_hash = calculator_task.await.unwrap();
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let tree_api_addr = (Ipv4Addr::LOCALHOST, 0).into();
+    let tree_api_server = tree_reader
+        .clone()
+        .create_api_server(&tree_api_addr, stop_receiver.clone())
+        .unwrap();
+    let tree_api_url = format!("http://{}", tree_api_server.local_addr());
+    let tree_api_task = tokio::spawn(tree_api_server.run());
+    let server_handles = spawn_http_server_with_tree_api(
+        &NetworkConfig::for_tests(),
+        pool,
+        tree_api_url,
+        stop_receiver,
+    )
+    .await;
+    server_handles.wait_until_ready().await;
+    let client = <HttpClient>::builder()
+        .build(format!("http://{}/", server_handles.local_addr))
+        .unwrap();
+
+    let log = gen_storage_logs(0..100, 1)[0][0];
+    let address = *log.key.address();
+    let missing_key = H256::repeat_byte(0xff);
+    let proof =
+        EthNamespaceClient::get_proof(&client, address, vec![*log.key.key(), missing_key], None)
+            .await
+            .unwrap();
+
+    assert_eq!(proof.address, address);
+    assert_eq!(proof.l1_batch_number, L1BatchNumber(1));
+    assert_eq!(proof.storage_hash, root_hash);
+    // The account doesn't have code, balance or nonce; these values are proven to be absent.
+    assert_eq!(proof.code_hash, H256::zero());
+    assert_eq!(proof.balance, 0.into());
+    assert_eq!(proof.nonce, 0.into());
+    assert_eq!(proof.storage_proof.len(), 2);
+    assert_eq!(proof.storage_proof[0].key, *log.key.key());
+    assert_eq!(proof.storage_proof[0].value, h256_to_u256(log.value));
+    assert_eq!(proof.storage_proof[1].key, missing_key);
+    assert_eq!(proof.storage_proof[1].value, 0.into());
+
+    let storage_keys = [
+        get_code_key(&address),
+        storage_key_for_eth_balance(&address),
+        get_nonce_key(&address),
+        log.key,
+        StorageKey::new(AccountTreeId::new(address), missing_key),
+    ];
+    let merkle_paths = [
+        &proof.account_proof,
+        &proof.balance_proof,
+        &proof.nonce_proof,
+        &proof.storage_proof[0].proof,
+        &proof.storage_proof[1].proof,
+    ];
+    let hashed_keys = storage_keys.iter().map(StorageKey::hashed_key_u256);
+    let tree_entries = tree_reader
+        .entries_with_proofs(L1BatchNumber(1), hashed_keys.collect())
+        .await
+        .unwrap();
+    for (i, (tree_entry, merkle_path)) in tree_entries.into_iter().zip(merkle_paths).enumerate() {
+        // Only the storage slot is present in the tree; other entries are proven to be absent.
+        assert_eq!(tree_entry.base.leaf_index != 0, i == 3);
+        // Proofs are returned in the root-to-leaf order, while the tree uses the reverse one.
+        let mut merkle_path = merkle_path.clone();
+        merkle_path.reverse();
+        assert_eq!(merkle_path, tree_entry.merkle_path);
+        let entry_with_proof = TreeEntryWithProof {
+            base: tree_entry.base,
+            merkle_path,
+        };
+        entry_with_proof.verify(&Blake2Hasher, root_hash);
+    }
+
+    stop_sender.send_replace(true);
+    server_handles.shutdown().await;
+    tree_api_task.await.unwrap().unwrap();
+}
+
+#[derive(Debug)]
+struct CallsWithStateOverrides;
+
+impl CallsWithStateOverrides {
+    const ALICE: Address = Address::repeat_byte(0xa1);
+    const BOB: Address = Address::repeat_byte(0xb0);
+
+    fn transfer_value() -> U256 {
+        U256::exp10(18)
+    }
+
+    fn transfer() -> CallRequest {
+        CallRequest::builder()
+            .from(Self::ALICE)
+            .to(Self::BOB)
+            .value(Self::transfer_value())
+            .build()
+    }
+
+    fn balance_override() -> StateOverride {
+        HashMap::from([(
+            Self::ALICE,
+            OverrideAccount {
+                balance: Some(Self::transfer_value() * 10),
+                ..OverrideAccount::default()
+            },
+        )])
+    }
+
+    /// Sets the balance of Alice by directly overriding the storage slot of the L2 ETH token contract.
+    fn balance_slot_override() -> StateOverride {
+        let balance_key = storage_key_for_eth_balance(&Self::ALICE);
+        let balance = u256_to_h256(Self::transfer_value() * 10);
+        HashMap::from([(
+            *balance_key.address(),
+            OverrideAccount {
+                state_diff: Some(HashMap::from([(*balance_key.key(), balance)])),
+                ..OverrideAccount::default()
+            },
+        )])
+    }
+
+    async fn test_eth_call(client: &HttpClient) -> anyhow::Result<()> {
+        // Alice has no funds without overrides.
+        let err = client.call(Self::transfer(), None, None).await.unwrap_err();
+        assert_matches!(err, RpcError::Call(_));
+
+        client
+            .call(Self::transfer(), None, Some(Self::balance_override()))
+            .await?;
+        client
+            .call(Self::transfer(), None, Some(Self::balance_slot_override()))
+            .await?;
+        Ok(())
+    }
+
+    async fn test_estimate_gas(client: &HttpClient) -> anyhow::Result<()> {
+        let err = client
+            .estimate_gas(Self::transfer(), None, None)
+            .await
+            .unwrap_err();
+        assert_matches!(err, RpcError::Call(_));
+
+        let gas = client
+            .estimate_gas(Self::transfer(), None, Some(Self::balance_override()))
+            .await?;
+        assert!(gas > U256::zero());
+        let gas = client
+            .estimate_gas(Self::transfer(), None, Some(Self::balance_slot_override()))
+            .await?;
+        assert!(gas > U256::zero());
+
+        // The nonce override is used as the transaction nonce if the latter is not specified.
+        let mut state_override = Self::balance_override();
+        state_override.get_mut(&Self::ALICE).unwrap().nonce = Some(5.into());
+        let gas = client
+            .estimate_gas(Self::transfer(), None, Some(state_override.clone()))
+            .await?;
+        assert!(gas > U256::zero());
+
+        let mut request = Self::transfer();
+        request.nonce = Some(5.into());
+        client
+            .estimate_gas(request.clone(), None, Some(state_override.clone()))
+            .await?;
+        request.nonce = Some(0.into());
+        let err = client
+            .estimate_gas(request, None, Some(state_override))
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            RpcError::Call(err) if err.code() == ErrorCode::InvalidParams.code()
+        );
+        Ok(())
+    }
+
+    async fn test_trace_call(client: &HttpClient) -> anyhow::Result<()> {
+        let trace_config = |state_overrides| TraceCallConfig {
+            options: TracerConfig {
+                tracer: SupportedTracers::CallTracer,
+                tracer_config: CallTracerConfig::default(),
+            },
+            state_overrides,
+        };
+
+        let trace = client
+            .trace_call(Self::transfer(), None, Some(trace_config(None)))
+            .await?;
+        let DebugTrace::Call(call) = trace else {
+            anyhow::bail!("Unexpected trace: {trace:?}");
+        };
+        assert!(
+            call.error.is_some() || call.revert_reason.is_some(),
+            "{call:?}"
+        );
+
+        let state_override = Some(Self::balance_override());
+        let trace = client
+            .trace_call(Self::transfer(), None, Some(trace_config(state_override)))
+            .await?;
+        let DebugTrace::Call(call) = trace else {
+            anyhow::bail!("Unexpected trace: {trace:?}");
+        };
+        assert_eq!(call.error, None, "{call:?}");
+        assert_eq!(call.revert_reason, None, "{call:?}");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HttpTest for CallsWithStateOverrides {
+    async fn test(&self, client: &HttpClient, _pool: &ConnectionPool) -> anyhow::Result<()> {
+        Self::test_eth_call(client).await?;
+        Self::test_estimate_gas(client).await?;
+        Self::test_trace_call(client).await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn calls_with_state_overrides() {
+    test_http_server(CallsWithStateOverrides).await;
+}
+
+#[derive(Debug)]
+struct InvalidBundleSimulation;
+
+#[async_trait]
+impl HttpTest for InvalidBundleSimulation {
+    async fn test(&self, client: &HttpClient, _pool: &ConnectionPool) -> anyhow::Result<()> {
+        let err = client
+            .simulate_bundle(vec![], None, None)
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            RpcError::Call(err) if err.code() == ErrorCode::InvalidParams.code()
+        );
+
+        let requests = vec![CallRequest::default(); 100];
+        let err = client
+            .simulate_bundle(requests, None, None)
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            RpcError::Call(err) if err.code() == ErrorCode::InvalidParams.code()
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn invalid_bundle_simulation() {
+    test_http_server(InvalidBundleSimulation).await;
+}
+
+#[derive(Debug)]
+struct BundleSimulationWithDependentCalls;
+
+#[async_trait]
+impl HttpTest for BundleSimulationWithDependentCalls {
+    async fn test(&self, client: &HttpClient, _pool: &ConnectionPool) -> anyhow::Result<()> {
+        let alice = Address::repeat_byte(0xa1);
+        let bob = Address::repeat_byte(0xb0);
+        let carol = Address::repeat_byte(0xc0);
+        let transfer_value = U256::exp10(18);
+        let transfer = |from, to| {
+            CallRequest::builder()
+                .from(from)
+                .to(to)
+                .value(transfer_value)
+                .build()
+        };
+        let state_override = HashMap::from([(
+            alice,
+            OverrideAccount {
+                balance: Some(transfer_value * 10),
+                ..OverrideAccount::default()
+            },
+        )]);
+        let config = BundleSimulationConfig {
+            with_call_traces: false,
+            state_overrides: Some(state_override),
+        };
+
+        // Bob can only send funds received from Alice in the first call, and only once.
+        let requests = vec![
+            transfer(alice, bob),
+            transfer(bob, carol),
+            transfer(bob, carol),
+        ];
+        let results = client.simulate_bundle(requests, None, Some(config)).await?;
+        assert_eq!(results.len(), 3);
+        for result in &results[..2] {
+            assert_eq!(result.error, None, "{result:?}");
+            assert_eq!(result.revert_reason, None, "{result:?}");
+        }
+        assert_eq!(results[2].error, None, "{:?}", results[2]);
+        assert!(results[2].revert_reason.is_some(), "{:?}", results[2]);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn bundle_simulation_with_dependent_calls() {
+    test_http_server(BundleSimulationWithDependentCalls).await;
+}
+
+#[derive(Debug)]
+struct DebugValidationWithMalformedTx;
+
+#[async_trait]
+impl HttpTest for DebugValidationWithMalformedTx {
+    async fn test(&self, client: &HttpClient, _pool: &ConnectionPool) -> anyhow::Result<()> {
+        let err = client
+            .debug_validation(Bytes(vec![1, 2, 3]))
+            .await
+            .unwrap_err();
+        // The error code corresponds to `Web3Error::SerializationError`.
+        assert_matches!(err, RpcError::Call(err) if err.code() == 3);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn debug_validation_with_malformed_tx() {
+    test_http_server(DebugValidationWithMalformedTx).await;
+}
+
+#[derive(Debug)]
+struct SendRawTransactionFailingValidation {
+    diagnostics_in_errors: bool,
+}
+
+impl SendRawTransactionFailingValidation {
+    /// Creates a transaction that fails account validation: its paymaster is not a contract,
+    /// so the paymaster preparation / validation step halts in the bootloader.
+    fn create_raw_tx(chain_id: L2ChainId) -> Bytes {
+        let fee = Fee {
+            gas_limit: 10_000_000.into(),
+            max_fee_per_gas: StateKeeperConfig::for_tests().fair_l2_gas_price.into(),
+            max_priority_fee_per_gas: 0.into(),
+            gas_per_pubdata_limit: 50_000.into(),
+        };
+        let paymaster_params = PaymasterParams {
+            paymaster: Address::repeat_byte(0x11),
+            paymaster_input: vec![],
+        };
+        let tx = L2Tx::new_signed(
+            Address::repeat_byte(0x22),
+            vec![],
+            Nonce(0),
+            fee,
+            U256::zero(),
+            chain_id,
+            &H256::random(),
+            None,
+            paymaster_params,
+        )
+        .unwrap();
+        let signature = PackedEthSignature::deserialize_packed(&tx.common_data.signature).unwrap();
+        let request = api::TransactionRequest::from(tx);
+        Bytes(request.get_signed_bytes(&signature, chain_id))
+    }
+}
+
+#[async_trait]
+impl HttpTest for SendRawTransactionFailingValidation {
+    fn web3_config(&self) -> Web3JsonRpcConfig {
+        Web3JsonRpcConfig {
+            validation_diagnostics_in_errors: Some(self.diagnostics_in_errors),
+            ..Web3JsonRpcConfig::for_tests()
+        }
+    }
+
+    async fn test(&self, client: &HttpClient, _pool: &ConnectionPool) -> anyhow::Result<()> {
+        let raw_tx = Self::create_raw_tx(NetworkConfig::for_tests().zksync_network_id);
+
+        let diagnostics = client.debug_validation(raw_tx.clone()).await?;
+        let validation_error = diagnostics.error.expect("transaction passed validation");
+        assert!(diagnostics.computational_gas_used > 0, "{diagnostics:?}");
+        assert_eq!(
+            diagnostics.computational_gas_limit,
+            StateKeeperConfig::for_tests().validation_computational_gas_limit
+        );
+
+        let err = client.send_raw_transaction(raw_tx).await.unwrap_err();
+        let RpcError::Call(err) = err else {
+            panic!("Unexpected error: {err:?}");
+        };
+        assert_eq!(err.code(), 3);
+        assert!(err.message().contains(&validation_error), "{err:?}");
+
+        let data: serde_json::Value = serde_json::from_str(err.data().unwrap().get())?;
+        if self.diagnostics_in_errors {
+            let data: api::ValidationDiagnostics = serde_json::from_value(data)?;
+            assert_eq!(data.error.as_ref(), Some(&validation_error));
+            assert_eq!(
+                data.computational_gas_used,
+                diagnostics.computational_gas_used
+            );
+            assert_eq!(
+                data.computational_gas_limit,
+                diagnostics.computational_gas_limit
+            );
+        } else {
+            assert_eq!(data, "0x");
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn send_raw_transaction_failing_validation() {
+    test_http_server(SendRawTransactionFailingValidation {
+        diagnostics_in_errors: false,
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn send_raw_transaction_failing_validation_with_diagnostics() {
+    test_http_server(SendRawTransactionFailingValidation {
+        diagnostics_in_errors: true,
+    })
+    .await;
+}
@@ -4,7 +4,11 @@ use async_trait::async_trait;
 use tokio::sync::watch;
 use zksync_config::configs::chain::NetworkConfig;
 use zksync_dal::ConnectionPool;
-use zksync_types::{api, Address, L1BatchNumber, H256, U64};
+use zksync_types::{
+    api,
+    block::{BlockGasCount, L1BatchHeader},
+    Address, L1BatchNumber, H256, U64,
+};
 use zksync_web3_decl::{
     jsonrpsee::{
         core::client::{Subscription, SubscriptionClientT},
@@ -12,7 +16,7 @@ use zksync_web3_decl::{
         ws_client::{WsClient, WsClientBuilder},
     },
     namespaces::{EthNamespaceClient, ZksNamespaceClient},
-    types::{BlockHeader, PubSubFilter},
+    types::{BlockHeader, PubSubFilter, SyncState},
 };
 
 use super::*;
@@ -463,3 +467,91 @@ impl WsTest for LogSubscriptionsWithDelay {
 async fn log_subscriptions_with_delay() {
     test_ws_server(LogSubscriptionsWithDelay).await;
 }
+
+#[derive(Debug)]
+struct SyncingSubscription;
+
+#[async_trait]
+impl WsTest for SyncingSubscription {
+    async fn test(
+        &self,
+        client: &WsClient,
+        _pool: &ConnectionPool,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        let params = rpc_params!["syncing"];
+        let mut syncing_subscription = client
+            .subscribe::<SyncState, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Syncing).await;
+
+        // The main node is always synced, and the status is reported right after subscribing.
+        let sync_state = tokio::time::timeout(TEST_TIMEOUT, syncing_subscription.next())
+            .await
+            .context("Timed out waiting for sync state")?
+            .context("Syncing subscription terminated")??;
+        assert_matches!(sync_state, SyncState::NotSyncing);
+        syncing_subscription.unsubscribe().await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn syncing_subscription() {
+    test_ws_server(SyncingSubscription).await;
+}
+
+#[derive(Debug)]
+struct L1BatchStatusSubscription;
+
+#[async_trait]
+impl WsTest for L1BatchStatusSubscription {
+    async fn test(
+        &self,
+        client: &WsClient,
+        pool: &ConnectionPool,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifier(&mut pub_sub_events, SubscriptionType::L1BatchStatuses).await;
+
+        let params = rpc_params!["zks_l1BatchStatus"];
+        let mut status_subscription = client
+            .subscribe::<api::L1BatchStatusUpdate, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::L1BatchStatuses).await;
+
+        let mut storage = pool.access_storage().await?;
+        let header = L1BatchHeader::new(
+            L1BatchNumber(1),
+            1,
+            Address::default(),
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+        );
+        storage
+            .blocks_dal()
+            .insert_l1_batch(&header, &[], BlockGasCount::default(), &[], &[])
+            .await?;
+        drop(storage);
+
+        let update = tokio::time::timeout(TEST_TIMEOUT, status_subscription.next())
+            .await
+            .context("Timed out waiting for L1 batch status update")?
+            .context("L1 batch status subscription terminated")??;
+        assert_eq!(
+            update,
+            api::L1BatchStatusUpdate {
+                l1_batch_number: L1BatchNumber(1),
+                status: api::L1BatchStatus::Sealed,
+                l1_tx_hash: None,
+            }
+        );
+        status_subscription.unsubscribe().await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn l1_batch_status_subscription() {
+    test_ws_server(L1BatchStatusSubscription).await;
+}
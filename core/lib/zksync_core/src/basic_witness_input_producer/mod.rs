@@ -15,7 +15,7 @@ use self::{
 };
 
 mod metrics;
-mod vm_interactions;
+pub(crate) mod vm_interactions;
 
 /// Component that extracts all data (from DB) necessary to run a Basic Witness Generator.
 /// Does this by rerunning an entire L1Batch and extracting information from both the VM run and DB.
@@ -37,7 +37,7 @@ impl BasicWitnessInputProducer {
     ) -> anyhow::Result<Self> {
         Ok(BasicWitnessInputProducer {
             connection_pool,
-            object_store: store_factory.create_store().await.into(),
+            object_store: store_factory.create_store().await?.into(),
             l2_chain_id,
         })
     }
@@ -80,7 +80,7 @@ impl BasicWitnessInputProducer {
             );
             for tx in &miniblock_data.txs {
                 tracing::trace!("Started execution of tx: {tx:?}");
-                execute_tx(tx, &mut vm)
+                execute_tx(tx, &mut vm, Vec::new)
                     .context("failed to execute transaction in BasicWitnessInputProducer")?;
                 tracing::trace!("Finished execution of tx: {tx:?}");
             }
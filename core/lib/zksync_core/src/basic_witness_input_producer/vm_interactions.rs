@@ -1,8 +1,8 @@
 use anyhow::{anyhow, Context};
 use multivm::{
-    interface::{VmInterface, VmInterfaceHistoryEnabled},
+    interface::{VmExecutionResultAndLogs, VmInterface, VmInterfaceHistoryEnabled},
     vm_latest::HistoryEnabled,
-    VmInstance,
+    MultiVmTracerPointer, VmInstance,
 };
 use tokio::runtime::Handle;
 use zksync_dal::StorageProcessor;
@@ -11,12 +11,12 @@ use zksync_types::{L1BatchNumber, L2ChainId, Transaction};
 
 use crate::state_keeper::io::common::load_l1_batch_params;
 
-pub(super) type VmAndStorage<'a> = (
+pub(crate) type VmAndStorage<'a> = (
     VmInstance<StorageView<PostgresStorage<'a>>, HistoryEnabled>,
     StoragePtr<StorageView<PostgresStorage<'a>>>,
 );
 
-pub(super) fn create_vm(
+pub(crate) fn create_vm(
     rt_handle: Handle,
     l1_batch_number: L1BatchNumber,
     mut connection: StorageProcessor<'_>,
@@ -46,7 +46,8 @@ pub(super) fn create_vm(
         })?;
 
     // In the state keeper, this value is used to reject execution.
-    // All batches ran by BasicWitnessInputProducer have already been executed by State Keeper.
+    // All batches re-executed using this VM (by BasicWitnessInputProducer or for API tracing)
+    // have already been executed by State Keeper.
     // This means we don't want to reject any execution, therefore we're using MAX as an allow all.
     let validation_computational_gas_limit = u32::MAX;
     let (system_env, l1_batch_env) = rt_handle
@@ -66,27 +67,24 @@ pub(super) fn create_vm(
     Ok((vm, storage_view))
 }
 
-pub(super) fn execute_tx<S: WriteStorage>(
+/// Executes a transaction the same way the state keeper does. `create_tracers` is called before each execution
+/// attempt, so that tracers don't retain data from a rolled back attempt.
+pub(crate) fn execute_tx<S: WriteStorage>(
     tx: &Transaction,
     vm: &mut VmInstance<S, HistoryEnabled>,
-) -> anyhow::Result<()> {
+    mut create_tracers: impl FnMut() -> Vec<MultiVmTracerPointer<S, HistoryEnabled>>,
+) -> anyhow::Result<VmExecutionResultAndLogs> {
     // Attempt to run VM with bytecode compression on.
     vm.make_snapshot();
-    if vm
-        .execute_transaction_with_bytecode_compression(tx.clone(), true)
-        .is_ok()
+    if let Ok(result) =
+        vm.inspect_transaction_with_bytecode_compression(create_tracers().into(), tx.clone(), true)
     {
         vm.pop_snapshot_no_rollback();
-        return Ok(());
+        return Ok(result);
     }
 
     // If failed with bytecode compression, attempt to run without bytecode compression.
     vm.rollback_to_the_latest_snapshot();
-    if vm
-        .execute_transaction_with_bytecode_compression(tx.clone(), false)
-        .is_err()
-    {
-        return Err(anyhow!("compression can't fail if we don't apply it"));
-    }
-    Ok(())
+    vm.inspect_transaction_with_bytecode_compression(create_tracers().into(), tx.clone(), false)
+        .map_err(|_| anyhow!("compression can't fail if we don't apply it"))
 }
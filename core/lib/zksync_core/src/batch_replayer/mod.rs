@@ -0,0 +1,574 @@
+//! Deterministic replay of sealed L1 batches.
+//!
+//! [`BatchReplayer`] re-executes an already sealed L1 batch via the state keeper batch executor
+//! on top of the Postgres state as of the end of the previous L1 batch, and compares the execution outputs
+//! with the data persisted when the batch was sealed. This allows catching VM regressions (e.g., when upgrading
+//! `multivm`) before they affect production.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use multivm::interface::{FinishedL1Batch, L2BlockEnv};
+use serde::Serialize;
+use zksync_dal::{ConnectionPool, StorageProcessor};
+use zksync_types::{
+    block::L1BatchHeader, event::extract_long_l2_to_l1_messages,
+    storage_writes_deduplicator::StorageWritesDeduplicator, L1BatchNumber, L2ChainId, LogQuery,
+    StorageKey, StorageLogQuery, H256, U256,
+};
+use zksync_utils::u256_to_h256;
+
+use crate::state_keeper::{
+    batch_executor::{BatchExecutorHandle, TxExecutionResult},
+    extractors,
+    io::common::load_l1_batch_params,
+};
+
+/// Max number of diverging storage slots included into a mismatch description.
+const MAX_REPORTED_STORAGE_SLOTS: usize = 10;
+/// Max time to wait for the root hash of the L1 batch preceding the replayed one.
+const PREV_L1_BATCH_HASH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Part of the L1 batch execution output checked during replay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayedOutput {
+    /// Execution of a transaction included into the batch.
+    TxExecution,
+    /// Final values of the storage slots written to in the batch.
+    StorageWrites,
+    /// Sorted and deduplicated events queue.
+    EventsQueue,
+    /// User L2-to-L1 logs.
+    UserL2ToL1Logs,
+    /// Preimages of long L2-to-L1 messages.
+    L2ToL1Messages,
+    /// System L2-to-L1 logs.
+    SystemLogs,
+    /// Hashes of the contracts used in the batch.
+    UsedContractHashes,
+    /// Final bootloader memory.
+    BootloaderMemory,
+    /// Storage refunds returned by the storage oracle.
+    StorageRefunds,
+}
+
+/// Divergence between the replayed and persisted L1 batch output.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReplayMismatch {
+    pub output: ReplayedOutput,
+    pub details: String,
+}
+
+/// Result of replaying a single L1 batch.
+#[derive(Debug, Clone, Serialize)]
+pub struct L1BatchReplayReport {
+    pub l1_batch_number: L1BatchNumber,
+    pub miniblock_count: usize,
+    pub tx_count: usize,
+    pub mismatches: Vec<ReplayMismatch>,
+}
+
+impl L1BatchReplayReport {
+    /// Checks whether the replayed batch output matches the persisted one.
+    pub fn is_match(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// L1 batch execution output persisted in Postgres.
+#[derive(Debug)]
+struct PersistedL1BatchOutput {
+    header: L1BatchHeader,
+    bootloader_memory: Vec<(usize, U256)>,
+    events_queue: Vec<LogQuery>,
+    storage_refunds: Option<Vec<u32>>,
+    touched_slots: HashMap<StorageKey, H256>,
+}
+
+impl PersistedL1BatchOutput {
+    async fn load(
+        storage: &mut StorageProcessor<'_>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Self> {
+        let header = storage
+            .blocks_dal()
+            .get_l1_batch_header(l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{l1_batch_number} is not persisted"))?;
+        anyhow::ensure!(
+            header.is_finished,
+            "L1 batch #{l1_batch_number} is not sealed"
+        );
+        let bootloader_memory = storage
+            .blocks_dal()
+            .get_initial_bootloader_heap(l1_batch_number)
+            .await?
+            .with_context(|| {
+                format!("bootloader memory for L1 batch #{l1_batch_number} is missing")
+            })?;
+        let events_queue = storage
+            .blocks_dal()
+            .get_events_queue(l1_batch_number)
+            .await?
+            .with_context(|| format!("events queue for L1 batch #{l1_batch_number} is missing"))?;
+        let storage_refunds = storage
+            .blocks_dal()
+            .get_storage_refunds(l1_batch_number)
+            .await?;
+        let touched_slots = storage
+            .storage_logs_dal()
+            .get_touched_slots_for_l1_batch(l1_batch_number)
+            .await;
+
+        Ok(Self {
+            header,
+            bootloader_memory,
+            events_queue,
+            storage_refunds,
+            touched_slots,
+        })
+    }
+
+    fn compare(
+        &self,
+        finished_batch: &FinishedL1Batch,
+        touched_slots: &HashMap<StorageKey, H256>,
+    ) -> Vec<ReplayMismatch> {
+        let state = &finished_batch.final_execution_state;
+        let l2_to_l1_messages = extract_long_l2_to_l1_messages(&state.events);
+
+        let mut mismatches = vec![];
+        mismatches.extend(compare_storage_writes(&self.touched_slots, touched_slots));
+        mismatches.extend(compare_sequences(
+            ReplayedOutput::EventsQueue,
+            &self.events_queue,
+            &state.deduplicated_events_logs,
+        ));
+        mismatches.extend(compare_sequences(
+            ReplayedOutput::UserL2ToL1Logs,
+            &self.header.l2_to_l1_logs,
+            &state.user_l2_to_l1_logs,
+        ));
+        mismatches.extend(compare_sequences(
+            ReplayedOutput::L2ToL1Messages,
+            &self.header.l2_to_l1_messages,
+            &l2_to_l1_messages,
+        ));
+        mismatches.extend(compare_sequences(
+            ReplayedOutput::SystemLogs,
+            &self.header.system_logs,
+            &state.system_logs,
+        ));
+        mismatches.extend(compare_sequences(
+            ReplayedOutput::UsedContractHashes,
+            &self.header.used_contract_hashes,
+            &state.used_contract_hashes,
+        ));
+        if let Some(bootloader_memory) = &finished_batch.final_bootloader_memory {
+            mismatches.extend(compare_sequences(
+                ReplayedOutput::BootloaderMemory,
+                &self.bootloader_memory,
+                bootloader_memory,
+            ));
+        }
+        if let Some(storage_refunds) = &self.storage_refunds {
+            mismatches.extend(compare_sequences(
+                ReplayedOutput::StorageRefunds,
+                storage_refunds,
+                &state.storage_refunds,
+            ));
+        }
+        mismatches
+    }
+}
+
+fn compare_sequences<T: PartialEq + fmt::Debug>(
+    output: ReplayedOutput,
+    expected: &[T],
+    actual: &[T],
+) -> Option<ReplayMismatch> {
+    let details = if expected.len() != actual.len() {
+        format!(
+            "expected {} entries, got {} entries",
+            expected.len(),
+            actual.len()
+        )
+    } else {
+        let (idx, (expected, actual)) = expected
+            .iter()
+            .zip(actual)
+            .enumerate()
+            .find(|(_, (expected, actual))| expected != actual)?;
+        format!("first divergence at entry #{idx}: expected {expected:?}, got {actual:?}")
+    };
+    Some(ReplayMismatch { output, details })
+}
+
+fn compare_storage_writes(
+    expected: &HashMap<StorageKey, H256>,
+    actual: &HashMap<StorageKey, H256>,
+) -> Option<ReplayMismatch> {
+    let diverging_keys: BTreeSet<_> = expected
+        .keys()
+        .chain(actual.keys())
+        .filter(|key| expected.get(key) != actual.get(key))
+        .collect();
+    if diverging_keys.is_empty() {
+        return None;
+    }
+
+    let examples: Vec<_> = diverging_keys
+        .iter()
+        .take(MAX_REPORTED_STORAGE_SLOTS)
+        .map(|key| {
+            format!(
+                "{:?}:{:?} (expected {:?}, got {:?})",
+                key.address(),
+                key.key(),
+                expected.get(key),
+                actual.get(key)
+            )
+        })
+        .collect();
+    Some(ReplayMismatch {
+        output: ReplayedOutput::StorageWrites,
+        details: format!(
+            "{} storage slot(s) diverge: {}",
+            diverging_keys.len(),
+            examples.join(", ")
+        ),
+    })
+}
+
+/// Accumulates final values of storage slots written to in an L1 batch in the same way
+/// the state keeper does when persisting miniblocks.
+#[derive(Debug, Default)]
+struct TouchedSlots {
+    slots: HashMap<StorageKey, H256>,
+    miniblock_writes: StorageWritesDeduplicator,
+}
+
+impl TouchedSlots {
+    fn apply(&mut self, logs: &[StorageLogQuery]) {
+        self.miniblock_writes
+            .apply(logs.iter().filter(|log| log.log_query.rw_flag));
+    }
+
+    fn seal_miniblock(&mut self) {
+        let writes = std::mem::take(&mut self.miniblock_writes).into_modified_key_values();
+        self.slots.extend(
+            writes
+                .into_iter()
+                .map(|(key, slot)| (key, u256_to_h256(slot.value))),
+        );
+    }
+}
+
+/// Re-executes sealed L1 batches and compares the outputs with the persisted data.
+#[derive(Debug)]
+pub struct BatchReplayer {
+    pool: ConnectionPool,
+    l2_chain_id: L2ChainId,
+}
+
+impl BatchReplayer {
+    /// Creates a new replayer. The connection pool must allow at least 2 connections, since the batch executor
+    /// holds a dedicated connection during replay.
+    pub fn new(pool: ConnectionPool, l2_chain_id: L2ChainId) -> Self {
+        Self { pool, l2_chain_id }
+    }
+
+    /// Replays the specified L1 batch.
+    pub async fn replay(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<L1BatchReplayReport> {
+        anyhow::ensure!(
+            l1_batch_number > L1BatchNumber(0),
+            "genesis L1 batch cannot be replayed"
+        );
+
+        let mut storage = self.pool.access_storage_tagged("batch_replayer").await?;
+        let persisted = PersistedL1BatchOutput::load(&mut storage, l1_batch_number).await?;
+        let (_, prev_miniblock_number) = storage
+            .blocks_dal()
+            .get_miniblock_range_of_l1_batch(l1_batch_number - 1)
+            .await?
+            .with_context(|| format!("L1 batch #{} has no miniblocks", l1_batch_number - 1))?;
+        let (_, last_miniblock_number) = storage
+            .blocks_dal()
+            .get_miniblock_range_of_l1_batch(l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{l1_batch_number} has no miniblocks"))?;
+        let fee_account = storage
+            .blocks_dal()
+            .get_fee_address_for_l1_batch(l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{l1_batch_number} has no fee account"))?;
+        // `load_l1_batch_params()` waits for the previous L1 batch hash indefinitely, so we check it beforehand.
+        extractors::wait_for_prev_l1_batch_params_with_timeout(
+            &mut storage,
+            l1_batch_number,
+            PREV_L1_BATCH_HASH_TIMEOUT,
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "root hash of L1 batch #{} is not computed after {PREV_L1_BATCH_HASH_TIMEOUT:?}",
+                l1_batch_number - 1
+            )
+        })?;
+        // Transactions in the batch were already accepted by the state keeper, so we don't want to reject any of them.
+        let (system_env, l1_batch_env) = load_l1_batch_params(
+            &mut storage,
+            l1_batch_number,
+            fee_account,
+            u32::MAX,
+            self.l2_chain_id,
+        )
+        .await
+        .with_context(|| format!("failed loading params for L1 batch #{l1_batch_number}"))?;
+        let miniblocks = storage
+            .transactions_dal()
+            .get_miniblocks_to_execute_for_l1_batch(l1_batch_number)
+            .await?;
+        // The last miniblock in the batch is fictive (i.e., contains no transactions) unless the batch is empty;
+        // it still needs to be started in the VM so that the batch is finished with the correct miniblock params.
+        let last_executed_miniblock_number = miniblocks
+            .last()
+            .map_or(prev_miniblock_number + 1, |miniblock| miniblock.number);
+        let fictive_miniblock = if last_executed_miniblock_number < last_miniblock_number {
+            let header = storage
+                .blocks_dal()
+                .get_miniblock_header(last_miniblock_number)
+                .await?
+                .with_context(|| format!("miniblock #{last_miniblock_number} is not persisted"))?;
+            let prev_header = storage
+                .blocks_dal()
+                .get_miniblock_header(last_miniblock_number - 1)
+                .await?
+                .with_context(|| {
+                    format!("miniblock #{} is not persisted", last_miniblock_number - 1)
+                })?;
+            Some(L2BlockEnv {
+                number: header.number.0,
+                timestamp: header.timestamp,
+                prev_block_hash: prev_header.hash,
+                max_virtual_blocks_to_create: header.virtual_blocks,
+            })
+        } else {
+            None
+        };
+        drop(storage);
+
+        tracing::info!(
+            "Replaying L1 batch #{l1_batch_number} with {} miniblocks on top of miniblock #{prev_miniblock_number}",
+            miniblocks.len()
+        );
+        let executor = BatchExecutorHandle::with_postgres_storage(
+            self.pool.clone(),
+            prev_miniblock_number,
+            U256::MAX,
+            l1_batch_env,
+            system_env,
+        );
+
+        let mut report = L1BatchReplayReport {
+            l1_batch_number,
+            miniblock_count: miniblocks.len(),
+            tx_count: 0,
+            mismatches: vec![],
+        };
+        let mut touched_slots = TouchedSlots::default();
+        for (i, miniblock) in miniblocks.iter().enumerate() {
+            if i > 0 {
+                executor
+                    .start_next_miniblock(L2BlockEnv::from_miniblock_data(miniblock))
+                    .await;
+            }
+            for tx in &miniblock.txs {
+                let tx_hash = tx.hash();
+                match executor.execute_tx(tx.clone()).await {
+                    TxExecutionResult::Success { tx_result, .. } => {
+                        touched_slots.apply(&tx_result.logs.storage_logs);
+                        report.tx_count += 1;
+                    }
+                    result => {
+                        // Further execution is meaningless since the VM state has diverged.
+                        report.mismatches.push(ReplayMismatch {
+                            output: ReplayedOutput::TxExecution,
+                            details: format!(
+                                "transaction {tx_hash:?} in miniblock #{} was rejected: {:?}",
+                                miniblock.number,
+                                result.err()
+                            ),
+                        });
+                        return Ok(report);
+                    }
+                }
+            }
+            touched_slots.seal_miniblock();
+        }
+        if let Some(fictive_miniblock) = fictive_miniblock {
+            executor.start_next_miniblock(fictive_miniblock).await;
+        }
+
+        let (finished_batch, _) = executor.finish_batch().await;
+        // Logs produced by the block tip are persisted in the fictive miniblock.
+        touched_slots.apply(&finished_batch.block_tip_execution_result.logs.storage_logs);
+        touched_slots.seal_miniblock();
+
+        report.mismatches = persisted.compare(&finished_batch, &touched_slots.slots);
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_test_account::Account;
+    use zksync_types::{AccountTreeId, Address, Execute, MiniblockNumber, StorageLog, Transaction};
+
+    use super::*;
+    use crate::{
+        genesis::{ensure_genesis_state, GenesisParams},
+        state_keeper::tests::execute_and_seal_l1_batch,
+    };
+
+    #[test]
+    fn comparing_sequences() {
+        let output = ReplayedOutput::StorageRefunds;
+        assert_eq!(compare_sequences::<u32>(output, &[], &[]), None);
+        assert_eq!(compare_sequences(output, &[1, 2, 3], &[1, 2, 3]), None);
+
+        let mismatch = compare_sequences(output, &[1, 2, 3], &[1, 2]).unwrap();
+        assert_eq!(mismatch.output, output);
+        assert_eq!(mismatch.details, "expected 3 entries, got 2 entries");
+
+        let mismatch = compare_sequences(output, &[1, 2, 3], &[1, 5, 4]).unwrap();
+        assert_eq!(
+            mismatch.details,
+            "first divergence at entry #1: expected 2, got 5"
+        );
+    }
+
+    #[test]
+    fn comparing_storage_writes() {
+        let key =
+            |byte| StorageKey::new(AccountTreeId::new(Address::zero()), H256::repeat_byte(byte));
+        let expected = HashMap::from([
+            (key(1), H256::repeat_byte(0xff)),
+            (key(2), H256::repeat_byte(0xfe)),
+        ]);
+        assert_eq!(compare_storage_writes(&expected, &expected), None);
+
+        let mut actual = expected.clone();
+        actual.insert(key(2), H256::zero());
+        actual.insert(key(3), H256::zero());
+        let mismatch = compare_storage_writes(&expected, &actual).unwrap();
+        assert_eq!(mismatch.output, ReplayedOutput::StorageWrites);
+        assert!(
+            mismatch.details.starts_with("2 storage slot(s) diverge"),
+            "{}",
+            mismatch.details
+        );
+    }
+
+    async fn seal_l1_batch(pool: &ConnectionPool, chain_id: L2ChainId) -> L1BatchNumber {
+        let mut storage = pool.access_storage().await.unwrap();
+        ensure_genesis_state(&mut storage, chain_id, &GenesisParams::mock())
+            .await
+            .unwrap();
+        drop(storage);
+
+        let account = Account::random();
+        let txs: Vec<Transaction> = (0..3)
+            .map(|serial_id| {
+                let execute = Execute {
+                    contract_address: Address::random(),
+                    calldata: vec![],
+                    value: 0.into(),
+                    factory_deps: None,
+                };
+                account.get_l1_tx(execute, serial_id)
+            })
+            .collect();
+        let miniblocks = vec![txs[..2].to_vec(), txs[2..].to_vec()];
+        execute_and_seal_l1_batch(pool, chain_id, miniblocks).await
+    }
+
+    #[tokio::test]
+    async fn replaying_sealed_l1_batch() {
+        let pool = ConnectionPool::test_pool().await;
+        let chain_id = L2ChainId::default();
+        let l1_batch_number = seal_l1_batch(&pool, chain_id).await;
+
+        let report = BatchReplayer::new(pool, chain_id)
+            .replay(l1_batch_number)
+            .await
+            .unwrap();
+        assert!(report.is_match(), "{:?}", report.mismatches);
+        assert_eq!(report.l1_batch_number, l1_batch_number);
+        assert_eq!(report.miniblock_count, 2);
+        assert_eq!(report.tx_count, 3);
+    }
+
+    #[tokio::test]
+    async fn replaying_tampered_l1_batch() {
+        let pool = ConnectionPool::test_pool().await;
+        let chain_id = L2ChainId::default();
+        let l1_batch_number = seal_l1_batch(&pool, chain_id).await;
+
+        // Persist a storage write that wasn't produced by the batch execution.
+        let tampered_key = StorageKey::new(AccountTreeId::new(Address::random()), H256::zero());
+        let tampered_log = StorageLog::new_write_log(tampered_key, H256::repeat_byte(1));
+        pool.access_storage()
+            .await
+            .unwrap()
+            .storage_logs_dal()
+            .append_storage_logs(MiniblockNumber(1), &[(H256::zero(), vec![tampered_log])])
+            .await;
+
+        let report = BatchReplayer::new(pool, chain_id)
+            .replay(l1_batch_number)
+            .await
+            .unwrap();
+        assert!(!report.is_match());
+        assert_eq!(report.mismatches.len(), 1, "{:?}", report.mismatches);
+        let mismatch = &report.mismatches[0];
+        assert_eq!(mismatch.output, ReplayedOutput::StorageWrites);
+        assert!(
+            mismatch.details.starts_with("1 storage slot(s) diverge"),
+            "{}",
+            mismatch.details
+        );
+    }
+
+    #[tokio::test]
+    async fn waiting_for_missing_prev_root_hash_times_out() {
+        let pool = ConnectionPool::test_pool().await;
+        let l1_batch_number = seal_l1_batch(&pool, L2ChainId::default()).await;
+
+        // The root hash of the sealed L1 batch is never computed, so the next batch cannot be loaded.
+        let mut storage = pool.access_storage().await.unwrap();
+        let params = extractors::wait_for_prev_l1_batch_params_with_timeout(
+            &mut storage,
+            l1_batch_number + 1,
+            Duration::from_millis(50),
+        )
+        .await;
+        assert_eq!(params, None);
+
+        let params = extractors::wait_for_prev_l1_batch_params_with_timeout(
+            &mut storage,
+            l1_batch_number,
+            Duration::from_millis(50),
+        )
+        .await;
+        assert!(params.is_some());
+    }
+}
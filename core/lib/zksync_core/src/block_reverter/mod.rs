@@ -1,12 +1,14 @@
 use std::{path::Path, time::Duration};
 
+use anyhow::Context as _;
 use bitflags::bitflags;
 use serde::Serialize;
 use tokio::time::sleep;
 use zksync_config::{ContractsConfig, ETHSenderConfig};
 use zksync_contracts::zksync_contract;
-use zksync_dal::ConnectionPool;
-use zksync_eth_signer::{EthereumSigner, PrivateKeySigner, TransactionParameters};
+use zksync_dal::{blocks_dal::RevertRowCounts, eth_sender_dal::EthTxSummary, ConnectionPool};
+use zksync_eth_client::clients::http::operator_signer;
+use zksync_eth_signer::{EthereumSigner, OperatorSigner, TransactionParameters};
 use zksync_merkle_tree::domain::ZkSyncTree;
 use zksync_state::RocksdbStorage;
 use zksync_storage::RocksDB;
@@ -19,7 +21,7 @@ use zksync_types::{
         types::{BlockId, BlockNumber},
         Web3,
     },
-    L1BatchNumber, PackedEthSignature, H160, H256, U256,
+    L1BatchNumber, MiniblockNumber, H160, H256, U256,
 };
 
 bitflags! {
@@ -45,7 +47,7 @@ pub enum L1ExecutedBatchesRevert {
 #[derive(Debug)]
 pub struct BlockReverterEthConfig {
     eth_client_url: String,
-    reverter_private_key: H256,
+    reverter_signer: OperatorSigner,
     reverter_address: H160,
     diamond_proxy_addr: H160,
     validator_timelock_addr: H160,
@@ -54,16 +56,12 @@ pub struct BlockReverterEthConfig {
 
 impl BlockReverterEthConfig {
     pub fn new(eth_config: ETHSenderConfig, contract: ContractsConfig, web3_url: String) -> Self {
-        let pk = eth_config
-            .sender
-            .private_key()
-            .expect("Private key is required for block reversion");
-        let operator_address = PackedEthSignature::address_from_private_key(&pk)
-            .expect("Failed to get address from private key");
+        // Supports both the local private key and the remote signer, depending on the sender config.
+        let (operator_address, signer) = operator_signer(&eth_config.sender);
 
         Self {
             eth_client_url: web3_url,
-            reverter_private_key: pk,
+            reverter_signer: signer,
             reverter_address: operator_address,
             diamond_proxy_addr: contract.diamond_proxy_addr,
             validator_timelock_addr: contract.validator_timelock_addr,
@@ -121,21 +119,11 @@ impl BlockReverter {
         let rollback_postgres = flags.contains(BlockReverterFlags::POSTGRES);
         let rollback_sk_cache = flags.contains(BlockReverterFlags::SK_CACHE);
 
-        if matches!(
-            self.executed_batches_revert_mode,
-            L1ExecutedBatchesRevert::Disallowed
-        ) {
-            let mut storage = self.connection_pool.access_storage().await.unwrap();
-            let last_executed_l1_batch = storage
-                .blocks_dal()
-                .get_number_of_last_l1_batch_executed_on_eth()
-                .await
-                .unwrap()
-                .expect("failed to get last executed L1 batch");
-            assert!(
-                last_l1_batch_to_keep >= last_executed_l1_batch,
-                "Attempt to revert already executed L1 batches"
-            );
+        if let Err(err) = self
+            .check_executed_batches_revert(last_l1_batch_to_keep)
+            .await
+        {
+            panic!("{err:#}");
         }
 
         // Tree needs to be reverted first to keep state recoverable
@@ -146,6 +134,186 @@ impl BlockReverter {
         }
     }
 
+    /// Checks that reverting to `last_l1_batch_to_keep` doesn't revert L1 batches executed on L1,
+    /// unless this is explicitly allowed.
+    async fn check_executed_batches_revert(
+        &self,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        if matches!(
+            self.executed_batches_revert_mode,
+            L1ExecutedBatchesRevert::Allowed
+        ) {
+            return Ok(());
+        }
+
+        let mut storage = self.connection_pool.access_storage().await?;
+        let last_executed_l1_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await?
+            .context("failed to get last executed L1 batch")?;
+        anyhow::ensure!(
+            last_l1_batch_to_keep >= last_executed_l1_batch,
+            "Attempt to revert already executed L1 batches"
+        );
+        Ok(())
+    }
+
+    /// Computes what would be reverted by [`Self::rollback_db()`] with the same arguments without modifying any data.
+    /// Performs the same checks as `rollback_db()` and returns an error if any of them fails.
+    /// RocksDB instances are opened in the read-only mode.
+    pub async fn plan_rollback(
+        &self,
+        last_l1_batch_to_keep: L1BatchNumber,
+        flags: BlockReverterFlags,
+    ) -> anyhow::Result<RollbackPlan> {
+        self.check_executed_batches_revert(last_l1_batch_to_keep)
+            .await?;
+
+        let mut storage = self.connection_pool.access_storage().await?;
+        let (_, last_miniblock_to_keep) = storage
+            .blocks_dal()
+            .get_miniblock_range_of_l1_batch(last_l1_batch_to_keep)
+            .await?
+            .context("L1 batch should contain at least one miniblock")?;
+
+        let postgres = if flags.contains(BlockReverterFlags::POSTGRES) {
+            let row_counts = storage
+                .blocks_dal()
+                .get_revert_row_counts(last_l1_batch_to_keep, last_miniblock_to_keep)
+                .await?;
+            Some(row_counts)
+        } else {
+            None
+        };
+
+        let merkle_tree = if flags.contains(BlockReverterFlags::TREE) {
+            storage
+                .blocks_dal()
+                .get_l1_batch_state_root(last_l1_batch_to_keep)
+                .await?
+                .context("failed to fetch root hash for target L1 batch")?;
+            let merkle_tree_path = Path::new(&self.merkle_tree_path);
+            let next_l1_batch_number = merkle_tree_path.exists().then(|| {
+                let db = RocksDB::read_only(merkle_tree_path);
+                ZkSyncTree::new_lightweight(db.into()).next_l1_batch_number()
+            });
+            Some(MerkleTreeRollbackPlan {
+                next_l1_batch_number,
+                reverted_l1_batches: next_l1_batch_number.map_or(0, |next_number| {
+                    next_number.0.saturating_sub(last_l1_batch_to_keep.0 + 1)
+                }),
+            })
+        } else {
+            None
+        };
+
+        let state_keeper_cache = if flags.contains(BlockReverterFlags::SK_CACHE) {
+            anyhow::ensure!(
+                Path::new(&self.state_keeper_cache_path).exists(),
+                "Path with state keeper cache DB doesn't exist"
+            );
+            let sk_cache = RocksdbStorage::read_only(self.state_keeper_cache_path.as_ref());
+            let next_l1_batch_number = sk_cache.l1_batch_number();
+            let mut plan = StateKeeperCacheRollbackPlan {
+                next_l1_batch_number,
+                keys_to_restore: 0,
+                keys_to_remove: 0,
+                factory_deps_to_remove: 0,
+            };
+            if next_l1_batch_number > last_l1_batch_to_keep + 1 {
+                let logs = storage
+                    .storage_logs_dal()
+                    .get_storage_logs_for_revert(last_l1_batch_to_keep)
+                    .await;
+                plan.keys_to_remove = logs.values().filter(|value| value.is_none()).count();
+                plan.keys_to_restore = logs.len() - plan.keys_to_remove;
+                plan.factory_deps_to_remove = storage
+                    .storage_dal()
+                    .get_factory_deps_for_revert(last_miniblock_to_keep)
+                    .await
+                    .len();
+            }
+            Some(plan)
+        } else {
+            None
+        };
+
+        let affected_eth_txs = storage
+            .eth_sender_dal()
+            .get_eth_txs_for_l1_batches_after(last_l1_batch_to_keep)
+            .await?;
+        Ok(RollbackPlan {
+            last_l1_batch_to_keep,
+            last_miniblock_to_keep,
+            postgres,
+            merkle_tree,
+            state_keeper_cache,
+            affected_eth_txs: affected_eth_txs.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Checks that the state components specified by `flags` are consistent with a completed
+    /// [`Self::rollback_db()`] call with the same arguments.
+    pub async fn check_rollback(
+        &self,
+        last_l1_batch_to_keep: L1BatchNumber,
+        flags: BlockReverterFlags,
+    ) -> anyhow::Result<()> {
+        let plan = self.plan_rollback(last_l1_batch_to_keep, flags).await?;
+        let mut storage = self.connection_pool.access_storage().await?;
+
+        if let Some(row_counts) = &plan.postgres {
+            anyhow::ensure!(
+                row_counts.is_empty(),
+                "Postgres contains data that should have been reverted: {row_counts:?}"
+            );
+            let sealed_l1_batch_number = storage.blocks_dal().get_sealed_l1_batch_number().await?;
+            anyhow::ensure!(
+                sealed_l1_batch_number == last_l1_batch_to_keep,
+                "Last sealed L1 batch in Postgres is #{sealed_l1_batch_number}, expected #{last_l1_batch_to_keep}"
+            );
+            let sealed_miniblock_number =
+                storage.blocks_dal().get_sealed_miniblock_number().await?;
+            anyhow::ensure!(
+                sealed_miniblock_number == plan.last_miniblock_to_keep,
+                "Last sealed miniblock in Postgres is #{sealed_miniblock_number}, expected #{}",
+                plan.last_miniblock_to_keep
+            );
+        }
+
+        if let Some(tree_plan) = &plan.merkle_tree {
+            anyhow::ensure!(
+                tree_plan.reverted_l1_batches == 0,
+                "Merkle tree contains {} L1 batches that should have been reverted",
+                tree_plan.reverted_l1_batches
+            );
+            if tree_plan.next_l1_batch_number == Some(last_l1_batch_to_keep + 1) {
+                let expected_root_hash = storage
+                    .blocks_dal()
+                    .get_l1_batch_state_root(last_l1_batch_to_keep)
+                    .await?
+                    .context("failed to fetch root hash for target L1 batch")?;
+                let db = RocksDB::read_only(Path::new(&self.merkle_tree_path));
+                let root_hash = ZkSyncTree::new_lightweight(db.into()).root_hash();
+                anyhow::ensure!(
+                    root_hash == expected_root_hash,
+                    "Merkle tree root hash {root_hash:?} differs from the one in Postgres: {expected_root_hash:?}"
+                );
+            }
+        }
+
+        if let Some(cache_plan) = &plan.state_keeper_cache {
+            anyhow::ensure!(
+                cache_plan.next_l1_batch_number <= last_l1_batch_to_keep + 1,
+                "State keeper cache is at L1 batch #{}, which should have been reverted",
+                cache_plan.next_l1_batch_number
+            );
+        }
+        Ok(())
+    }
+
     async fn rollback_rocks_dbs(
         &self,
         last_l1_batch_to_keep: L1BatchNumber,
@@ -296,7 +464,6 @@ impl BlockReverter {
 
         let web3 = Web3::new(Http::new(&eth_config.eth_client_url).unwrap());
         let contract = zksync_contract();
-        let signer = PrivateKeySigner::new(eth_config.reverter_private_key);
         let chain_id = web3.eth().chain_id().await.unwrap().as_u64();
 
         let revert_function = contract
@@ -329,7 +496,11 @@ impl BlockReverter {
             ..Default::default()
         };
 
-        let signed_tx = signer.sign_transaction(tx).await.unwrap();
+        let signed_tx = eth_config
+            .reverter_signer
+            .sign_transaction(tx)
+            .await
+            .unwrap();
         let hash = web3
             .eth()
             .send_raw_transaction(signed_tx.into())
@@ -439,3 +610,225 @@ pub struct SuggestedRollbackValues {
     pub nonce: u64,
     pub priority_fee: u64,
 }
+
+/// Plan of reverting the node state returned by [`BlockReverter::plan_rollback()`].
+/// State components not selected for the rollback are set to `None`.
+#[derive(Debug, Serialize)]
+pub struct RollbackPlan {
+    pub last_l1_batch_to_keep: L1BatchNumber,
+    pub last_miniblock_to_keep: MiniblockNumber,
+    /// Numbers of Postgres rows that would be removed or reset, per table.
+    pub postgres: Option<RevertRowCounts>,
+    pub merkle_tree: Option<MerkleTreeRollbackPlan>,
+    pub state_keeper_cache: Option<StateKeeperCacheRollbackPlan>,
+    /// Ethereum transactions sent for the reverted L1 batches.
+    pub affected_eth_txs: Vec<AffectedEthTx>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MerkleTreeRollbackPlan {
+    /// Next L1 batch number expected by the tree, or `None` if the tree doesn't exist.
+    pub next_l1_batch_number: Option<L1BatchNumber>,
+    /// Number of tree versions (one per L1 batch) that would be truncated.
+    pub reverted_l1_batches: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StateKeeperCacheRollbackPlan {
+    /// Next L1 batch number expected by the cache.
+    pub next_l1_batch_number: L1BatchNumber,
+    /// Number of storage keys that would be restored to their previous values.
+    pub keys_to_restore: usize,
+    /// Number of storage keys that would be removed from the cache.
+    pub keys_to_remove: usize,
+    pub factory_deps_to_remove: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AffectedEthTx {
+    pub id: u32,
+    pub nonce: u32,
+    pub tx_type: &'static str,
+    pub has_failed: bool,
+    pub confirmed_tx_hash: Option<H256>,
+}
+
+impl From<EthTxSummary> for AffectedEthTx {
+    fn from(tx: EthTxSummary) -> Self {
+        Self {
+            id: tx.id,
+            nonce: tx.nonce.0,
+            tx_type: tx.tx_type.as_str(),
+            has_failed: tx.has_failed,
+            confirmed_tx_hash: tx.confirmed_tx_hash,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops;
+
+    use tempfile::TempDir;
+    use zksync_dal::StorageProcessor;
+    use zksync_types::Address;
+
+    use super::*;
+    use crate::metadata_calculator::tests::{reset_db_state, run_calculator, setup_calculator};
+
+    async fn mark_l1_batches_executed(
+        storage: &mut StorageProcessor<'_>,
+        numbers: ops::RangeInclusive<L1BatchNumber>,
+    ) {
+        let eth_tx = storage
+            .eth_sender_dal()
+            .save_eth_tx(
+                0,
+                vec![],
+                AggregatedActionType::Execute,
+                Address::zero(),
+                0,
+                None,
+            )
+            .await
+            .unwrap();
+        let tx_hash = H256::repeat_byte(1);
+        storage
+            .eth_sender_dal()
+            .insert_tx_history(eth_tx.id, 0, 0, None, tx_hash, vec![])
+            .await
+            .unwrap();
+        storage
+            .eth_sender_dal()
+            .confirm_tx(tx_hash, U256::zero())
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .set_eth_tx_id(numbers, eth_tx.id, AggregatedActionType::Execute)
+            .await
+            .unwrap();
+    }
+
+    /// Seeds Postgres with 5 L1 batches (the first 2 of which are executed on L1), and creates
+    /// the Merkle tree and state keeper cache up to date with Postgres.
+    async fn setup_block_reverter(pool: &ConnectionPool, temp_dir: &TempDir) -> BlockReverter {
+        let (calculator, _) = setup_calculator(temp_dir.path(), pool).await;
+        reset_db_state(pool, 5).await;
+        run_calculator(calculator, pool.clone()).await;
+
+        let mut storage = pool.access_storage().await.unwrap();
+        mark_l1_batches_executed(&mut storage, L1BatchNumber(1)..=L1BatchNumber(2)).await;
+        let sk_cache_path = temp_dir.path().join("state_keeper_cache");
+        let mut sk_cache = RocksdbStorage::new(&sk_cache_path);
+        sk_cache.update_from_postgres(&mut storage).await;
+        assert_eq!(sk_cache.l1_batch_number(), L1BatchNumber(6));
+
+        BlockReverter::new(
+            sk_cache_path.to_str().unwrap().to_owned(),
+            temp_dir.path().join("new").to_str().unwrap().to_owned(),
+            None,
+            pool.clone(),
+            L1ExecutedBatchesRevert::Disallowed,
+        )
+    }
+
+    #[tokio::test]
+    async fn planning_rollback() {
+        let pool = ConnectionPool::test_pool().await;
+        let temp_dir = TempDir::new().unwrap();
+        let block_reverter = setup_block_reverter(&pool, &temp_dir).await;
+
+        let plan = block_reverter
+            .plan_rollback(L1BatchNumber(3), BlockReverterFlags::all())
+            .await
+            .unwrap();
+        assert_eq!(plan.last_l1_batch_to_keep, L1BatchNumber(3));
+        assert_eq!(plan.last_miniblock_to_keep, MiniblockNumber(3));
+        let row_counts = plan.postgres.unwrap();
+        assert_eq!(row_counts.l1_batches, 2);
+        assert_eq!(row_counts.miniblocks, 2);
+        assert!(row_counts.storage_logs > 0, "{row_counts:?}");
+        let tree_plan = plan.merkle_tree.unwrap();
+        assert_eq!(tree_plan.next_l1_batch_number, Some(L1BatchNumber(6)));
+        assert_eq!(tree_plan.reverted_l1_batches, 2);
+        let cache_plan = plan.state_keeper_cache.unwrap();
+        assert_eq!(cache_plan.next_l1_batch_number, L1BatchNumber(6));
+        assert!(cache_plan.keys_to_restore + cache_plan.keys_to_remove > 0);
+        assert!(plan.affected_eth_txs.is_empty());
+
+        let plan = block_reverter
+            .plan_rollback(L1BatchNumber(3), BlockReverterFlags::POSTGRES)
+            .await
+            .unwrap();
+        assert!(plan.postgres.is_some());
+        assert!(plan.merkle_tree.is_none());
+        assert!(plan.state_keeper_cache.is_none());
+
+        // Planning doesn't modify data.
+        let mut storage = pool.access_storage().await.unwrap();
+        let sealed_l1_batch_number = storage
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await
+            .unwrap();
+        assert_eq!(sealed_l1_batch_number, L1BatchNumber(5));
+        block_reverter
+            .check_rollback(L1BatchNumber(3), BlockReverterFlags::all())
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn planning_rollback_of_executed_l1_batches() {
+        let pool = ConnectionPool::test_pool().await;
+        let temp_dir = TempDir::new().unwrap();
+        let mut block_reverter = setup_block_reverter(&pool, &temp_dir).await;
+
+        let err = block_reverter
+            .plan_rollback(L1BatchNumber(1), BlockReverterFlags::all())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("already executed"), "{err:#}");
+
+        block_reverter
+            .change_rollback_executed_l1_batches_allowance(L1ExecutedBatchesRevert::Allowed);
+        let plan = block_reverter
+            .plan_rollback(L1BatchNumber(1), BlockReverterFlags::all())
+            .await
+            .unwrap();
+        assert_eq!(plan.postgres.unwrap().l1_batches, 4);
+        assert_eq!(plan.affected_eth_txs.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn checking_rollback() {
+        let pool = ConnectionPool::test_pool().await;
+        let temp_dir = TempDir::new().unwrap();
+        let block_reverter = setup_block_reverter(&pool, &temp_dir).await;
+
+        block_reverter
+            .rollback_db(L1BatchNumber(3), BlockReverterFlags::all())
+            .await;
+        block_reverter
+            .check_rollback(L1BatchNumber(3), BlockReverterFlags::all())
+            .await
+            .unwrap();
+        let plan = block_reverter
+            .plan_rollback(L1BatchNumber(3), BlockReverterFlags::all())
+            .await
+            .unwrap();
+        assert!(plan.postgres.unwrap().is_empty());
+        assert_eq!(plan.merkle_tree.unwrap().reverted_l1_batches, 0);
+        assert_eq!(
+            plan.state_keeper_cache.unwrap().next_l1_batch_number,
+            L1BatchNumber(4)
+        );
+
+        // Data for L1 batch #3 is still present.
+        block_reverter
+            .check_rollback(L1BatchNumber(2), BlockReverterFlags::all())
+            .await
+            .unwrap_err();
+    }
+}
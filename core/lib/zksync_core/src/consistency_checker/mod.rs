@@ -142,16 +142,32 @@ impl ConsistencyChecker {
             .unwrap_or(L1BatchNumber(0))
     }
 
+    /// Returns the first L1 batch that can be checked. For nodes recovered from a snapshot,
+    /// this is the first batch after the snapshot one.
+    async fn first_checked_batch(&self) -> L1BatchNumber {
+        let snapshot_recovery = self
+            .db
+            .access_storage()
+            .await
+            .unwrap()
+            .snapshot_recovery_dal()
+            .get_applied_snapshot_status()
+            .await
+            .unwrap();
+        snapshot_recovery.map_or(L1BatchNumber(1), |status| status.l1_batch_number + 1)
+    }
+
     pub async fn run(
         self,
         stop_receiver: tokio::sync::watch::Receiver<bool>,
     ) -> anyhow::Result<()> {
+        let first_checked_batch = self.first_checked_batch().await;
         let mut batch_number: L1BatchNumber = self
             .last_committed_batch()
             .await
             .0
             .saturating_sub(self.max_batches_to_recheck)
-            .max(1)
+            .max(first_checked_batch.0)
             .into();
 
         tracing::info!("Starting consistency checker from batch {}", batch_number.0);
@@ -1,6 +1,11 @@
-use zksync_config::configs::eth_sender::{ProofLoadingMode, ProofSendingMode, SenderConfig};
+use std::path::Path;
+
+use zksync_config::configs::eth_sender::{
+    ProofLoadingMode, ProofSendingMode, PubdataSendingMode, SenderConfig,
+};
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::StorageProcessor;
+use zksync_kzg::{KzgInfo, KzgSettings, MAX_BLOBS_PER_TX};
 use zksync_object_store::ObjectStore;
 use zksync_prover_utils::gcs_proof_fetcher::load_wrapped_fri_proofs_for_range;
 use zksync_types::{
@@ -8,14 +13,14 @@ use zksync_types::{
         AggregatedActionType, AggregatedOperation, L1BatchCommitOperation, L1BatchExecuteOperation,
         L1BatchProofOperation,
     },
-    commitment::L1BatchWithMetadata,
+    commitment::{L1BatchWithMetadata, PubdataBlob},
     helpers::unix_timestamp_ms,
     protocol_version::L1VerifierConfig,
     L1BatchNumber, ProtocolVersionId,
 };
 
 use super::publish_criterion::{
-    DataSizeCriterion, GasCriterion, L1BatchPublishCriterion, NumberCriterion,
+    BlobCountCriterion, DataSizeCriterion, GasCriterion, L1BatchPublishCriterion, NumberCriterion,
     TimestampDeadlineCriterion,
 };
 
@@ -26,30 +31,56 @@ pub struct Aggregator {
     execute_criteria: Vec<Box<dyn L1BatchPublishCriterion>>,
     config: SenderConfig,
     blob_store: Box<dyn ObjectStore>,
+    /// KZG settings used to commit to pubdata blobs; `None` if pubdata is posted as calldata.
+    kzg_settings: Option<KzgSettings>,
 }
 
 impl Aggregator {
     pub fn new(config: SenderConfig, blob_store: Box<dyn ObjectStore>) -> Self {
+        let kzg_settings = match config.pubdata_sending_mode {
+            PubdataSendingMode::Calldata => None,
+            PubdataSendingMode::Blobs => {
+                let path = config
+                    .kzg_trusted_setup_path
+                    .as_deref()
+                    .expect("KZG trusted setup path must be specified to post pubdata in blobs");
+                let settings =
+                    zksync_kzg::load_trusted_setup(Path::new(path)).unwrap_or_else(|err| {
+                        panic!("Cannot load KZG trusted setup from {path}: {err}")
+                    });
+                Some(settings)
+            }
+        };
+
+        let mut commit_criteria: Vec<Box<dyn L1BatchPublishCriterion>> = vec![
+            Box::from(NumberCriterion {
+                op: AggregatedActionType::Commit,
+                limit: config.max_aggregated_blocks_to_commit,
+            }),
+            Box::from(GasCriterion::new(
+                AggregatedActionType::Commit,
+                config.max_aggregated_tx_gas,
+            )),
+            Box::from(DataSizeCriterion {
+                op: AggregatedActionType::Commit,
+                data_limit: config.max_eth_tx_data_size,
+                pubdata_sending_mode: config.pubdata_sending_mode,
+            }),
+            Box::from(TimestampDeadlineCriterion {
+                op: AggregatedActionType::Commit,
+                deadline_seconds: config.aggregated_block_commit_deadline,
+                max_allowed_lag: Some(config.timestamp_criteria_max_allowed_lag),
+            }),
+        ];
+        if kzg_settings.is_some() {
+            commit_criteria.push(Box::from(BlobCountCriterion {
+                op: AggregatedActionType::Commit,
+                limit: MAX_BLOBS_PER_TX,
+            }));
+        }
+
         Self {
-            commit_criteria: vec![
-                Box::from(NumberCriterion {
-                    op: AggregatedActionType::Commit,
-                    limit: config.max_aggregated_blocks_to_commit,
-                }),
-                Box::from(GasCriterion::new(
-                    AggregatedActionType::Commit,
-                    config.max_aggregated_tx_gas,
-                )),
-                Box::from(DataSizeCriterion {
-                    op: AggregatedActionType::Commit,
-                    data_limit: config.max_eth_tx_data_size,
-                }),
-                Box::from(TimestampDeadlineCriterion {
-                    op: AggregatedActionType::Commit,
-                    deadline_seconds: config.aggregated_block_commit_deadline,
-                    max_allowed_lag: Some(config.timestamp_criteria_max_allowed_lag),
-                }),
-            ],
+            commit_criteria,
             proof_criteria: vec![
                 Box::from(NumberCriterion {
                     op: AggregatedActionType::PublishProofOnchain,
@@ -85,6 +116,7 @@ impl Aggregator {
             ],
             config,
             blob_store,
+            kzg_settings,
         }
     }
 
@@ -213,9 +245,22 @@ impl Aggregator {
         )
         .await;
 
-        batches.map(|batches| L1BatchCommitOperation {
+        let batches = batches?;
+        // Pre-boojum contracts don't support posting pubdata in blobs.
+        let kzg_settings = self
+            .kzg_settings
+            .as_ref()
+            .filter(|_| !protocol_version_id.is_pre_boojum());
+        let pubdata_blobs = kzg_settings.map(|settings| {
+            batches
+                .iter()
+                .map(|batch| pubdata_blobs(settings, batch))
+                .collect()
+        });
+        Some(L1BatchCommitOperation {
             last_committed_l1_batch,
             l1_batches: batches,
+            pubdata_blobs,
         })
     }
 
@@ -390,6 +435,27 @@ impl Aggregator {
     }
 }
 
+/// Packs pubdata of the L1 batch into blobs and commits to them.
+fn pubdata_blobs(kzg_settings: &KzgSettings, l1_batch: &L1BatchWithMetadata) -> Vec<PubdataBlob> {
+    zksync_kzg::pubdata_to_blobs(&l1_batch.construct_pubdata())
+        .into_iter()
+        .map(|blob| {
+            let info = KzgInfo::new(kzg_settings, blob).unwrap_or_else(|err| {
+                panic!(
+                    "Failed committing to pubdata blob for L1 batch #{}: {err}",
+                    l1_batch.header.number
+                )
+            });
+            PubdataBlob {
+                blob: info.blob,
+                commitment: info.commitment.to_vec(),
+                proof: info.proof.to_vec(),
+                versioned_hash: info.versioned_hash,
+            }
+        })
+        .collect()
+}
+
 async fn extract_ready_subrange(
     storage: &mut StorageProcessor<'_>,
     publish_criteria: &mut [Box<dyn L1BatchPublishCriterion>],
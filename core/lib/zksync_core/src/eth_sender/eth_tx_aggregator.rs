@@ -480,11 +480,18 @@ impl EthTxAggregator {
         contracts_are_pre_boojum: bool,
     ) -> Result<EthTx, ETHSenderError> {
         let mut transaction = storage.start_transaction().await.unwrap();
+        // Prevents nonces from being reassigned by `EthTxManager` concurrently.
+        transaction.eth_sender_dal().lock_nonces().await.unwrap();
         let nonce = self.get_next_nonce(&mut transaction).await?;
         let calldata = self.encode_aggregated_op(aggregated_op, contracts_are_pre_boojum);
         let l1_batch_number_range = aggregated_op.l1_batch_range();
         let op_type = aggregated_op.get_action_type();
 
+        let blob_sidecar = match aggregated_op {
+            AggregatedOperation::Commit(op) => op.blob_sidecar(),
+            _ => None,
+        };
+
         let predicted_gas_for_batches = transaction
             .blocks_dal()
             .get_l1_batches_predicted_gas(l1_batch_number_range.clone(), op_type)
@@ -500,6 +507,7 @@ impl EthTxAggregator {
                 op_type,
                 self.timelock_contract_address,
                 eth_tx_predicted_gas,
+                blob_sidecar,
             )
             .await
             .unwrap();
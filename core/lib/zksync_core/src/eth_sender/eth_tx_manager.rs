@@ -1,7 +1,8 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use tokio::sync::watch;
+use zksync_circuit_breaker::operator_nonce::{DetectedNonceIssue, OperatorNonceIssue};
 use zksync_config::configs::eth_sender::SenderConfig;
 use zksync_dal::{ConnectionPool, StorageProcessor};
 use zksync_eth_client::{
@@ -9,7 +10,7 @@ use zksync_eth_client::{
     BoundEthInterface,
 };
 use zksync_types::{
-    eth_sender::EthTx,
+    eth_sender::{EthTx, EthTxBlobSidecar},
     web3::{
         contract::Options,
         error::Error as Web3Error,
@@ -22,10 +23,32 @@ use zksync_utils::time::seconds_since_epoch;
 use super::{metrics::METRICS, ETHSenderError};
 use crate::{l1_gas_price::L1TxParamsProvider, metrics::BlockL1Stage};
 
+/// Gas limit for no-op transactions filling operator nonce gaps.
+const NOOP_TX_GAS_LIMIT: u64 = 21_000;
+/// Number of checks at distinct finalized L1 blocks during which an `eth_tx` must have no receipts
+/// while its nonce is consumed, for the nonce to be considered consumed externally. Guards against
+/// L1 nodes (e.g., behind a load balancer) temporarily missing receipts of mined transactions.
+pub(super) const ORPHANED_TX_CHECK_COUNT: usize = 3;
+
 #[derive(Debug)]
 struct EthFee {
     base_fee_per_gas: u64,
     priority_fee_per_gas: u64,
+    blob_base_fee_per_gas: Option<u64>,
+}
+
+/// `eth_tx` with a consumed nonce, but without receipts for any of its sending attempts.
+#[derive(Debug, Clone, Copy)]
+struct SuspectedOrphanedTx {
+    check_count: usize,
+    last_checked_finalized_block: L1BlockNumber,
+}
+
+/// No-op transaction filling an operator nonce gap that was sent, but is not mined yet.
+#[derive(Debug, Clone, Copy)]
+struct SentNoopTx {
+    base_fee_per_gas: u64,
+    priority_fee_per_gas: u64,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -47,11 +70,23 @@ pub(super) struct L1BlockNumbers {
 /// save it to the database, and send it to Ethereum.
 /// Based on eth_tx_history queue the component can mark txs as stuck and create the new attempt
 /// with higher gas price
+///
+/// The component also reconciles the operator nonce on L1 with the nonces of `eth_txs`:
+/// gaps in nonces are filled with no-op transactions, and `eth_txs` with nonces consumed
+/// by external transactions are reassigned new nonces. Detected issues are reported to
+/// the circuit breaker via `nonce_issues`.
 #[derive(Debug)]
 pub struct EthTxManager<E, G> {
     ethereum_gateway: E,
     config: SenderConfig,
     gas_adjuster: Arc<G>,
+    nonce_issues: watch::Sender<Option<DetectedNonceIssue>>,
+    /// L1 block at which the currently reported nonce issue was detected.
+    nonce_issue_detected_at_block: Option<L1BlockNumber>,
+    /// Sent no-op transactions filling the operator nonce gap, keyed by nonce.
+    noop_txs: HashMap<Nonce, SentNoopTx>,
+    /// `eth_txs` that may have their nonces consumed externally, keyed by ID.
+    suspected_orphaned_txs: HashMap<u32, SuspectedOrphanedTx>,
 }
 
 impl<E, G> EthTxManager<E, G>
@@ -59,11 +94,20 @@ where
     E: BoundEthInterface + Sync,
     G: L1TxParamsProvider,
 {
-    pub fn new(config: SenderConfig, gas_adjuster: Arc<G>, ethereum_gateway: E) -> Self {
+    pub fn new(
+        config: SenderConfig,
+        gas_adjuster: Arc<G>,
+        ethereum_gateway: E,
+        nonce_issues: watch::Sender<Option<DetectedNonceIssue>>,
+    ) -> Self {
         Self {
             ethereum_gateway,
             config,
             gas_adjuster,
+            nonce_issues,
+            nonce_issue_detected_at_block: None,
+            noop_txs: HashMap::new(),
+            suspected_orphaned_txs: HashMap::new(),
         }
     }
 
@@ -77,11 +121,13 @@ where
             .map_err(Into::into)
     }
 
+    /// Returns an error if no receipt was found and some of the attempts could not be checked.
     async fn check_all_sending_attempts(
         &self,
         storage: &mut StorageProcessor<'_>,
         op: &EthTx,
-    ) -> Option<ExecutedTxStatus> {
+    ) -> Result<Option<ExecutedTxStatus>, ETHSenderError> {
+        let mut last_error = None;
         // Checking history items, starting from most recently sent.
         for history_item in storage
             .eth_sender_dal()
@@ -93,16 +139,19 @@ where
             // because if we do and get an `Err`, we won't finish the for loop,
             // which means we might miss the transaction that actually succeeded.
             match self.get_tx_status(history_item.tx_hash).await {
-                Ok(Some(s)) => return Some(s),
+                Ok(Some(s)) => return Ok(Some(s)),
                 Ok(_) => continue,
-                Err(err) => tracing::warn!(
-                    "Can't check transaction {:?}: {:?}",
-                    history_item.tx_hash,
-                    err
-                ),
+                Err(err) => {
+                    tracing::warn!(
+                        "Can't check transaction {:?}: {:?}",
+                        history_item.tx_hash,
+                        err
+                    );
+                    last_error = Some(err);
+                }
             }
         }
-        None
+        last_error.map_or(Ok(None), Err)
     }
 
     async fn calculate_fee(
@@ -110,7 +159,14 @@ where
         storage: &mut StorageProcessor<'_>,
         tx: &EthTx,
         time_in_mempool: u32,
+        current_block: L1BlockNumber,
     ) -> Result<EthFee, ETHSenderError> {
+        if tx.blob_sidecar.is_some() {
+            return self
+                .calculate_blob_tx_fee(storage, tx, time_in_mempool, current_block)
+                .await;
+        }
+
         let base_fee_per_gas = self.gas_adjuster.get_base_fee(time_in_mempool);
 
         let priority_fee_per_gas = if time_in_mempool != 0 {
@@ -141,9 +197,95 @@ where
         Ok(EthFee {
             base_fee_per_gas,
             priority_fee_per_gas,
+            blob_base_fee_per_gas: None,
+        })
+    }
+
+    /// Calculates fees for a blob transaction. Blob transactions are only replaced in the L1 mempool
+    /// if all fees are at least doubled, so a sent blob transaction is only replaced if it's stuck
+    /// for at least the configured number of blocks.
+    async fn calculate_blob_tx_fee(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        tx: &EthTx,
+        time_in_mempool: u32,
+        current_block: L1BlockNumber,
+    ) -> Result<EthFee, ETHSenderError> {
+        let mut base_fee_per_gas = self.gas_adjuster.get_base_fee(time_in_mempool);
+        let mut priority_fee_per_gas = self.gas_adjuster.get_priority_fee();
+        let mut blob_base_fee_per_gas = self.gas_adjuster.get_blob_base_fee(time_in_mempool);
+        // Minimum priority fee required to replace the previously sent transaction.
+        let mut min_priority_fee_per_gas = 0;
+
+        let previous_sent_tx = if time_in_mempool == 0 {
+            None
+        } else {
+            storage
+                .eth_sender_dal()
+                .get_last_sent_eth_tx(tx.id)
+                .await
+                .unwrap()
+        };
+        if let Some(previous_sent_tx) = previous_sent_tx {
+            let previous_blob_base_fee = previous_sent_tx.blob_base_fee_per_gas.unwrap_or(0);
+            // If the previous attempt wasn't accepted by the L1 node, there's nothing to replace.
+            let fee_multiplier = if let Some(sent_at_block) = previous_sent_tx.sent_at_block {
+                let blocks_since_last_attempt = current_block.0.saturating_sub(sent_at_block);
+                let resend_interval = self.config.blob_tx_resend_interval_blocks();
+                if blocks_since_last_attempt < resend_interval {
+                    tracing::debug!(
+                        "Skipping resending blob operation {}: last attempt was sent {blocks_since_last_attempt} blocks ago, \
+                         while resend interval is {resend_interval} blocks",
+                        tx.id
+                    );
+                    return Err(ETHSenderError::from(Error::from(Web3Error::Internal)));
+                }
+                2
+            } else {
+                1
+            };
+            base_fee_per_gas =
+                base_fee_per_gas.max(previous_sent_tx.base_fee_per_gas * fee_multiplier);
+            min_priority_fee_per_gas = previous_sent_tx.priority_fee_per_gas * fee_multiplier;
+            priority_fee_per_gas = priority_fee_per_gas.max(min_priority_fee_per_gas);
+            blob_base_fee_per_gas =
+                blob_base_fee_per_gas.max(previous_blob_base_fee * fee_multiplier);
+
+            METRICS.transaction_resent.inc();
+            tracing::info!(
+                "Resending blob operation {} with base fee {base_fee_per_gas:?}, priority fee {priority_fee_per_gas:?} \
+                 and blob base fee {blob_base_fee_per_gas:?}",
+                tx.id
+            );
+        }
+
+        // The priority fee is never capped below the value required to replace the previously sent transaction;
+        // otherwise, the replacement would be rejected, and the blob operation would get stuck.
+        let priority_fee_per_gas = self
+            .cap_priority_fee(&format!("operation {}", tx.id), priority_fee_per_gas)
+            .max(min_priority_fee_per_gas);
+        Ok(EthFee {
+            base_fee_per_gas,
+            priority_fee_per_gas,
+            blob_base_fee_per_gas: Some(blob_base_fee_per_gas),
         })
     }
 
+    /// Caps the priority fee to prevent sending transactions with an extremely high priority fee.
+    fn cap_priority_fee(&self, operation: &str, priority_fee_per_gas: u64) -> u64 {
+        let max_priority_fee = self.config.max_acceptable_priority_fee_in_gwei;
+        if priority_fee_per_gas > max_priority_fee {
+            tracing::warn!(
+                "Extremely high value of priority_fee_per_gas is suggested for {operation}: \
+                 {priority_fee_per_gas}, while max acceptable is {max_priority_fee}; capping it"
+            );
+            METRICS.capped_priority_fee.inc();
+            max_priority_fee
+        } else {
+            priority_fee_per_gas
+        }
+    }
+
     async fn increase_priority_fee(
         &self,
         storage: &mut StorageProcessor<'_>,
@@ -157,17 +299,32 @@ where
             .unwrap()
             .unwrap();
 
-        let previous_base_fee = previous_sent_tx.base_fee_per_gas;
-        let previous_priority_fee = previous_sent_tx.priority_fee_per_gas;
+        self.bump_priority_fee(
+            &format!("operation {eth_tx_id}"),
+            previous_sent_tx.base_fee_per_gas,
+            previous_sent_tx.priority_fee_per_gas,
+            base_fee_per_gas,
+        )
+    }
+
+    /// Returns the priority fee for a replacement of a sent transaction, or an error if the replacement
+    /// should be skipped.
+    fn bump_priority_fee(
+        &self,
+        operation: &str,
+        previous_base_fee: u64,
+        previous_priority_fee: u64,
+        base_fee_per_gas: u64,
+    ) -> Result<u64, ETHSenderError> {
         let next_block_minimal_base_fee = self.gas_adjuster.get_next_block_minimal_base_fee();
 
         if base_fee_per_gas <= next_block_minimal_base_fee.min(previous_base_fee) {
             // If the base fee is lower than the previous used one
             // or is lower than the minimal possible value for the next block, sending is skipped.
             tracing::info!(
-                "Skipping gas adjustment for operation {}, \
+                "Skipping gas adjustment for {}, \
                  base_fee_per_gas: suggested for resending {:?}, previously sent {:?}, next block minimum {:?}",
-                eth_tx_id,
+                operation,
                 base_fee_per_gas,
                 previous_base_fee,
                 next_block_minimal_base_fee
@@ -190,15 +347,26 @@ where
         let EthFee {
             base_fee_per_gas,
             priority_fee_per_gas,
-        } = self.calculate_fee(storage, tx, time_in_mempool).await?;
+            blob_base_fee_per_gas,
+        } = self
+            .calculate_fee(storage, tx, time_in_mempool, current_block)
+            .await?;
 
         METRICS.used_base_fee_per_gas.observe(base_fee_per_gas);
         METRICS
             .used_priority_fee_per_gas
             .observe(priority_fee_per_gas);
+        if let Some(blob_base_fee_per_gas) = blob_base_fee_per_gas {
+            METRICS.used_blob_base_fee.observe(blob_base_fee_per_gas);
+        }
 
         let signed_tx = self
-            .sign_tx(tx, base_fee_per_gas, priority_fee_per_gas)
+            .sign_tx(
+                tx,
+                base_fee_per_gas,
+                priority_fee_per_gas,
+                blob_base_fee_per_gas,
+            )
             .await;
 
         if let Some(tx_history_id) = storage
@@ -207,6 +375,7 @@ where
                 tx.id,
                 base_fee_per_gas,
                 priority_fee_per_gas,
+                blob_base_fee_per_gas,
                 signed_tx.hash,
                 signed_tx.raw_tx.clone(),
             )
@@ -214,7 +383,13 @@ where
             .unwrap()
         {
             if let Err(error) = self
-                .send_raw_transaction(storage, tx_history_id, signed_tx.raw_tx, current_block)
+                .send_raw_transaction(
+                    storage,
+                    tx_history_id,
+                    signed_tx.raw_tx,
+                    tx.blob_sidecar.as_ref(),
+                    current_block,
+                )
                 .await
             {
                 tracing::warn!(
@@ -234,9 +409,19 @@ where
         storage: &mut StorageProcessor<'_>,
         tx_history_id: u32,
         raw_tx: Vec<u8>,
+        blob_sidecar: Option<&EthTxBlobSidecar>,
         current_block: L1BlockNumber,
     ) -> Result<H256, ETHSenderError> {
-        match self.ethereum_gateway.send_raw_tx(raw_tx).await {
+        // The sidecar is stored only once with the `EthTx`, so it's attached to the signed transaction here.
+        let send_result = match blob_sidecar {
+            Some(blob_sidecar) => {
+                self.ethereum_gateway
+                    .send_raw_blob_tx(raw_tx, blob_sidecar)
+                    .await
+            }
+            None => self.ethereum_gateway.send_raw_tx(raw_tx).await,
+        };
+        match send_result {
             Ok(tx_hash) => {
                 storage
                     .eth_sender_dal()
@@ -329,6 +514,7 @@ where
         );
 
         // Not confirmed transactions, ordered by nonce
+        let mut orphaned_tx_ids = vec![];
         for tx in inflight_txs {
             tracing::trace!("Checking tx id: {}", tx.id,);
 
@@ -336,6 +522,24 @@ where
             // that `tx` is not mined and we should resend it.
             // We only resend the first unmined transaction.
             if operator_nonce.latest <= tx.nonce {
+                if !orphaned_tx_ids.is_empty() {
+                    // Nonces were reassigned, so `tx` may be outdated; it will be resent on the next iteration.
+                    self.reassign_orphaned_nonces(
+                        storage,
+                        &orphaned_tx_ids,
+                        operator_nonce,
+                        l1_block_numbers,
+                    )
+                    .await?;
+                    return Ok(None);
+                }
+                if operator_nonce.latest < tx.nonce {
+                    self.fill_nonce_gap(storage, operator_nonce.latest, tx.nonce, l1_block_numbers)
+                        .await?;
+                } else {
+                    self.report_nonce_issue(None, l1_block_numbers.latest);
+                }
+
                 // None means txs hasn't been sent yet
                 let first_sent_at_block = storage
                     .eth_sender_dal()
@@ -362,46 +566,268 @@ where
             );
 
             match self.check_all_sending_attempts(storage, &tx).await {
-                Some(tx_status) => {
+                Ok(Some(tx_status)) => {
+                    self.suspected_orphaned_txs.remove(&tx.id);
                     self.apply_tx_status(storage, &tx, tx_status, l1_block_numbers.finalized)
                         .await;
                 }
-                None => {
-                    // The nonce has increased but we did not find the receipt.
-                    // This is an error because such a big reorg may cause transactions that were
-                    // previously recorded as confirmed to become pending again and we have to
-                    // make sure it's not the case - otherwise eth_sender may not work properly.
-                    tracing::error!(
-                        "Possible block reorgs: finalized nonce increase detected, but no tx receipt found for tx {:?}",
-                        &tx
-                    );
+                Ok(None) => {
+                    // The nonce has increased on a finalized block, but none of our sending attempts
+                    // was mined. This means that the nonce was consumed by an external transaction
+                    // (or that a deep reorg has happened), so `tx` will never be mined with its current nonce.
+                    // Since the L1 node may be temporarily missing the receipt, this must be confirmed
+                    // by several checks.
+                    if self.is_confirmed_orphaned_tx(tx.id, l1_block_numbers.finalized) {
+                        tracing::error!(
+                            "Finalized nonce increase detected, but no tx receipt found for tx {:?} \
+                             in {ORPHANED_TX_CHECK_COUNT} checks; the nonce is considered to be consumed externally",
+                            &tx
+                        );
+                        orphaned_tx_ids.push(tx.id);
+                    } else {
+                        tracing::warn!(
+                            "Finalized nonce increase detected, but no tx receipt found for tx {:?}; \
+                             will recheck on the following finalized L1 blocks",
+                            &tx
+                        );
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!("Cannot check sending attempts for tx {}: {err}", tx.id);
                 }
             }
         }
+
+        if !orphaned_tx_ids.is_empty() {
+            self.reassign_orphaned_nonces(
+                storage,
+                &orphaned_tx_ids,
+                operator_nonce,
+                l1_block_numbers,
+            )
+            .await?;
+        } else {
+            self.report_nonce_issue(None, l1_block_numbers.latest);
+        }
         Ok(None)
     }
 
+    /// Records a check of the `eth_tx` with a consumed nonce, but without receipts. Returns `true` once
+    /// the `eth_tx` had no receipts for [`ORPHANED_TX_CHECK_COUNT`] checks at distinct finalized L1 blocks.
+    fn is_confirmed_orphaned_tx(&mut self, tx_id: u32, finalized_block: L1BlockNumber) -> bool {
+        let first_check = SuspectedOrphanedTx {
+            check_count: 1,
+            last_checked_finalized_block: finalized_block,
+        };
+        let suspected_tx = self
+            .suspected_orphaned_txs
+            .entry(tx_id)
+            .or_insert(first_check);
+        if suspected_tx.last_checked_finalized_block < finalized_block {
+            suspected_tx.check_count += 1;
+            suspected_tx.last_checked_finalized_block = finalized_block;
+        }
+        suspected_tx.check_count >= ORPHANED_TX_CHECK_COUNT
+    }
+
+    /// Reports the current operator nonce issue (or its absence) to the circuit breaker.
+    fn report_nonce_issue(
+        &mut self,
+        issue: Option<OperatorNonceIssue>,
+        current_block: L1BlockNumber,
+    ) {
+        let is_new_issue = self.nonce_issues.send_if_modified(|detected| {
+            let current_issue = detected.map(|detected| detected.issue);
+            if current_issue == issue {
+                return false;
+            }
+            *detected = issue.map(DetectedNonceIssue::new);
+            true
+        });
+        if is_new_issue {
+            self.nonce_issue_detected_at_block = issue.map(|_| current_block);
+            if let Some(issue) = issue {
+                METRICS.operator_nonce_issues[&issue.into()].inc();
+            }
+        }
+    }
+
+    /// Reassigns nonces to `eth_txs` whose nonces were consumed by external transactions
+    /// and to all subsequent unmined `eth_txs`, so that they can be re-signed and mined.
+    async fn reassign_orphaned_nonces(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        orphaned_tx_ids: &[u32],
+        operator_nonce: OperatorNonce,
+        l1_block_numbers: L1BlockNumbers,
+    ) -> Result<(), ETHSenderError> {
+        let first_orphaned_tx_id = orphaned_tx_ids[0];
+        let first_orphaned_tx = storage
+            .eth_sender_dal()
+            .get_eth_tx(first_orphaned_tx_id)
+            .await
+            .context("get_eth_tx()")?
+            .with_context(|| format!("orphaned eth_tx {first_orphaned_tx_id} is missing"))?;
+        let issue = OperatorNonceIssue::ExternallyConsumed {
+            nonce: first_orphaned_tx.nonce.0.into(),
+        };
+        self.report_nonce_issue(Some(issue), l1_block_numbers.latest);
+
+        let reassigned_ids = storage
+            .eth_sender_dal()
+            .reassign_nonces(orphaned_tx_ids, operator_nonce.latest)
+            .await
+            .context("reassign_nonces()")?;
+        for tx_id in orphaned_tx_ids {
+            self.suspected_orphaned_txs.remove(tx_id);
+        }
+        tracing::warn!(
+            "Reassigned nonces starting from {} to eth_txs {reassigned_ids:?}",
+            operator_nonce.latest
+        );
+        METRICS
+            .reassigned_nonces
+            .inc_by(reassigned_ids.len() as u64);
+        Ok(())
+    }
+
+    /// Fills a gap between the operator nonce on L1 and the nonce of the first pending `eth_tx`
+    /// with no-op self-transfers. Does nothing if any of the nonces in the gap are used by `eth_txs`
+    /// (e.g., because of an L1 reorg).
+    ///
+    /// Sent no-op transactions are tracked, so that they are replaced with increased fees
+    /// the same way as stuck `eth_txs`.
+    async fn fill_nonce_gap(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        l1_nonce: Nonce,
+        first_pending_nonce: Nonce,
+        l1_block_numbers: L1BlockNumbers,
+    ) -> Result<(), ETHSenderError> {
+        // No-op transactions with nonces below the L1 nonce are mined.
+        self.noop_txs.retain(|&nonce, _| nonce >= l1_nonce);
+
+        let used_nonces_count = storage
+            .eth_sender_dal()
+            .get_number_of_eth_txs_with_nonces(l1_nonce..first_pending_nonce)
+            .await
+            .unwrap();
+        if used_nonces_count > 0 {
+            tracing::info!(
+                "Operator nonce {l1_nonce} is lower than nonce {first_pending_nonce} of the first pending eth_tx, \
+                 but nonces in between are used by eth_txs; skipping gap filling"
+            );
+            self.report_nonce_issue(None, l1_block_numbers.latest);
+            return Ok(());
+        }
+
+        let issue = OperatorNonceIssue::Gap {
+            l1_nonce: l1_nonce.0.into(),
+            first_pending_nonce: first_pending_nonce.0.into(),
+        };
+        self.report_nonce_issue(Some(issue), l1_block_numbers.latest);
+        tracing::warn!(
+            "Detected operator nonce gap: nonces {l1_nonce}..{first_pending_nonce} are not used by eth_txs; \
+             filling it with no-op transactions"
+        );
+
+        let time_in_mempool = self
+            .nonce_issue_detected_at_block
+            .map_or(0, |block| l1_block_numbers.latest.0.saturating_sub(block.0));
+        let base_fee_per_gas = self.gas_adjuster.get_base_fee(time_in_mempool);
+        let operator_address = self.ethereum_gateway.sender_account();
+
+        for nonce in l1_nonce.0..first_pending_nonce.0 {
+            let nonce = Nonce(nonce);
+            let operation = format!("no-op transaction with nonce {nonce}");
+            let priority_fee_per_gas = match self.noop_txs.get(&nonce) {
+                Some(previous_tx) => {
+                    let Ok(priority_fee_per_gas) = self.bump_priority_fee(
+                        &operation,
+                        previous_tx.base_fee_per_gas,
+                        previous_tx.priority_fee_per_gas,
+                        base_fee_per_gas,
+                    ) else {
+                        continue;
+                    };
+                    METRICS.transaction_resent.inc();
+                    priority_fee_per_gas
+                }
+                None => self.gas_adjuster.get_priority_fee(),
+            };
+            let priority_fee_per_gas = self.cap_priority_fee(&operation, priority_fee_per_gas);
+
+            let options = Options::with(|opt| {
+                opt.gas = Some(NOOP_TX_GAS_LIMIT.into());
+                opt.max_fee_per_gas = Some(U256::from(base_fee_per_gas + priority_fee_per_gas));
+                opt.max_priority_fee_per_gas = Some(U256::from(priority_fee_per_gas));
+                opt.nonce = Some(nonce.0.into());
+            });
+            let signed_tx = self
+                .ethereum_gateway
+                .sign_prepared_tx_for_addr(vec![], operator_address, options, "eth_tx_manager")
+                .await?;
+            match self.ethereum_gateway.send_raw_tx(signed_tx.raw_tx).await {
+                Ok(tx_hash) => {
+                    tracing::info!(
+                        "Sent no-op transaction {tx_hash:?} with nonce {nonce}, base fee {base_fee_per_gas} \
+                         and priority fee {priority_fee_per_gas}"
+                    );
+                    METRICS.noop_transactions_sent.inc();
+                    let sent_tx = SentNoopTx {
+                        base_fee_per_gas,
+                        priority_fee_per_gas,
+                    };
+                    self.noop_txs.insert(nonce, sent_tx);
+                }
+                Err(err) => {
+                    tracing::warn!("Error sending no-op transaction with nonce {nonce}: {err}");
+                }
+            }
+        }
+        Ok(())
+    }
+
     async fn sign_tx(
         &self,
         tx: &EthTx,
         base_fee_per_gas: u64,
         priority_fee_per_gas: u64,
+        blob_base_fee_per_gas: Option<u64>,
     ) -> SignedCallResult {
-        self.ethereum_gateway
-            .sign_prepared_tx_for_addr(
-                tx.raw_tx.clone(),
-                tx.contract_address,
-                Options::with(|opt| {
-                    // TODO Calculate gas for every operation SMA-1436
-                    opt.gas = Some(self.config.max_aggregated_tx_gas.into());
-                    opt.max_fee_per_gas = Some(U256::from(base_fee_per_gas + priority_fee_per_gas));
-                    opt.max_priority_fee_per_gas = Some(U256::from(priority_fee_per_gas));
-                    opt.nonce = Some(tx.nonce.0.into());
-                }),
-                "eth_tx_manager",
-            )
-            .await
-            .expect("Failed to sign transaction")
+        let options = Options::with(|opt| {
+            // TODO Calculate gas for every operation SMA-1436
+            opt.gas = Some(self.config.max_aggregated_tx_gas.into());
+            opt.max_fee_per_gas = Some(U256::from(base_fee_per_gas + priority_fee_per_gas));
+            opt.max_priority_fee_per_gas = Some(U256::from(priority_fee_per_gas));
+            opt.nonce = Some(tx.nonce.0.into());
+        });
+
+        let signed_tx = match (&tx.blob_sidecar, blob_base_fee_per_gas) {
+            (Some(blob_sidecar), Some(blob_base_fee_per_gas)) => {
+                self.ethereum_gateway
+                    .sign_prepared_blob_tx_for_addr(
+                        tx.raw_tx.clone(),
+                        tx.contract_address,
+                        options,
+                        blob_base_fee_per_gas.into(),
+                        blob_sidecar,
+                        "eth_tx_manager",
+                    )
+                    .await
+            }
+            _ => {
+                self.ethereum_gateway
+                    .sign_prepared_tx_for_addr(
+                        tx.raw_tx.clone(),
+                        tx.contract_address,
+                        options,
+                        "eth_tx_manager",
+                    )
+                    .await
+            }
+        };
+        signed_tx.expect("Failed to sign transaction")
     }
 
     async fn send_unsent_txs(
@@ -414,6 +840,12 @@ where
             // The common reason for this behaviour is that we sent tx and stop the server
             // before updating the database
             let tx_status = self.get_tx_status(tx.tx_hash).await;
+            let eth_tx = storage
+                .eth_sender_dal()
+                .get_eth_tx(tx.eth_tx_id)
+                .await
+                .unwrap()
+                .expect("Eth tx should exist");
 
             if let Ok(Some(tx_status)) = tx_status {
                 tracing::info!("The tx {:?} has been already sent", tx.tx_hash);
@@ -423,13 +855,6 @@ where
                     .await
                     .unwrap();
 
-                let eth_tx = storage
-                    .eth_sender_dal()
-                    .get_eth_tx(tx.eth_tx_id)
-                    .await
-                    .unwrap()
-                    .expect("Eth tx should exist");
-
                 self.apply_tx_status(storage, &eth_tx, tx_status, l1_block_numbers.finalized)
                     .await;
             } else if let Err(error) = self
@@ -437,6 +862,7 @@ where
                     storage,
                     tx.id,
                     tx.signed_raw_tx.clone(),
+                    eth_tx.blob_sidecar.as_ref(),
                     l1_block_numbers.latest,
                 )
                 .await
@@ -3,6 +3,7 @@
 use std::{fmt, time::Duration};
 
 use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics};
+use zksync_circuit_breaker::operator_nonce::OperatorNonceIssue;
 use zksync_dal::StorageProcessor;
 use zksync_types::{aggregated_operations::AggregatedActionType, eth_sender::EthTx};
 use zksync_utils::time::seconds_since_epoch;
@@ -34,6 +35,22 @@ impl fmt::Display for ActionTypeLabel {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[metrics(label = "kind", rename_all = "snake_case")]
+pub(super) enum NonceIssueKind {
+    Gap,
+    ExternallyConsumed,
+}
+
+impl From<OperatorNonceIssue> for NonceIssueKind {
+    fn from(issue: OperatorNonceIssue) -> Self {
+        match issue {
+            OperatorNonceIssue::Gap { .. } => Self::Gap,
+            OperatorNonceIssue::ExternallyConsumed { .. } => Self::ExternallyConsumed,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
 pub(super) struct AggregationReasonLabels {
     r#type: &'static str,
@@ -71,10 +88,14 @@ pub(super) struct EthSenderMetrics {
     pub block_range_size: Family<ActionTypeLabel, Histogram<u64>>,
     /// Number of transactions resent by the Ethereum sender.
     pub transaction_resent: Counter,
+    /// Number of times the suggested priority fee was capped to the max acceptable value.
+    pub capped_priority_fee: Counter,
     #[metrics(buckets = FEE_BUCKETS)]
     pub used_base_fee_per_gas: Histogram<u64>,
     #[metrics(buckets = FEE_BUCKETS)]
     pub used_priority_fee_per_gas: Histogram<u64>,
+    #[metrics(buckets = Buckets::exponential(1.0..=1e11, 10.0))]
+    pub used_blob_base_fee: Histogram<u64>,
     /// Last L1 block observed by the Ethereum sender.
     pub last_known_l1_block: Gauge<u64>,
     /// Number of in-flight txs produced by the Ethereum sender.
@@ -87,6 +108,12 @@ pub(super) struct EthSenderMetrics {
     pub l1_blocks_waited_in_mempool: Family<ActionTypeLabel, Histogram<u64>>,
     /// Number of L1 batches aggregated for publishing with a specific reason.
     pub block_aggregation_reason: Family<AggregationReasonLabels, Counter>,
+    /// Number of detected operator nonce issues of a specific kind.
+    pub operator_nonce_issues: Family<NonceIssueKind, Counter>,
+    /// Number of no-op transactions sent to fill operator nonce gaps.
+    pub noop_transactions_sent: Counter,
+    /// Number of `eth_txs` reassigned new nonces because their nonces were consumed externally.
+    pub reassigned_nonces: Counter,
 }
 
 impl EthSenderMetrics {
@@ -2,7 +2,9 @@ use std::fmt;
 
 use async_trait::async_trait;
 use chrono::Utc;
+use zksync_config::configs::eth_sender::PubdataSendingMode;
 use zksync_dal::StorageProcessor;
+use zksync_kzg::blob_count;
 use zksync_types::{
     aggregated_operations::AggregatedActionType, commitment::L1BatchWithMetadata, L1BatchNumber,
 };
@@ -197,6 +199,22 @@ impl L1BatchPublishCriterion for GasCriterion {
 pub struct DataSizeCriterion {
     pub op: AggregatedActionType,
     pub data_limit: usize,
+    /// If pubdata is posted in blobs, it doesn't count towards the calldata limit.
+    pub pubdata_sending_mode: PubdataSendingMode,
+}
+
+impl DataSizeCriterion {
+    fn l1_commit_data_size(&self, l1_batch: &L1BatchWithMetadata) -> usize {
+        // Pre-boojum L1 batches are always committed with pubdata in calldata.
+        let is_pre_boojum = l1_batch.header.protocol_version.unwrap().is_pre_boojum();
+        match self.pubdata_sending_mode {
+            PubdataSendingMode::Blobs if !is_pre_boojum => {
+                let blob_count = blob_count(l1_batch.construct_pubdata().len());
+                l1_batch.l1_commit_data_size_with_blobs(blob_count)
+            }
+            _ => l1_batch.l1_commit_data_size(),
+        }
+    }
 }
 
 #[async_trait]
@@ -215,13 +233,12 @@ impl L1BatchPublishCriterion for DataSizeCriterion {
         let mut data_size_left = self.data_limit - STORED_BLOCK_INFO_SIZE;
 
         for (index, l1_batch) in consecutive_l1_batches.iter().enumerate() {
-            if data_size_left < l1_batch.l1_commit_data_size() {
+            let l1_commit_data_size = self.l1_commit_data_size(l1_batch);
+            if data_size_left < l1_commit_data_size {
                 if index == 0 {
                     panic!(
                         "L1 batch #{} requires {} data, which is more than the range limit of {}",
-                        l1_batch.header.number,
-                        l1_batch.l1_commit_data_size(),
-                        self.data_limit
+                        l1_batch.header.number, l1_commit_data_size, self.data_limit
                     );
                 }
 
@@ -236,7 +253,56 @@ impl L1BatchPublishCriterion for DataSizeCriterion {
                 METRICS.block_aggregation_reason[&(self.op, "data_size").into()].inc();
                 return Some(output);
             }
-            data_size_left -= l1_batch.l1_commit_data_size();
+            data_size_left -= l1_commit_data_size;
+        }
+
+        None
+    }
+}
+
+/// Limits the number of EIP-4844 blobs necessary to post pubdata of the published L1 batches.
+#[derive(Debug)]
+pub struct BlobCountCriterion {
+    pub op: AggregatedActionType,
+    pub limit: usize,
+}
+
+#[async_trait]
+impl L1BatchPublishCriterion for BlobCountCriterion {
+    fn name(&self) -> &'static str {
+        "blob_count"
+    }
+
+    async fn last_l1_batch_to_publish(
+        &mut self,
+        _storage: &mut StorageProcessor<'_>,
+        consecutive_l1_batches: &[L1BatchWithMetadata],
+        _last_sealed_l1_batch: L1BatchNumber,
+    ) -> Option<L1BatchNumber> {
+        let mut blobs_left = self.limit;
+
+        for (index, l1_batch) in consecutive_l1_batches.iter().enumerate() {
+            let batch_blob_count = blob_count(l1_batch.construct_pubdata().len());
+            if blobs_left < batch_blob_count {
+                if index == 0 {
+                    panic!(
+                        "L1 batch #{} requires {} blobs, which is more than the range limit of {}",
+                        l1_batch.header.number, batch_blob_count, self.limit
+                    );
+                }
+
+                let first_l1_batch_number = consecutive_l1_batches.first().unwrap().header.number.0;
+                let output = l1_batch.header.number - 1;
+                tracing::debug!(
+                    "`blob_count` publish criterion (blobs={}) triggered for op {} with L1 batch range {:?}",
+                    self.limit - blobs_left,
+                    self.op,
+                    first_l1_batch_number..=output.0
+                );
+                METRICS.block_aggregation_reason[&(self.op, "blob_count").into()].inc();
+                return Some(output);
+            }
+            blobs_left -= batch_blob_count;
         }
 
         None
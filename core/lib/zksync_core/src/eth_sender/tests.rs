@@ -2,8 +2,10 @@ use std::sync::{atomic::Ordering, Arc};
 
 use assert_matches::assert_matches;
 use once_cell::sync::Lazy;
+use tokio::sync::watch;
+use zksync_circuit_breaker::operator_nonce::{DetectedNonceIssue, OperatorNonceIssue};
 use zksync_config::{
-    configs::eth_sender::{ProofSendingMode, SenderConfig},
+    configs::eth_sender::{ProofSendingMode, PubdataSendingMode, SenderConfig},
     ContractsConfig, ETHSenderConfig, GasAdjusterConfig,
 };
 use zksync_contracts::BaseSystemContractsHashes;
@@ -12,10 +14,12 @@ use zksync_eth_client::{clients::mock::MockEthereum, EthInterface};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_types::{
     aggregated_operations::{
-        AggregatedOperation, L1BatchCommitOperation, L1BatchExecuteOperation, L1BatchProofOperation,
+        AggregatedActionType, AggregatedOperation, L1BatchCommitOperation, L1BatchExecuteOperation,
+        L1BatchProofOperation,
     },
     block::L1BatchHeader,
     commitment::{L1BatchMetaParameters, L1BatchMetadata, L1BatchWithMetadata},
+    eth_sender::EthTxBlobSidecar,
     ethabi::Token,
     helpers::unix_timestamp_ms,
     web3::contract::Error,
@@ -24,7 +28,9 @@ use zksync_types::{
 
 use crate::{
     eth_sender::{
-        eth_tx_manager::L1BlockNumbers, Aggregator, ETHSenderError, EthTxAggregator, EthTxManager,
+        eth_tx_manager::{L1BlockNumbers, ORPHANED_TX_CHECK_COUNT},
+        publish_criterion::{DataSizeCriterion, L1BatchPublishCriterion},
+        Aggregator, ETHSenderError, EthTxAggregator, EthTxManager,
     },
     l1_gas_price::GasAdjuster,
 };
@@ -55,18 +61,31 @@ struct EthSenderTester {
     manager: MockEthTxManager,
     aggregator: EthTxAggregator,
     gas_adjuster: Arc<GasAdjuster<Arc<MockEthereum>>>,
+    nonce_issues: watch::Receiver<Option<DetectedNonceIssue>>,
 }
 
 impl EthSenderTester {
     const WAIT_CONFIRMATIONS: u64 = 10;
     const MAX_BASE_FEE_SAMPLES: usize = 3;
+    const BLOB_TX_RESEND_INTERVAL_BLOCKS: u32 = 3;
 
     async fn new(
         connection_pool: ConnectionPool,
         history: Vec<u64>,
         non_ordering_confirmations: bool,
     ) -> Self {
-        let eth_sender_config = ETHSenderConfig::for_tests();
+        Self::with_base_nonce(connection_pool, history, non_ordering_confirmations, 0).await
+    }
+
+    async fn with_base_nonce(
+        connection_pool: ConnectionPool,
+        history: Vec<u64>,
+        non_ordering_confirmations: bool,
+        base_nonce: u64,
+    ) -> Self {
+        let mut eth_sender_config = ETHSenderConfig::for_tests();
+        eth_sender_config.sender.blob_tx_resend_interval_blocks =
+            Some(Self::BLOB_TX_RESEND_INTERVAL_BLOCKS);
         let contracts_config = ContractsConfig::for_tests();
         let aggregator_config = SenderConfig {
             aggregated_proof_sizes: vec![1],
@@ -111,25 +130,28 @@ impl EthSenderTester {
             // Aggregator - unused
             Aggregator::new(
                 aggregator_config.clone(),
-                store_factory.create_store().await,
+                store_factory.create_store().await.unwrap(),
             ),
             // zkSync contract address
             Address::random(),
             contracts_config.l1_multicall3_addr,
             Address::random(),
-            0,
+            base_nonce,
         );
 
+        let (nonce_issues_sender, nonce_issues) = watch::channel(None);
         let manager = EthTxManager::new(
             eth_sender_config.sender,
             gas_adjuster.clone(),
             gateway.clone(),
+            nonce_issues_sender,
         );
         Self {
             gateway,
             manager,
             aggregator,
             gas_adjuster,
+            nonce_issues,
             conn: connection_pool,
         }
     }
@@ -138,6 +160,10 @@ impl EthSenderTester {
         self.conn.access_storage().await.unwrap()
     }
 
+    fn nonce_issue(&self) -> Option<OperatorNonceIssue> {
+        self.nonce_issues.borrow().map(|detected| detected.issue)
+    }
+
     async fn get_block_numbers(&self) -> L1BlockNumbers {
         let latest = self.gateway.block_number("").await.unwrap().as_u32().into();
         let finalized = latest - Self::WAIT_CONFIRMATIONS as u32;
@@ -315,6 +341,120 @@ async fn resend_each_block() -> anyhow::Result<()> {
     Ok(())
 }
 
+// Tests that blob transactions are resent only after the resend interval, with all fees (including the blob base fee)
+// at least doubled.
+#[tokio::test]
+async fn resend_blob_tx_with_doubled_fees() -> anyhow::Result<()> {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let mut tester = EthSenderTester::new(connection_pool, vec![7, 6, 5, 5, 5, 2, 1], false).await;
+
+    // after this, median should be 6
+    tester.gateway.advance_block_number(3);
+    tester.gas_adjuster.keep_updated().await?;
+
+    let blob_sidecar = EthTxBlobSidecar {
+        blobs: vec![vec![0; 32]],
+        commitments: vec![vec![1; 48]],
+        proofs: vec![vec![2; 48]],
+        versioned_hashes: vec![H256::repeat_byte(1)],
+    };
+    let tx = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .save_eth_tx(
+            0,
+            vec![],
+            AggregatedActionType::Commit,
+            Address::random(),
+            0,
+            Some(blob_sidecar.clone()),
+        )
+        .await?;
+    assert_eq!(tx.blob_sidecar.as_ref(), Some(&blob_sidecar));
+
+    let block = L1BlockNumber(tester.gateway.block_number("").await?.as_u32());
+    let hash = tester
+        .manager
+        .send_eth_tx(
+            &mut tester.conn.access_storage().await.unwrap(),
+            &tx,
+            0,
+            block,
+        )
+        .await?;
+
+    let sent_tx = tester.gateway.sent_txs.read().unwrap()[&hash];
+    assert_eq!(sent_tx.base_fee.as_usize(), 18); // 6 * 3 * 2^0
+    let last_sent_tx = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_last_sent_eth_tx(tx.id)
+        .await?
+        .unwrap();
+    // There's no blob fee history, so the blob base fee is 3 * 2^0 * 1
+    assert_eq!(last_sent_tx.blob_base_fee_per_gas, Some(3));
+    let initial_priority_fee = last_sent_tx.priority_fee_per_gas;
+
+    // now, median is 5
+    tester.gateway.advance_block_number(2);
+    tester.gas_adjuster.keep_updated().await?;
+    let block_numbers = tester.get_block_numbers().await;
+
+    let (to_resend, _) = tester
+        .manager
+        .monitor_inflight_transactions(
+            &mut tester.conn.access_storage().await.unwrap(),
+            block_numbers,
+        )
+        .await?
+        .unwrap();
+    assert_eq!(to_resend.blob_sidecar.as_ref(), Some(&blob_sidecar));
+
+    // The resend interval hasn't passed yet, so the transaction shouldn't be replaced.
+    let resend_result = tester
+        .manager
+        .send_eth_tx(
+            &mut tester.conn.access_storage().await.unwrap(),
+            &to_resend,
+            1,
+            block_numbers.latest,
+        )
+        .await;
+    assert!(resend_result.is_err());
+    assert_eq!(tester.gateway.sent_txs.read().unwrap().len(), 1);
+
+    tester.gateway.advance_block_number(1);
+    tester.gas_adjuster.keep_updated().await?;
+    let block_numbers = tester.get_block_numbers().await;
+    let resent_hash = tester
+        .manager
+        .send_eth_tx(
+            &mut tester.conn.access_storage().await.unwrap(),
+            &to_resend,
+            1,
+            block_numbers.latest,
+        )
+        .await?;
+
+    assert_eq!(tester.gateway.sent_txs.read().unwrap().len(), 2);
+    let resent_tx = tester.gateway.sent_txs.read().unwrap()[&resent_hash];
+    // The current base fee estimate is less than the doubled previous base fee
+    assert_eq!(resent_tx.base_fee.as_usize(), 36);
+    let last_sent_tx = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_last_sent_eth_tx(tx.id)
+        .await?
+        .unwrap();
+    assert_eq!(last_sent_tx.blob_base_fee_per_gas, Some(6));
+    assert_eq!(last_sent_tx.priority_fee_per_gas, 2 * initial_priority_fee);
+
+    Ok(())
+}
+
 // Tests that if transaction was mined, but not enough blocks has been mined since,
 // we won't mark it as confirmed but also won't resend it.
 #[tokio::test]
@@ -502,6 +642,220 @@ async fn failed_eth_tx() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn filling_operator_nonce_gap() -> anyhow::Result<()> {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let mut tester =
+        EthSenderTester::with_base_nonce(connection_pool, vec![100; 100], false, 2).await;
+    let tx = tester
+        .aggregator
+        .save_eth_tx(
+            &mut tester.conn.access_storage().await.unwrap(),
+            &DUMMY_OPERATION,
+            true,
+        )
+        .await?;
+    assert_eq!(tx.nonce.0, 2);
+    let hash = tester
+        .manager
+        .send_eth_tx(
+            &mut tester.conn.access_storage().await.unwrap(),
+            &tx,
+            0,
+            L1BlockNumber(tester.gateway.block_number("").await?.as_u32()),
+        )
+        .await?;
+
+    let to_resend = tester
+        .manager
+        .monitor_inflight_transactions(
+            &mut tester.conn.access_storage().await.unwrap(),
+            tester.get_block_numbers().await,
+        )
+        .await?;
+    let (to_resend, _) = to_resend.expect("eth_tx should be resent after filling the gap");
+    assert_eq!(to_resend.id, tx.id);
+
+    // No-op transactions should be sent for nonces 0 and 1.
+    let mut noop_txs: Vec<_> = tester
+        .gateway
+        .sent_txs
+        .read()
+        .unwrap()
+        .values()
+        .filter(|sent_tx| sent_tx.hash != hash)
+        .copied()
+        .collect();
+    noop_txs.sort_by_key(|tx| tx.nonce);
+    let noop_nonces: Vec<_> = noop_txs.iter().map(|tx| tx.nonce).collect();
+    assert_eq!(noop_nonces, [0, 1]);
+    assert_eq!(
+        tester.nonce_issue(),
+        Some(OperatorNonceIssue::Gap {
+            l1_nonce: 0,
+            first_pending_nonce: 2,
+        })
+    );
+
+    // If the gap persists, no-op transactions should be replaced with increased fees.
+    tester.gateway.block_number.fetch_add(1, Ordering::SeqCst);
+    tester
+        .manager
+        .monitor_inflight_transactions(
+            &mut tester.conn.access_storage().await.unwrap(),
+            tester.get_block_numbers().await,
+        )
+        .await?;
+    let replacement_txs: Vec<_> = tester
+        .gateway
+        .sent_txs
+        .read()
+        .unwrap()
+        .values()
+        .filter(|sent_tx| sent_tx.hash != hash && noop_txs.iter().all(|tx| tx.hash != sent_tx.hash))
+        .copied()
+        .collect();
+    assert_eq!(replacement_txs.len(), 2);
+    for replacement_tx in &replacement_txs {
+        let noop_tx = noop_txs
+            .iter()
+            .find(|tx| tx.nonce == replacement_tx.nonce)
+            .unwrap();
+        let previous_priority_fee = noop_tx.priority_fee.as_u64();
+        assert!(
+            replacement_tx.priority_fee.as_u64()
+                > previous_priority_fee + previous_priority_fee / 5,
+            "{replacement_tx:?}"
+        );
+    }
+
+    for noop_tx in &noop_txs {
+        tester.gateway.execute_tx(noop_tx.hash, true, 1)?;
+    }
+    tester
+        .gateway
+        .execute_tx(hash, true, EthSenderTester::WAIT_CONFIRMATIONS)?;
+
+    let to_resend = tester
+        .manager
+        .monitor_inflight_transactions(
+            &mut tester.conn.access_storage().await.unwrap(),
+            tester.get_block_numbers().await,
+        )
+        .await?;
+    assert!(to_resend.is_none());
+    assert_eq!(tester.nonce_issue(), None);
+    let inflight_txs = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_inflight_txs()
+        .await?;
+    assert!(inflight_txs.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn reassigning_externally_consumed_nonces() -> anyhow::Result<()> {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let mut tester = EthSenderTester::new(connection_pool, vec![100; 100], false).await;
+    let mut txs = vec![];
+    for _ in 0..2 {
+        let tx = tester
+            .aggregator
+            .save_eth_tx(
+                &mut tester.conn.access_storage().await.unwrap(),
+                &DUMMY_OPERATION,
+                true,
+            )
+            .await?;
+        txs.push(tx);
+    }
+    tester
+        .manager
+        .send_eth_tx(
+            &mut tester.conn.access_storage().await.unwrap(),
+            &txs[0],
+            0,
+            L1BlockNumber(tester.gateway.block_number("").await?.as_u32()),
+        )
+        .await?;
+
+    // Emulate an external transaction consuming nonce 0, which is finalized afterwards.
+    let block_number = tester
+        .gateway
+        .block_number
+        .fetch_add(EthSenderTester::WAIT_CONFIRMATIONS, Ordering::SeqCst);
+    tester.gateway.current_nonce.store(1, Ordering::SeqCst);
+    tester
+        .gateway
+        .nonces
+        .write()
+        .unwrap()
+        .insert(block_number, 1);
+
+    // The missing receipt must be confirmed at several finalized blocks; repeated checks
+    // at the same block don't count.
+    for i in 0..ORPHANED_TX_CHECK_COUNT - 1 {
+        for _ in 0..2 {
+            let to_resend = tester
+                .manager
+                .monitor_inflight_transactions(
+                    &mut tester.conn.access_storage().await.unwrap(),
+                    tester.get_block_numbers().await,
+                )
+                .await?;
+            // The next eth_tx has an unused nonce, so it can be sent.
+            let (to_resend, _) = to_resend.expect("next eth_tx should be sent");
+            assert_eq!(to_resend.id, txs[1].id, "{i}");
+            assert_eq!(tester.nonce_issue(), None, "{i}");
+        }
+        let tx = tester
+            .storage()
+            .await
+            .eth_sender_dal()
+            .get_eth_tx(txs[0].id)
+            .await?
+            .unwrap();
+        assert_eq!(tx.nonce.0, 0);
+        tester.gateway.block_number.fetch_add(1, Ordering::SeqCst);
+    }
+
+    let to_resend = tester
+        .manager
+        .monitor_inflight_transactions(
+            &mut tester.conn.access_storage().await.unwrap(),
+            tester.get_block_numbers().await,
+        )
+        .await?;
+    assert!(to_resend.is_none());
+    assert_eq!(
+        tester.nonce_issue(),
+        Some(OperatorNonceIssue::ExternallyConsumed { nonce: 0 })
+    );
+
+    let mut storage = tester.storage().await;
+    for (tx, expected_nonce) in txs.iter().zip([1, 2]) {
+        let tx = storage.eth_sender_dal().get_eth_tx(tx.id).await?.unwrap();
+        assert_eq!(tx.nonce.0, expected_nonce);
+    }
+    drop(storage);
+
+    // The orphaned eth_tx should be resent with the reassigned nonce.
+    let to_resend = tester
+        .manager
+        .monitor_inflight_transactions(
+            &mut tester.conn.access_storage().await.unwrap(),
+            tester.get_block_numbers().await,
+        )
+        .await?;
+    let (to_resend, _) = to_resend.expect("orphaned eth_tx should be resent");
+    assert_eq!(to_resend.id, txs[0].id);
+    assert_eq!(to_resend.nonce.0, 1);
+    assert_eq!(tester.nonce_issue(), None);
+    Ok(())
+}
+
 fn default_l1_batch_metadata() -> L1BatchMetadata {
     L1BatchMetadata {
         root_hash: Default::default(),
@@ -775,6 +1129,43 @@ async fn skipped_l1_batch_in_the_middle() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn data_size_criterion_ignores_blob_pubdata() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let mut storage = connection_pool.access_storage().await.unwrap();
+    let l1_batches: Vec<_> = (1..=2)
+        .map(|number| {
+            let header = L1BatchHeader::new(
+                L1BatchNumber(number),
+                0,
+                Address::zero(),
+                BaseSystemContractsHashes::default(),
+                ProtocolVersionId::latest(),
+            );
+            let mut l1_batch = l1_batch_with_metadata(header);
+            l1_batch.metadata.state_diffs_compressed = vec![1; 50_000];
+            l1_batch
+        })
+        .collect();
+
+    let mut criterion = DataSizeCriterion {
+        op: AggregatedActionType::Commit,
+        data_limit: 60_000,
+        pubdata_sending_mode: PubdataSendingMode::Calldata,
+    };
+    let last_l1_batch = criterion
+        .last_l1_batch_to_publish(&mut storage, &l1_batches, L1BatchNumber(2))
+        .await;
+    assert_eq!(last_l1_batch, Some(L1BatchNumber(1)));
+
+    // With pubdata posted in blobs, both L1 batches fit into the limit.
+    criterion.pubdata_sending_mode = PubdataSendingMode::Blobs;
+    let last_l1_batch = criterion
+        .last_l1_batch_to_publish(&mut storage, &l1_batches, L1BatchNumber(2))
+        .await;
+    assert_eq!(last_l1_batch, None);
+}
+
 #[tokio::test]
 async fn test_parse_multicall_data() {
     let connection_pool = ConnectionPool::test_pool().await;
@@ -940,6 +1331,7 @@ async fn commit_l1_batch(
     let operation = AggregatedOperation::Commit(L1BatchCommitOperation {
         last_committed_l1_batch: l1_batch_with_metadata(last_committed_l1_batch),
         l1_batches: vec![l1_batch_with_metadata(l1_batch)],
+        pubdata_blobs: None,
     });
     send_operation(tester, operation, confirm).await
 }
@@ -107,6 +107,7 @@ pub async fn ensure_genesis_state(
         vec![],
         H256::zero(),
         H256::zero(),
+        vec![],
         protocol_version.is_pre_boojum(),
     );
 
@@ -7,6 +7,7 @@ pub mod fri_scheduler_circuit_queuer;
 pub mod fri_witness_generator_jobs_retry_manager;
 pub mod fri_witness_generator_queue_monitor;
 pub mod gpu_prover_queue_monitor;
+pub mod object_store_garbage_collector;
 pub mod prover_job_retry_manager;
 pub mod prover_queue_monitor;
 pub mod waiting_to_queued_fri_witness_job_mover;
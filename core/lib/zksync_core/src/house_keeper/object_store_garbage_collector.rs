@@ -0,0 +1,426 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use zksync_dal::ConnectionPool;
+use zksync_object_store::{Bucket, ObjectStore, ObjectStoreError};
+use zksync_prover_utils::periodic_job::PeriodicJob;
+use zksync_types::L1BatchNumber;
+
+/// Buckets with prover artifacts that are no longer needed once the corresponding L1 batch is proven on L1.
+const PROVER_BUCKETS: [Bucket; 6] = [
+    Bucket::WitnessInput,
+    Bucket::ProverJobsFri,
+    Bucket::LeafAggregationWitnessJobsFri,
+    Bucket::NodeAggregationWitnessJobsFri,
+    Bucket::SchedulerWitnessJobsFri,
+    Bucket::ProofsFri,
+];
+
+/// Returns key prefixes preceding the L1 batch number in prover artifacts stored in the `bucket`.
+/// Should be kept in sync with `StoredObject` implementations.
+fn l1_batch_key_prefixes(bucket: Bucket) -> &'static [&'static str] {
+    match bucket {
+        Bucket::WitnessInput => &[
+            "witness_block_state_for_l1_batch_",
+            "merkel_tree_paths_",
+            "run_with_fixed_params_input_",
+        ],
+        Bucket::ProverJobsFri => &[""],
+        Bucket::LeafAggregationWitnessJobsFri => &["closed_form_inputs_"],
+        Bucket::NodeAggregationWitnessJobsFri => &["aggregations_"],
+        Bucket::SchedulerWitnessJobsFri => &["scheduler_witness_", "aux_output_witness_"],
+        Bucket::ProofsFri => &["l1_batch_proof_"],
+        _ => &[],
+    }
+}
+
+/// Returns prefixes of keys managed by the garbage collector in the `bucket`. Only keys with these prefixes
+/// are listed, so that objects in an unknown format (which are never removed) don't need to be listed
+/// on each run.
+fn listed_key_prefixes(bucket: Bucket) -> &'static [&'static str] {
+    match bucket {
+        // Keys start with the L1 batch number.
+        Bucket::ProverJobsFri => &["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"],
+        Bucket::ProofsFri => &["l1_batch_proof_", "proof_"],
+        _ => l1_batch_key_prefixes(bucket),
+    }
+}
+
+/// Extracts the L1 batch number from the key of a prover artifact. Returns `None` for keys in an unknown format
+/// and for keys not containing an L1 batch number (e.g., proofs keyed by the prover job ID).
+fn l1_batch_number_from_key(bucket: Bucket, key: &str) -> Option<L1BatchNumber> {
+    l1_batch_key_prefixes(bucket).iter().find_map(|prefix| {
+        let rest = key.strip_prefix(prefix)?;
+        let digits_len = rest.find(|ch: char| !ch.is_ascii_digit())?;
+        let (digits, tail) = rest.split_at(digits_len);
+        if !tail.starts_with(['_', '.']) {
+            return None;
+        }
+        digits.parse().ok().map(L1BatchNumber)
+    })
+}
+
+/// Extracts the prover job ID from the key of a FRI proof.
+fn prover_job_id_from_key(key: &str) -> Option<u32> {
+    key.strip_prefix("proof_")?
+        .strip_suffix(".bin")?
+        .parse()
+        .ok()
+}
+
+/// Garbage collection settings for storage snapshots.
+#[derive(Debug)]
+struct SnapshotsGc {
+    object_store: Box<dyn ObjectStore>,
+    retention_period: Duration,
+}
+
+/// Removes objects that are no longer needed from object stores:
+///
+/// - Prover artifacts for L1 batches proven on L1 (from the prover object store)
+/// - Storage snapshots created more than the retention period ago (from the snapshots object store; only if
+///   configured via [`Self::with_snapshots_gc()`]), together with their metadata in Postgres. The latest snapshot
+///   is never removed.
+#[derive(Debug)]
+pub struct ObjectStoreGarbageCollector {
+    pool: ConnectionPool,
+    prover_pool: ConnectionPool,
+    prover_object_store: Box<dyn ObjectStore>,
+    snapshots_gc: Option<SnapshotsGc>,
+    gc_interval_ms: u64,
+}
+
+impl ObjectStoreGarbageCollector {
+    pub fn new(
+        gc_interval_ms: u64,
+        pool: ConnectionPool,
+        prover_pool: ConnectionPool,
+        prover_object_store: Box<dyn ObjectStore>,
+    ) -> Self {
+        Self {
+            pool,
+            prover_pool,
+            prover_object_store,
+            snapshots_gc: None,
+            gc_interval_ms,
+        }
+    }
+
+    /// Enables removing snapshots older than `retention_period` from the specified store.
+    pub fn with_snapshots_gc(
+        mut self,
+        snapshots_object_store: Box<dyn ObjectStore>,
+        retention_period: Duration,
+    ) -> Self {
+        self.snapshots_gc = Some(SnapshotsGc {
+            object_store: snapshots_object_store,
+            retention_period,
+        });
+        self
+    }
+
+    async fn remove_objects(
+        object_store: &dyn ObjectStore,
+        bucket: Bucket,
+        keys: &[String],
+    ) -> anyhow::Result<()> {
+        for key in keys {
+            match object_store.remove_raw(bucket, key).await {
+                Ok(()) | Err(ObjectStoreError::KeyNotFound(_)) => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        if !keys.is_empty() {
+            tracing::info!("Removed {} objects from bucket {bucket}", keys.len());
+        }
+        metrics::counter!(
+            "server.object_store_gc.removed_objects",
+            keys.len() as u64,
+            "bucket" => bucket.to_string()
+        );
+        Ok(())
+    }
+
+    async fn remove_proven_artifacts(&self) -> anyhow::Result<()> {
+        let mut storage = self.pool.access_storage_tagged("house_keeper").await?;
+        let last_proven_l1_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_proven_on_eth()
+            .await?;
+        drop(storage);
+        let Some(last_proven_l1_batch) = last_proven_l1_batch else {
+            return Ok(()); // No L1 batches are proven yet
+        };
+
+        for bucket in PROVER_BUCKETS {
+            let mut keys = vec![];
+            for prefix in listed_key_prefixes(bucket) {
+                keys.extend(self.prover_object_store.list_raw(bucket, prefix).await?);
+            }
+            let mut keys_to_remove = vec![];
+            let mut proofs = vec![];
+            for key in keys {
+                if let Some(l1_batch_number) = l1_batch_number_from_key(bucket, &key) {
+                    if l1_batch_number <= last_proven_l1_batch {
+                        keys_to_remove.push(key);
+                    }
+                } else if bucket == Bucket::ProofsFri {
+                    if let Some(job_id) = prover_job_id_from_key(&key) {
+                        proofs.push((job_id, key));
+                    }
+                }
+            }
+
+            if !proofs.is_empty() {
+                let job_ids: Vec<_> = proofs.iter().map(|(job_id, _)| *job_id).collect();
+                let mut prover_storage = self
+                    .prover_pool
+                    .access_storage_tagged("house_keeper")
+                    .await?;
+                let l1_batch_numbers = prover_storage
+                    .fri_prover_jobs_dal()
+                    .get_l1_batch_numbers_for_jobs(&job_ids)
+                    .await?;
+                drop(prover_storage);
+
+                keys_to_remove.extend(proofs.into_iter().filter_map(|(job_id, key)| {
+                    let l1_batch_number = *l1_batch_numbers.get(&job_id)?;
+                    (l1_batch_number <= last_proven_l1_batch).then_some(key)
+                }));
+            }
+            Self::remove_objects(self.prover_object_store.as_ref(), bucket, &keys_to_remove)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn remove_expired_snapshots(&self, snapshots_gc: &SnapshotsGc) -> anyhow::Result<()> {
+        let object_store = snapshots_gc.object_store.as_ref();
+        let mut storage = self.pool.access_storage_tagged("house_keeper").await?;
+        let expired_snapshots = storage
+            .snapshots_dal()
+            .get_expired_snapshots(snapshots_gc.retention_period)
+            .await?;
+
+        for l1_batch_number in expired_snapshots {
+            // Files are removed before metadata, so that a failure in between is retried on the next run.
+            let prefix = format!("snapshot_l1_batch_{l1_batch_number}_");
+            let keys = object_store
+                .list_raw(Bucket::StorageSnapshot, &prefix)
+                .await?;
+            Self::remove_objects(object_store, Bucket::StorageSnapshot, &keys).await?;
+            storage
+                .snapshots_dal()
+                .delete_snapshot(l1_batch_number)
+                .await?;
+            tracing::info!("Removed expired snapshot for L1 batch #{l1_batch_number}");
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PeriodicJob for ObjectStoreGarbageCollector {
+    const SERVICE_NAME: &'static str = "ObjectStoreGarbageCollector";
+
+    async fn run_routine_task(&mut self) -> anyhow::Result<()> {
+        self.remove_proven_artifacts().await?;
+        if let Some(snapshots_gc) = &self.snapshots_gc {
+            self.remove_expired_snapshots(snapshots_gc).await?;
+        }
+        Ok(())
+    }
+
+    fn polling_interval_ms(&self) -> u64 {
+        self.gc_interval_ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_object_store::ObjectStoreFactory;
+    use zksync_types::{
+        aggregated_operations::AggregatedActionType, Address, L2ChainId, H256, U256,
+    };
+
+    use super::*;
+    use crate::genesis::{ensure_genesis_state, GenesisParams};
+
+    /// Marks the genesis L1 batch as proven on L1.
+    async fn prepare_proven_genesis(pool: &ConnectionPool) {
+        let mut storage = pool.access_storage().await.unwrap();
+        ensure_genesis_state(&mut storage, L2ChainId::from(270), &GenesisParams::mock())
+            .await
+            .unwrap();
+
+        let eth_tx = storage
+            .eth_sender_dal()
+            .save_eth_tx(
+                0,
+                vec![],
+                AggregatedActionType::PublishProofOnchain,
+                Address::zero(),
+                0,
+                None,
+            )
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .set_eth_tx_id(
+                L1BatchNumber(0)..=L1BatchNumber(0),
+                eth_tx.id,
+                AggregatedActionType::PublishProofOnchain,
+            )
+            .await
+            .unwrap();
+        let tx_hash = H256::repeat_byte(1);
+        storage
+            .eth_sender_dal()
+            .insert_tx_history(eth_tx.id, 0, 0, None, tx_hash, vec![])
+            .await
+            .unwrap();
+        storage
+            .eth_sender_dal()
+            .confirm_tx(tx_hash, U256::zero())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn objects_are_removed_from_matching_stores() {
+        let pool = ConnectionPool::test_pool().await;
+        prepare_proven_genesis(&pool).await;
+        let mut storage = pool.access_storage().await.unwrap();
+        for l1_batch_number in [0, 1].map(L1BatchNumber) {
+            storage
+                .snapshots_dal()
+                .add_snapshot(l1_batch_number, &[], "")
+                .await
+                .unwrap();
+        }
+        drop(storage);
+
+        // Mock factories return the same store on each call.
+        let prover_store_factory = ObjectStoreFactory::mock();
+        let prover_store = prover_store_factory.create_store().await.unwrap();
+        let snapshots_store_factory = ObjectStoreFactory::mock();
+        let snapshots_store = snapshots_store_factory.create_store().await.unwrap();
+        let prover_keys = [
+            "merkel_tree_paths_0.bin",
+            "merkel_tree_paths_1.bin",
+            "unknown_0.bin",
+        ];
+        let snapshot_keys = [
+            "snapshot_l1_batch_0_factory_deps.proto.gzip",
+            "snapshot_l1_batch_1_factory_deps.proto.gzip",
+        ];
+        // Put the same objects into both stores to check that each store is only pruned of its own objects.
+        for store in [&prover_store, &snapshots_store] {
+            for key in prover_keys {
+                store
+                    .put_raw(Bucket::WitnessInput, key, vec![1])
+                    .await
+                    .unwrap();
+            }
+            for key in snapshot_keys {
+                store
+                    .put_raw(Bucket::StorageSnapshot, key, vec![1])
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let mut gc = ObjectStoreGarbageCollector::new(
+            1_000,
+            pool.clone(),
+            pool.clone(),
+            prover_store_factory.create_store().await.unwrap(),
+        )
+        .with_snapshots_gc(
+            snapshots_store_factory.create_store().await.unwrap(),
+            Duration::ZERO,
+        );
+        gc.run_routine_task().await.unwrap();
+
+        // Artifacts for the proven L1 batch #0 are removed only from the prover store.
+        let prover_objects = prover_store.list_raw(Bucket::WitnessInput, "").await;
+        assert_eq!(
+            prover_objects.unwrap(),
+            ["merkel_tree_paths_1.bin", "unknown_0.bin"]
+        );
+        let prover_objects = snapshots_store.list_raw(Bucket::WitnessInput, "").await;
+        assert_eq!(prover_objects.unwrap(), prover_keys);
+
+        // The expired snapshot for L1 batch #0 is removed only from the snapshots store;
+        // the latest snapshot is retained.
+        let snapshot_objects = snapshots_store.list_raw(Bucket::StorageSnapshot, "").await;
+        assert_eq!(snapshot_objects.unwrap(), [snapshot_keys[1]]);
+        let snapshot_objects = prover_store.list_raw(Bucket::StorageSnapshot, "").await;
+        assert_eq!(snapshot_objects.unwrap(), snapshot_keys);
+
+        let mut storage = pool.access_storage().await.unwrap();
+        let snapshots = storage.snapshots_dal().get_all_snapshots().await.unwrap();
+        assert_eq!(snapshots.snapshots_l1_batch_numbers, [L1BatchNumber(1)]);
+    }
+
+    #[test]
+    fn parsing_l1_batch_numbers_from_keys() {
+        let valid_keys = [
+            (
+                Bucket::WitnessInput,
+                "witness_block_state_for_l1_batch_12.bin",
+            ),
+            (Bucket::WitnessInput, "merkel_tree_paths_12.bin"),
+            (Bucket::ProverJobsFri, "12_3_1_BasicCircuits_0.bin"),
+            (
+                Bucket::LeafAggregationWitnessJobsFri,
+                "closed_form_inputs_12_4.bin",
+            ),
+            (
+                Bucket::NodeAggregationWitnessJobsFri,
+                "aggregations_12_4_1.bin",
+            ),
+            (Bucket::SchedulerWitnessJobsFri, "scheduler_witness_12.bin"),
+            (Bucket::SchedulerWitnessJobsFri, "aux_output_witness_12.bin"),
+            (Bucket::ProofsFri, "l1_batch_proof_12.bin"),
+        ];
+        for (bucket, key) in valid_keys {
+            assert_eq!(
+                l1_batch_number_from_key(bucket, key),
+                Some(L1BatchNumber(12)),
+                "{bucket}/{key}"
+            );
+            assert!(
+                listed_key_prefixes(bucket)
+                    .iter()
+                    .any(|prefix| key.starts_with(prefix)),
+                "{bucket}/{key}"
+            );
+        }
+
+        let invalid_keys = [
+            (Bucket::ProofsFri, "proof_12.bin"),
+            (Bucket::ProverJobsFri, "other_12.bin"),
+            (Bucket::ProverJobsFri, "12"),
+            (Bucket::SchedulerWitnessJobsFri, "scheduler_witness_12a.bin"),
+            (
+                Bucket::StorageSnapshot,
+                "snapshot_l1_batch_12_factory_deps.proto.gzip",
+            ),
+        ];
+        for (bucket, key) in invalid_keys {
+            assert_eq!(
+                l1_batch_number_from_key(bucket, key),
+                None,
+                "{bucket}/{key}"
+            );
+        }
+
+        assert_eq!(prover_job_id_from_key("proof_123.bin"), Some(123));
+        assert!(listed_key_prefixes(Bucket::ProofsFri)
+            .iter()
+            .any(|prefix| "proof_123.bin".starts_with(prefix)));
+        assert_eq!(prover_job_id_from_key("l1_batch_proof_12.bin"), None);
+    }
+}
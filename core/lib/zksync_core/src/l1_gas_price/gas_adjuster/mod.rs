@@ -18,10 +18,12 @@ mod metrics;
 mod tests;
 
 /// This component keeps track of the median base_fee from the last `max_base_fee_samples` blocks.
-/// It is used to adjust the base_fee of transactions sent to L1.
+/// It is used to adjust the base_fee of transactions sent to L1. Similarly, it tracks the median blob base fee
+/// (EIP-4844) used for transactions carrying blobs.
 #[derive(Debug)]
 pub struct GasAdjuster<E> {
     pub(super) statistics: GasStatistics,
+    pub(super) blob_base_fee_statistics: GasStatistics,
     pub(super) config: GasAdjusterConfig,
     eth_client: E,
 }
@@ -39,8 +41,16 @@ impl<E: EthInterface> GasAdjuster<E> {
         let history = eth_client
             .base_fee_history(current_block, config.max_base_fee_samples, "gas_adjuster")
             .await?;
+        let blob_history = eth_client
+            .blob_base_fee_history(current_block, config.max_base_fee_samples, "gas_adjuster")
+            .await?;
         Ok(Self {
             statistics: GasStatistics::new(config.max_base_fee_samples, current_block, &history),
+            blob_base_fee_statistics: GasStatistics::new(
+                config.max_base_fee_samples,
+                current_block,
+                &blob_history,
+            ),
             eth_client,
             config,
         })
@@ -76,6 +86,19 @@ impl<E: EthInterface> GasAdjuster<E> {
                 .current_base_fee_per_gas
                 .set(*history.last().unwrap());
             self.statistics.add_samples(&history);
+
+            let blob_history = self
+                .eth_client
+                .blob_base_fee_history(
+                    current_block,
+                    current_block - last_processed_block,
+                    "gas_adjuster",
+                )
+                .await?;
+            if let Some(&current_blob_base_fee) = blob_history.last() {
+                METRICS.current_blob_base_fee.set(current_blob_base_fee);
+            }
+            self.blob_base_fee_statistics.add_samples(&blob_history);
         }
         Ok(())
     }
@@ -149,6 +172,18 @@ impl<E: EthInterface> L1TxParamsProvider for GasAdjuster<E> {
         new_fee as u64
     }
 
+    // Blob base fee is adjusted using the same formula as the base fee.
+    fn get_blob_base_fee(&self, time_in_mempool: u32) -> u64 {
+        let a = self.config.pricing_formula_parameter_a;
+        let b = self.config.pricing_formula_parameter_b;
+        let scale_factor = a * b.powf(time_in_mempool as f64);
+        let median = self.blob_base_fee_statistics.median();
+        METRICS.median_blob_base_fee.set(median);
+        // Blob base fee cannot be lower than 1 wei.
+        let new_fee = median.max(1) as f64 * scale_factor;
+        new_fee as u64
+    }
+
     fn get_next_block_minimal_base_fee(&self) -> u64 {
         let last_block_base_fee = self.statistics.last_added_value();
 
@@ -209,6 +244,10 @@ impl GasStatisticsInner {
 
         let extra = self.samples.len().saturating_sub(self.max_samples);
         self.samples.drain(..extra);
+        if self.samples.is_empty() {
+            // May happen for blob base fees if the L1 node doesn't support EIP-4844.
+            return;
+        }
 
         let mut samples: Vec<_> = self.samples.iter().cloned().collect();
         let (_, &mut median, _) = samples.select_nth_unstable(self.samples.len() / 2);
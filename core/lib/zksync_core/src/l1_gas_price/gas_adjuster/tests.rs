@@ -4,6 +4,7 @@ use zksync_config::GasAdjusterConfig;
 use zksync_eth_client::clients::mock::MockEthereum;
 
 use super::{GasAdjuster, GasStatisticsInner};
+use crate::l1_gas_price::L1TxParamsProvider;
 
 /// Check that we compute the median correctly
 #[test]
@@ -58,3 +59,45 @@ async fn kept_updated() {
     assert_eq!(adjuster.statistics.0.read().unwrap().samples.len(), 5);
     assert_eq!(adjuster.statistics.0.read().unwrap().median(), 7);
 }
+
+/// Check that we track blob base fees and handle L1 nodes not supporting EIP-4844
+#[tokio::test]
+async fn blob_base_fee_kept_updated() {
+    let config = GasAdjusterConfig {
+        default_priority_fee_per_gas: 5,
+        max_base_fee_samples: 5,
+        pricing_formula_parameter_a: 1.0,
+        pricing_formula_parameter_b: 1.0,
+        internal_l1_pricing_multiplier: 0.8,
+        internal_enforced_l1_gas_price: None,
+        poll_period: 5,
+        max_l1_gas_price: None,
+    };
+    let eth_client = Arc::new(
+        MockEthereum::default()
+            .with_fee_history(vec![0, 4, 6, 8, 7, 5, 5, 8, 10, 9])
+            .with_blob_fee_history(vec![0, 1, 1, 3, 2, 2, 4, 7, 8, 9]),
+    );
+    eth_client.advance_block_number(5);
+
+    let adjuster = GasAdjuster::new(Arc::clone(&eth_client), config)
+        .await
+        .unwrap();
+    assert_eq!(adjuster.blob_base_fee_statistics.median(), 1);
+
+    eth_client.advance_block_number(3);
+    adjuster.keep_updated().await.unwrap();
+    assert_eq!(adjuster.blob_base_fee_statistics.median(), 3);
+    assert_eq!(adjuster.get_blob_base_fee(0), 3);
+
+    let eth_client =
+        Arc::new(MockEthereum::default().with_fee_history(vec![0, 4, 6, 8, 7, 5, 5, 8, 10, 9]));
+    eth_client.advance_block_number(5);
+    let adjuster = GasAdjuster::new(Arc::clone(&eth_client), config)
+        .await
+        .unwrap();
+    eth_client.advance_block_number(3);
+    adjuster.keep_updated().await.unwrap();
+    assert_eq!(adjuster.blob_base_fee_statistics.median(), 0);
+    assert_eq!(adjuster.get_blob_base_fee(0), 1);
+}
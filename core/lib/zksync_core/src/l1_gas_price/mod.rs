@@ -24,6 +24,9 @@ pub trait L1TxParamsProvider: L1GasPriceProvider {
     /// Returns the recommended `max_fee_per_gas` value (EIP1559).
     fn get_base_fee(&self, time_in_mempool: u32) -> u64;
 
+    /// Returns the recommended `max_fee_per_blob_gas` value (EIP4844).
+    fn get_blob_base_fee(&self, time_in_mempool: u32) -> u64;
+
     /// Returns the recommended `max_priority_fee_per_gas` value (EIP1559).
     fn get_priority_fee(&self) -> u64;
 
@@ -8,8 +8,10 @@ use prometheus_exporter::PrometheusExporterConfig;
 use temp_config_store::TempConfigStore;
 use tokio::{sync::watch, task::JoinHandle};
 use zksync_circuit_breaker::{
-    l1_txs::FailedL1TransactionChecker, replication_lag::ReplicationLagChecker, CircuitBreaker,
-    CircuitBreakerChecker, CircuitBreakerError,
+    l1_txs::FailedL1TransactionChecker,
+    operator_nonce::{DetectedNonceIssue, OperatorNonceChecker},
+    replication_lag::ReplicationLagChecker,
+    CircuitBreaker, CircuitBreakerChecker, CircuitBreakerError,
 };
 use zksync_config::{
     configs::{
@@ -20,11 +22,14 @@ use zksync_config::{
         },
         contracts::ProverAtGenesis,
         database::MerkleTreeMode,
+        eth_sender::{OperatorSignerMode, PubdataSendingMode},
     },
     ApiConfig, ContractsConfig, DBConfig, ETHSenderConfig, PostgresConfig,
 };
 use zksync_contracts::{governance_contract, BaseSystemContracts};
-use zksync_dal::{healthcheck::ConnectionPoolHealthCheck, ConnectionPool};
+use zksync_dal::{
+    healthcheck::ConnectionPoolHealthCheck, transactions_dal::L2TxInsertionLimits, ConnectionPool,
+};
 use zksync_eth_client::{
     clients::http::{PKSigningClient, QueryClient},
     BoundEthInterface, EthInterface,
@@ -63,6 +68,7 @@ use crate::{
         fri_witness_generator_jobs_retry_manager::FriWitnessGeneratorJobRetryManager,
         fri_witness_generator_queue_monitor::FriWitnessGeneratorStatsReporter,
         gpu_prover_queue_monitor::GpuProverQueueMonitor,
+        object_store_garbage_collector::ObjectStoreGarbageCollector,
         prover_job_retry_manager::ProverJobRetryManager, prover_queue_monitor::ProverStatsReporter,
         waiting_to_queued_fri_witness_job_mover::WaitingToQueuedFriWitnessJobMover,
     },
@@ -76,6 +82,7 @@ use crate::{
 
 pub mod api_server;
 pub mod basic_witness_input_producer;
+pub mod batch_replayer;
 pub mod block_reverter;
 mod consensus;
 pub mod consistency_checker;
@@ -108,13 +115,19 @@ pub async fn genesis_init(
         .await
         .context("failed to build connection_pool")?;
     let mut storage = pool.access_storage().await.context("access_storage()")?;
-    let operator_address = PackedEthSignature::address_from_private_key(
-        &eth_sender
+    let operator_address = match eth_sender.sender.signer_mode {
+        OperatorSignerMode::PrivateKey => PackedEthSignature::address_from_private_key(
+            &eth_sender
+                .sender
+                .private_key()
+                .context("Private key is required for genesis init")?,
+        )
+        .context("Failed to restore operator address from private key")?,
+        OperatorSignerMode::Remote => eth_sender
             .sender
-            .private_key()
-            .context("Private key is required for genesis init")?,
-    )
-    .context("Failed to restore operator address from private key")?;
+            .remote_signer_operator_address
+            .context("Operator address is required for genesis init with remote signer")?,
+    };
 
     // Select the first prover to be used during genesis.
     // Later we can change provers using the system upgrades, but for genesis
@@ -323,10 +336,16 @@ pub async fn initialize_components(
         .clone()
         .context("circuit_breaker_config")?;
 
+    let (nonce_issues_sender, nonce_issues_receiver) = watch::channel(None);
     let circuit_breaker_checker = CircuitBreakerChecker::new(
-        circuit_breakers_for_components(&components, &postgres_config, &circuit_breaker_config)
-            .await
-            .context("circuit_breakers_for_components")?,
+        circuit_breakers_for_components(
+            &components,
+            &postgres_config,
+            &circuit_breaker_config,
+            nonce_issues_receiver,
+        )
+        .await
+        .context("circuit_breakers_for_components")?,
         &circuit_breaker_config,
     );
     circuit_breaker_checker.check().await.unwrap_or_else(|err| {
@@ -374,11 +393,18 @@ pub async fn initialize_components(
             .clone()
             .context("state_keeper_config")?;
         let network_config = configs.network_config.clone().context("network_config")?;
-        let tx_sender_config = TxSenderConfig::new(
+        let mempool_config = configs.mempool_config.clone().context("mempool_config")?;
+        let mut tx_sender_config = TxSenderConfig::new(
             &state_keeper_config,
             &api_config.web3_json_rpc,
             network_config.zksync_network_id,
         );
+        tx_sender_config.mempool_limits = L2TxInsertionLimits {
+            min_replacement_fee_bump_percent: mempool_config
+                .min_replacement_fee_bump_percent
+                .unwrap_or(0),
+            max_pending_txs_per_account: mempool_config.max_pending_txs_per_account,
+        };
         let internal_api_config = InternalApiConfig::new(
             &network_config,
             &api_config.web3_json_rpc,
@@ -507,7 +533,7 @@ pub async fn initialize_components(
             &db_config,
             &configs.mempool_config.clone().context("mempool_config")?,
             bounded_gas_adjuster,
-            store_factory.create_store().await,
+            store_factory.create_store().await?,
             stop_receiver.clone(),
         )
         .await
@@ -567,7 +593,7 @@ pub async fn initialize_components(
             eth_sender.sender.clone(),
             Aggregator::new(
                 eth_sender.sender.clone(),
-                store_factory.create_store().await,
+                store_factory.create_store().await?,
             ),
             contracts_config.validator_timelock_addr,
             contracts_config.l1_multicall3_addr,
@@ -604,6 +630,7 @@ pub async fn initialize_components(
                 .await
                 .context("gas_adjuster.get_or_init()")?,
             eth_client,
+            nonce_issues_sender,
         );
         task_futures.extend([tokio::spawn(
             eth_tx_manager_actor.run(eth_manager_pool, stop_receiver.clone()),
@@ -673,7 +700,7 @@ pub async fn initialize_components(
                 .contracts_config
                 .clone()
                 .context("contracts_config")?,
-            store_factory.create_store().await,
+            store_factory.create_store().await?,
             connection_pool.clone(),
             stop_receiver.clone(),
         )));
@@ -723,7 +750,7 @@ async fn add_state_keeper_to_task_futures<E: L1GasPriceProvider + Send + Sync +
         .transactions_dal()
         .next_priority_id()
         .await;
-    let mempool = MempoolGuard::new(next_priority_id, mempool_config.capacity);
+    let mempool = MempoolGuard::from_config(next_priority_id, mempool_config, fair_l2_gas_price);
     mempool.register_metrics();
 
     let miniblock_sealer_pool = pool_builder
@@ -794,6 +821,12 @@ async fn add_trees_to_task_futures(
     let api_config = components
         .contains(&Component::TreeApi)
         .then_some(&api_config);
+    // L1 batch commitments must include blob versioned hashes if pubdata is posted in blobs.
+    let kzg_trusted_setup_path = configs
+        .eth_sender_config
+        .as_ref()
+        .filter(|config| config.sender.pubdata_sending_mode == PubdataSendingMode::Blobs)
+        .and_then(|config| config.sender.kzg_trusted_setup_path.clone());
 
     let has_tree_component = components.contains(&Component::Tree);
     let has_lightweight_component = components.contains(&Component::TreeLightweight);
@@ -827,6 +860,7 @@ async fn add_trees_to_task_futures(
         api_config,
         &operation_config,
         mode,
+        kzg_trusted_setup_path.as_deref(),
         stop_receiver,
     )
     .await
@@ -842,6 +876,7 @@ async fn run_tree(
     api_config: Option<&MerkleTreeApiConfig>,
     operation_manager: &OperationsManagerConfig,
     mode: MetadataCalculatorModeConfig<'_>,
+    kzg_trusted_setup_path: Option<&str>,
     stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<()> {
     let started_at = Instant::now();
@@ -852,9 +887,13 @@ async fn run_tree(
     };
     tracing::info!("Initializing Merkle tree in {mode_str} mode");
 
-    let config =
-        MetadataCalculatorConfig::for_main_node(&db_config.merkle_tree, operation_manager, mode);
-    let metadata_calculator = MetadataCalculator::new(&config).await;
+    let config = MetadataCalculatorConfig::for_main_node(
+        &db_config.merkle_tree,
+        operation_manager,
+        mode,
+        kzg_trusted_setup_path,
+    );
+    let metadata_calculator = MetadataCalculator::new(&config).await?;
     if let Some(api_config) = api_config {
         let address = (Ipv4Addr::UNSPECIFIED, api_config.port).into();
         let tree_reader = metadata_calculator.tree_reader();
@@ -1035,6 +1074,39 @@ async fn add_house_keeper_to_task_futures(
         prover_connection_pool.clone(),
     );
     task_futures.push(tokio::spawn(fri_proof_compressor_retry_manager.run()));
+
+    if let Some(gc_interval_ms) = house_keeper_config.object_store_gc_interval_ms {
+        let prover_object_store_config = configs
+            .prover_object_store_config
+            .clone()
+            .context("prover_object_store_config")?;
+        let prover_object_store = ObjectStoreFactory::new(prover_object_store_config)
+            .create_store()
+            .await?;
+        // Removing expired snapshots modifies the main DB, so we cannot use the replica pool.
+        let gc_pool = ConnectionPool::singleton(postgres_config.master_url()?)
+            .build()
+            .await
+            .context("failed to build gc_pool")?;
+        let mut object_store_gc = ObjectStoreGarbageCollector::new(
+            gc_interval_ms,
+            gc_pool,
+            prover_connection_pool.clone(),
+            prover_object_store,
+        );
+        if let Some(retention_period) = house_keeper_config.snapshots_retention_period() {
+            let snapshots_object_store_config = configs
+                .snapshots_object_store_config
+                .clone()
+                .context("snapshots_object_store_config")?;
+            let snapshots_object_store = ObjectStoreFactory::new(snapshots_object_store_config)
+                .create_store()
+                .await?;
+            object_store_gc =
+                object_store_gc.with_snapshots_gc(snapshots_object_store, retention_period);
+        }
+        task_futures.push(tokio::spawn(object_store_gc.run()));
+    }
     Ok(())
 }
 
@@ -1203,6 +1275,7 @@ async fn circuit_breakers_for_components(
     components: &[Component],
     postgres_config: &PostgresConfig,
     circuit_breaker_config: &CircuitBreakerConfig,
+    nonce_issues: watch::Receiver<Option<DetectedNonceIssue>>,
 ) -> anyhow::Result<Vec<Box<dyn CircuitBreaker>>> {
     let mut circuit_breakers: Vec<Box<dyn CircuitBreaker>> = Vec::new();
 
@@ -1219,6 +1292,15 @@ async fn circuit_breakers_for_components(
         circuit_breakers.push(Box::new(FailedL1TransactionChecker { pool }));
     }
 
+    if components.contains(&Component::EthTxManager) {
+        if let Some(limit) = circuit_breaker_config.operator_nonce_issue_limit() {
+            circuit_breakers.push(Box::new(OperatorNonceChecker {
+                issues: nonce_issues,
+                limit,
+            }));
+        }
+    }
+
     if components.iter().any(|c| {
         matches!(
             c,
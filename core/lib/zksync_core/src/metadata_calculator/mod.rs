@@ -3,9 +3,11 @@
 
 use std::{
     future::{self, Future},
+    path::Path,
     time::Duration,
 };
 
+use anyhow::Context as _;
 use tokio::sync::watch;
 use zksync_config::configs::{
     chain::OperationsManagerConfig,
@@ -13,6 +15,7 @@ use zksync_config::configs::{
 };
 use zksync_dal::{ConnectionPool, StorageProcessor};
 use zksync_health_check::{HealthUpdater, ReactiveHealthCheck};
+use zksync_kzg::KzgSettings;
 use zksync_merkle_tree::domain::TreeMetadata;
 use zksync_object_store::{ObjectStore, ObjectStoreFactory};
 use zksync_types::{
@@ -80,6 +83,9 @@ pub struct MetadataCalculatorConfig<'a> {
     pub memtable_capacity: usize,
     /// Timeout to wait for the Merkle tree database to run compaction on stalled writes.
     pub stalled_writes_timeout: Duration,
+    /// Path to the KZG trusted setup. Must be set iff pubdata is posted in EIP-4844 blobs; in this case,
+    /// L1 batch commitments include versioned hashes of the blobs.
+    pub kzg_trusted_setup_path: Option<&'a str>,
 }
 
 impl<'a> MetadataCalculatorConfig<'a> {
@@ -87,6 +93,7 @@ impl<'a> MetadataCalculatorConfig<'a> {
         merkle_tree_config: &'a MerkleTreeConfig,
         operation_config: &'a OperationsManagerConfig,
         mode: MetadataCalculatorModeConfig<'a>,
+        kzg_trusted_setup_path: Option<&'a str>,
     ) -> Self {
         Self {
             db_path: &merkle_tree_config.path,
@@ -97,6 +104,7 @@ impl<'a> MetadataCalculatorConfig<'a> {
             block_cache_capacity: merkle_tree_config.block_cache_size(),
             memtable_capacity: merkle_tree_config.memtable_capacity(),
             stalled_writes_timeout: merkle_tree_config.stalled_writes_timeout(),
+            kzg_trusted_setup_path,
         }
     }
 }
@@ -106,6 +114,7 @@ pub struct MetadataCalculator {
     tree: GenericAsyncTree,
     tree_reader: watch::Sender<Option<AsyncTreeReader>>,
     object_store: Option<Box<dyn ObjectStore>>,
+    kzg_settings: Option<KzgSettings>,
     delayer: Delayer,
     health_updater: HealthUpdater,
     max_l1_batches_per_iter: usize,
@@ -113,7 +122,7 @@ pub struct MetadataCalculator {
 
 impl MetadataCalculator {
     /// Creates a calculator with the specified `config`.
-    pub async fn new(config: &MetadataCalculatorConfig<'_>) -> Self {
+    pub async fn new(config: &MetadataCalculatorConfig<'_>) -> anyhow::Result<Self> {
         // TODO (SMA-1726): restore the tree from backup if appropriate
         assert!(
             config.max_l1_batches_per_iter > 0,
@@ -123,11 +132,18 @@ impl MetadataCalculator {
         let mode = config.mode.to_mode();
         let object_store = match config.mode {
             MetadataCalculatorModeConfig::Full { store_factory } => match store_factory {
-                Some(f) => Some(f.create_store().await),
+                Some(f) => Some(f.create_store().await?),
                 None => None,
             },
             MetadataCalculatorModeConfig::Lightweight => None,
         };
+        let kzg_settings = config
+            .kzg_trusted_setup_path
+            .map(|path| {
+                zksync_kzg::load_trusted_setup(Path::new(path))
+                    .with_context(|| format!("cannot load KZG trusted setup from {path}"))
+            })
+            .transpose()?;
 
         let db = create_db(
             config.db_path.into(),
@@ -140,14 +156,15 @@ impl MetadataCalculator {
         let tree = GenericAsyncTree::new(db, mode).await;
 
         let (_, health_updater) = ReactiveHealthCheck::new("tree");
-        Self {
+        Ok(Self {
             tree,
             tree_reader: watch::channel(None).0,
             object_store,
+            kzg_settings,
             delayer: Delayer::new(config.delay_interval),
             health_updater,
             max_l1_batches_per_iter: config.max_l1_batches_per_iter,
-        }
+        })
     }
 
     /// Returns a health check for this calculator.
@@ -185,7 +202,12 @@ impl MetadataCalculator {
         };
         self.tree_reader.send_replace(Some(tree.reader()));
 
-        let updater = TreeUpdater::new(tree, self.max_l1_batches_per_iter, self.object_store);
+        let updater = TreeUpdater::new(
+            tree,
+            self.max_l1_batches_per_iter,
+            self.object_store,
+            self.kzg_settings,
+        );
         updater
             .loop_updating_tree(self.delayer, &pool, stop_receiver, self.health_updater)
             .await
@@ -220,6 +242,7 @@ impl MetadataCalculator {
         header: &L1BatchHeader,
         events_queue_commitment: Option<H256>,
         bootloader_initial_content_commitment: Option<H256>,
+        blob_versioned_hashes: Vec<H256>,
     ) -> L1BatchMetadata {
         let is_pre_boojum = header
             .protocol_version
@@ -240,6 +263,7 @@ impl MetadataCalculator {
             tree_metadata.state_diffs,
             bootloader_initial_content_commitment.unwrap_or_default(),
             events_queue_commitment.unwrap_or_default(),
+            blob_versioned_hashes,
             is_pre_boojum,
         );
         let commitment_hash = commitment.hash();
@@ -421,8 +421,24 @@ impl AsyncTreeRecovery {
     }
 }
 
-async fn snapshot_l1_batch(_pool: &ConnectionPool) -> anyhow::Result<Option<L1BatchNumber>> {
-    Ok(None) // FIXME: implement real logic
+async fn snapshot_l1_batch(pool: &ConnectionPool) -> anyhow::Result<Option<L1BatchNumber>> {
+    let mut storage = pool.access_storage_tagged("metadata_calculator").await?;
+    let status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .context("Failed getting snapshot recovery status")?;
+    let Some(status) = status else {
+        return Ok(None);
+    };
+    anyhow::ensure!(
+        status.is_finished(),
+        "Snapshot recovery for L1 batch #{} is not finished in Postgres ({} storage logs chunks left to process); \
+         Merkle tree cannot be recovered",
+        status.l1_batch_number,
+        status.storage_logs_chunks_left_to_process().len()
+    );
+    Ok(Some(status.l1_batch_number))
 }
 
 #[cfg(test)]
@@ -434,7 +450,7 @@ mod tests {
     use test_casing::test_casing;
     use zksync_config::configs::database::MerkleTreeMode;
     use zksync_health_check::{CheckHealth, ReactiveHealthCheck};
-    use zksync_types::{L2ChainId, StorageLog};
+    use zksync_types::{snapshots::SnapshotRecoveryStatus, L2ChainId, StorageLog};
     use zksync_utils::h256_to_u256;
 
     use super::*;
@@ -501,6 +517,39 @@ mod tests {
         assert_eq!(snapshot.chunk_count(), 1);
     }
 
+    #[tokio::test]
+    async fn getting_snapshot_l1_batch() {
+        let pool = ConnectionPool::test_pool().await;
+        assert_eq!(snapshot_l1_batch(&pool).await.unwrap(), None);
+
+        let mut status = SnapshotRecoveryStatus {
+            l1_batch_number: L1BatchNumber(23),
+            l1_batch_root_hash: H256::repeat_byte(1),
+            miniblock_number: MiniblockNumber(42),
+            miniblock_hash: H256::repeat_byte(2),
+            storage_logs_chunks_processed: vec![true, false],
+        };
+        let mut storage = pool.access_storage().await.unwrap();
+        storage
+            .snapshot_recovery_dal()
+            .set_applied_snapshot_status(&status)
+            .await
+            .unwrap();
+        let err = snapshot_l1_batch(&pool).await.unwrap_err();
+        assert!(format!("{err:#}").contains("not finished"), "{err:#}");
+
+        status.storage_logs_chunks_processed = vec![true; 2];
+        storage
+            .snapshot_recovery_dal()
+            .set_applied_snapshot_status(&status)
+            .await
+            .unwrap();
+        assert_eq!(
+            snapshot_l1_batch(&pool).await.unwrap(),
+            Some(L1BatchNumber(23))
+        );
+    }
+
     async fn create_tree_recovery(path: PathBuf, l1_batch: L1BatchNumber) -> AsyncTreeRecovery {
         let db = create_db(
             path,
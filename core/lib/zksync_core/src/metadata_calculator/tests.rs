@@ -370,7 +370,7 @@ pub(crate) async fn setup_calculator(
     };
     let calculator =
         setup_calculator_with_options(&merkle_tree_config, &operation_manager, pool, mode).await;
-    (calculator, store_factory.create_store().await)
+    (calculator, store_factory.create_store().await.unwrap())
 }
 
 async fn setup_lightweight_calculator(db_path: &Path, pool: &ConnectionPool) -> MetadataCalculator {
@@ -398,8 +398,8 @@ async fn setup_calculator_with_options(
     mode: MetadataCalculatorModeConfig<'_>,
 ) -> MetadataCalculator {
     let calculator_config =
-        MetadataCalculatorConfig::for_main_node(merkle_tree_config, operation_config, mode);
-    let metadata_calculator = MetadataCalculator::new(&calculator_config).await;
+        MetadataCalculatorConfig::for_main_node(merkle_tree_config, operation_config, mode, None);
+    let metadata_calculator = MetadataCalculator::new(&calculator_config).await.unwrap();
 
     let mut storage = pool.access_storage().await.unwrap();
     if storage.blocks_dal().is_genesis_needed().await.unwrap() {
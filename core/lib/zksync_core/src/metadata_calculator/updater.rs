@@ -9,9 +9,15 @@ use zksync_commitment_utils::{bootloader_initial_content_commitment, events_queu
 use zksync_config::configs::database::MerkleTreeMode;
 use zksync_dal::{ConnectionPool, StorageProcessor};
 use zksync_health_check::HealthUpdater;
+use zksync_kzg::KzgSettings;
 use zksync_merkle_tree::domain::TreeMetadata;
 use zksync_object_store::ObjectStore;
-use zksync_types::{block::L1BatchHeader, writes::InitialStorageWrite, L1BatchNumber, H256, U256};
+use zksync_types::{
+    block::L1BatchHeader,
+    commitment::L1BatchWithMetadata,
+    writes::{compress_state_diffs, InitialStorageWrite, StateDiffRecord},
+    L1BatchNumber, H256, U256,
+};
 
 use super::{
     helpers::{AsyncTree, Delayer, L1BatchWithLogs},
@@ -24,6 +30,7 @@ pub(super) struct TreeUpdater {
     tree: AsyncTree,
     max_l1_batches_per_iter: usize,
     object_store: Option<Box<dyn ObjectStore>>,
+    kzg_settings: Option<KzgSettings>,
 }
 
 impl TreeUpdater {
@@ -31,11 +38,13 @@ impl TreeUpdater {
         tree: AsyncTree,
         max_l1_batches_per_iter: usize,
         object_store: Option<Box<dyn ObjectStore>>,
+        kzg_settings: Option<KzgSettings>,
     ) -> Self {
         Self {
             tree,
             max_l1_batches_per_iter,
             object_store,
+            kzg_settings,
         }
     }
 
@@ -128,6 +137,9 @@ impl TreeUpdater {
                 } else {
                     (None, None)
                 };
+            let blob_versioned_hashes = self
+                .blob_versioned_hashes(storage, &header, &metadata.state_diffs)
+                .await;
 
             let build_metadata_latency = METRICS.start_stage(TreeUpdateStage::BuildMetadata);
             let metadata = MetadataCalculator::build_l1_batch_metadata(
@@ -135,6 +147,7 @@ impl TreeUpdater {
                 &header,
                 events_queue_commitment,
                 bootloader_initial_content_commitment,
+                blob_versioned_hashes,
             );
             build_metadata_latency.observe();
 
@@ -233,6 +246,49 @@ impl TreeUpdater {
         )
     }
 
+    /// Computes versioned hashes of EIP-4844 blobs carrying pubdata of the L1 batch. Returns an empty list
+    /// if pubdata is posted as calldata.
+    async fn blob_versioned_hashes(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        header: &L1BatchHeader,
+        state_diffs: &[StateDiffRecord],
+    ) -> Vec<H256> {
+        let Some(kzg_settings) = &self.kzg_settings else {
+            return vec![];
+        };
+        let is_pre_boojum = header
+            .protocol_version
+            .map(|v| v.is_pre_boojum())
+            .unwrap_or(true);
+        if is_pre_boojum {
+            return vec![];
+        }
+
+        let unsorted_factory_deps = storage
+            .blocks_dal()
+            .get_l1_batch_factory_deps(header.number)
+            .await
+            .unwrap();
+        let factory_deps: Vec<_> =
+            L1BatchWithMetadata::factory_deps_in_appearance_order(header, &unsorted_factory_deps)
+                .collect();
+        // Must match pubdata posted by `eth_sender`, which uses compressed state diffs from L1 batch metadata.
+        let state_diffs_compressed = compress_state_diffs(state_diffs.to_vec());
+        let pubdata = L1BatchWithMetadata::pubdata(header, &factory_deps, &state_diffs_compressed);
+        zksync_kzg::pubdata_to_blobs(&pubdata)
+            .iter()
+            .map(|blob| {
+                zksync_kzg::blob_versioned_hash(kzg_settings, blob).unwrap_or_else(|err| {
+                    panic!(
+                        "Failed committing to pubdata blob for L1 batch #{}: {err}",
+                        header.number
+                    )
+                })
+            })
+            .collect()
+    }
+
     async fn step(
         &mut self,
         mut storage: StorageProcessor<'_>,
@@ -4,15 +4,15 @@ use tokio::sync::watch;
 use zksync_dal::ConnectionPool;
 use zksync_types::{L1BatchNumber, MiniblockNumber};
 use zksync_web3_decl::{
-    jsonrpsee::{
-        core::Error as RpcError,
-        http_client::{HttpClient, HttpClientBuilder},
-    },
+    jsonrpsee::core::Error as RpcError,
     namespaces::{EthNamespaceClient, ZksNamespaceClient},
     RpcResult,
 };
 
-use crate::metrics::{CheckerComponent, EN_METRICS};
+use crate::{
+    metrics::{CheckerComponent, EN_METRICS},
+    sync_layer::FailoverMainNodeClient,
+};
 
 const SLEEP_INTERVAL: Duration = Duration::from_secs(5);
 
@@ -32,16 +32,17 @@ const SLEEP_INTERVAL: Duration = Duration::from_secs(5);
 /// and is special-cased in the `zksync_external_node` crate.
 #[derive(Debug)]
 pub struct ReorgDetector {
-    client: HttpClient,
+    client: FailoverMainNodeClient,
     pool: ConnectionPool,
     should_stop: watch::Receiver<bool>,
 }
 
 impl ReorgDetector {
-    pub fn new(url: &str, pool: ConnectionPool, should_stop: watch::Receiver<bool>) -> Self {
-        let client = HttpClientBuilder::default()
-            .build(url)
-            .expect("Failed to create HTTP client");
+    pub fn new(
+        client: FailoverMainNodeClient,
+        pool: ConnectionPool,
+        should_stop: watch::Receiver<bool>,
+    ) -> Self {
         Self {
             client,
             pool,
@@ -70,7 +71,9 @@ impl ReorgDetector {
 
         let Some(hash) = self
             .client
-            .get_block_by_number(miniblock_number.0.into(), false)
+            .call("get_block_by_number", |client| {
+                client.get_block_by_number(miniblock_number.0.into(), false)
+            })
             .await?
             .map(|header| header.hash)
         else {
@@ -103,7 +106,9 @@ impl ReorgDetector {
             });
         let Some(hash) = self
             .client
-            .get_l1_batch_details(l1_batch_number)
+            .call("get_l1_batch_details", |client| {
+                client.get_l1_batch_details(l1_batch_number)
+            })
             .await?
             .and_then(|b| b.base.root_hash)
         else {
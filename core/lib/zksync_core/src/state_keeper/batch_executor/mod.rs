@@ -12,12 +12,15 @@ use multivm::{
 };
 use once_cell::sync::OnceCell;
 use tokio::{
+    runtime::Handle,
     sync::{mpsc, oneshot},
     task::JoinHandle,
 };
 use zksync_dal::ConnectionPool;
-use zksync_state::{RocksdbStorage, StorageView, WriteStorage};
-use zksync_types::{vm_trace::Call, witness_block_state::WitnessBlockState, Transaction, U256};
+use zksync_state::{PostgresStorage, ReadStorage, RocksdbStorage, StorageView, WriteStorage};
+use zksync_types::{
+    vm_trace::Call, witness_block_state::WitnessBlockState, MiniblockNumber, Transaction, U256,
+};
 use zksync_utils::bytecode::CompressedBytecodeInfo;
 
 use crate::{
@@ -54,7 +57,7 @@ pub(crate) enum TxExecutionResult {
 
 impl TxExecutionResult {
     /// Returns a revert reason if either transaction was rejected or bootloader ran out of gas.
-    pub(super) fn err(&self) -> Option<&Halt> {
+    pub(crate) fn err(&self) -> Option<&Halt> {
         match self {
             Self::Success { .. } => None,
             Self::RejectedByVm {
@@ -182,6 +185,37 @@ impl BatchExecutorHandle {
         }
     }
 
+    /// Creates a handle executing an L1 batch on top of the Postgres storage snapshot as of the end
+    /// of the specified miniblock. Unlike [`RocksdbStorage`], which only reflects the latest state,
+    /// this allows re-executing already sealed L1 batches.
+    pub(crate) fn with_postgres_storage(
+        pool: ConnectionPool,
+        miniblock_number: MiniblockNumber,
+        max_allowed_tx_gas_limit: U256,
+        l1_batch_env: L1BatchEnv,
+        system_env: SystemEnv,
+    ) -> Self {
+        let (commands_sender, commands_receiver) = mpsc::channel(1);
+        let executor = BatchExecutor {
+            save_call_traces: false,
+            max_allowed_tx_gas_limit,
+            commands: commands_receiver,
+        };
+
+        let handle = tokio::task::spawn_blocking(move || {
+            let rt_handle = Handle::current();
+            let connection = rt_handle
+                .block_on(pool.access_storage_tagged("batch_executor"))
+                .expect("failed getting connection for Postgres-backed batch executor");
+            let storage = PostgresStorage::new(rt_handle, connection, miniblock_number, true);
+            executor.run(storage, l1_batch_env, system_env, false)
+        });
+        Self {
+            handle,
+            commands: commands_sender,
+        }
+    }
+
     /// Creates a batch executor handle from the provided sender and thread join handle.
     /// Can be used to inject an alternative batch executor implementation.
     #[cfg(test)]
@@ -189,7 +223,7 @@ impl BatchExecutorHandle {
         Self { handle, commands }
     }
 
-    pub(super) async fn execute_tx(&self, tx: Transaction) -> TxExecutionResult {
+    pub(crate) async fn execute_tx(&self, tx: Transaction) -> TxExecutionResult {
         let tx_gas_limit = tx.gas_limit().as_u32();
 
         let (response_sender, response_receiver) = oneshot::channel();
@@ -221,7 +255,7 @@ impl BatchExecutorHandle {
         res
     }
 
-    pub(super) async fn start_next_miniblock(&self, miniblock_info: L2BlockEnv) {
+    pub(crate) async fn start_next_miniblock(&self, miniblock_info: L2BlockEnv) {
         // While we don't get anything from the channel, it's useful to have it as a confirmation that the operation
         // indeed has been processed.
         let (response_sender, response_receiver) = oneshot::channel();
@@ -251,7 +285,7 @@ impl BatchExecutorHandle {
         latency.observe();
     }
 
-    pub(super) async fn finish_batch(self) -> (FinishedL1Batch, Option<WitnessBlockState>) {
+    pub(crate) async fn finish_batch(self) -> (FinishedL1Batch, Option<WitnessBlockState>) {
         let (response_sender, response_receiver) = oneshot::channel();
         self.commands
             .send(Command::FinishBatch(response_sender))
@@ -289,9 +323,9 @@ pub(super) struct BatchExecutor {
 }
 
 impl BatchExecutor {
-    pub(super) fn run(
+    pub(super) fn run<S: ReadStorage>(
         mut self,
-        secondary_storage: RocksdbStorage,
+        secondary_storage: S,
         l1_batch_params: L1BatchEnv,
         system_env: SystemEnv,
         upload_witness_inputs_to_gcs: bool,
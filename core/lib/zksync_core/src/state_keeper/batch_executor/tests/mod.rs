@@ -46,6 +46,26 @@ async fn execute_l2_tx() {
     executor.finish_batch().await;
 }
 
+/// Checks that the batch executor can execute transactions after the state keeper cache is recovered
+/// from a snapshot.
+#[tokio::test]
+async fn execute_l2_tx_after_snapshot_recovery() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let mut alice = Account::random();
+
+    let tester = Tester::new(connection_pool);
+    tester.genesis().await;
+    tester.fund(&[alice.address()]).await;
+    tester.mark_genesis_as_snapshot().await;
+    let executor = tester.create_batch_executor().await;
+
+    let res = executor.execute_tx(alice.execute()).await;
+    assert_executed(&res);
+    let res = executor.execute_tx(alice.execute()).await;
+    assert_executed(&res);
+    executor.finish_batch().await;
+}
+
 /// Checks that we can successfully execute a single L1 tx in batch executor.
 #[tokio::test]
 async fn execute_l1_tx() {
@@ -12,10 +12,11 @@ use zksync_dal::ConnectionPool;
 use zksync_state::RocksdbStorage;
 use zksync_test_account::{Account, DeployContractsTx, TxType};
 use zksync_types::{
-    ethabi::Token, fee::Fee, system_contracts::get_system_smart_contracts,
-    utils::storage_key_for_standard_token_balance, AccountTreeId, Address, Execute, L1BatchNumber,
-    L2ChainId, MiniblockNumber, PriorityOpId, ProtocolVersionId, StorageLog, Transaction, H256,
-    L2_ETH_TOKEN_ADDRESS, SYSTEM_CONTEXT_MINIMAL_BASE_FEE, U256,
+    ethabi::Token, fee::Fee, snapshots::SnapshotRecoveryStatus,
+    system_contracts::get_system_smart_contracts, utils::storage_key_for_standard_token_balance,
+    AccountTreeId, Address, Execute, L1BatchNumber, L2ChainId, MiniblockNumber, PriorityOpId,
+    ProtocolVersionId, StorageLog, Transaction, H256, L2_ETH_TOKEN_ADDRESS,
+    SYSTEM_CONTEXT_MINIMAL_BASE_FEE, U256,
 };
 use zksync_utils::u256_to_h256;
 
@@ -155,6 +156,36 @@ impl Tester {
         }
     }
 
+    /// Marks the genesis L1 batch as recovered from a snapshot, so that the state keeper cache is initialized
+    /// from the snapshot storage logs rather than by replaying L1 batches.
+    /// Expects genesis to be performed and accounts to be funded beforehand.
+    pub(super) async fn mark_genesis_as_snapshot(&self) {
+        let mut storage = self
+            .pool
+            .access_storage_tagged("state_keeper")
+            .await
+            .unwrap();
+        let miniblock_hash = storage
+            .blocks_dal()
+            .get_miniblock_header(MiniblockNumber(0))
+            .await
+            .unwrap()
+            .expect("no genesis miniblock")
+            .hash;
+        let status = SnapshotRecoveryStatus {
+            l1_batch_number: L1BatchNumber(0),
+            l1_batch_root_hash: H256::zero(), // Not important in this context.
+            miniblock_number: MiniblockNumber(0),
+            miniblock_hash,
+            storage_logs_chunks_processed: vec![true],
+        };
+        storage
+            .snapshot_recovery_dal()
+            .set_applied_snapshot_status(&status)
+            .await
+            .unwrap();
+    }
+
     /// Adds funds for specified account list.
     /// Expects genesis to be performed (i.e. `setup_storage` called beforehand).
     pub(super) async fn fund(&self, addresses: &[Address]) {
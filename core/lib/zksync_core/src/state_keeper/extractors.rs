@@ -45,6 +45,18 @@ pub(crate) async fn wait_for_prev_l1_batch_params(
     wait_for_l1_batch_params_unchecked(storage, number - 1).await
 }
 
+/// Same as [`wait_for_prev_l1_batch_params()`], but gives up after the specified `timeout`, returning `None`.
+/// Should be used outside the state keeper, where the previous L1 batch may never get its root hash computed
+/// (e.g., if the Merkle tree isn't running).
+pub(crate) async fn wait_for_prev_l1_batch_params_with_timeout(
+    storage: &mut StorageProcessor<'_>,
+    number: L1BatchNumber,
+    timeout: Duration,
+) -> Option<(U256, u64)> {
+    let params = wait_for_prev_l1_batch_params(storage, number);
+    tokio::time::timeout(timeout, params).await.ok()
+}
+
 /// # Warning
 ///
 /// If invoked for a `L1BatchNumber` of a non-existent l1 batch, will block current thread indefinitely.
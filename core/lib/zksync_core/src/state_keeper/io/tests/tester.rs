@@ -78,7 +78,7 @@ impl Tester {
             virtual_blocks_per_miniblock: 1,
             ..StateKeeperConfig::default()
         };
-        let object_store = ObjectStoreFactory::mock().create_store().await;
+        let object_store = ObjectStoreFactory::mock().create_store().await.unwrap();
         let l2_erc20_bridge_addr = Address::repeat_byte(0x5a); // Isn't relevant.
         let io = MempoolIO::new(
             mempool.clone(),
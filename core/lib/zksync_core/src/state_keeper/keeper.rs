@@ -639,6 +639,8 @@ impl ZkSyncStateKeeper {
                     gas_count: tx_gas_excluding_writes + tx_writes_l1_gas,
                     cumulative_size: encoding_len,
                     writes_metrics: tx_writes_metrics,
+                    called_contract: Some(tx.execute.contract_address),
+                    called_contract_gas_used: tx_execution_metrics.gas_used,
                 };
                 let block_data = SealData {
                     execution_metrics: tx_data.execution_metrics
@@ -649,6 +651,9 @@ impl ZkSyncStateKeeper {
                     cumulative_size: tx_data.cumulative_size
                         + updates_manager.pending_txs_encoding_size(),
                     writes_metrics: block_writes_metrics,
+                    called_contract: tx_data.called_contract,
+                    called_contract_gas_used: tx_data.called_contract_gas_used
+                        + updates_manager.pending_gas_used_by_contract(tx.execute.contract_address),
                 };
 
                 if let Some(sealer) = &self.sealer {
@@ -79,6 +79,12 @@ impl<G: L1GasPriceProvider> MempoolFetcher<G> {
             let mempool_info = self.mempool.get_mempool_info();
             let l2_tx_filter = l2_tx_filter(self.l1_gas_price_provider.as_ref(), fair_l2_gas_price);
 
+            if !mempool_info.purged_transactions.is_empty() {
+                storage
+                    .transactions_dal()
+                    .remove_mempool_txs(&mempool_info.purged_transactions)
+                    .await;
+            }
             let (transactions, nonces) = storage
                 .transactions_dal()
                 .sync_mempool(
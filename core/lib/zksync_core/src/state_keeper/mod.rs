@@ -20,7 +20,7 @@ pub(crate) use self::{
 };
 use crate::l1_gas_price::L1GasPriceProvider;
 
-mod batch_executor;
+pub(crate) mod batch_executor;
 pub(crate) mod extractors;
 pub(crate) mod io;
 mod keeper;
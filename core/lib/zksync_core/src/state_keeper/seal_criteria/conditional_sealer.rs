@@ -3,7 +3,7 @@
 //! It is used on the main node to decide when the batch should be sealed (as opposed to the external node,
 //! which unconditionally follows the instructions from the main node).
 
-use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_config::configs::chain::{OptionalSealCriterion, StateKeeperConfig};
 use zksync_types::ProtocolVersionId;
 
 use super::{criteria, SealCriterion, SealData, SealResolution, AGGREGATION_METRICS};
@@ -12,6 +12,9 @@ use super::{criteria, SealCriterion, SealData, SealResolution, AGGREGATION_METRI
 ///
 /// The checks are deterministic, i.e., should depend solely on execution metrics and [`StateKeeperConfig`].
 /// Non-deterministic seal criteria are expressed using [`IoSealCriteria`](super::IoSealCriteria).
+///
+/// Besides the built-in criteria, which are always applied, the sealer applies optional criteria
+/// listed in [`StateKeeperConfig::seal_criteria`] in the specified order.
 #[derive(Debug)]
 pub struct ConditionalSealer {
     config: StateKeeperConfig,
@@ -25,7 +28,7 @@ impl ConditionalSealer {
         data: &SealData,
         protocol_version: ProtocolVersionId,
     ) -> Option<&'static str> {
-        for sealer in &Self::default_sealers() {
+        for sealer in &Self::sealers(config) {
             const MOCK_BLOCK_TIMESTAMP: u128 = 0;
             const TX_COUNT: usize = 1;
 
@@ -45,7 +48,21 @@ impl ConditionalSealer {
     }
 
     pub(crate) fn new(config: StateKeeperConfig) -> Self {
-        let sealers = Self::default_sealers();
+        for criterion in &config.seal_criteria {
+            let is_configured = match criterion {
+                OptionalSealCriterion::L2ToL1Messages => {
+                    config.max_l2_to_l1_messages_in_batch.is_some()
+                }
+                OptionalSealCriterion::ContractGasCaps => !config.contract_gas_caps.is_empty(),
+            };
+            if !is_configured {
+                tracing::warn!(
+                    "Seal criterion {criterion:?} is enabled, but its limit is not configured; \
+                     the criterion will have no effect"
+                );
+            }
+        }
+        let sealers = Self::sealers(&config);
         Self { config, sealers }
     }
 
@@ -100,6 +117,22 @@ impl ConditionalSealer {
         final_seal_resolution
     }
 
+    fn sealers(config: &StateKeeperConfig) -> Vec<Box<dyn SealCriterion>> {
+        let mut sealers = Self::default_sealers();
+        sealers.extend(config.seal_criteria.iter().map(|&criterion| {
+            let sealer: Box<dyn SealCriterion> = match criterion {
+                OptionalSealCriterion::L2ToL1Messages => {
+                    Box::new(criteria::L2ToL1MessagesCriterion)
+                }
+                OptionalSealCriterion::ContractGasCaps => {
+                    Box::new(criteria::ContractGasCapsCriterion)
+                }
+            };
+            sealer
+        }));
+        sealers
+    }
+
     fn default_sealers() -> Vec<Box<dyn SealCriterion>> {
         vec![
             Box::new(criteria::SlotsCriterion),
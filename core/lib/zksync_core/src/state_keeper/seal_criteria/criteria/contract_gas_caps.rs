@@ -0,0 +1,110 @@
+use zksync_types::ProtocolVersionId;
+
+use crate::state_keeper::seal_criteria::{
+    SealCriterion, SealData, SealResolution, StateKeeperConfig,
+};
+
+/// Limits L2 gas used in an L1 batch by transactions calling specific contracts, as specified by
+/// [`StateKeeperConfig::contract_gas_caps`].
+#[derive(Debug)]
+pub struct ContractGasCapsCriterion;
+
+impl SealCriterion for ContractGasCapsCriterion {
+    fn should_seal(
+        &self,
+        config: &StateKeeperConfig,
+        _block_open_timestamp_ms: u128,
+        _tx_count: usize,
+        block_data: &SealData,
+        tx_data: &SealData,
+        _protocol_version_id: ProtocolVersionId,
+    ) -> SealResolution {
+        let Some(called_contract) = tx_data.called_contract else {
+            return SealResolution::NoSeal;
+        };
+        let Some(cap) = config
+            .contract_gas_caps
+            .iter()
+            .find(|cap| cap.address == called_contract)
+        else {
+            return SealResolution::NoSeal;
+        };
+
+        if tx_data.called_contract_gas_used as u64 > cap.max_gas {
+            let message =
+                "Transaction cannot be included due to the gas cap of the called contract";
+            SealResolution::Unexecutable(message.into())
+        } else if block_data.called_contract_gas_used as u64 > cap.max_gas {
+            SealResolution::ExcludeAndSeal
+        } else {
+            SealResolution::NoSeal
+        }
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        "contract_gas_caps"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_config::configs::chain::ContractGasCap;
+    use zksync_types::Address;
+
+    use super::*;
+
+    fn seal_data(called_contract: Address, gas_used: usize) -> SealData {
+        SealData {
+            called_contract: Some(called_contract),
+            called_contract_gas_used: gas_used,
+            ..SealData::default()
+        }
+    }
+
+    #[test]
+    fn seal_criterion() {
+        let capped_contract = Address::repeat_byte(1);
+        let other_contract = Address::repeat_byte(2);
+        let config = StateKeeperConfig {
+            contract_gas_caps: vec![ContractGasCap {
+                address: capped_contract,
+                max_gas: 1_000,
+            }],
+            ..Default::default()
+        };
+        let criterion = ContractGasCapsCriterion;
+        let resolution = |contract, block_gas, tx_gas| {
+            criterion.should_seal(
+                &config,
+                0,
+                0,
+                &seal_data(contract, block_gas),
+                &seal_data(contract, tx_gas),
+                ProtocolVersionId::latest(),
+            )
+        };
+
+        assert_eq!(
+            resolution(capped_contract, 500, 100),
+            SealResolution::NoSeal
+        );
+        assert_eq!(
+            resolution(capped_contract, 1_000, 100),
+            SealResolution::NoSeal
+        );
+        assert_eq!(
+            resolution(capped_contract, 1_001, 100),
+            SealResolution::ExcludeAndSeal
+        );
+        assert_eq!(
+            resolution(capped_contract, 1_001, 1_001),
+            SealResolution::Unexecutable(
+                "Transaction cannot be included due to the gas cap of the called contract".into()
+            )
+        );
+        assert_eq!(
+            resolution(other_contract, 10_000, 10_000),
+            SealResolution::NoSeal
+        );
+    }
+}
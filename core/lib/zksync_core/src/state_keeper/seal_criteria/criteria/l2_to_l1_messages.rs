@@ -0,0 +1,98 @@
+use zksync_types::ProtocolVersionId;
+
+use crate::state_keeper::seal_criteria::{
+    SealCriterion, SealData, SealResolution, StateKeeperConfig,
+};
+
+/// Limits the total size of long L2-to-L1 messages (i.e., ones sent via the `L1Messenger` system contract)
+/// in an L1 batch to [`StateKeeperConfig::max_l2_to_l1_messages_in_batch`] bytes.
+#[derive(Debug)]
+pub struct L2ToL1MessagesCriterion;
+
+impl SealCriterion for L2ToL1MessagesCriterion {
+    fn should_seal(
+        &self,
+        config: &StateKeeperConfig,
+        _block_open_timestamp_ms: u128,
+        _tx_count: usize,
+        block_data: &SealData,
+        tx_data: &SealData,
+        _protocol_version_id: ProtocolVersionId,
+    ) -> SealResolution {
+        let Some(max_messages_size) = config.max_l2_to_l1_messages_in_batch else {
+            return SealResolution::NoSeal;
+        };
+
+        if tx_data.execution_metrics.l2_l1_long_messages > max_messages_size {
+            let message = "Transaction cannot be included due to oversized L2-to-L1 messages";
+            SealResolution::Unexecutable(message.into())
+        } else if block_data.execution_metrics.l2_l1_long_messages > max_messages_size {
+            SealResolution::ExcludeAndSeal
+        } else if block_data.execution_metrics.l2_l1_long_messages == max_messages_size {
+            SealResolution::IncludeAndSeal
+        } else {
+            SealResolution::NoSeal
+        }
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        "l2_to_l1_messages"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::tx::tx_execution_info::ExecutionMetrics;
+
+    use super::*;
+
+    fn seal_data(l2_l1_long_messages: usize) -> SealData {
+        SealData {
+            execution_metrics: ExecutionMetrics {
+                l2_l1_long_messages,
+                ..ExecutionMetrics::default()
+            },
+            ..SealData::default()
+        }
+    }
+
+    #[test]
+    fn seal_criterion() {
+        let config = StateKeeperConfig {
+            max_l2_to_l1_messages_in_batch: Some(1_000),
+            ..Default::default()
+        };
+        let criterion = L2ToL1MessagesCriterion;
+        let resolution = |block_size, tx_size| {
+            criterion.should_seal(
+                &config,
+                0,
+                0,
+                &seal_data(block_size),
+                &seal_data(tx_size),
+                ProtocolVersionId::latest(),
+            )
+        };
+
+        assert_eq!(resolution(0, 0), SealResolution::NoSeal);
+        assert_eq!(resolution(904, 96), SealResolution::NoSeal);
+        assert_eq!(resolution(1_000, 96), SealResolution::IncludeAndSeal);
+        assert_eq!(resolution(1_096, 96), SealResolution::ExcludeAndSeal);
+        assert_eq!(
+            resolution(1_096, 1_096),
+            SealResolution::Unexecutable(
+                "Transaction cannot be included due to oversized L2-to-L1 messages".into()
+            )
+        );
+
+        let resolution = criterion.should_seal(
+            &StateKeeperConfig::default(),
+            0,
+            0,
+            &seal_data(10_000),
+            &seal_data(10_000),
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::NoSeal);
+    }
+}
@@ -1,15 +1,19 @@
+mod contract_gas_caps;
 mod gas;
 mod geometry_seal_criteria;
+mod l2_to_l1_messages;
 mod pubdata_bytes;
 mod slots;
 mod tx_encoding_size;
 
 pub(in crate::state_keeper) use self::{
+    contract_gas_caps::ContractGasCapsCriterion,
     gas::GasCriterion,
     geometry_seal_criteria::{
         ComputationalGasCriterion, InitialWritesCriterion, L2ToL1LogsCriterion, MaxCyclesCriterion,
         RepeatedWritesCriterion,
     },
+    l2_to_l1_messages::L2ToL1MessagesCriterion,
     pubdata_bytes::PubDataBytesCriterion,
     slots::SlotsCriterion,
     tx_encoding_size::TxEncodingSizeCriterion,
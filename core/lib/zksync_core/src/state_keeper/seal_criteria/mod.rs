@@ -10,7 +10,7 @@
 //! Maintaining all the criteria in one place has proven itself to be very error-prone,
 //! thus now every criterion is independent of the others.
 
-use std::fmt;
+use std::{fmt, time::Duration};
 
 use multivm::vm_latest::TransactionVmExt;
 use zksync_config::configs::chain::StateKeeperConfig;
@@ -18,7 +18,7 @@ use zksync_types::{
     block::BlockGasCount,
     fee::TransactionExecutionMetrics,
     tx::tx_execution_info::{DeduplicatedWritesMetrics, ExecutionMetrics},
-    ProtocolVersionId, Transaction,
+    Address, ProtocolVersionId, Transaction,
 };
 use zksync_utils::time::millis_since;
 
@@ -81,6 +81,10 @@ pub struct SealData {
     pub(super) gas_count: BlockGasCount,
     pub(super) cumulative_size: usize,
     pub(super) writes_metrics: DeduplicatedWritesMetrics,
+    /// Contract called by the transaction.
+    pub(super) called_contract: Option<Address>,
+    /// L2 gas used by transactions calling [`Self::called_contract`].
+    pub(super) called_contract_gas_used: usize,
 }
 
 impl SealData {
@@ -96,6 +100,8 @@ impl SealData {
         let gas_count = gas_count_from_tx_and_metrics(&transaction, &execution_metrics)
             + gas_count_from_writes(&writes_metrics, protocol_version);
         Self {
+            called_contract: Some(transaction.execute.contract_address),
+            called_contract_gas_used: execution_metrics.gas_used,
             execution_metrics,
             gas_count,
             cumulative_size: transaction.bootloader_encoding_size(),
@@ -133,6 +139,7 @@ pub trait IoSealCriteria {
 pub(super) struct TimeoutSealer {
     block_commit_deadline_ms: u64,
     miniblock_commit_deadline_ms: u64,
+    max_batch_execution_time: Option<Duration>,
 }
 
 impl TimeoutSealer {
@@ -140,8 +147,27 @@ impl TimeoutSealer {
         Self {
             block_commit_deadline_ms: config.block_commit_deadline_ms,
             miniblock_commit_deadline_ms: config.miniblock_commit_deadline_ms,
+            max_batch_execution_time: config.max_batch_execution_time(),
         }
     }
+
+    fn should_seal_by_execution_time(&self, manager: &UpdatesManager) -> bool {
+        const RULE_NAME: &str = "execution_time_timeout";
+
+        let Some(max_execution_time) = self.max_batch_execution_time else {
+            return false;
+        };
+        let execution_time = manager.batch_execution_time();
+        let should_seal = execution_time > max_execution_time;
+        if should_seal {
+            AGGREGATION_METRICS.inc_criterion(RULE_NAME);
+            tracing::debug!(
+                "Decided to seal L1 batch using rule `{RULE_NAME}`; execution time: {execution_time:?}, \
+                 max execution time: {max_execution_time:?}"
+            );
+        }
+        should_seal
+    }
 }
 
 impl IoSealCriteria for TimeoutSealer {
@@ -166,7 +192,7 @@ impl IoSealCriteria for TimeoutSealer {
                 extractors::display_timestamp(manager.batch_timestamp())
             );
         }
-        should_seal_timeout
+        should_seal_timeout || self.should_seal_by_execution_time(manager)
     }
 
     fn should_seal_miniblock(&mut self, manager: &UpdatesManager) -> bool {
@@ -202,6 +228,7 @@ mod tests {
         let mut timeout_miniblock_sealer = TimeoutSealer {
             block_commit_deadline_ms: 10_000,
             miniblock_commit_deadline_ms: 10_000,
+            max_batch_execution_time: None,
         };
 
         let mut manager = create_updates_manager();
@@ -228,4 +255,31 @@ mod tests {
             "Non-empty miniblock with too recent timestamp shouldn't be sealed"
         );
     }
+
+    #[test]
+    fn timeout_sealer_with_max_execution_time() {
+        let mut sealer = TimeoutSealer {
+            block_commit_deadline_ms: u64::MAX,
+            miniblock_commit_deadline_ms: u64::MAX,
+            max_batch_execution_time: Some(Duration::ZERO),
+        };
+        let mut manager = create_updates_manager();
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(
+            !sealer.should_seal_l1_batch_unconditionally(&manager),
+            "Empty L1 batch shouldn't be sealed"
+        );
+
+        apply_tx_to_manager(&mut manager);
+        assert!(
+            sealer.should_seal_l1_batch_unconditionally(&manager),
+            "L1 batch executed for too long should be sealed"
+        );
+
+        // This relies on the test not running for more than an hour.
+        sealer.max_batch_execution_time = Some(Duration::from_secs(3_600));
+        assert!(!sealer.should_seal_l1_batch_unconditionally(&manager));
+        sealer.max_batch_execution_time = None;
+        assert!(!sealer.should_seal_l1_batch_unconditionally(&manager));
+    }
 }
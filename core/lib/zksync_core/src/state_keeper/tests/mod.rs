@@ -14,8 +14,11 @@ use multivm::{
     vm_latest::{constants::BLOCK_GAS_LIMIT, VmExecutionLogs},
 };
 use once_cell::sync::Lazy;
+use tempfile::TempDir;
 use zksync_config::configs::chain::StateKeeperConfig;
 use zksync_contracts::{BaseSystemContracts, BaseSystemContractsHashes};
+use zksync_dal::ConnectionPool;
+use zksync_state::RocksdbStorage;
 use zksync_system_constants::ZKPORTER_IS_AVAILABLE;
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
@@ -39,6 +42,9 @@ pub(crate) use self::tester::{MockBatchExecutorBuilder, TestBatchExecutorBuilder
 use crate::{
     gas_tracker::l1_batch_base_cost,
     state_keeper::{
+        batch_executor::{BatchExecutorHandle, TxExecutionResult},
+        extractors,
+        io::{common::l1_batch_params, MiniblockParams},
         keeper::POLL_WAIT_DURATION,
         seal_criteria::{
             criteria::{GasCriterion, SlotsCriterion},
@@ -142,6 +148,128 @@ pub(super) fn create_updates_manager() -> UpdatesManager {
     )
 }
 
+/// Executes transactions in a new L1 batch using the real VM and seals the batch the same way the state keeper does.
+/// Each item in `miniblocks` contains transactions of a single miniblock. Transactions are inserted into Postgres
+/// during sealing, and call traces are persisted. Expects that the previous L1 batch has its root hash computed.
+pub(crate) async fn execute_and_seal_l1_batch(
+    pool: &ConnectionPool,
+    chain_id: L2ChainId,
+    miniblocks: Vec<Vec<Transaction>>,
+) -> L1BatchNumber {
+    let fee_account = Address::repeat_byte(0x01);
+    let mut storage = pool.access_storage_tagged("state_keeper").await.unwrap();
+    let l1_batch_number = storage
+        .blocks_dal()
+        .get_sealed_l1_batch_number()
+        .await
+        .unwrap()
+        + 1;
+    let (prev_l1_batch_hash, _) =
+        extractors::wait_for_prev_l1_batch_params(&mut storage, l1_batch_number).await;
+    let prev_miniblock_number = storage
+        .blocks_dal()
+        .get_sealed_miniblock_number()
+        .await
+        .unwrap();
+    let prev_miniblock_header = storage
+        .blocks_dal()
+        .get_miniblock_header(prev_miniblock_number)
+        .await
+        .unwrap()
+        .expect("no sealed miniblock");
+
+    let (system_env, l1_batch_env) = l1_batch_params(
+        l1_batch_number,
+        fee_account,
+        prev_miniblock_header.timestamp + 1,
+        prev_l1_batch_hash,
+        1,
+        1,
+        prev_miniblock_number + 1,
+        prev_miniblock_header.hash,
+        BASE_SYSTEM_CONTRACTS.clone(),
+        StateKeeperConfig::for_tests().validation_computational_gas_limit,
+        ProtocolVersionId::latest(),
+        1,
+        chain_id,
+    );
+
+    let db_dir = TempDir::new().unwrap();
+    let mut secondary_storage = RocksdbStorage::new(db_dir.path());
+    secondary_storage.update_from_postgres(&mut storage).await;
+    let batch_executor = BatchExecutorHandle::new(
+        true,
+        u32::MAX.into(),
+        secondary_storage,
+        l1_batch_env.clone(),
+        system_env,
+        false,
+    );
+    let mut updates_manager = UpdatesManager::new(
+        l1_batch_env.clone(),
+        BASE_SYSTEM_CONTRACTS.hashes(),
+        ProtocolVersionId::latest(),
+    );
+
+    let mut miniblock_number = prev_miniblock_number + 1;
+    for txs in miniblocks {
+        for tx in txs {
+            let tx_hash = tx.hash();
+            let TxExecutionResult::Success {
+                tx_result,
+                tx_metrics,
+                compressed_bytecodes,
+                call_tracer_result,
+                ..
+            } = batch_executor.execute_tx(tx.clone()).await
+            else {
+                panic!("Transaction {tx_hash:?} was not executed");
+            };
+            updates_manager.extend_from_executed_transaction(
+                tx,
+                *tx_result,
+                compressed_bytecodes,
+                tx_metrics.l1_gas,
+                tx_metrics.execution_metrics,
+                call_tracer_result,
+            );
+        }
+
+        updates_manager
+            .seal_miniblock_command(
+                l1_batch_number,
+                miniblock_number,
+                Address::default(),
+                None,
+                true,
+            )
+            .seal(&mut storage)
+            .await;
+        miniblock_number += 1;
+        // The last started miniblock becomes the fictive one.
+        updates_manager.push_miniblock(MiniblockParams {
+            timestamp: updates_manager.miniblock.timestamp + 1,
+            virtual_blocks: 1,
+        });
+        batch_executor
+            .start_next_miniblock(updates_manager.miniblock.get_miniblock_env())
+            .await;
+    }
+
+    let (finished_batch, _) = batch_executor.finish_batch().await;
+    updates_manager
+        .seal_l1_batch(
+            &mut storage,
+            miniblock_number,
+            &l1_batch_env,
+            finished_batch,
+            Address::default(),
+            None,
+        )
+        .await;
+    l1_batch_number
+}
+
 pub(crate) fn create_l2_transaction(fee_per_gas: u64, gas_per_pubdata: u32) -> L2Tx {
     let fee = Fee {
         gas_limit: 1000_u64.into(),
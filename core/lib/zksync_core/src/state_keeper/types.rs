@@ -3,6 +3,7 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use zksync_config::configs::chain::MempoolConfig;
 use zksync_mempool::{L2TxFilter, MempoolInfo, MempoolStore};
 use zksync_types::{
     block::BlockGasCount, tx::ExecutionMetrics, Address, Nonce, PriorityOpId, Transaction,
@@ -19,6 +20,23 @@ impl MempoolGuard {
         Self(Arc::new(Mutex::new(store)))
     }
 
+    /// Creates a mempool with the capacity, per-account limits and ordering policy specified in the config.
+    pub fn from_config(
+        next_priority_id: PriorityOpId,
+        config: &MempoolConfig,
+        fair_l2_gas_price: u64,
+    ) -> Self {
+        let mut store = MempoolStore::new(next_priority_id, config.capacity)
+            .with_ordering(config.tx_ordering, fair_l2_gas_price);
+        if let Some(percent) = config.min_replacement_fee_bump_percent {
+            store = store.with_min_replacement_fee_bump(percent);
+        }
+        if let Some(limit) = config.max_pending_txs_per_account {
+            store = store.with_max_transactions_per_account(limit);
+        }
+        Self(Arc::new(Mutex::new(store)))
+    }
+
     pub fn insert(&mut self, transactions: Vec<Transaction>, nonces: HashMap<Address, Nonce>) {
         self.0
             .lock()
@@ -1,3 +1,8 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
 use multivm::interface::{L1BatchEnv, VmExecutionResultAndLogs};
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::blocks_dal::ConsensusBlockFields;
@@ -31,6 +36,10 @@ pub struct UpdatesManager {
     pub l1_batch: L1BatchUpdates,
     pub miniblock: MiniblockUpdates,
     pub storage_writes_deduplicator: StorageWritesDeduplicator,
+    /// L2 gas used in the pending L1 batch by transactions grouped by the called contract.
+    gas_used_by_contract: HashMap<Address, usize>,
+    /// Wall-clock time when the state keeper started executing the L1 batch.
+    execution_started_at: Instant,
 }
 
 impl UpdatesManager {
@@ -55,6 +64,8 @@ impl UpdatesManager {
                 protocol_version,
             ),
             storage_writes_deduplicator: StorageWritesDeduplicator::new(),
+            gas_used_by_contract: HashMap::new(),
+            execution_started_at: Instant::now(),
         }
     }
 
@@ -62,6 +73,11 @@ impl UpdatesManager {
         self.batch_timestamp
     }
 
+    /// Returns the wall-clock time elapsed since the state keeper started executing the L1 batch.
+    pub(crate) fn batch_execution_time(&self) -> Duration {
+        self.execution_started_at.elapsed()
+    }
+
     pub(crate) fn base_system_contract_hashes(&self) -> BaseSystemContractsHashes {
         self.base_system_contract_hashes
     }
@@ -113,6 +129,10 @@ impl UpdatesManager {
     ) {
         self.storage_writes_deduplicator
             .apply(&tx_execution_result.logs.storage_logs);
+        *self
+            .gas_used_by_contract
+            .entry(tx.execute.contract_address)
+            .or_default() += execution_metrics.gas_used;
         self.miniblock.extend_from_executed_transaction(
             tx,
             tx_execution_result,
@@ -159,6 +179,14 @@ impl UpdatesManager {
     pub(crate) fn pending_txs_encoding_size(&self) -> usize {
         self.l1_batch.txs_encoding_size + self.miniblock.txs_encoding_size
     }
+
+    /// Returns L2 gas used in the pending L1 batch by transactions calling the specified contract.
+    pub(crate) fn pending_gas_used_by_contract(&self, contract_address: Address) -> usize {
+        self.gas_used_by_contract
+            .get(&contract_address)
+            .copied()
+            .unwrap_or(0)
+    }
 }
 
 /// Command to seal a miniblock containing all necessary data for it.
@@ -7,13 +7,12 @@ use zksync_types::{
     aggregated_operations::AggregatedActionType, api::BlockDetails, L1BatchNumber, MiniblockNumber,
     H256,
 };
-use zksync_web3_decl::{
-    jsonrpsee::http_client::{HttpClient, HttpClientBuilder},
-    namespaces::ZksNamespaceClient,
-    RpcResult,
-};
+use zksync_web3_decl::{namespaces::ZksNamespaceClient, RpcResult};
 
-use super::metrics::{FetchStage, L1BatchStage, FETCHER_METRICS};
+use super::{
+    metrics::{FetchStage, L1BatchStage, FETCHER_METRICS},
+    FailoverMainNodeClient,
+};
 use crate::metrics::EN_METRICS;
 
 /// Represents a change in the batch status.
@@ -51,7 +50,7 @@ impl StatusChanges {
 /// the module updates the database to mirror the state observable from the main node.
 #[derive(Debug)]
 pub struct BatchStatusUpdater {
-    client: HttpClient,
+    client: FailoverMainNodeClient,
     pool: ConnectionPool,
 
     last_executed_l1_batch: L1BatchNumber,
@@ -60,30 +59,34 @@ pub struct BatchStatusUpdater {
 }
 
 impl BatchStatusUpdater {
-    pub async fn new(main_node_url: &str, pool: ConnectionPool) -> Self {
-        let client = HttpClientBuilder::default()
-            .build(main_node_url)
-            .expect("Unable to create a main node client");
-
+    pub async fn new(client: FailoverMainNodeClient, pool: ConnectionPool) -> Self {
         let mut storage = pool.access_storage_tagged("sync_layer").await.unwrap();
+        // If the node was recovered from a snapshot, batches up to and including the snapshot one
+        // are not present in Postgres, so we start tracking statuses from the snapshot L1 batch.
+        let first_l1_batch = storage
+            .snapshot_recovery_dal()
+            .get_applied_snapshot_status()
+            .await
+            .unwrap()
+            .map_or(L1BatchNumber(0), |status| status.l1_batch_number);
         let last_executed_l1_batch = storage
             .blocks_dal()
             .get_number_of_last_l1_batch_executed_on_eth()
             .await
             .unwrap()
-            .unwrap_or_default();
+            .unwrap_or(first_l1_batch);
         let last_proven_l1_batch = storage
             .blocks_dal()
             .get_number_of_last_l1_batch_proven_on_eth()
             .await
             .unwrap()
-            .unwrap_or_default();
+            .unwrap_or(first_l1_batch);
         let last_committed_l1_batch = storage
             .blocks_dal()
             .get_number_of_last_l1_batch_committed_on_eth()
             .await
             .unwrap()
-            .unwrap_or_default();
+            .unwrap_or(first_l1_batch);
         drop(storage);
 
         Self {
@@ -150,7 +153,13 @@ impl BatchStatusUpdater {
             // While we may receive `None` for the `self.current_l1_batch`, it's OK: open batch is guaranteed to not
             // be sent to L1.
             let request_latency = FETCHER_METRICS.requests[&FetchStage::GetMiniblockRange].start();
-            let Some((start_miniblock, _)) = self.client.get_miniblock_range(batch).await? else {
+            let Some((start_miniblock, _)) = self
+                .client
+                .call("get_miniblock_range", |client| {
+                    client.get_miniblock_range(batch)
+                })
+                .await?
+            else {
                 return Ok(());
             };
             request_latency.observe();
@@ -159,7 +168,9 @@ impl BatchStatusUpdater {
             let request_latency = FETCHER_METRICS.requests[&FetchStage::GetBlockDetails].start();
             let Some(batch_info) = self
                 .client
-                .get_block_details(MiniblockNumber(start_miniblock.as_u32()))
+                .call("get_block_details", |client| {
+                    client.get_block_details(MiniblockNumber(start_miniblock.as_u32()))
+                })
                 .await?
             else {
                 // We cannot recover from an external API inconsistency.
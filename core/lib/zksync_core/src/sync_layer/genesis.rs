@@ -14,6 +14,19 @@ pub async fn perform_genesis_if_needed(
     zksync_chain_id: L2ChainId,
     client: &dyn MainNodeClient,
 ) -> anyhow::Result<()> {
+    let snapshot_recovery = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await?;
+    if let Some(status) = snapshot_recovery {
+        // The node was bootstrapped from a snapshot, so it has no genesis L1 batch to validate.
+        tracing::info!(
+            "Node was recovered from a snapshot at L1 batch #{}; skipping genesis",
+            status.l1_batch_number
+        );
+        return Ok(());
+    }
+
     let mut transaction = storage.start_transaction().await?;
     // We want to check whether the genesis is needed before we create genesis params to not
     // make the node startup slower.
@@ -0,0 +1,1238 @@
+//! Trustless verification of the external node state against L1.
+//!
+//! The external node can reconstruct the rollup state purely from the data published on L1 and check it
+//! against L1 commitments. [`L1BatchSyncer`] watches `BlockCommit` events emitted by the diamond proxy, decodes
+//! `commitBatches` calldata of the corresponding L1 transactions, and applies the published pubdata
+//! (state diffs, bytecodes, L2-to-L1 logs and messages) to Postgres and a dedicated Merkle tree. After applying
+//! each L1 batch, the tree root hash and the leaf count are checked against the values committed on L1,
+//! so a successfully synced state is guaranteed to match L1 commitments. `BlocksRevert` events are followed
+//! by rolling back the reconstructed state. Root hashes of the reconstructed L1 batches are compared with
+//! the root hashes computed locally by the node (i.e., for the state received from the main node); the result
+//! is reported via the `l1_sync` health check.
+//!
+//! Limitations:
+//!
+//! - The reconstructed state is only used for verification; it is *not* a sync source. Pubdata doesn't contain
+//!   preimages of hashed storage keys, transactions or miniblocks, so the state is stored in the dedicated
+//!   `l1_sync_*` tables rather than in the tables used by the API server and VM, and the node still syncs
+//!   from the main node.
+//! - Sync starts from the local state as of the genesis L1 batch, since the genesis state is never published
+//!   on L1. Pre-boojum L1 batches use a different pubdata format and are not reconstructed; for chains started
+//!   before the boojum upgrade, sync starts from the local state as of the last pre-boojum L1 batch.
+//!   In both cases, the initial state is implicitly verified once the next L1 batch is reconstructed.
+//! - L1 batches with pubdata posted in EIP-4844 blobs cannot be reconstructed from calldata.
+//! - Failures (e.g., a mismatch with an L1 commitment or with the local state) stop L1 sync, but not the node.
+
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_contracts::PRE_BOOJUM_COMMIT_FUNCTION;
+use zksync_dal::{
+    l1_sync_dal::{L1SyncBatch, L1SyncStorageEntry},
+    ConnectionPool, StorageProcessor,
+};
+use zksync_eth_client::{types::Error as EthClientError, EthInterface};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_merkle_tree::{
+    recovery::MerkleTreeRecovery, BlockOutput, Database, MerkleTree, PruneDatabase, TreeEntry,
+};
+use zksync_types::{
+    commitment::{L1BatchCommitData, L1BatchPubdata},
+    ethabi,
+    web3::types::{BlockId, BlockNumber, FilterBuilder, Log},
+    writes::StateDiffKey,
+    Address, L1BatchNumber, ProtocolVersionId, H256, U256,
+};
+use zksync_utils::{bytecode::hash_bytecode, h256_to_u256, u256_to_h256};
+
+use super::metrics::L1_SYNC_METRICS;
+
+const COMPONENT: &str = "l1_sync";
+/// Maximum number of L1 blocks queried for events at once.
+const MAX_L1_BLOCKS_PER_QUERY: u64 = 10_000;
+/// Number of storage slots loaded at once when building the tree for the initial state.
+const SEED_CHUNK_SIZE: usize = 10_000;
+/// Maximum number of L1 batches compared with the local state at once.
+const MAX_COMPARED_L1_BATCHES: usize = 1_000;
+
+#[derive(Debug, thiserror::Error)]
+enum L1SyncError {
+    /// Transient error communicating with L1; sync will be retried.
+    #[error("L1 client error: {0}")]
+    Eth(#[from] EthClientError),
+    #[error(transparent)]
+    Fatal(#[from] anyhow::Error),
+}
+
+/// Position of the syncer on L1.
+#[derive(Debug)]
+struct SyncCursor {
+    last_batch: L1SyncBatch,
+    next_l1_block: u64,
+    /// Last L1 batch with the root hash compared with the local state.
+    last_compared_batch: L1BatchNumber,
+}
+
+/// Health details reported by [`L1BatchSyncer`].
+#[derive(Debug, Default, Serialize)]
+struct L1SyncHealthDetails {
+    last_synced_l1_batch: Option<L1BatchNumber>,
+    /// Last local L1 batch with the root hash matching the one reconstructed from L1.
+    last_verified_l1_batch: Option<L1BatchNumber>,
+    /// First local L1 batch with the root hash diverging from the one reconstructed from L1.
+    diverged_l1_batch: Option<L1BatchNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl From<&L1SyncHealthDetails> for Health {
+    fn from(details: &L1SyncHealthDetails) -> Self {
+        let status = if details.diverged_l1_batch.is_some() || details.error.is_some() {
+            HealthStatus::NotReady
+        } else {
+            HealthStatus::Ready
+        };
+        Self::from(status).with_details(details)
+    }
+}
+
+/// Reconstructs the rollup state from pubdata published on L1 and verifies it against L1 commitments.
+/// See the module docs for details.
+#[derive(Debug)]
+pub struct L1BatchSyncer<E, DB> {
+    eth_client: E,
+    pool: ConnectionPool,
+    diamond_proxy_address: Address,
+    commit_function: ethabi::Function,
+    commit_event_topic: H256,
+    revert_event_topic: H256,
+    /// Tree database; taken when the tree is initialized.
+    tree_db: Option<DB>,
+    /// `Some(_)` after initialization unless the tree is being updated in a blocking task.
+    tree: Option<MerkleTree<DB>>,
+    confirmations: Option<u64>,
+    poll_interval: Duration,
+    health_details: L1SyncHealthDetails,
+    health_updater: HealthUpdater,
+}
+
+impl<E: EthInterface, DB: PruneDatabase + 'static> L1BatchSyncer<E, DB> {
+    pub fn new(
+        eth_client: E,
+        pool: ConnectionPool,
+        diamond_proxy_address: Address,
+        tree_db: DB,
+    ) -> Self {
+        let contract = zksync_contracts::zksync_contract();
+        let (_, health_updater) = ReactiveHealthCheck::new("l1_sync");
+        Self {
+            eth_client,
+            pool,
+            diamond_proxy_address,
+            commit_function: contract.function("commitBatches").unwrap().clone(),
+            commit_event_topic: contract.event("BlockCommit").unwrap().signature(),
+            revert_event_topic: contract.event("BlocksRevert").unwrap().signature(),
+            tree_db: Some(tree_db),
+            tree: None,
+            confirmations: None,
+            poll_interval: Duration::from_secs(10),
+            health_details: L1SyncHealthDetails::default(),
+            health_updater,
+        }
+    }
+
+    /// Returns a health check for this syncer. The check is not ready after the sync has failed
+    /// or has detected divergence of the local state from L1.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    /// Sets the number of confirmations for L1 blocks to be processed. If not set (which is the default),
+    /// only finalized L1 blocks are processed.
+    pub fn with_confirmations(mut self, confirmations: Option<u64>) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        if let Err(err) = self.run_inner(&mut stop_receiver).await {
+            // The reconstructed state is only used for verification, so the node can continue running.
+            tracing::error!("L1 sync failed; it is stopped until the node is restarted: {err:#}");
+            L1_SYNC_METRICS.failed.set(1);
+            self.health_details.error = Some(format!("{err:#}"));
+            self.update_health();
+            // Wait for the stop signal so that the node isn't shut down because of a finished task.
+            stop_receiver.changed().await.ok();
+        }
+        Ok(())
+    }
+
+    async fn run_inner(&mut self, stop_receiver: &mut watch::Receiver<bool>) -> anyhow::Result<()> {
+        // Waiting for the local state to start the sync from can take a long time (e.g., for chains
+        // started before the boojum upgrade), so the node shouldn't be marked as not ready meanwhile.
+        self.update_health();
+        let mut cursor = loop {
+            if *stop_receiver.borrow_and_update() {
+                tracing::info!("Stop signal received, L1 sync is shutting down");
+                return Ok(());
+            }
+            if let Some(cursor) = self.initialize().await? {
+                break cursor;
+            }
+            tracing::info!(
+                "Local state for the L1 batch to start L1 sync from is not available yet, waiting"
+            );
+            if tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                tracing::info!("Stop signal received, L1 sync is shutting down");
+                return Ok(());
+            }
+        };
+        tracing::info!(
+            "Starting L1 sync from L1 batch #{} (L1 block #{})",
+            cursor.last_batch.number,
+            cursor.next_l1_block
+        );
+        self.health_details.last_synced_l1_batch = Some(cursor.last_batch.number);
+        self.update_health();
+
+        while !*stop_receiver.borrow_and_update() {
+            let has_more_blocks = match self.sync_step(&mut cursor).await {
+                Ok(has_more_blocks) => has_more_blocks,
+                Err(L1SyncError::Eth(err)) => {
+                    tracing::warn!("Failed querying L1 for commit data, will retry: {err}");
+                    false
+                }
+                Err(L1SyncError::Fatal(err)) => return Err(err),
+            };
+            let has_more_batches = self.compare_with_local_state(&mut cursor).await?;
+            self.health_details.last_synced_l1_batch = Some(cursor.last_batch.number);
+            self.update_health();
+
+            let should_wait = !has_more_blocks && !has_more_batches;
+            if should_wait
+                && tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                    .await
+                    .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, L1 sync is shutting down");
+        Ok(())
+    }
+
+    /// Loads the sync cursor from Postgres, seeding the state from the local storage if necessary,
+    /// and aligns the Merkle tree with it. Returns `None` if the local state to seed from is not available yet.
+    async fn initialize(&mut self) -> anyhow::Result<Option<SyncCursor>> {
+        let mut storage = self.pool.access_storage_tagged("sync_layer").await?;
+        let last_batch = storage
+            .l1_sync_dal()
+            .get_last_batch()
+            .await
+            .context("failed getting last L1-synced batch")?;
+        drop(storage);
+
+        let last_batch = match last_batch {
+            Some(last_batch) => last_batch,
+            None => match self.seed_from_local_state().await? {
+                Some(seeded_batch) => seeded_batch,
+                None => return Ok(None),
+            },
+        };
+        self.initialize_tree(&last_batch).await?;
+
+        L1_SYNC_METRICS
+            .last_l1_batch
+            .set(last_batch.number.0.into());
+        Ok(Some(SyncCursor {
+            next_l1_block: last_batch.commit_l1_block_number,
+            last_batch,
+            // Batches synced before the restart are compared again.
+            last_compared_batch: L1BatchNumber(0),
+        }))
+    }
+
+    /// Returns the L1 batch to start the sync from, or `None` if it isn't available locally yet.
+    /// This is the genesis L1 batch, or the last pre-boojum L1 batch for chains started before the boojum upgrade.
+    async fn start_batch(
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Option<L1BatchNumber>> {
+        const GENESIS: L1BatchNumber = L1BatchNumber(0);
+
+        let genesis_version = storage
+            .blocks_dal()
+            .get_batch_protocol_version_id(GENESIS)
+            .await?
+            .context("genesis L1 batch is missing; genesis must be performed before L1 sync")?;
+        if !genesis_version.is_pre_boojum() {
+            return Ok(Some(GENESIS));
+        }
+
+        let first_post_boojum_batch = storage
+            .l1_sync_dal()
+            .get_first_local_batch_with_protocol_version(ProtocolVersionId::Version18)
+            .await
+            .context("failed getting first post-boojum L1 batch")?;
+        Ok(first_post_boojum_batch.map(|number| L1BatchNumber(number.0 - 1)))
+    }
+
+    async fn seed_from_local_state(&self) -> anyhow::Result<Option<L1SyncBatch>> {
+        let mut storage = self.pool.access_storage_tagged("sync_layer").await?;
+        let Some(start_batch) = Self::start_batch(&mut storage).await? else {
+            return Ok(None);
+        };
+        let Some(root_hash) = storage
+            .blocks_dal()
+            .get_l1_batch_state_root(start_batch)
+            .await?
+        else {
+            return Ok(None); // The state root hash is not computed yet
+        };
+        let header = storage
+            .blocks_dal()
+            .get_l1_batch_header(start_batch)
+            .await?
+            .with_context(|| format!("L1 batch #{start_batch} is missing"))?;
+        let (_, last_miniblock) = storage
+            .blocks_dal()
+            .get_miniblock_range_of_l1_batch(start_batch)
+            .await?
+            .with_context(|| format!("L1 batch #{start_batch} has no miniblocks"))?;
+
+        tracing::info!("Seeding L1 sync state from the local state as of L1 batch #{start_batch}");
+        let mut transaction = storage.start_transaction().await?;
+        let seeded_slots = transaction
+            .l1_sync_dal()
+            .seed_from_local_state(start_batch, last_miniblock)
+            .await
+            .context("failed seeding L1 sync state")?;
+        let start_batch = L1SyncBatch {
+            number: start_batch,
+            timestamp: header.timestamp,
+            root_hash,
+            rollup_last_leaf_index: seeded_slots + 1,
+            l2_to_l1_logs: vec![],
+            l2_to_l1_messages: vec![],
+            commit_tx_hash: None,
+            commit_l1_block_number: 0,
+        };
+        transaction.l1_sync_dal().insert_batch(&start_batch).await?;
+        transaction.commit().await?;
+        Ok(Some(start_batch))
+    }
+
+    async fn initialize_tree(&mut self, last_batch: &L1SyncBatch) -> anyhow::Result<()> {
+        let tree_db = self
+            .tree_db
+            .take()
+            .context("Merkle tree is already initialized")?;
+        let manifest = tree_db.manifest();
+        let is_recovering = manifest
+            .as_ref()
+            .map_or(false, |manifest| manifest.recovered_version().is_some());
+        // The tree is built from Postgres if the node was stopped after seeding Postgres, but before
+        // the tree was fully built.
+        let is_seeded_batch = last_batch.commit_tx_hash.is_none();
+        let mut tree = if is_seeded_batch && (manifest.is_none() || is_recovering) {
+            self.build_tree(tree_db, last_batch.number).await?
+        } else {
+            MerkleTree::new(tree_db)
+        };
+
+        let tree_version = tree.latest_version();
+        let expected_version = u64::from(last_batch.number.0);
+        anyhow::ensure!(
+            tree_version >= Some(expected_version),
+            "L1 sync Merkle tree (version {tree_version:?}) is behind Postgres (L1 batch #{}); \
+             the tree must be removed together with L1 sync data in Postgres",
+            last_batch.number
+        );
+        // The tree may be ahead of Postgres if the node was stopped after updating the tree,
+        // but before persisting the L1 batch.
+        tree.truncate_recent_versions(expected_version + 1);
+        anyhow::ensure!(
+            tree.latest_root_hash() == last_batch.root_hash,
+            "L1 sync Merkle tree root hash doesn't match L1 batch #{}",
+            last_batch.number
+        );
+        self.tree = Some(tree);
+        Ok(())
+    }
+
+    /// Builds the tree from the L1 sync state seeded in Postgres.
+    async fn build_tree(
+        &self,
+        tree_db: DB,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<MerkleTree<DB>> {
+        let mut recovery = MerkleTreeRecovery::new(tree_db, l1_batch_number.0.into());
+        let mut storage = self.pool.access_storage_tagged("sync_layer").await?;
+        let mut last_index = 0;
+        loop {
+            let chunk = storage
+                .l1_sync_dal()
+                .get_storage_chunk(last_index, SEED_CHUNK_SIZE)
+                .await?;
+            let Some(last_entry) = chunk.last() else {
+                break;
+            };
+            last_index = last_entry.enumeration_index;
+            let tree_entries = chunk.iter().map(tree_entry).collect();
+            recovery = tokio::task::spawn_blocking(move || {
+                recovery.extend_random(tree_entries);
+                recovery
+            })
+            .await
+            .context("panicked while building Merkle tree")?;
+        }
+        let tree_db = tokio::task::spawn_blocking(|| recovery.finalize())
+            .await
+            .context("panicked while building Merkle tree")?;
+        Ok(MerkleTree::new(tree_db))
+    }
+
+    /// Returns the last L1 block that can be processed.
+    async fn last_processed_l1_block(&self) -> Result<u64, L1SyncError> {
+        if let Some(confirmations) = self.confirmations {
+            let latest_block = self.eth_client.block_number(COMPONENT).await?.as_u64();
+            return Ok(latest_block.saturating_sub(confirmations));
+        }
+        let block = self
+            .eth_client
+            .block(BlockId::Number(BlockNumber::Finalized), COMPONENT)
+            .await?
+            .context("finalized L1 block is missing")?;
+        let number = block.number.context("finalized L1 block has no number")?;
+        Ok(number.as_u64())
+    }
+
+    /// Processes commit and revert events in the next range of L1 blocks. Returns `true` if there are more L1 blocks
+    /// to process.
+    async fn sync_step(&mut self, cursor: &mut SyncCursor) -> Result<bool, L1SyncError> {
+        let last_l1_block = self.last_processed_l1_block().await?;
+        if cursor.next_l1_block > last_l1_block {
+            return Ok(false);
+        }
+        let from_block = cursor.next_l1_block;
+        let to_block = last_l1_block.min(from_block + MAX_L1_BLOCKS_PER_QUERY - 1);
+
+        let topics = vec![self.commit_event_topic, self.revert_event_topic];
+        let filter = FilterBuilder::default()
+            .address(vec![self.diamond_proxy_address])
+            .topics(Some(topics), None, None, None)
+            .from_block(BlockNumber::Number(from_block.into()))
+            .to_block(BlockNumber::Number(to_block.into()))
+            .build();
+        let mut logs = self.eth_client.logs(filter, COMPONENT).await?;
+        // Filter logs defensively; some L1 clients are known to return logs outside the requested range.
+        logs.retain(|log| self.is_relevant_log(log, from_block..=to_block));
+        logs.sort_unstable_by_key(|log| (log.block_number, log.log_index));
+
+        for log in logs {
+            if log.topics[0] == self.revert_event_topic {
+                self.revert_batches(cursor, &log).await?;
+                continue;
+            }
+
+            let batch_number = log.topics[1];
+            let batch_number = h256_to_u256(batch_number);
+            if batch_number > U256::from(u32::MAX) {
+                return Err(anyhow::anyhow!("L1 batch number {batch_number} is too large").into());
+            }
+            let batch_number = L1BatchNumber(batch_number.as_u32());
+            if batch_number <= cursor.last_batch.number {
+                continue; // The batch is already synced
+            }
+
+            let commit_tx_hash = log.transaction_hash.context("log without tx hash")?;
+            let l1_block_number = log.block_number.context("log without block number")?;
+            let commit_data = self.fetch_commit_data(commit_tx_hash, batch_number).await?;
+            let latency = L1_SYNC_METRICS.apply_l1_batch.start();
+            let batch = self
+                .apply_batch(
+                    &cursor.last_batch,
+                    commit_data,
+                    commit_tx_hash,
+                    l1_block_number.as_u64(),
+                )
+                .await
+                .with_context(|| format!("failed applying L1 batch #{batch_number}"))?;
+            latency.observe();
+
+            tracing::info!(
+                "Synced L1 batch #{batch_number} from L1 (commit tx: {commit_tx_hash:?})"
+            );
+            L1_SYNC_METRICS.last_l1_batch.set(batch_number.0.into());
+            cursor.last_batch = batch;
+        }
+        cursor.next_l1_block = to_block + 1;
+        Ok(to_block < last_l1_block)
+    }
+
+    fn is_relevant_log(&self, log: &Log, l1_blocks: std::ops::RangeInclusive<u64>) -> bool {
+        let is_in_range = log
+            .block_number
+            .map_or(false, |number| l1_blocks.contains(&number.as_u64()));
+        let is_commit = log.topics.len() >= 2 && log.topics[0] == self.commit_event_topic;
+        let is_revert = log.topics.first() == Some(&self.revert_event_topic);
+        log.address == self.diamond_proxy_address && (is_commit || is_revert) && is_in_range
+    }
+
+    /// Rolls back the reconstructed state according to a `BlocksRevert` event.
+    async fn revert_batches(&mut self, cursor: &mut SyncCursor, log: &Log) -> anyhow::Result<()> {
+        let param_types = vec![ethabi::ParamType::Uint(256); 3];
+        let tokens = ethabi::decode(&param_types, &log.data.0)
+            .context("failed decoding `BlocksRevert` event")?;
+        let total_batches_committed = tokens[0].clone().into_uint().unwrap();
+        // ^ `unwrap()` is safe: the token type is checked during decoding
+        if total_batches_committed >= U256::from(cursor.last_batch.number.0) {
+            return Ok(()); // No synced batches are reverted
+        }
+        let last_batch_to_keep = L1BatchNumber(total_batches_committed.as_u32());
+
+        let mut storage = self.pool.access_storage_tagged("sync_layer").await?;
+        let mut transaction = storage.start_transaction().await?;
+        transaction
+            .l1_sync_dal()
+            .rollback_to_batch(last_batch_to_keep)
+            .await?;
+        let last_batch = transaction
+            .l1_sync_dal()
+            .get_last_batch()
+            .await?
+            .filter(|batch| batch.number == last_batch_to_keep)
+            .with_context(|| {
+                format!(
+                    "cannot revert to L1 batch #{last_batch_to_keep}, which precedes the batch \
+                     L1 sync was started from"
+                )
+            })?;
+        transaction.commit().await?;
+
+        // The tree is truncated after Postgres, so that it's aligned with Postgres on restart
+        // if the node is stopped in between.
+        let tree = self.tree.as_mut().context("Merkle tree is poisoned")?;
+        tree.truncate_recent_versions(u64::from(last_batch_to_keep.0) + 1);
+        anyhow::ensure!(
+            tree.latest_root_hash() == last_batch.root_hash,
+            "L1 sync Merkle tree root hash doesn't match L1 batch #{last_batch_to_keep} after revert"
+        );
+
+        tracing::info!(
+            "Reverted L1 batches #{} to #{} following revert on L1",
+            last_batch_to_keep + 1,
+            cursor.last_batch.number
+        );
+        L1_SYNC_METRICS
+            .last_l1_batch
+            .set(last_batch_to_keep.0.into());
+        cursor.last_batch = last_batch;
+        cursor.last_compared_batch = cursor.last_compared_batch.min(last_batch_to_keep);
+        Ok(())
+    }
+
+    /// Compares root hashes of the L1 batches reconstructed from L1 with the root hashes of the same L1 batches
+    /// computed locally. Returns an error if a divergence is detected, or `true` if there are more L1 batches
+    /// to compare.
+    async fn compare_with_local_state(&mut self, cursor: &mut SyncCursor) -> anyhow::Result<bool> {
+        let mut storage = self.pool.access_storage_tagged("sync_layer").await?;
+        let l1_root_hashes = storage
+            .l1_sync_dal()
+            .get_root_hashes(cursor.last_compared_batch, MAX_COMPARED_L1_BATCHES)
+            .await
+            .context("failed getting root hashes of L1-synced batches")?;
+        let is_full_chunk = l1_root_hashes.len() == MAX_COMPARED_L1_BATCHES;
+
+        for (number, l1_root_hash) in l1_root_hashes {
+            let local_root_hash = storage.blocks_dal().get_l1_batch_state_root(number).await?;
+            let Some(local_root_hash) = local_root_hash else {
+                return Ok(false); // The local state is not computed yet
+            };
+            if local_root_hash != l1_root_hash {
+                L1_SYNC_METRICS.diverged_l1_batch.set(number.0.into());
+                self.health_details.diverged_l1_batch = Some(number);
+                anyhow::bail!(
+                    "local state diverges from L1 at L1 batch #{number}: local root hash \
+                     {local_root_hash:?}, root hash reconstructed from L1 {l1_root_hash:?}"
+                );
+            }
+            cursor.last_compared_batch = number;
+            L1_SYNC_METRICS.last_verified_l1_batch.set(number.0.into());
+            self.health_details.last_verified_l1_batch = Some(number);
+        }
+        Ok(is_full_chunk)
+    }
+
+    fn update_health(&self) {
+        self.health_updater.update((&self.health_details).into());
+    }
+
+    async fn fetch_commit_data(
+        &self,
+        commit_tx_hash: H256,
+        batch_number: L1BatchNumber,
+    ) -> Result<L1BatchCommitData, L1SyncError> {
+        let commit_tx = self
+            .eth_client
+            .get_tx(commit_tx_hash, COMPONENT)
+            .await?
+            .with_context(|| format!("commit transaction {commit_tx_hash:?} not found on L1"))?;
+        let input = &commit_tx.input.0;
+        if input.len() >= 4 && input[..4] == PRE_BOOJUM_COMMIT_FUNCTION.short_signature() {
+            let err = anyhow::anyhow!(
+                "L1 batch #{batch_number} is committed in pre-boojum format by {commit_tx_hash:?}, \
+                 which cannot be reconstructed"
+            );
+            return Err(err.into());
+        }
+        if input.len() < 4 || input[..4] != self.commit_function.short_signature() {
+            let err =
+                anyhow::anyhow!("transaction {commit_tx_hash:?} is not a `commitBatches` call");
+            return Err(err.into());
+        }
+        let mut tokens = self
+            .commit_function
+            .decode_input(&input[4..])
+            .with_context(|| format!("failed decoding calldata of {commit_tx_hash:?}"))?;
+        let commitments = tokens
+            .pop()
+            .and_then(ethabi::Token::into_array)
+            .with_context(|| format!("unexpected calldata of {commit_tx_hash:?}"))?;
+        for token in commitments {
+            let commit_data = L1BatchCommitData::from_token(token)
+                .with_context(|| format!("failed parsing commit data in {commit_tx_hash:?}"))?;
+            if commit_data.number == batch_number {
+                return Ok(commit_data);
+            }
+        }
+        let err = anyhow::anyhow!(
+            "transaction {commit_tx_hash:?} doesn't commit L1 batch #{batch_number}"
+        );
+        Err(err.into())
+    }
+
+    async fn apply_batch(
+        &mut self,
+        prev_batch: &L1SyncBatch,
+        commit_data: L1BatchCommitData,
+        commit_tx_hash: H256,
+        l1_block_number: u64,
+    ) -> anyhow::Result<L1SyncBatch> {
+        anyhow::ensure!(
+            commit_data.number == prev_batch.number + 1,
+            "L1 batches are not sequential: last synced batch is #{}",
+            prev_batch.number
+        );
+        anyhow::ensure!(
+            !commit_data.is_pubdata_in_blobs(),
+            "pubdata is posted in EIP-4844 blobs, which is not supported"
+        );
+        let pubdata = L1BatchPubdata::parse(&commit_data.pubdata).context("invalid pubdata")?;
+
+        let repeated_indices: Vec<_> = pubdata
+            .state_diffs
+            .iter()
+            .filter_map(|diff| match diff.key {
+                StateDiffKey::Repeated(index) => Some(index),
+                StateDiffKey::Initial(_) => None,
+            })
+            .collect();
+        let mut storage = self.pool.access_storage_tagged("sync_layer").await?;
+        let prev_entries = storage
+            .l1_sync_dal()
+            .get_storage_by_indices(&repeated_indices)
+            .await?;
+        drop(storage);
+
+        let mut next_index = prev_batch.rollup_last_leaf_index;
+        let mut storage_entries = Vec::with_capacity(pubdata.state_diffs.len());
+        for diff in &pubdata.state_diffs {
+            let entry = match diff.key {
+                StateDiffKey::Initial(derived_key) => {
+                    let entry = L1SyncStorageEntry {
+                        hashed_key: H256(derived_key),
+                        enumeration_index: next_index,
+                        value: u256_to_h256(diff.final_value(U256::zero())),
+                    };
+                    next_index += 1;
+                    entry
+                }
+                StateDiffKey::Repeated(index) => {
+                    let prev_entry = prev_entries.get(&index).with_context(|| {
+                        format!("repeated write refers to unknown enumeration index {index}")
+                    })?;
+                    let prev_value = h256_to_u256(prev_entry.value);
+                    L1SyncStorageEntry {
+                        value: u256_to_h256(diff.final_value(prev_value)),
+                        ..*prev_entry
+                    }
+                }
+            };
+            storage_entries.push(entry);
+        }
+
+        let tree_entries = storage_entries.iter().map(tree_entry).collect();
+        let output = self.extend_tree(tree_entries).await?;
+        if output.root_hash != commit_data.merkle_root_hash
+            || output.leaf_count + 1 != commit_data.rollup_last_leaf_index
+        {
+            // Revert the tree to the last verified state.
+            let tree = self.tree.as_mut().unwrap();
+            tree.truncate_recent_versions(u64::from(commit_data.number.0));
+            anyhow::bail!(
+                "reconstructed state doesn't match L1 commitment: root hash {:?}, leaf count {}; \
+                 committed root hash {:?}, last leaf index {}",
+                output.root_hash,
+                output.leaf_count,
+                commit_data.merkle_root_hash,
+                commit_data.rollup_last_leaf_index
+            );
+        }
+
+        let factory_deps: HashMap<_, _> = pubdata
+            .published_bytecodes
+            .into_iter()
+            .map(|bytecode| (hash_bytecode(&bytecode), bytecode))
+            .collect();
+        let batch = L1SyncBatch {
+            number: commit_data.number,
+            timestamp: commit_data.timestamp,
+            root_hash: commit_data.merkle_root_hash,
+            rollup_last_leaf_index: commit_data.rollup_last_leaf_index,
+            l2_to_l1_logs: pubdata.l2_to_l1_logs,
+            l2_to_l1_messages: pubdata.l2_to_l1_messages,
+            commit_tx_hash: Some(commit_tx_hash),
+            commit_l1_block_number: l1_block_number,
+        };
+
+        let mut storage = self.pool.access_storage_tagged("sync_layer").await?;
+        let mut transaction = storage.start_transaction().await?;
+        let mut dal = transaction.l1_sync_dal();
+        dal.insert_storage(batch.number, &storage_entries).await?;
+        dal.insert_factory_deps(batch.number, &factory_deps).await?;
+        dal.insert_batch(&batch).await?;
+        transaction.commit().await?;
+        Ok(batch)
+    }
+
+    async fn extend_tree(&mut self, entries: Vec<TreeEntry>) -> anyhow::Result<BlockOutput> {
+        let mut tree = self.tree.take().context("Merkle tree is poisoned")?;
+        let (tree, output) = tokio::task::spawn_blocking(move || {
+            let output = tree.extend(entries);
+            (tree, output)
+        })
+        .await
+        .context("panicked while updating Merkle tree")?;
+        self.tree = Some(tree);
+        Ok(output)
+    }
+}
+
+fn tree_entry(entry: &L1SyncStorageEntry) -> TreeEntry {
+    let key = U256::from_little_endian(entry.hashed_key.as_bytes());
+    TreeEntry::new(key, entry.enumeration_index, entry.value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tempfile::TempDir;
+    use zksync_contracts::BaseSystemContractsHashes;
+    use zksync_eth_client::clients::mock::MockEthereum;
+    use zksync_health_check::CheckHealth;
+    use zksync_merkle_tree::{PatchSet, RocksDBWrapper};
+    use zksync_types::{
+        block::{BlockGasCount, L1BatchHeader},
+        commitment::L1BatchWithMetadata,
+        web3::types::{Bytes, Transaction},
+        writes::{compress_state_diffs, StateDiffRecord},
+        L2ChainId, ProtocolVersionId,
+    };
+
+    use super::*;
+    use crate::{
+        genesis::{ensure_genesis_state, GenesisParams},
+        state_keeper::tests::create_l1_batch_metadata,
+    };
+
+    const DIAMOND_PROXY_ADDRESS: Address = Address::repeat_byte(0x11);
+
+    type TestSyncer = L1BatchSyncer<Arc<MockEthereum>, RocksDBWrapper>;
+
+    async fn prepare_syncer(
+        pool: &ConnectionPool,
+        temp_dir: &TempDir,
+    ) -> (TestSyncer, Arc<MockEthereum>) {
+        let mut storage = pool.access_storage().await.unwrap();
+        ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+            .await
+            .unwrap();
+        drop(storage);
+
+        let eth_client = Arc::new(MockEthereum::default());
+        let syncer = create_syncer(eth_client.clone(), pool, temp_dir);
+        (syncer, eth_client)
+    }
+
+    fn create_syncer(
+        eth_client: Arc<MockEthereum>,
+        pool: &ConnectionPool,
+        temp_dir: &TempDir,
+    ) -> TestSyncer {
+        let tree_db = RocksDBWrapper::new(temp_dir.path());
+        L1BatchSyncer::new(eth_client, pool.clone(), DIAMOND_PROXY_ADDRESS, tree_db)
+            .with_confirmations(Some(0))
+    }
+
+    /// Builds a Merkle tree with the current L1 sync state.
+    async fn reference_tree(pool: &ConnectionPool) -> MerkleTree<PatchSet> {
+        let mut storage = pool.access_storage().await.unwrap();
+        let entries = storage
+            .l1_sync_dal()
+            .get_storage_chunk(0, 100_000)
+            .await
+            .unwrap();
+        let mut tree = MerkleTree::new(PatchSet::default());
+        tree.extend(entries.iter().map(tree_entry).collect());
+        tree
+    }
+
+    /// Records an L1 transaction committing the specified L1 batch in the mock L1 client.
+    fn commit_l1_batch(
+        eth_client: &MockEthereum,
+        syncer: &TestSyncer,
+        number: u32,
+        output: &BlockOutput,
+        state_diffs: Vec<StateDiffRecord>,
+        factory_deps: Vec<Vec<u8>>,
+    ) -> H256 {
+        let header = L1BatchHeader::new(
+            L1BatchNumber(number),
+            number.into(),
+            Address::default(),
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+        );
+        let mut metadata = create_l1_batch_metadata(number);
+        metadata.merkle_root_hash = output.root_hash;
+        metadata.rollup_last_leaf_index = output.leaf_count + 1;
+        metadata.state_diffs_compressed = compress_state_diffs(state_diffs);
+        let l1_batch = L1BatchWithMetadata {
+            header,
+            metadata,
+            factory_deps,
+        };
+
+        let calldata = syncer
+            .commit_function
+            .encode_input(&[
+                l1_batch.l1_header_data(),
+                ethabi::Token::Array(vec![l1_batch.l1_commit_data()]),
+            ])
+            .unwrap();
+        let tx_hash = H256::random();
+        let tx = Transaction {
+            hash: tx_hash,
+            input: Bytes(calldata),
+            ..Transaction::default()
+        };
+        let log = Log {
+            address: DIAMOND_PROXY_ADDRESS,
+            topics: vec![
+                syncer.commit_event_topic,
+                H256::from_low_u64_be(number.into()),
+                H256::zero(),
+                H256::zero(),
+            ],
+            data: Bytes(vec![]),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+        eth_client.advance_block_number(1);
+        eth_client.add_l1_transaction(tx, vec![log]);
+        tx_hash
+    }
+
+    /// Records an L1 transaction reverting L1 batches after `last_batch_to_keep` in the mock L1 client.
+    fn revert_l1_batches(eth_client: &MockEthereum, syncer: &TestSyncer, last_batch_to_keep: u32) {
+        let total_batches = ethabi::Token::Uint(last_batch_to_keep.into());
+        let log = Log {
+            address: DIAMOND_PROXY_ADDRESS,
+            topics: vec![syncer.revert_event_topic],
+            data: Bytes(ethabi::encode(&[
+                total_batches.clone(),
+                total_batches.clone(),
+                total_batches,
+            ])),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+        let tx = Transaction {
+            hash: H256::random(),
+            ..Transaction::default()
+        };
+        eth_client.advance_block_number(1);
+        eth_client.add_l1_transaction(tx, vec![log]);
+    }
+
+    /// Inserts an L1 batch with the specified root hash into the local state of the node.
+    async fn insert_local_l1_batch(pool: &ConnectionPool, number: u32, root_hash: H256) {
+        let header = L1BatchHeader::new(
+            L1BatchNumber(number),
+            number.into(),
+            Address::default(),
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+        );
+        let mut metadata = create_l1_batch_metadata(number);
+        metadata.root_hash = root_hash;
+
+        let mut storage = pool.access_storage().await.unwrap();
+        storage
+            .blocks_dal()
+            .insert_l1_batch(&header, &[], BlockGasCount::default(), &[], &[])
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .save_l1_batch_metadata(header.number, &metadata, H256::zero(), false)
+            .await
+            .unwrap();
+    }
+
+    /// Commits L1 batch #1 with a single new slot on L1 and syncs it.
+    async fn sync_first_l1_batch(
+        pool: &ConnectionPool,
+        syncer: &mut TestSyncer,
+        eth_client: &MockEthereum,
+        cursor: &mut SyncCursor,
+    ) -> H256 {
+        let mut reference_tree = reference_tree(pool).await;
+        let new_entry = new_slot_entry(0xaa, 42, cursor.last_batch.rollup_last_leaf_index);
+        let output = reference_tree.extend(vec![tree_entry(&new_entry)]);
+        let state_diffs = vec![new_slot_diff(0xaa, 42)];
+        commit_l1_batch(eth_client, syncer, 1, &output, state_diffs, vec![]);
+        syncer.sync_step(cursor).await.unwrap();
+        assert_eq!(cursor.last_batch.number, L1BatchNumber(1));
+        output.root_hash
+    }
+
+    fn new_slot_diff(derived_key: u8, value: u64) -> StateDiffRecord {
+        StateDiffRecord {
+            address: Address::repeat_byte(derived_key),
+            key: U256::one(),
+            derived_key: [derived_key; 32],
+            enumeration_index: 0,
+            initial_value: U256::zero(),
+            final_value: value.into(),
+        }
+    }
+
+    fn new_slot_entry(derived_key: u8, value: u64, enumeration_index: u64) -> L1SyncStorageEntry {
+        L1SyncStorageEntry {
+            hashed_key: H256::repeat_byte(derived_key),
+            enumeration_index,
+            value: H256::from_low_u64_be(value),
+        }
+    }
+
+    #[tokio::test]
+    async fn syncing_l1_batches_from_l1() {
+        let pool = ConnectionPool::test_pool().await;
+        let temp_dir = TempDir::new().unwrap();
+        let (mut syncer, eth_client) = prepare_syncer(&pool, &temp_dir).await;
+        let mut cursor = syncer.initialize().await.unwrap().unwrap();
+        assert_eq!(cursor.last_batch.number, L1BatchNumber(0));
+        assert_eq!(cursor.next_l1_block, 0);
+
+        let mut reference_tree = reference_tree(&pool).await;
+        assert_eq!(
+            reference_tree.latest_root_hash(),
+            cursor.last_batch.root_hash
+        );
+        let mut storage = pool.access_storage().await.unwrap();
+        let existing_entry = storage.l1_sync_dal().get_storage_chunk(0, 1).await.unwrap()[0];
+        drop(storage);
+
+        let existing_value = h256_to_u256(existing_entry.value);
+        let state_diffs = vec![
+            new_slot_diff(0xaa, 42),
+            StateDiffRecord {
+                address: Address::repeat_byte(0xbb),
+                key: U256::one(),
+                derived_key: existing_entry.hashed_key.0,
+                enumeration_index: existing_entry.enumeration_index,
+                initial_value: existing_value,
+                final_value: existing_value + 1,
+            },
+        ];
+        let new_entry = new_slot_entry(0xaa, 42, cursor.last_batch.rollup_last_leaf_index);
+        let updated_entry = L1SyncStorageEntry {
+            value: u256_to_h256(existing_value + 1),
+            ..existing_entry
+        };
+        let output =
+            reference_tree.extend(vec![tree_entry(&new_entry), tree_entry(&updated_entry)]);
+        let bytecode = vec![0; 32];
+        let commit_tx_hash = commit_l1_batch(
+            &eth_client,
+            &syncer,
+            1,
+            &output,
+            state_diffs,
+            vec![bytecode.clone()],
+        );
+
+        let has_more_blocks = syncer.sync_step(&mut cursor).await.unwrap();
+        assert!(!has_more_blocks);
+        assert_eq!(cursor.next_l1_block, 2);
+        assert_eq!(cursor.last_batch.number, L1BatchNumber(1));
+        assert_eq!(cursor.last_batch.root_hash, output.root_hash);
+        assert_eq!(cursor.last_batch.commit_tx_hash, Some(commit_tx_hash));
+        assert_eq!(cursor.last_batch.commit_l1_block_number, 1);
+
+        let mut storage = pool.access_storage().await.unwrap();
+        let last_batch = storage.l1_sync_dal().get_last_batch().await.unwrap();
+        assert_eq!(last_batch.as_ref(), Some(&cursor.last_batch));
+        let entries = storage
+            .l1_sync_dal()
+            .get_storage_by_indices(&[new_entry.enumeration_index, updated_entry.enumeration_index])
+            .await
+            .unwrap();
+        assert_eq!(entries[&new_entry.enumeration_index], new_entry);
+        assert_eq!(entries[&updated_entry.enumeration_index], updated_entry);
+        let factory_dep = storage
+            .l1_sync_dal()
+            .get_factory_dep(hash_bytecode(&bytecode))
+            .await
+            .unwrap();
+        assert_eq!(factory_dep, Some(bytecode));
+        drop(storage);
+
+        // Syncing again should be a no-op.
+        let has_more_blocks = syncer.sync_step(&mut cursor).await.unwrap();
+        assert!(!has_more_blocks);
+        assert_eq!(cursor.last_batch.number, L1BatchNumber(1));
+
+        // Restarting the syncer should resume from the last synced batch.
+        drop(syncer);
+        let mut syncer = create_syncer(eth_client, &pool, &temp_dir);
+        let cursor = syncer.initialize().await.unwrap().unwrap();
+        assert_eq!(cursor.last_batch.number, L1BatchNumber(1));
+        assert_eq!(cursor.next_l1_block, 1);
+    }
+
+    #[tokio::test]
+    async fn mismatched_root_hash_is_detected() {
+        let pool = ConnectionPool::test_pool().await;
+        let temp_dir = TempDir::new().unwrap();
+        let (mut syncer, eth_client) = prepare_syncer(&pool, &temp_dir).await;
+        let mut cursor = syncer.initialize().await.unwrap().unwrap();
+
+        let state_diffs = vec![new_slot_diff(0xaa, 42)];
+        let bogus_output = BlockOutput {
+            root_hash: H256::repeat_byte(0xff),
+            leaf_count: cursor.last_batch.rollup_last_leaf_index,
+            logs: vec![],
+        };
+        commit_l1_batch(&eth_client, &syncer, 1, &bogus_output, state_diffs, vec![]);
+
+        let err = syncer.sync_step(&mut cursor).await.unwrap_err();
+        let L1SyncError::Fatal(err) = err else {
+            panic!("unexpected error: {err}");
+        };
+        assert!(
+            format!("{err:#}").contains("doesn't match L1 commitment"),
+            "{err:#}"
+        );
+
+        // The tree and Postgres must retain the last verified state.
+        assert_eq!(cursor.last_batch.number, L1BatchNumber(0));
+        let tree = syncer.tree.as_ref().unwrap();
+        assert_eq!(tree.latest_version(), Some(0));
+        assert_eq!(tree.latest_root_hash(), cursor.last_batch.root_hash);
+        let mut storage = pool.access_storage().await.unwrap();
+        let last_batch = storage
+            .l1_sync_dal()
+            .get_last_batch()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(last_batch.number, L1BatchNumber(0));
+    }
+
+    #[tokio::test]
+    async fn syncing_only_confirmed_l1_blocks() {
+        let pool = ConnectionPool::test_pool().await;
+        let temp_dir = TempDir::new().unwrap();
+        let (syncer, eth_client) = prepare_syncer(&pool, &temp_dir).await;
+        let mut syncer = syncer.with_confirmations(Some(2));
+        let mut cursor = syncer.initialize().await.unwrap().unwrap();
+
+        let mut reference_tree = reference_tree(&pool).await;
+        let new_entry = new_slot_entry(0xaa, 42, cursor.last_batch.rollup_last_leaf_index);
+        let output = reference_tree.extend(vec![tree_entry(&new_entry)]);
+        let state_diffs = vec![new_slot_diff(0xaa, 42)];
+        commit_l1_batch(&eth_client, &syncer, 1, &output, state_diffs, vec![]);
+
+        let has_more_blocks = syncer.sync_step(&mut cursor).await.unwrap();
+        assert!(!has_more_blocks);
+        assert_eq!(cursor.next_l1_block, 1);
+        assert_eq!(cursor.last_batch.number, L1BatchNumber(0));
+
+        eth_client.advance_block_number(2);
+        let has_more_blocks = syncer.sync_step(&mut cursor).await.unwrap();
+        assert!(!has_more_blocks);
+        assert_eq!(cursor.next_l1_block, 2);
+        assert_eq!(cursor.last_batch.number, L1BatchNumber(1));
+        assert_eq!(cursor.last_batch.root_hash, output.root_hash);
+    }
+
+    #[tokio::test]
+    async fn following_l1_batch_revert_and_recommit() {
+        let pool = ConnectionPool::test_pool().await;
+        let temp_dir = TempDir::new().unwrap();
+        let (mut syncer, eth_client) = prepare_syncer(&pool, &temp_dir).await;
+        let mut cursor = syncer.initialize().await.unwrap().unwrap();
+        let genesis_batch = cursor.last_batch.clone();
+
+        let mut reference_tree = reference_tree(&pool).await;
+        let leaf_index = genesis_batch.rollup_last_leaf_index;
+        let reverted_entry = new_slot_entry(0xaa, 42, leaf_index);
+        let reverted_output = reference_tree.extend(vec![tree_entry(&reverted_entry)]);
+        let reverted_diffs = vec![new_slot_diff(0xaa, 42)];
+        let bytecode = vec![0; 32];
+        commit_l1_batch(
+            &eth_client,
+            &syncer,
+            1,
+            &reverted_output,
+            reverted_diffs,
+            vec![bytecode.clone()],
+        );
+        syncer.sync_step(&mut cursor).await.unwrap();
+        assert_eq!(cursor.last_batch.number, L1BatchNumber(1));
+        assert_eq!(cursor.last_batch.root_hash, reverted_output.root_hash);
+
+        revert_l1_batches(&eth_client, &syncer, 0);
+        syncer.sync_step(&mut cursor).await.unwrap();
+        assert_eq!(cursor.last_batch, genesis_batch);
+        let tree = syncer.tree.as_ref().unwrap();
+        assert_eq!(tree.latest_version(), Some(0));
+        assert_eq!(tree.latest_root_hash(), genesis_batch.root_hash);
+        let mut storage = pool.access_storage().await.unwrap();
+        let last_batch = storage.l1_sync_dal().get_last_batch().await.unwrap();
+        assert_eq!(last_batch.as_ref(), Some(&genesis_batch));
+        let entries = storage
+            .l1_sync_dal()
+            .get_storage_by_indices(&[leaf_index])
+            .await
+            .unwrap();
+        assert!(entries.is_empty(), "{entries:?}");
+        let factory_dep = storage
+            .l1_sync_dal()
+            .get_factory_dep(hash_bytecode(&bytecode))
+            .await
+            .unwrap();
+        assert_eq!(factory_dep, None);
+        drop(storage);
+
+        // Recommit L1 batch #1 with different contents.
+        reference_tree.truncate_recent_versions(1);
+        let recommitted_entry = new_slot_entry(0xbb, 23, leaf_index);
+        let output = reference_tree.extend(vec![tree_entry(&recommitted_entry)]);
+        let state_diffs = vec![new_slot_diff(0xbb, 23)];
+        let commit_tx_hash = commit_l1_batch(&eth_client, &syncer, 1, &output, state_diffs, vec![]);
+        syncer.sync_step(&mut cursor).await.unwrap();
+        assert_eq!(cursor.last_batch.number, L1BatchNumber(1));
+        assert_eq!(cursor.last_batch.root_hash, output.root_hash);
+        assert_eq!(cursor.last_batch.commit_tx_hash, Some(commit_tx_hash));
+
+        let mut storage = pool.access_storage().await.unwrap();
+        let entries = storage
+            .l1_sync_dal()
+            .get_storage_by_indices(&[leaf_index])
+            .await
+            .unwrap();
+        assert_eq!(entries[&leaf_index], recommitted_entry);
+    }
+
+    #[tokio::test]
+    async fn comparing_synced_l1_batches_with_local_state() {
+        let pool = ConnectionPool::test_pool().await;
+        let temp_dir = TempDir::new().unwrap();
+        let (mut syncer, eth_client) = prepare_syncer(&pool, &temp_dir).await;
+        let health_check = syncer.health_check();
+        let mut cursor = syncer.initialize().await.unwrap().unwrap();
+        let root_hash = sync_first_l1_batch(&pool, &mut syncer, &eth_client, &mut cursor).await;
+
+        // The local state doesn't have L1 batch #1 yet.
+        let has_more_batches = syncer.compare_with_local_state(&mut cursor).await.unwrap();
+        assert!(!has_more_batches);
+        assert_eq!(cursor.last_compared_batch, L1BatchNumber(0));
+
+        insert_local_l1_batch(&pool, 1, root_hash).await;
+        let has_more_batches = syncer.compare_with_local_state(&mut cursor).await.unwrap();
+        assert!(!has_more_batches);
+        assert_eq!(cursor.last_compared_batch, L1BatchNumber(1));
+
+        syncer.update_health();
+        let health = health_check.check_health().await;
+        assert_eq!(health.status(), HealthStatus::Ready);
+        let details = serde_json::to_value(&syncer.health_details).unwrap();
+        assert_eq!(details["last_verified_l1_batch"], 1);
+        assert_eq!(details["diverged_l1_batch"], serde_json::Value::Null);
+    }
+
+    #[tokio::test]
+    async fn diverged_local_state_is_detected() {
+        let pool = ConnectionPool::test_pool().await;
+        let temp_dir = TempDir::new().unwrap();
+        let (mut syncer, eth_client) = prepare_syncer(&pool, &temp_dir).await;
+        let health_check = syncer.health_check();
+        let mut cursor = syncer.initialize().await.unwrap().unwrap();
+        sync_first_l1_batch(&pool, &mut syncer, &eth_client, &mut cursor).await;
+        insert_local_l1_batch(&pool, 1, H256::repeat_byte(0xff)).await;
+
+        let err = syncer
+            .compare_with_local_state(&mut cursor)
+            .await
+            .unwrap_err();
+        assert!(
+            format!("{err:#}").contains("diverges from L1 at L1 batch #1"),
+            "{err:#}"
+        );
+        assert_eq!(cursor.last_compared_batch, L1BatchNumber(0));
+        assert_eq!(
+            syncer.health_details.diverged_l1_batch,
+            Some(L1BatchNumber(1))
+        );
+
+        syncer.update_health();
+        let health = health_check.check_health().await;
+        assert_eq!(health.status(), HealthStatus::NotReady);
+    }
+}
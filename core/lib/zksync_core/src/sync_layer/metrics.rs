@@ -57,3 +57,61 @@ pub(super) struct ActionQueueMetrics {
 
 #[vise::register]
 pub(super) static QUEUE_METRICS: vise::Global<ActionQueueMetrics> = vise::Global::new();
+
+/// Metrics for the sync from L1.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "external_node_l1_sync")]
+pub(super) struct L1SyncMetrics {
+    /// Number of the last L1 batch reconstructed from L1.
+    pub last_l1_batch: Gauge<u64>,
+    /// Latency of applying a single L1 batch.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub apply_l1_batch: Histogram<Duration>,
+    /// Set to 1 if the sync from L1 has failed and is stopped.
+    pub failed: Gauge<u64>,
+    /// Number of the last local L1 batch with the root hash matching the one reconstructed from L1.
+    pub last_verified_l1_batch: Gauge<u64>,
+    /// Number of the first local L1 batch with the root hash diverging from the one reconstructed from L1;
+    /// 0 if no divergence was detected.
+    pub diverged_l1_batch: Gauge<u64>,
+}
+
+#[vise::register]
+pub(super) static L1_SYNC_METRICS: vise::Global<L1SyncMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(super) struct MainNodeEndpointLabel {
+    pub endpoint: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(super) struct MainNodeRequestLabels {
+    pub endpoint: String,
+    pub method: &'static str,
+}
+
+/// Metrics for the main node client with failover between endpoints.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "external_node_main_node_client")]
+pub(super) struct MainNodeClientMetrics {
+    /// Latency of requests to a main node endpoint that did not fail with a transport error.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub request_latency: Family<MainNodeRequestLabels, Histogram<Duration>>,
+    /// Number of transport errors returned by a main node endpoint.
+    pub request_errors: Family<MainNodeRequestLabels, Counter>,
+    /// Latency of successful health checks of a main node endpoint.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub health_check_latency: Family<MainNodeEndpointLabel, Histogram<Duration>>,
+    /// Whether a main node endpoint passed the last health check (1) or not (0).
+    pub endpoint_healthy: Family<MainNodeEndpointLabel, Gauge<u64>>,
+    /// Number of switches between main node endpoints.
+    pub endpoint_switches: Counter,
+    /// Number of times a main node endpoint was not switched to because it disagreed on miniblock hashes.
+    pub inconsistent_endpoint: Family<MainNodeEndpointLabel, Counter>,
+    /// Number of times a main node endpoint was not switched to because it was behind fetched miniblocks.
+    pub lagging_endpoint: Family<MainNodeEndpointLabel, Counter>,
+}
+
+#[vise::register]
+pub(super) static MAIN_NODE_CLIENT_METRICS: vise::Global<MainNodeClientMetrics> =
+    vise::Global::new();
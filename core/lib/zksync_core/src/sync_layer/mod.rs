@@ -1,16 +1,19 @@
 pub mod batch_status_updater;
 mod client;
 pub mod external_io;
+mod failover;
 pub mod fetcher;
 pub mod genesis;
 mod gossip;
+pub mod l1_sync;
 mod metrics;
+pub mod snapshot_recovery;
 pub(crate) mod sync_action;
 mod sync_state;
 #[cfg(test)]
 mod tests;
 
 pub use self::{
-    client::MainNodeClient, external_io::ExternalIO, gossip::run_gossip_fetcher,
-    sync_action::ActionQueue, sync_state::SyncState,
+    client::MainNodeClient, external_io::ExternalIO, failover::FailoverMainNodeClient,
+    gossip::run_gossip_fetcher, sync_action::ActionQueue, sync_state::SyncState,
 };
@@ -0,0 +1,861 @@
+//! Snapshot recovery for the external node.
+//!
+//! Instead of syncing from genesis, an external node can bootstrap its Postgres state from a snapshot
+//! created by `snapshots_creator` on the main node. The snapshot header is obtained via the `snapshots`
+//! namespace of the main node API, and the snapshot data (storage logs chunks and factory dependencies)
+//! is downloaded from the object store.
+//!
+//! Recovery proceeds as follows:
+//!
+//! 1. The snapshot L1 batch (with its metadata) and its last miniblock are inserted into Postgres together
+//!   with factory dependencies and a [`SnapshotRecoveryStatus`]. Initial writes from the snapshot reference
+//!   L1 batches missing from Postgres; this is fine since initial writes are not linked to L1 batches with a foreign key.
+//! 2. Storage logs chunks are applied one by one. Each chunk is applied in a single DB transaction, which
+//!   also marks the chunk as processed in the recovery status. Thus, recovery can be resumed after a restart.
+//! 3. After all chunks are applied, the Merkle tree is recovered from Postgres by the metadata calculator,
+//!   which checks that the tree root hash matches the one from the snapshot L1 batch metadata.
+//!
+//! After recovery, the node continues syncing from the miniblock following the snapshot one.
+//! Note that the recovered node doesn't have historical data (transactions, events, etc.) preceding the snapshot.
+
+use std::{collections::HashMap, fmt};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_dal::ConnectionPool;
+use zksync_object_store::ObjectStore;
+use zksync_types::{
+    api::{self, en::SyncBlock},
+    block::{BlockGasCount, MiniblockHeader},
+    snapshots::{
+        SnapshotFactoryDependencies, SnapshotHeader, SnapshotRecoveryStatus,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+    },
+    L1BatchNumber, MiniblockNumber, ProtocolVersionId, StorageLog, H256, U64,
+};
+use zksync_utils::bytecode::hash_bytecode;
+use zksync_web3_decl::{
+    jsonrpsee::http_client::HttpClient,
+    namespaces::{
+        EnNamespaceClient, EthNamespaceClient, SnapshotsNamespaceClient, ZksNamespaceClient,
+    },
+};
+
+use super::FailoverMainNodeClient;
+use crate::genesis::add_eth_token;
+
+/// Main node API used during snapshot recovery.
+#[async_trait]
+pub trait SnapshotsMainNodeClient: 'static + Send + Sync + fmt::Debug {
+    async fn fetch_snapshot_l1_batch_numbers(&self) -> anyhow::Result<Vec<L1BatchNumber>>;
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<SnapshotHeader>>;
+
+    async fn fetch_l2_block(&self, number: MiniblockNumber) -> anyhow::Result<Option<SyncBlock>>;
+
+    /// Fetches the base fee for the specified miniblock. The base fee cannot be derived locally since
+    /// the derivation depends on the VM version the miniblock was executed with.
+    async fn fetch_l2_block_base_fee(&self, number: MiniblockNumber)
+        -> anyhow::Result<Option<u64>>;
+
+    async fn fetch_l1_batch_root_hash(&self, number: L1BatchNumber)
+        -> anyhow::Result<Option<H256>>;
+
+    async fn fetch_protocol_version(
+        &self,
+        protocol_version: ProtocolVersionId,
+    ) -> anyhow::Result<api::ProtocolVersion>;
+}
+
+#[async_trait]
+impl SnapshotsMainNodeClient for HttpClient {
+    async fn fetch_snapshot_l1_batch_numbers(&self) -> anyhow::Result<Vec<L1BatchNumber>> {
+        let snapshots = self
+            .get_all_snapshots()
+            .await
+            .context("get_all_snapshots")?;
+        Ok(snapshots.snapshots_l1_batch_numbers)
+    }
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<SnapshotHeader>> {
+        self.get_snapshot_by_l1_batch_number(l1_batch_number)
+            .await
+            .with_context(|| format!("get_snapshot_by_l1_batch_number({l1_batch_number})"))
+    }
+
+    async fn fetch_l2_block(&self, number: MiniblockNumber) -> anyhow::Result<Option<SyncBlock>> {
+        self.sync_l2_block(number, true)
+            .await
+            .with_context(|| format!("sync_l2_block({number})"))
+    }
+
+    async fn fetch_l2_block_base_fee(
+        &self,
+        number: MiniblockNumber,
+    ) -> anyhow::Result<Option<u64>> {
+        let block = self
+            .get_block_by_number(api::BlockNumber::Number(U64::from(number.0)), false)
+            .await
+            .with_context(|| format!("get_block_by_number({number})"))?;
+        let Some(block) = block else {
+            return Ok(None);
+        };
+        let base_fee = u64::try_from(block.base_fee_per_gas).map_err(|err| {
+            anyhow::anyhow!("base fee for miniblock #{number} overflows u64: {err}")
+        })?;
+        Ok(Some(base_fee))
+    }
+
+    async fn fetch_l1_batch_root_hash(
+        &self,
+        number: L1BatchNumber,
+    ) -> anyhow::Result<Option<H256>> {
+        let details = self
+            .get_l1_batch_details(number)
+            .await
+            .with_context(|| format!("get_l1_batch_details({number})"))?;
+        Ok(details.and_then(|details| details.base.root_hash))
+    }
+
+    async fn fetch_protocol_version(
+        &self,
+        protocol_version: ProtocolVersionId,
+    ) -> anyhow::Result<api::ProtocolVersion> {
+        self.get_protocol_version(Some(protocol_version as u16))
+            .await?
+            .with_context(|| {
+                format!("Protocol version {protocol_version:?} must exist on main node")
+            })
+    }
+}
+
+#[async_trait]
+impl SnapshotsMainNodeClient for FailoverMainNodeClient {
+    async fn fetch_snapshot_l1_batch_numbers(&self) -> anyhow::Result<Vec<L1BatchNumber>> {
+        self.call("fetch_snapshot_l1_batch_numbers", |client| {
+            client.fetch_snapshot_l1_batch_numbers()
+        })
+        .await
+    }
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<SnapshotHeader>> {
+        self.call("fetch_snapshot", |client| {
+            client.fetch_snapshot(l1_batch_number)
+        })
+        .await
+    }
+
+    async fn fetch_l2_block(&self, number: MiniblockNumber) -> anyhow::Result<Option<SyncBlock>> {
+        self.call("fetch_l2_block", |client| client.fetch_l2_block(number))
+            .await
+    }
+
+    async fn fetch_l2_block_base_fee(
+        &self,
+        number: MiniblockNumber,
+    ) -> anyhow::Result<Option<u64>> {
+        self.call("fetch_l2_block_base_fee", |client| {
+            client.fetch_l2_block_base_fee(number)
+        })
+        .await
+    }
+
+    async fn fetch_l1_batch_root_hash(
+        &self,
+        number: L1BatchNumber,
+    ) -> anyhow::Result<Option<H256>> {
+        self.call("fetch_l1_batch_root_hash", |client| {
+            client.fetch_l1_batch_root_hash(number)
+        })
+        .await
+    }
+
+    async fn fetch_protocol_version(
+        &self,
+        protocol_version: ProtocolVersionId,
+    ) -> anyhow::Result<api::ProtocolVersion> {
+        self.call("fetch_protocol_version", |client| {
+            client.fetch_protocol_version(protocol_version)
+        })
+        .await
+    }
+}
+
+/// Applies the newest snapshot available on the main node to Postgres.
+#[derive(Debug)]
+pub struct SnapshotApplier<'a> {
+    pool: &'a ConnectionPool,
+    main_node_client: &'a dyn SnapshotsMainNodeClient,
+    blob_store: &'a dyn ObjectStore,
+}
+
+impl<'a> SnapshotApplier<'a> {
+    pub fn new(
+        pool: &'a ConnectionPool,
+        main_node_client: &'a dyn SnapshotsMainNodeClient,
+        blob_store: &'a dyn ObjectStore,
+    ) -> Self {
+        Self {
+            pool,
+            main_node_client,
+            blob_store,
+        }
+    }
+
+    /// Recovers Postgres from the newest snapshot, or resumes previously started recovery.
+    ///
+    /// Returns the status of the finished recovery, or `None` if the node was initialized without a snapshot
+    /// (i.e., Postgres is not empty, but doesn't contain snapshot recovery information). In the latter case,
+    /// Postgres is not modified.
+    pub async fn apply(self) -> anyhow::Result<Option<SnapshotRecoveryStatus>> {
+        let mut storage = self.pool.access_storage_tagged("sync_layer").await?;
+        let status = storage
+            .snapshot_recovery_dal()
+            .get_applied_snapshot_status()
+            .await
+            .context("failed getting snapshot recovery status")?;
+        let is_genesis_needed = storage
+            .blocks_dal()
+            .is_genesis_needed()
+            .await
+            .context("failed checking whether Postgres is empty")?;
+        drop(storage);
+
+        let (snapshot, status) = match status {
+            Some(status) if status.is_finished() => {
+                tracing::info!(
+                    "Snapshot recovery for L1 batch #{} is already finished",
+                    status.l1_batch_number
+                );
+                return Ok(Some(status));
+            }
+            Some(status) => {
+                let l1_batch_number = status.l1_batch_number;
+                let snapshot = self
+                    .main_node_client
+                    .fetch_snapshot(l1_batch_number)
+                    .await?
+                    .with_context(|| {
+                        format!(
+                            "snapshot for L1 batch #{l1_batch_number} being recovered is no longer available \
+                             on the main node; Postgres needs to be reset to recover from another snapshot"
+                        )
+                    })?;
+                anyhow::ensure!(
+                    snapshot.storage_logs_chunks.len() == status.storage_logs_chunks_processed.len(),
+                    "Snapshot for L1 batch #{l1_batch_number} has {} storage logs chunks, while recovery status \
+                     in Postgres has {}",
+                    snapshot.storage_logs_chunks.len(),
+                    status.storage_logs_chunks_processed.len()
+                );
+                tracing::info!(
+                    "Resuming snapshot recovery for L1 batch #{l1_batch_number}; {} / {} storage logs chunks \
+                     are left to process",
+                    status.storage_logs_chunks_left_to_process().len(),
+                    status.storage_logs_chunks_processed.len()
+                );
+                (snapshot, status)
+            }
+            None if !is_genesis_needed => {
+                tracing::warn!(
+                    "Postgres is not empty and does not contain snapshot recovery information; \
+                     skipping snapshot recovery"
+                );
+                return Ok(None);
+            }
+            None => {
+                let snapshot = self.fetch_newest_snapshot().await?;
+                tracing::info!(
+                    "Starting snapshot recovery for L1 batch #{} (miniblock #{}) with {} storage logs chunks",
+                    snapshot.l1_batch_number,
+                    snapshot.miniblock_number,
+                    snapshot.storage_logs_chunks.len()
+                );
+                let status = self.prepare_storage(&snapshot).await?;
+                (snapshot, status)
+            }
+        };
+
+        let chunk_ids = status.storage_logs_chunks_left_to_process();
+        let chunk_count = status.storage_logs_chunks_processed.len();
+        for (i, chunk_id) in chunk_ids.iter().enumerate() {
+            self.recover_storage_logs_chunk(&snapshot, *chunk_id)
+                .await
+                .with_context(|| format!("failed recovering storage logs chunk #{chunk_id}"))?;
+            tracing::info!(
+                "Recovered storage logs chunk #{chunk_id} ({} / {} remaining chunks, {chunk_count} total)",
+                i + 1,
+                chunk_ids.len()
+            );
+        }
+
+        tracing::info!(
+            "Finished applying snapshot for L1 batch #{} to Postgres; the Merkle tree will be recovered \
+             by the metadata calculator",
+            snapshot.l1_batch_number
+        );
+        Ok(Some(SnapshotRecoveryStatus {
+            storage_logs_chunks_processed: vec![true; chunk_count],
+            ..status
+        }))
+    }
+
+    async fn fetch_newest_snapshot(&self) -> anyhow::Result<SnapshotHeader> {
+        let l1_batch_numbers = self
+            .main_node_client
+            .fetch_snapshot_l1_batch_numbers()
+            .await?;
+        let l1_batch_number = l1_batch_numbers
+            .into_iter()
+            .max()
+            .context("main node doesn't have snapshots")?;
+        self.main_node_client
+            .fetch_snapshot(l1_batch_number)
+            .await?
+            .with_context(|| format!("snapshot for L1 batch #{l1_batch_number} disappeared"))
+    }
+
+    /// Inserts the snapshot L1 batch, its last miniblock, factory deps and the initial recovery status to Postgres.
+    async fn prepare_storage(
+        &self,
+        snapshot: &SnapshotHeader,
+    ) -> anyhow::Result<SnapshotRecoveryStatus> {
+        let l1_batch_number = snapshot.l1_batch_number;
+        let miniblock_number = snapshot.miniblock_number;
+        let l1_batch = &snapshot.last_l1_batch_with_metadata;
+        anyhow::ensure!(
+            l1_batch.header.number == l1_batch_number,
+            "Snapshot for L1 batch #{l1_batch_number} contains L1 batch #{}",
+            l1_batch.header.number
+        );
+
+        let miniblock = self
+            .main_node_client
+            .fetch_l2_block(miniblock_number)
+            .await?
+            .with_context(|| format!("miniblock #{miniblock_number} is missing on main node"))?;
+        anyhow::ensure!(
+            miniblock.l1_batch_number == l1_batch_number && miniblock.last_in_batch,
+            "Snapshot miniblock #{miniblock_number} is not the last miniblock in L1 batch #{l1_batch_number}"
+        );
+        let miniblock_hash = miniblock
+            .hash
+            .with_context(|| format!("miniblock #{miniblock_number} doesn't have a hash"))?;
+        let base_fee_per_gas = self
+            .main_node_client
+            .fetch_l2_block_base_fee(miniblock_number)
+            .await?
+            .with_context(|| format!("miniblock #{miniblock_number} is missing on main node"))?;
+        let miniblock_header = Self::miniblock_header(miniblock, miniblock_hash, base_fee_per_gas);
+
+        let previous_root_hash = if l1_batch_number == L1BatchNumber(0) {
+            H256::zero()
+        } else {
+            let previous_l1_batch = l1_batch_number - 1;
+            self.main_node_client
+                .fetch_l1_batch_root_hash(previous_l1_batch)
+                .await?
+                .with_context(|| {
+                    format!("root hash for L1 batch #{previous_l1_batch} is missing on main node")
+                })?
+        };
+
+        let protocol_version_id = l1_batch
+            .header
+            .protocol_version
+            .context("snapshot L1 batch doesn't have protocol version")?;
+        let protocol_version = self
+            .main_node_client
+            .fetch_protocol_version(protocol_version_id)
+            .await?;
+
+        let factory_deps: SnapshotFactoryDependencies = self
+            .blob_store
+            .get(l1_batch_number)
+            .await
+            .context("failed fetching factory dependencies from object store")?;
+        let factory_deps: HashMap<_, _> = factory_deps
+            .factory_deps
+            .into_iter()
+            .map(|dep| (hash_bytecode(&dep.bytecode.0), dep.bytecode.0))
+            .collect();
+        tracing::info!(
+            "Fetched {} factory dependencies for snapshot",
+            factory_deps.len()
+        );
+
+        let status = SnapshotRecoveryStatus {
+            l1_batch_number,
+            l1_batch_root_hash: l1_batch.metadata.root_hash,
+            miniblock_number,
+            miniblock_hash,
+            storage_logs_chunks_processed: vec![false; snapshot.storage_logs_chunks.len()],
+        };
+
+        let mut storage = self.pool.access_storage_tagged("sync_layer").await?;
+        let mut transaction = storage.start_transaction().await?;
+        transaction
+            .protocol_versions_dal()
+            .save_protocol_version(
+                protocol_version_id,
+                protocol_version.timestamp,
+                protocol_version.verification_keys_hashes,
+                protocol_version.base_system_contracts,
+                // Verifier is not used in the external node, so we can pass an empty address.
+                Default::default(),
+                // The upgrade transaction (if any) was executed before the snapshot, so it's not present in Postgres.
+                None,
+            )
+            .await;
+        transaction
+            .blocks_dal()
+            .insert_l1_batch(&l1_batch.header, &[], BlockGasCount::default(), &[], &[])
+            .await
+            .context("failed inserting snapshot L1 batch")?;
+        let is_pre_boojum = protocol_version_id.is_pre_boojum();
+        transaction
+            .blocks_dal()
+            .save_l1_batch_metadata(
+                l1_batch_number,
+                &l1_batch.metadata,
+                previous_root_hash,
+                is_pre_boojum,
+            )
+            .await
+            .context("failed saving snapshot L1 batch metadata")?;
+        transaction
+            .blocks_dal()
+            .insert_miniblock(&miniblock_header)
+            .await
+            .context("failed inserting snapshot miniblock")?;
+        transaction
+            .blocks_dal()
+            .mark_miniblocks_as_executed_in_l1_batch(l1_batch_number)
+            .await?;
+        transaction
+            .storage_dal()
+            .insert_factory_deps(miniblock_number, &factory_deps)
+            .await;
+        add_eth_token(&mut transaction).await;
+        transaction
+            .snapshot_recovery_dal()
+            .set_applied_snapshot_status(&status)
+            .await?;
+        transaction.commit().await?;
+        Ok(status)
+    }
+
+    fn miniblock_header(
+        miniblock: SyncBlock,
+        hash: H256,
+        base_fee_per_gas: u64,
+    ) -> MiniblockHeader {
+        let transactions = miniblock.transactions.unwrap_or_default();
+        let l1_tx_count = transactions.iter().filter(|tx| tx.is_l1()).count();
+        let l2_tx_count = transactions.len() - l1_tx_count;
+
+        MiniblockHeader {
+            number: miniblock.number,
+            timestamp: miniblock.timestamp,
+            hash,
+            l1_tx_count: l1_tx_count as u16,
+            l2_tx_count: l2_tx_count as u16,
+            base_fee_per_gas,
+            l1_gas_price: miniblock.l1_gas_price,
+            l2_fair_gas_price: miniblock.l2_fair_gas_price,
+            base_system_contracts_hashes: miniblock.base_system_contracts_hashes,
+            protocol_version: Some(miniblock.protocol_version),
+            virtual_blocks: miniblock.virtual_blocks.unwrap_or(0),
+        }
+    }
+
+    async fn recover_storage_logs_chunk(
+        &self,
+        snapshot: &SnapshotHeader,
+        chunk_id: u64,
+    ) -> anyhow::Result<()> {
+        let l1_batch_number = snapshot.l1_batch_number;
+        let storage_key = SnapshotStorageLogsStorageKey {
+            l1_batch_number,
+            chunk_id,
+        };
+        let chunk: SnapshotStorageLogsChunk = self
+            .blob_store
+            .get(storage_key)
+            .await
+            .context("failed fetching storage logs chunk from object store")?;
+        let storage_logs = chunk.storage_logs;
+
+        if let Some(log) = storage_logs
+            .iter()
+            .find(|log| log.l1_batch_number_of_initial_write > l1_batch_number)
+        {
+            anyhow::bail!(
+                "Storage log {log:?} in snapshot for L1 batch #{l1_batch_number} has initial write \
+                 in a future L1 batch"
+            );
+        }
+
+        let mut storage = self.pool.access_storage_tagged("sync_layer").await?;
+        let mut transaction = storage.start_transaction().await?;
+        transaction
+            .storage_logs_dal()
+            .insert_storage_logs_from_snapshot(snapshot.miniblock_number, &storage_logs)
+            .await;
+        transaction
+            .storage_logs_dedup_dal()
+            .insert_initial_writes_from_snapshot(&storage_logs)
+            .await
+            .context("failed inserting initial writes")?;
+        let storage_logs: Vec<_> = storage_logs
+            .iter()
+            .map(|log| StorageLog::new_write_log(log.key, log.value))
+            .collect();
+        transaction
+            .storage_dal()
+            .apply_storage_logs(&[(H256::zero(), storage_logs)])
+            .await;
+        transaction
+            .snapshot_recovery_dal()
+            .mark_storage_logs_chunk_as_processed(l1_batch_number, chunk_id)
+            .await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_contracts::BaseSystemContractsHashes;
+    use zksync_dal::StorageProcessor;
+    use zksync_object_store::ObjectStoreFactory;
+    use zksync_types::{
+        block::L1BatchHeader,
+        commitment::L1BatchWithMetadata,
+        snapshots::{
+            SnapshotFactoryDependency, SnapshotStorageLog, SnapshotStorageLogsChunkMetadata,
+        },
+        AccountTreeId, Address, Bytes, L2ChainId, StorageKey,
+    };
+
+    use super::*;
+    use crate::{
+        genesis::{ensure_genesis_state, GenesisParams},
+        state_keeper::tests::create_l1_batch_metadata,
+    };
+
+    const SNAPSHOT_L1_BATCH: L1BatchNumber = L1BatchNumber(5);
+    const SNAPSHOT_MINIBLOCK: MiniblockNumber = MiniblockNumber(12);
+    const SNAPSHOT_BASE_FEE: u64 = 250_000_000;
+
+    #[derive(Debug, Default)]
+    struct MockMainNodeClient {
+        snapshot: Option<SnapshotHeader>,
+        miniblock: Option<SyncBlock>,
+    }
+
+    #[async_trait]
+    impl SnapshotsMainNodeClient for MockMainNodeClient {
+        async fn fetch_snapshot_l1_batch_numbers(&self) -> anyhow::Result<Vec<L1BatchNumber>> {
+            Ok(self
+                .snapshot
+                .iter()
+                .map(|snapshot| snapshot.l1_batch_number)
+                .collect())
+        }
+
+        async fn fetch_snapshot(
+            &self,
+            l1_batch_number: L1BatchNumber,
+        ) -> anyhow::Result<Option<SnapshotHeader>> {
+            Ok(self
+                .snapshot
+                .clone()
+                .filter(|snapshot| snapshot.l1_batch_number == l1_batch_number))
+        }
+
+        async fn fetch_l2_block(
+            &self,
+            number: MiniblockNumber,
+        ) -> anyhow::Result<Option<SyncBlock>> {
+            Ok(self
+                .miniblock
+                .clone()
+                .filter(|block| block.number == number))
+        }
+
+        async fn fetch_l2_block_base_fee(
+            &self,
+            number: MiniblockNumber,
+        ) -> anyhow::Result<Option<u64>> {
+            Ok(self
+                .miniblock
+                .as_ref()
+                .filter(|block| block.number == number)
+                .map(|_| SNAPSHOT_BASE_FEE))
+        }
+
+        async fn fetch_l1_batch_root_hash(
+            &self,
+            number: L1BatchNumber,
+        ) -> anyhow::Result<Option<H256>> {
+            Ok(Some(H256::from_low_u64_be(number.0.into())))
+        }
+
+        async fn fetch_protocol_version(
+            &self,
+            protocol_version: ProtocolVersionId,
+        ) -> anyhow::Result<api::ProtocolVersion> {
+            Ok(api::ProtocolVersion {
+                version_id: protocol_version as u16,
+                timestamp: 0,
+                verification_keys_hashes: Default::default(),
+                base_system_contracts: BaseSystemContractsHashes::default(),
+                l2_system_upgrade_tx_hash: None,
+            })
+        }
+    }
+
+    fn gen_storage_logs(chunk_id: u64) -> Vec<SnapshotStorageLog> {
+        (0..10)
+            .map(|i| {
+                let index = chunk_id * 10 + i + 1;
+                SnapshotStorageLog {
+                    key: StorageKey::new(
+                        AccountTreeId::new(Address::repeat_byte(1)),
+                        H256::from_low_u64_be(index),
+                    ),
+                    value: H256::repeat_byte(0xff),
+                    l1_batch_number_of_initial_write: L1BatchNumber(index as u32 % 5 + 1),
+                    enumeration_index: index,
+                }
+            })
+            .collect()
+    }
+
+    async fn prepare_snapshot(
+        chunk_count: u64,
+        blob_store: &dyn ObjectStore,
+    ) -> MockMainNodeClient {
+        let mut header = L1BatchHeader::new(
+            SNAPSHOT_L1_BATCH,
+            100,
+            Address::default(),
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+        );
+        header.is_finished = true;
+        let l1_batch = L1BatchWithMetadata {
+            header,
+            metadata: create_l1_batch_metadata(SNAPSHOT_L1_BATCH.0),
+            factory_deps: vec![],
+        };
+
+        let mut storage_logs_chunks = vec![];
+        for chunk_id in 0..chunk_count {
+            let key = SnapshotStorageLogsStorageKey {
+                l1_batch_number: SNAPSHOT_L1_BATCH,
+                chunk_id,
+            };
+            let chunk = SnapshotStorageLogsChunk {
+                storage_logs: gen_storage_logs(chunk_id),
+            };
+            let filepath = blob_store.put(key, &chunk).await.unwrap();
+            storage_logs_chunks.push(SnapshotStorageLogsChunkMetadata { chunk_id, filepath });
+        }
+
+        let factory_deps = SnapshotFactoryDependencies {
+            factory_deps: vec![SnapshotFactoryDependency {
+                bytecode: Bytes(vec![0; 32]),
+            }],
+        };
+        let factory_deps_filepath = blob_store
+            .put(SNAPSHOT_L1_BATCH, &factory_deps)
+            .await
+            .unwrap();
+
+        let miniblock = SyncBlock {
+            number: SNAPSHOT_MINIBLOCK,
+            l1_batch_number: SNAPSHOT_L1_BATCH,
+            last_in_batch: true,
+            timestamp: 100,
+            l1_gas_price: 2,
+            l2_fair_gas_price: 3,
+            base_system_contracts_hashes: BaseSystemContractsHashes::default(),
+            operator_address: Address::default(),
+            transactions: Some(vec![]),
+            virtual_blocks: Some(0),
+            hash: Some(H256::repeat_byte(0x12)),
+            protocol_version: ProtocolVersionId::latest(),
+            consensus: None,
+        };
+
+        MockMainNodeClient {
+            snapshot: Some(SnapshotHeader {
+                l1_batch_number: SNAPSHOT_L1_BATCH,
+                miniblock_number: SNAPSHOT_MINIBLOCK,
+                storage_logs_chunks,
+                factory_deps_filepath,
+                last_l1_batch_with_metadata: l1_batch,
+            }),
+            miniblock: Some(miniblock),
+        }
+    }
+
+    async fn assert_recovered_storage(storage: &mut StorageProcessor<'_>, chunk_count: u64) {
+        assert_eq!(
+            storage
+                .blocks_dal()
+                .get_sealed_l1_batch_number()
+                .await
+                .unwrap(),
+            SNAPSHOT_L1_BATCH
+        );
+        assert_eq!(
+            storage
+                .blocks_dal()
+                .get_sealed_miniblock_number()
+                .await
+                .unwrap(),
+            SNAPSHOT_MINIBLOCK
+        );
+        let miniblock = storage
+            .blocks_dal()
+            .get_miniblock_header(SNAPSHOT_MINIBLOCK)
+            .await
+            .unwrap()
+            .expect("no snapshot miniblock");
+        assert_eq!(miniblock.base_fee_per_gas, SNAPSHOT_BASE_FEE);
+        let l1_batch = storage
+            .blocks_dal()
+            .get_l1_batch_metadata(SNAPSHOT_L1_BATCH)
+            .await
+            .unwrap()
+            .expect("no snapshot L1 batch metadata");
+        assert_eq!(
+            l1_batch.metadata.root_hash,
+            create_l1_batch_metadata(SNAPSHOT_L1_BATCH.0).root_hash
+        );
+
+        let log_count = storage
+            .storage_logs_dal()
+            .count_miniblock_storage_logs(SNAPSHOT_MINIBLOCK)
+            .await
+            .unwrap();
+        assert_eq!(log_count, chunk_count * 10);
+        for chunk_id in 0..chunk_count {
+            for log in gen_storage_logs(chunk_id) {
+                let value = storage.storage_dal().get_by_key(&log.key).await;
+                assert_eq!(value, Some(log.value));
+            }
+        }
+        let max_index = storage
+            .storage_logs_dedup_dal()
+            .max_enumeration_index()
+            .await;
+        assert_eq!(max_index, Some(chunk_count * 10));
+        let factory_dep = storage
+            .storage_dal()
+            .get_factory_dep(hash_bytecode(&[0; 32]))
+            .await;
+        assert_eq!(factory_dep, Some(vec![0; 32]));
+    }
+
+    #[tokio::test]
+    async fn applying_snapshot() {
+        let pool = ConnectionPool::test_pool().await;
+        let blob_store = ObjectStoreFactory::mock().create_store().await.unwrap();
+        let client = prepare_snapshot(3, &*blob_store).await;
+
+        let status = SnapshotApplier::new(&pool, &client, &*blob_store)
+            .apply()
+            .await
+            .unwrap()
+            .expect("snapshot was not applied");
+        assert!(status.is_finished());
+        assert_eq!(status.l1_batch_number, SNAPSHOT_L1_BATCH);
+        assert_eq!(status.miniblock_number, SNAPSHOT_MINIBLOCK);
+
+        let mut storage = pool.access_storage().await.unwrap();
+        assert_recovered_storage(&mut storage, 3).await;
+        let stored_status = storage
+            .snapshot_recovery_dal()
+            .get_applied_snapshot_status()
+            .await
+            .unwrap();
+        assert_eq!(stored_status.as_ref(), Some(&status));
+        drop(storage);
+
+        // Applying the snapshot again should be a no-op.
+        let new_status = SnapshotApplier::new(&pool, &client, &*blob_store)
+            .apply()
+            .await
+            .unwrap();
+        assert_eq!(new_status, Some(status));
+    }
+
+    #[tokio::test]
+    async fn resuming_snapshot_recovery() {
+        let pool = ConnectionPool::test_pool().await;
+        let blob_store = ObjectStoreFactory::mock().create_store().await.unwrap();
+        let client = prepare_snapshot(3, &*blob_store).await;
+
+        // Emulate a failure when fetching the last chunk.
+        let incomplete_blob_store = ObjectStoreFactory::mock().create_store().await.unwrap();
+        let snapshot = client.snapshot.as_ref().unwrap();
+        prepare_snapshot(2, &*incomplete_blob_store).await;
+        let err = SnapshotApplier::new(&pool, &client, &*incomplete_blob_store)
+            .apply()
+            .await
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("chunk #2"), "{err:#}");
+
+        let status = pool
+            .access_storage()
+            .await
+            .unwrap()
+            .snapshot_recovery_dal()
+            .get_applied_snapshot_status()
+            .await
+            .unwrap()
+            .expect("no recovery status");
+        assert_eq!(status.l1_batch_number, snapshot.l1_batch_number);
+        assert_eq!(status.storage_logs_chunks_left_to_process(), [2]);
+
+        let status = SnapshotApplier::new(&pool, &client, &*blob_store)
+            .apply()
+            .await
+            .unwrap()
+            .expect("snapshot was not applied");
+        assert!(status.is_finished());
+        let mut storage = pool.access_storage().await.unwrap();
+        assert_recovered_storage(&mut storage, 3).await;
+    }
+
+    #[tokio::test]
+    async fn snapshot_recovery_is_skipped_for_non_empty_postgres() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+            .await
+            .unwrap();
+        drop(storage);
+
+        let blob_store = ObjectStoreFactory::mock().create_store().await.unwrap();
+        let client = prepare_snapshot(1, &*blob_store).await;
+        let status = SnapshotApplier::new(&pool, &client, &*blob_store)
+            .apply()
+            .await
+            .unwrap();
+        assert_eq!(status, None);
+    }
+}
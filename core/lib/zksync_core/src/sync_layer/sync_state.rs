@@ -1,6 +1,6 @@
 use std::sync::{Arc, RwLock};
 
-use zksync_types::MiniblockNumber;
+use zksync_types::{web3::types as web3, MiniblockNumber};
 
 use crate::metrics::EN_METRICS;
 
@@ -68,6 +68,19 @@ impl SyncState {
         self.update_sync_metric(&inner);
     }
 
+    /// Returns the sync status in the format used by the Web3 API (e.g., in `eth_syncing`).
+    pub(crate) fn web3_status(&self) -> web3::SyncState {
+        if self.is_synced() {
+            web3::SyncState::NotSyncing
+        } else {
+            web3::SyncState::Syncing(web3::SyncInfo {
+                starting_block: 0u64.into(), // We always start syncing from genesis right now.
+                current_block: self.get_local_block().0.into(),
+                highest_block: self.get_main_node_block().0.into(),
+            })
+        }
+    }
+
     pub(crate) fn is_synced(&self) -> bool {
         let inner = self.inner.read().unwrap();
         self.is_synced_inner(&inner).0
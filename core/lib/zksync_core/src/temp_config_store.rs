@@ -47,4 +47,6 @@ pub struct TempConfigStore {
     pub gas_adjuster_config: Option<GasAdjusterConfig>,
     pub prover_configs: Option<ProverConfigs>,
     pub object_store_config: Option<ObjectStoreConfig>,
+    pub prover_object_store_config: Option<ObjectStoreConfig>,
+    pub snapshots_object_store_config: Option<ObjectStoreConfig>,
 }
@@ -45,6 +45,8 @@ pub enum CircuitSynthesizerError {
     InputLoadFailed(ObjectStoreError),
     #[error("GetRegionFailed: {0}")]
     GetRegionFailed(anyhow::Error),
+    #[error("ObjectStoreInitFailed: {0}")]
+    ObjectStoreInitFailed(anyhow::Error),
     #[error("GetZoneFailed: {0}")]
     GetZoneFailed(anyhow::Error),
 }
@@ -93,7 +95,10 @@ impl CircuitSynthesizer {
 
         Ok(Self {
             config,
-            blob_store: store_factory.create_store().await,
+            blob_store: store_factory
+                .create_store()
+                .await
+                .map_err(CircuitSynthesizerError::ObjectStoreInitFailed)?,
             allowed_circuit_types: allowed_circuit_types
                 .map(|x| x.into_iter().map(|x| x.1).collect()),
             region: get_region(&prover_groups)
@@ -85,7 +85,7 @@ pub async fn save_proof(
                 if shall_save_to_public_bucket {
                     public_blob_store
                         .expect("public_object_store shall not be empty while running with shall_save_to_public_bucket config")
-                        .put(artifacts.block_number.0, &proof)
+                        .put_unwrapped(artifacts.block_number.0, &proof)
                         .await
                         .unwrap();
                 }
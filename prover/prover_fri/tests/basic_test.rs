@@ -27,7 +27,7 @@ async fn prover_and_assert_base_layer(
     object_store_config.file_backed_base_path = "./tests/data/".to_owned();
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
-        .await;
+        .await?;
     let expected_proof = object_store
         .get(expected_proof_id)
         .await
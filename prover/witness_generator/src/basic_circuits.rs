@@ -102,15 +102,15 @@ impl BasicWitnessGenerator {
         connection_pool: ConnectionPool,
         prover_connection_pool: ConnectionPool,
         protocol_versions: Vec<FriProtocolVersionId>,
-    ) -> Self {
-        Self {
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
             config: Arc::new(config),
-            object_store: store_factory.create_store().await.into(),
+            object_store: store_factory.create_store().await?.into(),
             public_blob_store,
             connection_pool,
             prover_connection_pool,
             protocol_versions,
-        }
+        })
     }
 
     async fn process_job_impl(
@@ -428,7 +428,7 @@ async fn save_scheduler_artifacts(
     if shall_save_to_public_bucket {
         public_object_store
             .expect("public_object_store shall not be empty while running with shall_save_to_public_bucket config")
-            .put(block_number, &aux_output_witness_wrapper)
+            .put_unwrapped(block_number, &aux_output_witness_wrapper)
             .await
             .unwrap();
     }
@@ -83,13 +83,13 @@ impl LeafAggregationWitnessGenerator {
         store_factory: &ObjectStoreFactory,
         prover_connection_pool: ConnectionPool,
         protocol_versions: Vec<FriProtocolVersionId>,
-    ) -> Self {
-        Self {
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
             config,
-            object_store: store_factory.create_store().await,
+            object_store: store_factory.create_store().await?,
             prover_connection_pool,
             protocol_versions,
-        }
+        })
     }
 
     pub fn process_job_sync(
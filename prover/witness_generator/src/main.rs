@@ -175,7 +175,7 @@ async fn main() -> anyhow::Result<()> {
                                 .context("ObjectStoreConfig::from_env()")?,
                         )
                         .create_store()
-                        .await,
+                        .await?,
                     ),
                 };
                 let generator = BasicWitnessGenerator::new(
@@ -186,7 +186,7 @@ async fn main() -> anyhow::Result<()> {
                     prover_connection_pool.clone(),
                     protocol_versions.clone(),
                 )
-                .await;
+                .await?;
                 generator.run(stop_receiver.clone(), opt.batch_size)
             }
             AggregationRound::LeafAggregation => {
@@ -196,7 +196,7 @@ async fn main() -> anyhow::Result<()> {
                     prover_connection_pool.clone(),
                     protocol_versions.clone(),
                 )
-                .await;
+                .await?;
                 generator.run(stop_receiver.clone(), opt.batch_size)
             }
             AggregationRound::NodeAggregation => {
@@ -206,7 +206,7 @@ async fn main() -> anyhow::Result<()> {
                     prover_connection_pool.clone(),
                     protocol_versions.clone(),
                 )
-                .await;
+                .await?;
                 generator.run(stop_receiver.clone(), opt.batch_size)
             }
             AggregationRound::Scheduler => {
@@ -216,7 +216,7 @@ async fn main() -> anyhow::Result<()> {
                     prover_connection_pool.clone(),
                     protocol_versions.clone(),
                 )
-                .await;
+                .await?;
                 generator.run(stop_receiver.clone(), opt.batch_size)
             }
         };
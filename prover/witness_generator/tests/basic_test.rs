@@ -32,7 +32,8 @@ async fn test_leaf_witness_gen() {
     object_store_config.file_backed_base_path = "./tests/data/leaf/".to_owned();
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
-        .await;
+        .await
+        .unwrap();
 
     let circuit_id = 4;
     let block_number = L1BatchNumber(125010);
@@ -68,7 +69,8 @@ async fn test_node_witness_gen() {
     object_store_config.file_backed_base_path = "./tests/data/node/".to_owned();
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
-        .await;
+        .await
+        .unwrap();
 
     let circuit_id = 8;
     let block_number = L1BatchNumber(127856);
@@ -105,7 +107,8 @@ async fn test_scheduler_witness_gen() {
     object_store_config.file_backed_base_path = "./tests/data/scheduler/".to_owned();
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
-        .await;
+        .await
+        .unwrap();
     let block_number = L1BatchNumber(128599);
     let key = FriCircuitKey {
         block_number,